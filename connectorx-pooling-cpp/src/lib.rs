@@ -3,9 +3,11 @@ mod plan;
 use arrow::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
 use connectorx::prelude::*;
 use libc::c_char;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::env;
+use std::error::Error;
 use std::ffi::{CStr, CString};
 use std::sync::Arc;
 
@@ -176,10 +178,17 @@ pub unsafe extern "C" fn connectorx_scan(conn: *const c_char, query: *const c_ch
     let conn_str = unsafe { CStr::from_ptr(conn) }.to_str().unwrap();
     let query_str = unsafe { CStr::from_ptr(query) }.to_str().unwrap();
     let source_conn = SourceConn::try_from(conn_str).unwrap();
-    let record_batches = get_arrow(&source_conn, None, &[CXQuery::from(query_str)], None, None)
-        .unwrap()
-        .arrow()
-        .unwrap();
+    let record_batches = get_arrow(
+        &source_conn,
+        None,
+        &[CXQuery::from(query_str)],
+        None,
+        None,
+        None,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
 
     // arrow::util::pretty::print_batches(&record_batches[..]).unwrap();
 
@@ -263,33 +272,68 @@ pub unsafe extern "C" fn free_record_batch(rb: *mut CXSlice<CXArray>) {
         })
 }
 
+thread_local! {
+    /// The error of the last failed call on this thread, see [`connectorx_last_error`].
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(error: Option<&dyn Error>) {
+    let message = error.map(|e| CString::new(e.to_string().replace('\0', "")).unwrap_or_default());
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// The message of the error that made the last `connectorx_scan_iter` or
+/// `connectorx_iter_next` on the calling thread return null, or null if that call succeeded
+/// or the stream was exhausted. The message is freed by the next of those calls.
+#[no_mangle]
+pub extern "C" fn connectorx_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(message) => message.as_ptr(),
+        None => std::ptr::null(),
+    })
+}
+
+/// Starts a stream of the results of `queries`, or returns null on a failure, whose message
+/// `connectorx_last_error` then returns.
 #[no_mangle]
 pub unsafe extern "C" fn connectorx_scan_iter(
     conn: *const c_char,
     queries: *const CXSlice<*const c_char>,
     batch_size: usize,
 ) -> *mut Box<dyn RecordBatchIterator> {
-    let conn_str = unsafe { CStr::from_ptr(conn) }.to_str().unwrap();
-    let source_conn = SourceConn::try_from(conn_str).unwrap();
+    let scan = || -> Result<Box<dyn RecordBatchIterator>, Box<dyn Error>> {
+        let conn_str = unsafe { CStr::from_ptr(conn) }.to_str()?;
+        let source_conn = SourceConn::try_from(conn_str)?;
 
-    let query_slice = unsafe { std::slice::from_raw_parts((*queries).ptr, (*queries).len) };
+        let query_slice = unsafe { std::slice::from_raw_parts((*queries).ptr, (*queries).len) };
 
-    let mut query_vec = vec![];
-    for &q in query_slice {
-        let query = unsafe { CStr::from_ptr(q).to_str().unwrap() };
-        query_vec.push(CXQuery::from(query));
-    }
+        let mut query_vec = vec![];
+        for &q in query_slice {
+            let query = unsafe { CStr::from_ptr(q).to_str()? };
+            query_vec.push(CXQuery::from(query));
+        }
 
-    let arrow_iter: Box<dyn RecordBatchIterator> = new_record_batch_iter(
-        &source_conn,
-        None,
-        query_vec.as_slice(),
-        batch_size,
-        None,
-        None,
-    );
+        Ok(new_record_batch_iter(
+            &source_conn,
+            None,
+            query_vec.as_slice(),
+            batch_size,
+            None,
+            None,
+            None,
+        )?)
+    };
 
-    Box::into_raw(Box::new(arrow_iter))
+    match scan() {
+        Ok(arrow_iter) => {
+            set_last_error(None);
+            Box::into_raw(Box::new(arrow_iter))
+        }
+        Err(e) => {
+            set_last_error(Some(e.as_ref()));
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[no_mangle]
@@ -337,12 +381,16 @@ pub unsafe extern "C" fn connectorx_prepare(iter: *mut Box<dyn RecordBatchIterat
     arrow_iter.prepare();
 }
 
+/// The next batch of the stream, or null once it is exhausted or on a failure, which
+/// `connectorx_last_error` tells apart.
 #[no_mangle]
 pub unsafe extern "C" fn connectorx_iter_next(
     iter: *mut Box<dyn RecordBatchIterator>,
 ) -> *mut CXSlice<CXArray> {
     let arrow_iter = unsafe { &mut *iter };
-    match arrow_iter.next_batch() {
+    let batch = arrow_iter.try_next_batch();
+    set_last_error(batch.as_ref().err().map(|e| e as &dyn Error));
+    match batch.ok().flatten() {
         Some(rb) => {
             let mut cols = vec![];
 
@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+from collections.abc import Awaitable
 from typing import overload, Literal, Any, TypeAlias, TypedDict
 import numpy as np
 
@@ -7,6 +8,8 @@ _ArrowArrayPtr: TypeAlias = int
 _ArrowSchemaPtr: TypeAlias = int
 _Header: TypeAlias = str
 
+class UnsupportedPoolBackend(ValueError): ...
+
 class PandasBlockInfo:
     cids: list[int]
     dt: int
@@ -15,6 +18,7 @@ class _DataframeInfos(TypedDict):
     data: list[tuple[np.ndarray, ...] | np.ndarray]
     headers: list[_Header]
     block_infos: list[PandasBlockInfo]
+    categories: list[list[str] | None]
 
 _ArrowInfos = tuple[list[_Header], list[list[tuple[_ArrowArrayPtr, _ArrowSchemaPtr]]]]
 
@@ -26,6 +30,8 @@ def read_sql(
     queries: list[str] | None,
     partition_query: dict[str, Any] | None,
     pre_execution_queries: list[str] | None,
+    pool: Any | None = None,
+    params: list[int | float | str | bool | bytes | None] | None = None,
     **kwargs
 ) -> _DataframeInfos: ...
 @overload
@@ -36,10 +42,36 @@ def read_sql(
     queries: list[str] | None,
     partition_query: dict[str, Any] | None,
     pre_execution_queries: list[str] | None,
+    pool: Any | None = None,
+    params: list[int | float | str | bool | bytes | None] | None = None,
     **kwargs
 ) -> _ArrowInfos: ...
+def read_sql_async(
+    conn: str | None,
+    protocol: str | None,
+    queries: list[str] | None,
+    partition_query: dict[str, Any] | None,
+    pre_execution_queries: list[str] | None,
+    pool: Any | None = None,
+) -> Awaitable[_DataframeInfos]: ...
 def partition_sql(conn: str, partition_query: dict[str, Any]) -> list[str]: ...
+def parse_connection(conn: str) -> dict[str, Any]: ...
+def normalize_connection_string(conn: str, redact: bool = False) -> str: ...
+def to_parquet(
+    conn: str | None,
+    queries: list[str],
+    path: str,
+    protocol: str | None = None,
+    batch_size: int = 10000,
+    pool: Any | None = None,
+) -> int: ...
 def read_sql2(sql: str, db_map: dict[str, str]) -> _ArrowInfos: ...
+def set_default_pool_config(config: dict[str, Any]) -> None: ...
+def get_default_pool_config() -> dict[str, Any]: ...
+def set_global_connection_budget(max: int | None) -> None: ...
+def get_global_connection_budget() -> dict[str, int | None]: ...
+def set_async_runtime_threads(n: int) -> None: ...
+def split_statements(sql: str) -> list[str]: ...
 def get_meta(
     conn: str,
     protocol: Literal["csv", "binary", "cursor", "simple", "text"] | None,
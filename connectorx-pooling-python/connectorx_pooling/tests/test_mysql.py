@@ -302,15 +302,15 @@ def test_mysql_types_binary(mysql_url: str) -> None:
             "test_longlong": pd.Series(
                 [-9223372036854775808, 9223372036854775807, None], dtype="Int64"
             ),
-            "test_tiny_unsigned": pd.Series([None, 255, 0], dtype="Int64"),
-            "test_short_unsigned": pd.Series([None, 65535, 0], dtype="Int64"),
-            "test_int24_unsigned": pd.Series([None, 16777215, 0], dtype="Int64"),
-            "test_long_unsigned": pd.Series([None, 4294967295, 0], dtype="Int64"),
+            "test_tiny_unsigned": pd.Series([None, 255, 0], dtype="UInt8"),
+            "test_short_unsigned": pd.Series([None, 65535, 0], dtype="UInt16"),
+            "test_int24_unsigned": pd.Series([None, 16777215, 0], dtype="UInt32"),
+            "test_long_unsigned": pd.Series([None, 4294967295, 0], dtype="UInt32"),
             "test_longlong_unsigned": pd.Series(
-                [None, 18446744070000001024.0, 0.0], dtype="float"
+                [None, 18446744070000001024, 0], dtype="UInt64"
             ),
             "test_long_notnull": pd.Series([1, 2147483647, -2147483648], dtype="int64"),
-            "test_short_unsigned_notnull": pd.Series([1, 65535, 0], dtype="int64"),
+            "test_short_unsigned_notnull": pd.Series([1, 65535, 0], dtype="uint16"),
             "test_float": pd.Series([None, -1.1e-38, 3.4e38], dtype="float"),
             "test_double": pd.Series([-2.2e-308, None, 1.7e308], dtype="float"),
             "test_double_notnull": pd.Series([1.2345, -1.1e-3, 1.7e30], dtype="float"),
@@ -374,15 +374,15 @@ def test_mysql_types_text(mysql_url: str) -> None:
             "test_longlong": pd.Series(
                 [-9223372036854775808, 9223372036854775807, None], dtype="Int64"
             ),
-            "test_tiny_unsigned": pd.Series([None, 255, 0], dtype="Int64"),
-            "test_short_unsigned": pd.Series([None, 65535, 0], dtype="Int64"),
-            "test_int24_unsigned": pd.Series([None, 16777215, 0], dtype="Int64"),
-            "test_long_unsigned": pd.Series([None, 4294967295, 0], dtype="Int64"),
+            "test_tiny_unsigned": pd.Series([None, 255, 0], dtype="UInt8"),
+            "test_short_unsigned": pd.Series([None, 65535, 0], dtype="UInt16"),
+            "test_int24_unsigned": pd.Series([None, 16777215, 0], dtype="UInt32"),
+            "test_long_unsigned": pd.Series([None, 4294967295, 0], dtype="UInt32"),
             "test_longlong_unsigned": pd.Series(
-                [None, 18446744070000001024.0, 0.0], dtype="float"
+                [None, 18446744070000001024, 0], dtype="UInt64"
             ),
             "test_long_notnull": pd.Series([1, 2147483647, -2147483648], dtype="int64"),
-            "test_short_unsigned_notnull": pd.Series([1, 65535, 0], dtype="int64"),
+            "test_short_unsigned_notnull": pd.Series([1, 65535, 0], dtype="uint16"),
             "test_float": pd.Series([None, -1.1e-38, 3.4e38], dtype="float"),
             "test_double": pd.Series([-2.2e-308, None, 1.7e308], dtype="float"),
             "test_double_notnull": pd.Series([1.2345, -1.1e-3, 1.7e30], dtype="float"),
@@ -417,6 +417,27 @@ def test_mysql_types_text(mysql_url: str) -> None:
     assert_frame_equal(df, expected, check_names=True)
 
 
+def test_mysql_bigint_unsigned(mysql_url: str) -> None:
+    query = """
+        SELECT v AS test_ubig FROM (
+            SELECT 1 AS id, CAST(18446744073709551615 AS UNSIGNED) AS v
+            UNION ALL SELECT 2, CAST(18446744073709551614 AS UNSIGNED)
+            UNION ALL SELECT 3, NULL
+        ) t ORDER BY id
+    """
+    expected = [18446744073709551615, 18446744073709551614, None]
+    for protocol in ["binary", "text"]:
+        df = read_sql(mysql_url, query, protocol=protocol)
+        assert_frame_equal(
+            df,
+            pd.DataFrame({"test_ubig": pd.Series(expected, dtype="UInt64")}),
+            check_names=True,
+        )
+        table = read_sql(mysql_url, query, protocol=protocol, return_type="arrow")
+        assert str(table.column("test_ubig").type) == "uint64"
+        assert table.column("test_ubig").to_pylist() == expected
+
+
 def test_empty_result(mysql_url: str) -> None:
     query = "SELECT * FROM test_table where test_int < -100"
     df = read_sql(mysql_url, query)
@@ -486,7 +507,7 @@ def test_mysql_single_pre_execution_queries(mysql_url: str) -> None:
     expected = pd.DataFrame(
         index=range(1),
         data={
-            "max_execution_time": pd.Series([2151], dtype="float64")
+            "max_execution_time": pd.Series([2151], dtype="UInt64")
         },
     )
     assert_frame_equal(df, expected, check_names=True)
@@ -502,8 +523,8 @@ def test_mysql_multiple_pre_execution_queries(mysql_url: str) -> None:
     expected = pd.DataFrame(
         index=range(1),
         data={
-            "max_execution_time": pd.Series([2151], dtype="float64"),
-            "wait_timeout": pd.Series([2252], dtype="float64")
+            "max_execution_time": pd.Series([2151], dtype="UInt64"),
+            "wait_timeout": pd.Series([2252], dtype="UInt64")
         },
     )
     assert_frame_equal(df, expected, check_names=True)
@@ -522,7 +543,7 @@ def test_mysql_partitioned_pre_execution_queries(mysql_url: str) -> None:
         index=range(2),
         data={
             "name": pd.Series(["max_execution_time", "wait_timeout"], dtype="str"),
-            "setting": pd.Series([2151, 2252], dtype="float64"),
+            "setting": pd.Series([2151, 2252], dtype="UInt64"),
         },
     ).sort_values(by=['name']).reset_index(drop=True)
     
@@ -33,6 +33,16 @@ def test_pool_properties(pool: ConnectionPool):
     assert repr(pool) == "ConnectionPool(max_size=5, status=open)"
 
 
+def test_pool_list_tables(pool: ConnectionPool):
+    schemas = pool.list_schemas()
+    assert "mysql" not in schemas
+    tables = pool.list_tables()
+    assert any(t.endswith(".test_table") for t in tables)
+    schema = next(t for t in tables if t.endswith(".test_table")).split(".")[0]
+    assert schema in schemas
+    assert f"{schema}.test_table" in pool.list_tables(schema)
+
+
 # ---------------------------------------------------------------------------
 # Basic query execution
 # ---------------------------------------------------------------------------
@@ -143,3 +153,50 @@ def test_pool_close_is_idempotent(mysql_url: str):
     p.close()
     p.close()
     assert p.is_closed
+
+
+def test_pool_all_null_columns(pool: ConnectionPool):
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_all_null")
+    pool.execute_unchecked(
+        "CREATE TABLE test_all_null(id INT NOT NULL, value INT, note VARCHAR(16))"
+    )
+    try:
+        pool.execute_unchecked("INSERT INTO test_all_null(id) VALUES (1), (2)")
+        query = "SELECT id, value, note FROM test_all_null ORDER BY id"
+        with pool.transaction() as txn:
+            in_txn = txn.read_sql(query)
+        # the dtypes follow the declared column types, however the frame is built
+        for df in (read_sql(pool, query), in_txn):
+            assert df["value"].dtype == "Int64"
+            assert df["note"].dtype == object
+            assert df[["value", "note"]].isna().all().all()
+    finally:
+        pool.execute_unchecked("DROP TABLE test_all_null")
+
+
+def test_pool_unsigned_columns(pool: ConnectionPool):
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_unsigned_pool")
+    pool.execute_unchecked(
+        "CREATE TABLE test_unsigned_pool(id INT NOT NULL, tiny TINYINT UNSIGNED, big BIGINT UNSIGNED)"
+    )
+    try:
+        pool.execute_unchecked(
+            "INSERT INTO test_unsigned_pool VALUES (1, 255, 18446744073709551615), (2, NULL, NULL)"
+        )
+        df = read_sql(pool, "SELECT tiny, big FROM test_unsigned_pool ORDER BY id")
+        expected = pd.DataFrame(
+            {
+                "tiny": pd.Series([255, None], dtype="UInt8"),
+                "big": pd.Series([18446744073709551615, None], dtype="UInt64"),
+            }
+        )
+        assert_frame_equal(df, expected)
+    finally:
+        pool.execute_unchecked("DROP TABLE test_unsigned_pool")
+
+
+def test_pool_read_only(mysql_url: str):
+    with ConnectionPool(mysql_url, max_size=2, read_only=True) as p:
+        assert len(read_sql(p, "SELECT test_int FROM test_table")) > 0
+        with pytest.raises(RuntimeError, match="READ ONLY"):
+            p.execute_unchecked("DELETE FROM test_table WHERE 1 = 0")
@@ -33,6 +33,34 @@ def test_pool_properties(pool: ConnectionPool):
     assert repr(pool) == "ConnectionPool(max_size=5, status=open)"
 
 
+def test_pool_list_tables(pool: ConnectionPool):
+    schemas = pool.list_schemas()
+    assert "public" in schemas
+    assert "pg_catalog" not in schemas
+    tables = pool.list_tables("public")
+    assert "public.test_table" in tables
+    assert all(t.startswith("public.") for t in tables)
+    assert set(tables) <= set(pool.list_tables())
+
+
+def test_pool_get_meta(pool: ConnectionPool):
+    meta = pool.get_meta("SELECT test_int, test_str FROM test_table")
+    table = read_sql(pool, "SELECT test_int, test_str FROM test_table", return_type="arrow")
+    assert [name for name, _ in meta] == table.column_names
+    assert meta[0] == ("test_int", "Int32")
+    assert len(meta) == table.num_columns
+
+    with pytest.raises(ValueError):
+        pool.get_meta("SELECT missing_column FROM test_table")
+
+
+def test_pool_capabilities(pool: ConnectionPool):
+    capabilities = pool.capabilities()
+    assert capabilities["supports_copy"] is True
+    assert capabilities["supports_tls"] is True
+    assert capabilities["supports_transactions"] is True
+
+
 # ---------------------------------------------------------------------------
 # Basic query execution
 # ---------------------------------------------------------------------------
@@ -130,3 +158,89 @@ def test_pool_close_is_idempotent(postgres_url: str):
     p.close()
     p.close()
     assert p.is_closed
+
+
+def test_pool_get_or_create_ignores_param_order(postgres_url: str):
+    sep = "&" if "?" in postgres_url else "?"
+    first = ConnectionPool.get_or_create(
+        f"{postgres_url}{sep}cxprotocol=cursor&sslmode=disable", max_size=2
+    )
+    second = ConnectionPool.get_or_create(f"{postgres_url}{sep}sslmode=disable&cxprotocol=cursor")
+    try:
+        assert second.max_size == 2
+        assert second.default_protocol == "cursor"
+        with first.transaction():
+            assert first.state() == second.state()
+    finally:
+        first.close()
+        second.close()
+
+
+def test_pool_application_name(postgres_url: str):
+    with ConnectionPool(postgres_url, max_size=2, application_name="cx nightly") as p:
+        df = read_sql(p, "SELECT current_setting('application_name') AS name")
+        assert df["name"].tolist() == ["cx nightly"]
+
+
+def test_pool_read_sql_file(pool: ConnectionPool, tmp_path):
+    from .. import read_sql_file
+
+    # the statements before the last one run ahead of it on its connection
+    path = tmp_path / "query.sql"
+    path.write_text(
+        "SET SESSION statement_timeout = 2151;\n"
+        "SELECT current_setting('statement_timeout') AS timeout, 'a;b' AS s;\n"
+    )
+    df = read_sql_file(pool, path)
+    assert df["timeout"].tolist() == ["2151ms"]
+    assert df["s"].tolist() == ["a;b"]
+
+
+def test_pool_on_return_query(postgres_url: str):
+    with ConnectionPool(postgres_url, max_size=1, on_return_query="DISCARD ALL") as p:
+        with p.transaction() as txn:
+            txn.execute("SET SESSION statement_timeout = 1234")
+        # the next borrower gets the same connection with its session reset
+        df = read_sql(p, "SELECT current_setting('statement_timeout') AS timeout")
+        assert df["timeout"].tolist() == ["0"]
+
+
+def test_pool_trim_idle(postgres_url: str):
+    with ConnectionPool(postgres_url, max_size=8, min_idle=0) as p:
+        p.reserve(8).release()
+        assert p.state()["idle_connections"] == 8
+
+        assert p.trim_idle(2) == 6
+        assert p.state()["idle_connections"] == 2
+
+        # the pool stays usable and reopens connections on demand
+        df = read_sql(p, "SELECT 1 AS one")
+        assert df["one"].tolist() == [1]
+
+
+def test_pool_all_null_columns(pool: ConnectionPool):
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_all_null")
+    pool.execute_unchecked(
+        "CREATE TABLE test_all_null(id INTEGER NOT NULL, value BIGINT, flag BOOLEAN, note TEXT)"
+    )
+    try:
+        pool.execute_unchecked("INSERT INTO test_all_null(id) VALUES (1), (2)")
+        query = "SELECT id, value, flag, note FROM test_all_null ORDER BY id"
+        with pool.transaction() as txn:
+            in_txn = txn.read_sql(query)
+        # the dtypes follow the declared column types, however the frame is built
+        for df in (read_sql(pool, query), in_txn):
+            assert df["value"].dtype == "Int64"
+            assert df["flag"].dtype == "boolean"
+            assert df["note"].dtype == object
+            assert df[["value", "flag", "note"]].isna().all().all()
+    finally:
+        pool.execute_unchecked("DROP TABLE test_all_null")
+
+
+def test_pool_read_only(postgres_url: str):
+    with ConnectionPool(postgres_url, max_size=2, read_only=True) as p:
+        df = read_sql(p, "SELECT test_int FROM test_table ORDER BY test_int LIMIT 1")
+        assert df["test_int"].tolist() == [0]
+        with pytest.raises(RuntimeError):
+            p.execute_unchecked("DELETE FROM test_table WHERE false")
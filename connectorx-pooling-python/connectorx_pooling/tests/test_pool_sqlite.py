@@ -1,10 +1,14 @@
 """SQLite connection pool integration tests."""
+import asyncio
+import itertools
 import os
+import sqlite3
+import time
 
 import pytest
 from pandas.testing import assert_frame_equal
 
-from .. import ConnectionPool, read_sql
+from .. import ConnectionPool, read_sql, read_sql_async
 
 
 @pytest.fixture(scope="module")  # type: ignore
@@ -32,11 +36,110 @@ def test_pool_properties(pool: ConnectionPool):
     assert repr(pool) == "ConnectionPool(max_size=5, status=open)"
 
 
+def test_pool_capabilities(pool: ConnectionPool):
+    assert pool.capabilities() == {
+        "supports_transactions": True,
+        "supports_prepared": True,
+        "supports_copy": False,
+        "supports_partition": True,
+        "supports_tls": False,
+    }
+
+
+def test_pool_metrics(pool: ConnectionPool):
+    read_sql(pool, "SELECT test_int FROM test_table")
+    text = pool.metrics()
+    assert "# HELP connectorx_pool_connections " in text
+    assert "# TYPE connectorx_pool_connections gauge" in text
+    assert "# TYPE connectorx_pool_checkouts_total counter" in text
+    assert 'connectorx_pool_max_size{backend="sqlite"} 5' in text
+    assert 'cx_max_size{backend="sqlite"} 5' in pool.metrics("cx")
+    assert "label=" not in pool.metrics()
+
+
+def test_pool_metrics_label(sqlite_url: str):
+    with ConnectionPool(sqlite_url, max_size=2, label="tenant-a") as p:
+        text = p.metrics()
+        assert 'connectorx_pool_connections{backend="sqlite",label="tenant-a"}' in text
+        assert 'connectorx_pool_max_size{backend="sqlite",label="tenant-a"} 2' in text
+
+
+def test_pool_metrics_closed_raises(pool: ConnectionPool):
+    pool.close()
+    with pytest.raises(ValueError, match="closed"):
+        pool.metrics()
+
+
+def test_pool_server_version(pool: ConnectionPool):
+    version = pool.server_version()
+    # the bundled SQLite, which may differ from the one Python's sqlite3 links against
+    assert version.split(".")[0] == "3"
+    assert pool.server_version() == version
+
+
+def test_pool_diagnose(sqlite_url: str, tmp_path):
+    assert ConnectionPool.diagnose(sqlite_url) == {"diagnosis": "Ok", "message": None}
+
+    missing = tmp_path / "missing" / "db.sqlite"
+    result = ConnectionPool.diagnose(f"sqlite://{missing}?mode=ro")
+    assert result["diagnosis"] == "DatabaseNotFound"
+    assert "unable to open database file" in result["message"]
+
+
+def test_pool_env_conn(sqlite_url: str, monkeypatch):
+    monkeypatch.setenv("CX_TEST_SQLITE_URL", sqlite_url)
+    with ConnectionPool("env:CX_TEST_SQLITE_URL", max_size=2) as pool:
+        df = read_sql(pool, "SELECT test_int FROM test_table")
+        assert len(df) > 0
+    df = read_sql("env:CX_TEST_SQLITE_URL", "SELECT test_int FROM test_table")
+    assert len(df) > 0
+
+    monkeypatch.delenv("CX_TEST_SQLITE_URL")
+    with pytest.raises(RuntimeError, match="CX_TEST_SQLITE_URL"):
+        ConnectionPool("env:CX_TEST_SQLITE_URL")
+    with pytest.raises(RuntimeError, match="CX_TEST_SQLITE_URL"):
+        read_sql("env:CX_TEST_SQLITE_URL", "SELECT 1")
+
+
+def test_pool_execute_unchecked(pool: ConnectionPool):
+    pool.execute_unchecked("SELECT 1")
+    assert not pool.is_closed
+
+
+def test_pool_execute_unchecked_closed_raises(pool: ConnectionPool):
+    pool.close()
+    with pytest.raises(ValueError, match="closed"):
+        pool.execute_unchecked("SELECT 1")
+
+
+def test_pool_get_meta(pool: ConnectionPool):
+    meta = pool.get_meta("SELECT test_int, test_str FROM test_table")
+    table = read_sql(pool, "SELECT test_int, test_str FROM test_table", return_type="arrow")
+    assert [name for name, _ in meta] == table.column_names
+    assert meta[0] == ("test_int", "Int64")
+    assert len(meta) == table.num_columns
+
+    with pytest.raises(ValueError):
+        pool.get_meta("SELECT missing_column FROM test_table")
+
+
 # ---------------------------------------------------------------------------
 # Basic query execution
 # ---------------------------------------------------------------------------
 
 
+def test_pool_init_queries(sqlite_url: str):
+    init_queries = [
+        "CREATE TEMP TABLE IF NOT EXISTS init_runs(n INTEGER)",
+        "INSERT INTO temp.init_runs VALUES (1)",
+    ]
+    with ConnectionPool(sqlite_url, max_size=1, init_queries=init_queries) as pool:
+        # every read checks the one connection out again, the init queries ran once on it
+        for _ in range(3):
+            df = read_sql(pool, "SELECT count(*) AS runs FROM temp.init_runs")
+            assert df["runs"].tolist() == [1]
+
+
 def test_pool_single_query(pool: ConnectionPool):
     df = read_sql(pool, "SELECT test_int FROM test_table")
     assert len(df) == 6
@@ -66,6 +169,53 @@ def test_pool_partitioned_query(pool: ConnectionPool):
     assert len(df) == 6
 
 
+def test_pool_return_meta(pool: ConnectionPool):
+    df, meta = read_sql(
+        pool,
+        "SELECT test_int FROM test_table",
+        partition_on="test_int",
+        partition_num=3,
+        return_meta=True,
+    )
+    assert len(df) == 6
+    assert meta == {"protocol": pool.default_protocol, "partitions": 3, "pooled": True}
+
+
+def test_pool_return_meta_null_counts(pool: ConnectionPool):
+    _, meta = read_sql(
+        pool,
+        "SELECT test_int, test_nullint, test_str, test_datetime FROM test_table",
+        partition_on="test_int",
+        partition_num=3,
+        return_type="arrow",
+        return_meta=True,
+    )
+    assert meta["null_counts"] == {
+        "test_int": 0,
+        "test_nullint": 1,
+        "test_str": 1,
+        "test_datetime": 2,
+    }
+
+
+@pytest.mark.parametrize("return_type", ["pandas", "arrow"])
+def test_pool_collect_timings(pool: ConnectionPool, return_type: str):
+    queries = [
+        "SELECT test_int FROM test_table WHERE test_int < 2",
+        "SELECT test_int FROM test_table WHERE test_int >= 2",
+    ]
+    df, timings = read_sql(pool, queries, return_type=return_type, collect_timings=True)
+    assert len(timings) == len(queries)
+    assert [t["query"] for t in timings] == queries
+    assert sum(t["rows"] for t in timings) == len(df)
+    for timing in timings:
+        assert timing["checkout_ms"] >= 0
+        assert timing["exec_ms"] >= 0
+
+    with pytest.raises(ValueError, match="collect_timings"):
+        read_sql(pool, queries[0], return_meta=True, collect_timings=True)
+
+
 def test_pool_partition_num_exceeds_max_size_raises(sqlite_url: str):
     p = ConnectionPool(sqlite_url, max_size=3)
     try:
@@ -126,3 +276,555 @@ def test_pool_close_is_idempotent(sqlite_url: str):
     p.close()
     p.close()
     assert p.is_closed
+
+
+def test_pool_event_callback(sqlite_url: str):
+    import time
+
+    events = []
+    p = ConnectionPool(sqlite_url, max_size=3)
+    p.set_event_callback(events.append)
+    read_sql(p, "SELECT test_int FROM test_table")
+    p.close()
+
+    # callbacks run on a background thread
+    deadline = time.monotonic() + 5
+    while not (events and events[-1]["event"] == "close") and time.monotonic() < deadline:
+        time.sleep(0.01)
+    acquired = [e for e in events if e["event"] == "acquire"]
+    assert acquired and all(e["latency_ms"] >= 0 for e in acquired)
+    assert events[-1]["event"] == "close"
+    assert events[-1]["connections"] >= 1
+    assert all("label" not in e for e in events)
+
+
+def test_pool_event_callback_label(sqlite_url: str):
+    events = []
+    p = ConnectionPool(sqlite_url, max_size=2, label="tenant-a")
+    p.set_event_callback(events.append)
+    read_sql(p, "SELECT test_int FROM test_table")
+    p.close()
+
+    deadline = time.monotonic() + 5
+    while not (events and events[-1]["event"] == "close") and time.monotonic() < deadline:
+        time.sleep(0.01)
+    assert events[-1]["event"] == "close"
+    assert all(e["label"] == "tenant-a" for e in events)
+
+
+def test_pool_read_sql_async(pool: ConnectionPool):
+    query = "SELECT test_int, test_str FROM test_table"
+
+    async def read_both():
+        return await asyncio.gather(
+            read_sql_async(pool, query),
+            read_sql_async(pool, query, partition_on="test_int", partition_num=2),
+        )
+
+    whole, partitioned = asyncio.run(read_both())
+    expected = read_sql(pool, query)
+    assert_frame_equal(whole, expected, check_names=True)
+    assert_frame_equal(
+        partitioned.sort_values("test_int").reset_index(drop=True),
+        expected.sort_values("test_int").reset_index(drop=True),
+        check_names=True,
+    )
+
+
+def test_pool_transaction(tmp_path):
+    db = tmp_path / "txn.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL, name TEXT)")
+
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        with pytest.raises(RuntimeError, match="boom"):
+            with pool.transaction() as txn:
+                txn.execute("INSERT INTO items VALUES (1, 'a')")
+                txn.execute("INSERT INTO items VALUES (2, 'b')")
+                # reads on the transaction see its uncommitted rows
+                assert len(txn.read_sql("SELECT * FROM items")) == 2
+                raise RuntimeError("boom")
+        assert len(read_sql(pool, "SELECT * FROM items")) == 0
+
+        with pool.transaction() as txn:
+            txn.execute("INSERT INTO items VALUES (3, 'c')")
+        assert read_sql(pool, "SELECT id FROM items")["id"].tolist() == [3]
+
+
+def test_pool_transaction_commit_while_executing(tmp_path):
+    import threading
+
+    db = tmp_path / "txn_threads.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL, name TEXT)")
+
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        txn = pool.transaction()
+        insert = threading.Thread(
+            target=txn.execute,
+            args=(
+                "INSERT INTO items WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000000) SELECT i, NULL FROM n",
+            ),
+        )
+        insert.start()
+        time.sleep(0.05)
+        # waits for the insert with the GIL released instead of deadlocking with it
+        txn.commit()
+        insert.join(timeout=30)
+        assert not insert.is_alive()
+        assert len(read_sql(pool, "SELECT id FROM items")) == 1000000
+
+
+def test_pool_get_or_create(tmp_path):
+    db = tmp_path / "shared.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+
+    first = ConnectionPool.get_or_create(f"sqlite://{db}", max_size=3)
+    second = ConnectionPool.get_or_create(f"sqlite://{db}")
+    # the settings of the first call win
+    assert second.max_size == 3
+    with first.transaction():
+        assert first.state() == second.state()
+        state = second.state()
+        assert state["connections"] - state["idle_connections"] == 1
+
+    # a plain ConnectionPool is never shared
+    with ConnectionPool(f"sqlite://{db}", max_size=3) as separate:
+        with first.transaction():
+            assert separate.state()["connections"] - separate.state()["idle_connections"] == 0
+
+    # once every sharing pool is closed the next call builds a new one
+    first.close()
+    assert not second.is_closed
+    second.close()
+    third = ConnectionPool.get_or_create(f"sqlite://{db}")
+    assert third.max_size == 10
+    third.close()
+
+
+def test_pool_reserve(tmp_path):
+    db = tmp_path / "reserve.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+
+    with ConnectionPool(f"sqlite://{db}", max_size=4, connection_timeout=1) as pool:
+        with pytest.raises(ValueError, match="at most 4"):
+            pool.reserve(5)
+
+        with pool.reserve(3) as reservation:
+            assert len(reservation) == 3
+            reservation.execute(0, "INSERT INTO items VALUES (1)")
+            assert reservation.read_sql(2, "SELECT id FROM items")["id"].tolist() == [1]
+            with pytest.raises(IndexError):
+                reservation.execute(3, "SELECT 1")
+
+            # the fourth connection is still free, a fifth times out
+            with pool.transaction():
+                with pytest.raises(RuntimeError):
+                    pool.transaction()
+        assert len(reservation) == 0
+
+        # every reserved connection is back in the pool
+        with pool.reserve(4) as reservation:
+            assert len(reservation) == 4
+
+
+def test_pool_trim_idle(tmp_path):
+    db = tmp_path / "trim.db"
+    sqlite3.connect(db).close()
+
+    # by default the pool keeps all its connections open
+    with ConnectionPool(f"sqlite://{db}", max_size=4) as pool:
+        with pytest.raises(RuntimeError, match="min_idle"):
+            pool.trim_idle(2)
+        assert pool.trim_idle(4) == 0
+
+    with ConnectionPool(f"sqlite://{db}", max_size=4, min_idle=0) as pool:
+        pool.reserve(4).release()
+        assert pool.trim_idle(1) == 3
+        assert pool.state() == {"connections": 1, "idle_connections": 1}
+        assert read_sql(pool, "SELECT 1 AS one")["one"].tolist() == [1]
+
+
+def test_pool_execute_many(tmp_path):
+    db = tmp_path / "many.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL, name TEXT)")
+
+    rows = [(i, f"item{i}" if i % 2 else None) for i in range(1000)]
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        assert pool.execute_many("INSERT INTO items VALUES (?, ?)", rows) == 1000
+
+        # a failing row rolls the whole batch back
+        with pytest.raises(RuntimeError):
+            pool.execute_many("INSERT INTO items VALUES (?, ?)", [(1000, "a"), (None, "b")])
+        assert len(read_sql(pool, "SELECT * FROM items")) == 1000
+
+    # committed rows are visible to a fresh connection
+    with sqlite3.connect(db) as conn:
+        assert conn.execute("SELECT COUNT(*), COUNT(name) FROM items").fetchone() == (1000, 500)
+
+
+def test_pool_read_sql_file(pool: ConnectionPool, tmp_path):
+    from .. import read_sql_file
+
+    path = tmp_path / "query.sql"
+    path.write_text("-- ints; no strings\nSELECT test_int FROM test_table WHERE test_int > 0;\n")
+    df = read_sql_file(pool, path)
+    assert_frame_equal(df, read_sql(pool, "SELECT test_int FROM test_table WHERE test_int > 0"))
+
+    path.write_text("-- nothing to run\n")
+    with pytest.raises(ValueError, match="no SQL statement"):
+        read_sql_file(pool, path)
+
+
+def test_pool_stream_sql(tmp_path):
+    import gc
+    import pickle
+
+    db = tmp_path / "stream.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL, name TEXT)")
+        conn.executemany("INSERT INTO items VALUES (?, ?)", [(i, f"item{i}") for i in range(1000)])
+
+    query = "SELECT id, name FROM items"
+    # a single connection, so reads after the stream only succeed once it has been returned
+    with ConnectionPool(f"sqlite://{db}", max_size=1, connection_timeout=2) as pool:
+        batches = list(pool.stream_sql(query, batch_size=64))
+        assert len(batches) > 1
+        assert all(batch.num_rows <= 64 for batch in batches)
+        assert batches[0].schema.names == ["id", "name"]
+        streamed = sum(sum(batch.column("id").to_pylist()) for batch in batches)
+        buffered = read_sql(pool, query, return_type="arrow")
+        assert streamed == sum(buffered.column("id").to_pylist())
+
+        # a partially read stream gives its connection back once collected
+        stream = pool.stream_sql(query, batch_size=64)
+        next(stream)
+        with pytest.raises(TypeError):
+            pickle.dumps(stream)
+        del stream
+        gc.collect()
+        assert len(read_sql(pool, query)) == 1000
+
+
+def test_pool_stream_reader(tmp_path):
+    import pyarrow as pa
+
+    db = tmp_path / "reader.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL, name TEXT)")
+        conn.executemany("INSERT INTO items VALUES (?, ?)", [(i, f"item{i}") for i in range(1000)])
+
+    query = "SELECT id, name FROM items ORDER BY id"
+    # a single connection, so reads after the reader only succeed once it has been returned
+    with ConnectionPool(f"sqlite://{db}", max_size=1, connection_timeout=2) as pool:
+        reader = pool.stream_reader(query, batch_size=64)
+        assert isinstance(reader, pa.RecordBatchReader)
+        assert reader.schema.names == ["id", "name"]
+        table = reader.read_all()
+        assert all(batch.num_rows <= 64 for batch in table.to_batches())
+        buffered = read_sql(pool, query, return_type="arrow")
+        assert table.num_rows == 1000
+        assert table.column("id").to_pylist() == buffered.column("id").to_pylist()
+        assert table.column("name").to_pylist() == buffered.column("name").to_pylist()
+
+        # a partially read reader gives its connection back once closed
+        reader = pool.stream_reader(query, batch_size=64)
+        assert reader.read_next_batch().num_rows == 64
+        reader.close()
+        assert len(read_sql(pool, query)) == 1000
+
+
+def test_pool_stream_failed_read_raises(tmp_path):
+    import pyarrow as pa
+
+    db = tmp_path / "failed.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+        conn.executemany("INSERT INTO items VALUES (?)", [(i,) for i in range(1000)])
+
+    # typed as an integer by the first row, the text of the last rows fails the read
+    query = "SELECT CASE WHEN id < 900 THEN id ELSE 'x' END AS id FROM items ORDER BY id"
+    with ConnectionPool(f"sqlite://{db}", max_size=1, connection_timeout=2) as pool:
+        with pytest.raises(RuntimeError):
+            list(pool.stream_sql(query, batch_size=64))
+        with pytest.raises(pa.ArrowInvalid):
+            pool.stream_reader(query, batch_size=64).read_all()
+        with pytest.raises(RuntimeError):
+            pool.read_dicts(query)
+        # the failed streams gave their connection back
+        assert len(read_sql(pool, "SELECT id FROM items")) == 1000
+
+
+def test_pool_benchmark_protocols(tmp_path):
+    db = tmp_path / "benchmark.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+        conn.executemany("INSERT INTO items VALUES (?)", [(i,) for i in range(100)])
+
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        results = pool.benchmark_protocols("SELECT id FROM items", repeat=2)
+        assert set(results) == {"binary", "csv", "cursor"}
+        assert set(results["binary"]) == {"median_ms", "rows", "runs"}
+        assert results["binary"]["rows"] == 100
+        assert results["binary"]["runs"] == 2
+        assert results["binary"]["median_ms"] >= 0
+        # SQLite only reads through the binary protocol
+        for protocol in ["csv", "cursor"]:
+            assert results[protocol] == {"skipped": f"SQLite does not support the {protocol} protocol"}
+
+        with pytest.raises(ValueError, match="repeat"):
+            pool.benchmark_protocols("SELECT id FROM items", repeat=0)
+
+
+def test_pool_circuit_breaker(tmp_path):
+    db = tmp_path / "breaker.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+
+    with ConnectionPool(f"sqlite://{db}", max_size=1, connection_timeout=1) as pool:
+        assert pool.circuit_state() is None
+
+    with ConnectionPool(
+        f"sqlite://{db}",
+        max_size=1,
+        connection_timeout=1,
+        failure_threshold=2,
+        open_duration=2,
+    ) as pool:
+        assert pool.circuit_state() == "closed"
+        with pool.transaction():
+            # the only connection is taken, so every checkout times out
+            for _ in range(2):
+                with pytest.raises(RuntimeError):
+                    pool.transaction()
+            assert pool.circuit_state() == "open"
+            start = time.monotonic()
+            with pytest.raises(RuntimeError, match="Circuit breaker open"):
+                pool.transaction()
+            assert time.monotonic() - start < 0.5
+
+        time.sleep(2)
+        assert pool.circuit_state() == "half_open"
+        assert len(read_sql(pool, "SELECT * FROM items")) == 0
+        assert pool.circuit_state() == "closed"
+
+
+def test_pool_read_dicts(tmp_path):
+    db = tmp_path / "dicts.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute(
+            "CREATE TABLE items(id INTEGER NOT NULL, name TEXT, score REAL, data BLOB, at DATETIME)"
+        )
+        conn.executemany(
+            "INSERT INTO items VALUES (?, ?, ?, ?, ?)",
+            [
+                (1, "a", 0.5, b"\x00\x01", "2024-01-01 10:00:00"),
+                (2, None, 1.5, b"", "2024-02-29 23:59:59"),
+                (3, "c", -2.0, None, None),
+            ],
+        )
+
+    query = "SELECT * FROM items ORDER BY id"
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        dicts = pool.read_dicts(query)
+        assert [type(row["id"]) for row in dicts] == [int, int, int]
+        assert dicts[1]["name"] is None
+        assert dicts[0]["data"] == b"\x00\x01"
+        assert dicts[2]["at"] is None
+        records = read_sql(pool, query).to_dict("records")
+        assert [row["at"] for row in dicts[:2]] == [row["at"] for row in records[:2]]
+        for row, record in zip(dicts, records):
+            assert {k: v for k, v in row.items() if k != "at"} == {
+                k: v for k, v in record.items() if k != "at"
+            }
+
+        assert pool.read_dicts(query, limit=2) == dicts[:2]
+        assert pool.read_dicts(query, limit=0) == []
+        assert pool.read_dicts("SELECT * FROM items WHERE id > 3") == []
+
+        conn = sqlite3.connect(db)
+        conn.execute("CREATE TABLE many(id INTEGER NOT NULL)")
+        conn.execute(
+            "INSERT INTO many WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 100001) SELECT i FROM n"
+        )
+        conn.commit()
+        conn.close()
+        with pytest.raises(ValueError, match="more than 100000 rows"):
+            pool.read_dicts("SELECT id FROM many")
+        assert len(pool.read_dicts("SELECT id FROM many", limit=100001)) == 100001
+
+
+def test_pool_list_tables(tmp_path):
+    db = tmp_path / "catalog.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE orders(id INTEGER)")
+        conn.execute("CREATE TABLE customers(id INTEGER)")
+        conn.execute("CREATE VIEW big_orders AS SELECT * FROM orders WHERE id > 10")
+
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        assert pool.list_schemas() == ["main"]
+        assert pool.list_tables() == ["main.customers", "main.orders"]
+        assert pool.list_tables("main") == ["main.customers", "main.orders"]
+        assert pool.list_tables(schema="missing") == []
+
+        pool.close()
+        with pytest.raises(ValueError, match="closed"):
+            pool.list_tables()
+
+
+def test_pool_bypass_pool(tmp_path):
+    db = tmp_path / "bypass.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+        conn.executemany("INSERT INTO items VALUES (?)", [(1,), (2,), (3,)])
+
+    queries = ["SELECT * FROM items WHERE id < 3", "SELECT * FROM items WHERE id >= 3"]
+    with ConnectionPool(f"sqlite://{db}", max_size=1, min_idle=0) as pool:
+        assert pool.state()["connections"] == 0
+        df = read_sql(pool, queries, bypass_pool=True)
+        assert sorted(df["id"].tolist()) == [1, 2, 3]
+        table = read_sql(pool, queries, return_type="arrow", bypass_pool=True)
+        assert table.num_rows == 3
+        # more partitions than the pool holds are fine without the pool
+        df = read_sql(
+            pool,
+            "SELECT * FROM items",
+            partition_on="id",
+            partition_num=3,
+            bypass_pool=True,
+        )
+        assert len(df) == 3
+        assert pool.state()["connections"] == 0
+
+        read_sql(pool, "SELECT * FROM items")
+        assert pool.state()["connections"] == 1
+
+
+def test_pool_warm_async(tmp_path):
+    db = tmp_path / "warm.db"
+    sqlite3.connect(db).close()
+    with ConnectionPool(f"sqlite://{db}", max_size=6, min_idle=0) as pool:
+        assert pool.wait_warm(0)
+        pool.warm_async(4)
+        assert pool.wait_warm(timeout=10)
+        assert pool.state()["idle_connections"] >= 4
+        # nothing is left to wait for
+        assert pool.wait_warm()
+
+        with pytest.raises(ValueError, match="at most 6"):
+            pool.warm_async(7)
+
+
+def test_pool_wait_warm_concurrent(tmp_path):
+    import threading
+
+    db = tmp_path / "warm_concurrent.db"
+    sqlite3.connect(db).close()
+    with ConnectionPool(f"sqlite://{db}", max_size=6, min_idle=0) as pool:
+        pool.warm_async(4)
+        # waits on other threads while this one keeps starting warmings, which would
+        # deadlock if a wait held the pool's lock with the GIL released
+        waiters = [threading.Thread(target=pool.wait_warm) for _ in range(4)]
+        for waiter in waiters:
+            waiter.start()
+        for _ in range(20):
+            pool.warm_async(2)
+        for waiter in waiters:
+            waiter.join(timeout=10)
+            assert not waiter.is_alive()
+        assert pool.wait_warm(timeout=10)
+
+
+def test_pool_all_null_columns(tmp_path):
+    db = tmp_path / "all_null.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute(
+            "CREATE TABLE readings(id INTEGER NOT NULL, value INTEGER, flag BOOLEAN, note TEXT)"
+        )
+        conn.executemany("INSERT INTO readings(id) VALUES (?)", [(1,), (2,)])
+
+    query = "SELECT id, value, flag, note FROM readings ORDER BY id"
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        with pool.transaction() as txn:
+            in_txn = txn.read_sql(query)
+        with_params = read_sql(pool, query.replace("ORDER BY", "WHERE id > ? ORDER BY"), params=[0])
+        # the dtypes follow the declared column types, however the frame is built
+        for df in (read_sql(pool, query), in_txn, with_params):
+            assert df["value"].dtype == "Int64"
+            assert df["flag"].dtype == "boolean"
+            assert df["note"].dtype == object
+            assert df[["value", "flag", "note"]].isna().all().all()
+
+
+def test_pool_read_only(tmp_path):
+    db = tmp_path / "read_only.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+        conn.execute("INSERT INTO items VALUES (1)")
+
+    with ConnectionPool(f"sqlite://{db}", max_size=2, read_only=True) as pool:
+        assert read_sql(pool, "SELECT id FROM items")["id"].tolist() == [1]
+        with pytest.raises(RuntimeError, match="readonly"):
+            pool.execute_unchecked("INSERT INTO items VALUES (2)")
+
+
+def test_pool_schema_cache(tmp_path):
+    db = tmp_path / "schema_cache.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL, name TEXT)")
+        conn.executemany("INSERT INTO items VALUES (?, ?)", [(1, "a"), (2, "b")])
+
+    with ConnectionPool(f"sqlite://{db}", max_size=2, schema_cache=True) as pool:
+        first = read_sql(pool, "SELECT id, name FROM items ORDER BY id")
+        # misses count the reads that described their query, so the second one skipped it
+        second = read_sql(pool, "SELECT id, name\n  FROM items ORDER BY id")
+        assert_frame_equal(first, second)
+        assert pool.schema_cache_info() == {"hits": 1, "misses": 1, "entries": 1}
+
+        pool.clear_schema_cache()
+        assert pool.schema_cache_info()["entries"] == 0
+
+    with ConnectionPool(f"sqlite://{db}", max_size=2) as pool:
+        assert pool.schema_cache_info() is None
+
+
+def test_pool_read_sql_progress_callback(tmp_path):
+    db = tmp_path / "progress.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE items(id INTEGER NOT NULL)")
+        conn.executemany("INSERT INTO items VALUES (?)", [(i,) for i in range(1000)])
+
+    queries = [f"SELECT id FROM items WHERE id % 4 = {i}" for i in range(4)]
+    with ConnectionPool(f"sqlite://{db}", max_size=4) as pool:
+        calls = []
+        df = pool.read_sql(queries, progress_callback=lambda *args: calls.append(args))
+        assert sorted(df["id"].tolist()) == list(range(1000))
+        assert calls == [(1, 4), (2, 4), (3, 4), (4, 4)]
+
+        def fail(completed, total):
+            raise ZeroDivisionError("stop")
+
+        with pytest.raises(ZeroDivisionError, match="stop"):
+            pool.read_sql(queries, return_type="arrow", progress_callback=fail)
+
+        calls = []
+        batches = list(
+            pool.stream_sql(
+                "SELECT id FROM items",
+                batch_size=64,
+                progress_callback=lambda *args: calls.append(args),
+            )
+        )
+        assert [rows for rows, _ in calls] == list(itertools.accumulate(b.num_rows for b in batches))
+        assert all(total is None for _, total in calls)
+        assert calls[-1][0] == 1000
+
+        stream = pool.stream_sql("SELECT id FROM items", batch_size=64, progress_callback=fail)
+        with pytest.raises(ZeroDivisionError):
+            next(stream)
+        # the aborted stream gave its connection back
+        assert next(stream, None) is None
+        assert len(pool.read_sql("SELECT id FROM items")) == 1000
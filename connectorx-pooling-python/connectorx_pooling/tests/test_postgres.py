@@ -471,6 +471,41 @@ def test_postgres_types_vec_csv(postgres_url: str) -> None:
     df = read_sql(postgres_url, query, protocol="csv")
     verify_data_types_vec(df)
 
+def test_postgres_return_meta(postgres_url: str) -> None:
+    query = "SELECT * FROM test_table"
+    df, meta = read_sql(
+        postgres_url,
+        query,
+        protocol="cursor",
+        partition_on="test_int",
+        partition_range=(0, 2000),
+        partition_num=3,
+        return_meta=True,
+    )
+    assert len(df) == 6
+    assert meta == {"protocol": "cursor", "partitions": 3, "pooled": True}
+
+    _, meta = read_sql(postgres_url, query, return_type="arrow", return_meta=True)
+    assert meta["protocol"] == "binary"
+    assert meta["partitions"] == 1
+
+def test_postgres_cxprotocol_param(postgres_url: str) -> None:
+    sep = "&" if "?" in postgres_url else "?"
+    query = "SELECT * FROM test_table"
+    df, meta = read_sql(f"{postgres_url}{sep}cxprotocol=cursor", query, return_meta=True)
+    assert len(df) == 6
+    assert meta["protocol"] == "cursor"
+
+    # an explicit protocol argument takes precedence
+    _, meta = read_sql(
+        f"{postgres_url}{sep}cxprotocol=cursor", query, protocol="simple", return_meta=True
+    )
+    assert meta["protocol"] == "simple"
+
+    with pytest.raises(RuntimeError, match="Unsupported protocol"):
+        read_sql(f"{postgres_url}{sep}cxprotocol=text", query)
+
+
 def test_postgres_types_cursor(postgres_url: str) -> None:
     query = "SELECT test_date, test_timestamp, test_timestamptz, test_int2, test_int4, test_int8, test_float4, test_float8, test_numeric, test_bpchar, test_char, test_varchar, test_uuid, test_time, test_json, test_jsonb, test_bytea, test_citext, test_ltree, test_lquery, test_ltxtquery, test_name FROM test_types"
     df = read_sql(postgres_url, query, protocol="cursor")
@@ -577,7 +612,10 @@ def verify_data_types(df, protocol) -> None:
                 dtype="object",
             ),
             "test_enum": pd.Series(
-                ["happy", "very happy", "ecstatic", "ecstatic", None]
+                pd.Categorical(
+                    ["happy", "very happy", "ecstatic", "ecstatic", None],
+                    categories=["happy", "very happy", "ecstatic"],
+                )
             ),
             "test_citext": pd.Series(
                 ["str_citext", "", "abcdef", "1234", None]
@@ -1141,3 +1179,96 @@ def test_postgres_vector_types(postgres_url: str) -> None:
         },
     )
     assert_frame_equal(df[['binary_vector', 'sparse_vector']], expected[['binary_vector', 'sparse_vector']], check_names=True)
+
+
+@pytest.mark.parametrize("protocol", ["binary", "csv", "cursor", "simple"])
+def test_postgres_arrays_with_nulls(postgres_url: str, protocol: str) -> None:
+    query = """
+        SELECT * FROM (VALUES
+            (1, '{1,NULL,3}'::int4[], '{a,NULL,"b,c"}'::text[]),
+            (2, '{}'::int4[], '{}'::text[]),
+            (3, NULL::int4[], NULL::text[])
+        ) AS t(id, ia, ta) ORDER BY id
+    """
+    df = read_sql(postgres_url, query, protocol=protocol)
+    assert df["ia"].dtype == np.dtype("object")
+    assert df["ta"].dtype == np.dtype("object")
+    assert list(df["ia"]) == [[1, None, 3], [], None]
+    assert list(df["ta"]) == [["a", None, "b,c"], [], None]
+
+
+def test_postgres_multi_dimensional_array(postgres_url: str) -> None:
+    with pytest.raises(RuntimeError, match="multi-dimensional array"):
+        read_sql(postgres_url, "SELECT '{{1,2},{3,4}}'::int4[] AS ia")
+
+
+@pytest.mark.parametrize("protocol", ["binary", "csv", "cursor", "simple"])
+def test_postgres_interval(postgres_url: str, protocol: str) -> None:
+    query = """
+        SELECT * FROM (VALUES
+            (1, interval '1 day 2:03:04', interval '3 months'),
+            (2, interval '-1 day +00:00:01.5', interval '1 year -2 days'),
+            (3, NULL, NULL)
+        ) AS t(id, plain, monthly) ORDER BY id
+    """
+    df = read_sql(postgres_url, query, protocol=protocol)
+    assert df["plain"].dtype == np.dtype("timedelta64[ns]")
+    assert df["plain"][0] == pd.Timedelta(days=1, hours=2, minutes=3, seconds=4)
+    assert df["plain"][1] == pd.Timedelta(days=-1, seconds=1.5)
+    assert pd.isna(df["plain"][2])
+    # months have no fixed length, so columns holding any are left as text
+    assert df["monthly"].tolist() == ["3 mons", "1 year -2 days", None]
+
+    table = read_sql(postgres_url, query, protocol=protocol, return_type="arrow")
+    assert str(table.column("monthly").type) == "month_day_nano_interval"
+    assert table.column("monthly").to_pylist()[:2] == [(3, 0, 0), (12, -2, 0)]
+
+
+# COPY ... CSV writes an empty hstore and NULL alike, so csv reads the empty one as None
+@pytest.mark.parametrize("protocol", ["binary", "cursor", "simple"])
+def test_postgres_hstore(postgres_url: str, protocol: str) -> None:
+    query = """
+        SELECT * FROM (VALUES
+            (1, 'a=>1, "b c"=>NULL, "q\\"uote"=>"x,y"'::hstore),
+            (2, ''::hstore),
+            (3, NULL)
+        ) AS t(id, attrs) ORDER BY id
+    """
+    expected = [{"a": "1", "b c": None, 'q"uote': "x,y"}, {}, None]
+
+    df = read_sql(postgres_url, query, protocol=protocol)
+    assert df["attrs"].dtype == np.dtype("object")
+    assert df["attrs"].tolist() == expected
+
+    table = read_sql(postgres_url, query, protocol=protocol, return_type="arrow")
+    assert str(table.column("attrs").type) == "map<string, string>"
+    assert [
+        None if pairs is None else dict(pairs) for pairs in table.column("attrs").to_pylist()
+    ] == expected
+
+
+@pytest.mark.parametrize("protocol", ["binary", "csv"])
+def test_postgres_enum_categorical(postgres_url: str, protocol: str) -> None:
+    query = """
+        SELECT id, v::happiness AS mood FROM (VALUES
+            (1, 'ecstatic'), (2, 'happy'), (3, NULL)
+        ) AS t(id, v) ORDER BY id
+    """
+    df = read_sql(postgres_url, query, protocol=protocol)
+    assert isinstance(df["mood"].dtype, pd.CategoricalDtype)
+    # every declared label, in declared order, even the unused "very happy"
+    assert list(df["mood"].cat.categories) == ["happy", "very happy", "ecstatic"]
+    assert df["mood"].tolist()[:2] == ["ecstatic", "happy"]
+    assert pd.isna(df["mood"][2])
+
+    table = read_sql(postgres_url, query, protocol=protocol, return_type="arrow")
+    mood = table.column("mood").combine_chunks()
+    assert str(mood.type) == "dictionary<values=string, indices=int32, ordered=0>"
+    assert mood.dictionary.to_pylist() == ["happy", "very happy", "ecstatic"]
+
+    df = read_sql(postgres_url, query, protocol=protocol, enum_dictionary=False)
+    assert df["mood"].dtype == np.dtype("object")
+    table = read_sql(
+        postgres_url, query, protocol=protocol, return_type="arrow", enum_dictionary=False
+    )
+    assert str(table.column("mood").type) == "string"
@@ -1,11 +1,13 @@
+import asyncio
 import os
+import sqlite3
 
 import numpy as np
 import pandas as pd
 import pytest
 from pandas.testing import assert_frame_equal
 
-from .. import read_sql, ConnectionUrl
+from .. import read_sql, read_sql_async, to_parquet, ConnectionUrl
 
 
 @pytest.fixture(scope="module")  # type: ignore
@@ -317,16 +319,17 @@ def test_sqlite_with_partition_and_spa(sqlite_db: str) -> None:
 def test_empty_result(sqlite_db: str) -> None:
     query = "SELECT * FROM test_table where test_int < -100"
     df = read_sql(sqlite_db, query)
+    # the columns take their declared types, there is no row to infer them from
     expected = pd.DataFrame(
         data={
-            "test_int": pd.Series([], dtype="object"),
-            "test_nullint": pd.Series([], dtype="object"),
+            "test_int": pd.Series([], dtype="Int64"),
+            "test_nullint": pd.Series([], dtype="Int64"),
             "test_str": pd.Series([], dtype="object"),
-            "test_float": pd.Series([], dtype="object"),
-            "test_bool": pd.Series([], dtype="object"),
-            "test_date": pd.Series([], dtype="object"),
+            "test_float": pd.Series([], dtype="float64"),
+            "test_bool": pd.Series([], dtype="boolean"),
+            "test_date": pd.Series([], dtype="datetime64[ns]"),
             "test_time": pd.Series([], dtype="object"),
-            "test_datetime": pd.Series([], dtype="object"),
+            "test_datetime": pd.Series([], dtype="datetime64[ns]"),
         }
     )
     assert_frame_equal(df, expected, check_names=True)
@@ -335,16 +338,17 @@ def test_empty_result(sqlite_db: str) -> None:
 def test_empty_result_on_partition(sqlite_db: str) -> None:
     query = "SELECT * FROM test_table where test_int < -100"
     df = read_sql(sqlite_db, query, partition_on="test_int", partition_num=3)
+    # the columns take their declared types, there is no row to infer them from
     expected = pd.DataFrame(
         data={
-            "test_int": pd.Series([], dtype="object"),
-            "test_nullint": pd.Series([], dtype="object"),
+            "test_int": pd.Series([], dtype="Int64"),
+            "test_nullint": pd.Series([], dtype="Int64"),
             "test_str": pd.Series([], dtype="object"),
-            "test_float": pd.Series([], dtype="object"),
-            "test_bool": pd.Series([], dtype="object"),
-            "test_date": pd.Series([], dtype="object"),
+            "test_float": pd.Series([], dtype="float64"),
+            "test_bool": pd.Series([], dtype="boolean"),
+            "test_date": pd.Series([], dtype="datetime64[ns]"),
             "test_time": pd.Series([], dtype="object"),
-            "test_datetime": pd.Series([], dtype="object"),
+            "test_datetime": pd.Series([], dtype="datetime64[ns]"),
         }
     )
     assert_frame_equal(df, expected, check_names=True)
@@ -394,3 +398,73 @@ def test_sqlite_cte(sqlite_db: str) -> None:
 
 def test_connection_url(sqlite_db: str) -> None:
     test_sqlite_cte(ConnectionUrl(sqlite_db))
+
+
+def test_sqlite_to_parquet(sqlite_db: str, tmp_path) -> None:
+    import pyarrow.parquet as pq
+
+    path = tmp_path / "test_table.parquet"
+    query = "SELECT test_int, test_str, test_float FROM test_table"
+    nrows = to_parquet(sqlite_db, query, path, batch_size=2)
+    table = pq.read_table(path)
+    expected = read_sql(sqlite_db, query, return_type="arrow")
+    assert nrows == 6
+    assert table.num_rows == expected.num_rows
+    assert table.schema.names == expected.schema.names
+
+
+def test_sqlite_to_parquet_empty(sqlite_db: str, tmp_path) -> None:
+    import pyarrow.parquet as pq
+
+    path = tmp_path / "empty.parquet"
+    nrows = to_parquet(sqlite_db, "SELECT test_int, test_str FROM test_table WHERE 1 = 0", path)
+    table = pq.read_table(path)
+    assert nrows == 0
+    assert table.num_rows == 0
+    assert table.schema.names == ["test_int", "test_str"]
+
+
+def test_sqlite_params(sqlite_db: str) -> None:
+    query = "SELECT test_int, test_str FROM test_table WHERE test_int > ? AND test_str = ?"
+    table = read_sql(sqlite_db, query, return_type="arrow", params=[1, "str2"])
+    assert table.column("test_int").to_pylist() == [2]
+
+    # the bound value is compared as a string, never spliced into the SQL
+    table = read_sql(sqlite_db, query, return_type="arrow", params=[0, "str2' OR '1'='1"])
+    assert table.num_rows == 0
+
+
+def test_sqlite_params_rejects_partition(sqlite_db: str) -> None:
+    with pytest.raises(ValueError):
+        read_sql(
+            sqlite_db,
+            "SELECT * FROM test_table WHERE test_int > ?",
+            params=[1],
+            partition_on="test_int",
+            partition_num=2,
+        )
+
+
+def test_sqlite_read_sql_async(sqlite_db: str) -> None:
+    query = "SELECT test_int, test_nullint, test_str, test_float FROM test_table"
+    df = asyncio.run(read_sql_async(sqlite_db, query, index_col="test_int"))
+    expected = read_sql(sqlite_db, query, index_col="test_int")
+    assert_frame_equal(df, expected, check_names=True)
+
+
+def test_sqlite_binary(tmp_path) -> None:
+    db = tmp_path / "binary.db"
+    with sqlite3.connect(db) as conn:
+        conn.execute("CREATE TABLE payloads(id INTEGER NOT NULL, payload BLOB)")
+        conn.executemany(
+            "INSERT INTO payloads VALUES (?, ?)",
+            [(1, b"\x00\xff\x10"), (2, b""), (3, None)],
+        )
+
+    query = "SELECT payload FROM payloads ORDER BY id"
+    df = read_sql(f"sqlite://{db}", query)
+    assert df["payload"].dtype == object
+    assert df["payload"].tolist() == [b"\x00\xff\x10", b"", None]
+
+    table = read_sql(f"sqlite://{db}", query, return_type="arrow")
+    assert table.column("payload").to_pylist() == [b"\x00\xff\x10", b"", None]
@@ -1,15 +1,24 @@
 use crate::errors::ConnectorXPythonError;
+use crate::progress::PyProgress;
 use anyhow::anyhow;
-use arrow::record_batch::RecordBatch;
+use arrow::datatypes::SchemaRef;
+use arrow::error::ArrowError;
+use arrow::ffi_stream::FFI_ArrowArrayStream;
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
 use connectorx::pool::PoolVariant;
+use connectorx::schema_cache::SchemaCache;
 use connectorx::source_router::SourceConn;
 use connectorx::{prelude::*, sql::CXQuery};
 use fehler::throws;
 use libc::uintptr_t;
+use parking_lot::Mutex;
 use pyo3::pyclass;
+use pyo3::types::PyCapsule;
 use pyo3::{prelude::*, IntoPyObjectExt};
 use pyo3::{PyAny, Python};
+use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::ffi::CString;
 use std::sync::Arc;
 
 /// Python-exposed RecordBatch wrapper
@@ -20,6 +29,162 @@ pub struct PyRecordBatch(Option<RecordBatch>);
 #[pyclass(module = "connectorx")]
 pub struct PyRecordBatchIterator(Box<dyn RecordBatchIterator + Send + Sync>);
 
+/// Python iterator yielding the `pyarrow.RecordBatch`es of `ConnectionPool.stream_sql`. It
+/// holds pooled connections until the stream is exhausted or the iterator is dropped. It
+/// cannot be pickled, as the stream lives on those connections.
+#[pyclass(module = "connectorx")]
+pub struct PyBatchStream {
+    iter: Mutex<Option<Box<dyn RecordBatchIterator>>>,
+    progress: Option<PyProgress>,
+    rows: usize,
+}
+
+impl PyBatchStream {
+    pub fn new(iter: Box<dyn RecordBatchIterator>) -> Self {
+        PyBatchStream {
+            iter: Mutex::new(Some(iter)),
+            progress: None,
+            rows: 0,
+        }
+    }
+
+    /// Report the rows streamed so far to `progress` after every batch, the total being
+    /// unknown.
+    pub fn with_progress(mut self, progress: PyProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+}
+
+#[pymethods]
+impl PyBatchStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        // `PyRefMut` is exclusive, so the iterator is reached without taking the lock
+        let iter = match slf.iter.get_mut().as_mut() {
+            Some(iter) => iter,
+            None => return Ok(None),
+        };
+        let batch = match py.detach(|| iter.try_next_batch()) {
+            Ok(batch) => batch,
+            Err(e) => {
+                // a failed read ends the stream, which gives its connections back
+                *slf.iter.get_mut() = None;
+                return Err(ConnectorXPythonError::from(e).into());
+            }
+        };
+        match batch {
+            Some(rb) => {
+                slf.rows += rb.num_rows();
+                let reported = match &slf.progress {
+                    Some(progress) => progress.call(py, slf.rows, None),
+                    None => Ok(()),
+                };
+                if let Err(e) = reported {
+                    // the callback aborts the stream, which gives its connections back
+                    *slf.iter.get_mut() = None;
+                    return Err(e);
+                }
+                Ok(Some(to_pyarrow_batch(py, rb)?))
+            }
+            None => {
+                // gives the connections back without waiting for the iterator to be dropped
+                *slf.iter.get_mut() = None;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// The stream of `ConnectionPool.stream_reader`, exported once through the Arrow C stream
+/// interface, e.g. to `pyarrow.RecordBatchReader.from_stream`. The exported stream then
+/// holds the pooled connections until it is exhausted or released.
+#[pyclass(module = "connectorx")]
+pub struct PyBatchReader(Mutex<Option<Box<dyn RecordBatchIterator>>>);
+
+impl PyBatchReader {
+    pub fn new(iter: Box<dyn RecordBatchIterator>) -> Self {
+        PyBatchReader(Mutex::new(Some(iter)))
+    }
+}
+
+#[pymethods]
+impl PyBatchReader {
+    /// The batches keep the Arrow types of the query: a `requested_schema` is not applied.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let iter = self.0.lock().take().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("The stream has already been exported")
+        })?;
+        let reader = BatchReader {
+            schema: iter.schema(),
+            iter: Some(iter),
+        };
+        let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+        PyCapsule::new(py, stream, Some(CString::new("arrow_array_stream")?))
+    }
+}
+
+/// Reads a [`RecordBatchIterator`] as an Arrow [`RecordBatchReader`].
+struct BatchReader {
+    iter: Option<Box<dyn RecordBatchIterator>>,
+    schema: SchemaRef,
+}
+
+impl Iterator for BatchReader {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.as_mut()?.try_next_batch() {
+            Ok(Some(rb)) => Some(Ok(rb)),
+            Ok(None) => {
+                // gives the connections back without waiting for the stream to be released
+                self.iter = None;
+                None
+            }
+            Err(e) => {
+                self.iter = None;
+                Some(Err(ArrowError::ExternalError(Box::new(e))))
+            }
+        }
+    }
+}
+
+impl RecordBatchReader for BatchReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+/// Hands `rb` to Python as a `pyarrow.RecordBatch`.
+fn to_pyarrow_batch<'py>(py: Python<'py>, rb: RecordBatch) -> PyResult<Bound<'py, PyAny>> {
+    let names: Vec<String> = rb
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+    let pa = py.import("pyarrow")?;
+    let array = pa.getattr("Array")?;
+    let arrays = to_ptrs_rb(rb)
+        .into_iter()
+        .map(|ptrs| array.call_method1("_import_from_c", ptrs))
+        .collect::<PyResult<Vec<_>>>()?;
+    pa.getattr("RecordBatch")?
+        .call_method1("from_arrays", (arrays, names))
+}
+
 #[pymethods]
 impl PyRecordBatch {
     pub fn num_rows(&self) -> usize {
@@ -67,7 +232,11 @@ impl PyRecordBatchIterator {
         mut slf: PyRefMut<'py, Self>,
         py: Python<'py>,
     ) -> PyResult<Option<Py<PyRecordBatch>>> {
-        match slf.0.next_batch() {
+        match slf
+            .0
+            .try_next_batch()
+            .map_err(|e| ConnectorXPythonError::from(e))?
+        {
             Some(rb) => {
                 let wrapped = PyRecordBatch(Some(rb));
                 let py_obj = Py::new(py, wrapped)?;
@@ -79,6 +248,7 @@ impl PyRecordBatchIterator {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[throws(ConnectorXPythonError)]
 pub fn write_arrow<'py>(
     py: Python<'py>,
@@ -87,16 +257,54 @@ pub fn write_arrow<'py>(
     queries: &[CXQuery<String>],
     pre_execution_queries: Option<&[String]>,
     pool: Option<&PoolVariant>,
-) -> Bound<'py, PyAny> {
-    let ptrs = py.detach(
-        || -> Result<(Vec<String>, Vec<Vec<(uintptr_t, uintptr_t)>>), ConnectorXPythonError> {
-            let destination = get_arrow(source_conn, origin_query, queries, pre_execution_queries, pool)?;
-            let rbs = destination.arrow()?;
-            Ok(to_ptrs(rbs))
-        },
-    )?;
+    schema_cache: Option<Arc<SchemaCache>>,
+    enum_dictionary: bool,
+) -> (
+    Bound<'py, PyAny>,
+    Vec<PartitionTiming>,
+    HashMap<String, usize>,
+) {
+    let (ptrs, timings, null_counts) = py.detach(|| -> Result<_, ConnectorXPythonError> {
+        let mut query = ArrowQuery::new(source_conn)
+            .queries(queries)
+            .enum_dictionary(enum_dictionary);
+        if let Some(origin_query) = origin_query {
+            query = query.origin_query(origin_query);
+        }
+        if let Some(pre_execution_queries) = pre_execution_queries {
+            query = query.pre_execution_queries(pre_execution_queries);
+        }
+        if let Some(pool) = pool {
+            query = query.pool(pool);
+        }
+        if let Some(cache) = &schema_cache {
+            query = query.schema_cache(cache);
+        }
+        let (destination, timings) = query.run_with_timings()?;
+        let null_counts = destination.null_counts()?;
+        let rbs = destination.arrow()?;
+        Ok((to_ptrs(rbs), timings, null_counts))
+    })?;
     let obj: Py<PyAny> = ptrs.into_py_any(py)?;
-    obj.into_bound(py)
+    (obj.into_bound(py), timings, null_counts)
+}
+
+#[throws(ConnectorXPythonError)]
+pub fn write_arrow_params<'py>(
+    py: Python<'py>,
+    source_conn: &SourceConn,
+    query: &str,
+    params: &[ScalarValue],
+    pool: Option<&PoolVariant>,
+) -> (Bound<'py, PyAny>, HashMap<String, usize>) {
+    let (ptrs, null_counts) = py.detach(|| -> Result<_, ConnectorXPythonError> {
+        let destination = get_arrow_params(source_conn, query, params, pool)?;
+        let null_counts = destination.null_counts()?;
+        let rbs = destination.arrow()?;
+        Ok((to_ptrs(rbs), null_counts))
+    })?;
+    let obj: Py<PyAny> = ptrs.into_py_any(py)?;
+    (obj.into_bound(py), null_counts)
 }
 
 #[throws(ConnectorXPythonError)]
@@ -116,7 +324,8 @@ pub fn get_arrow_rb_iter<'py>(
         batch_size,
         pre_execution_queries,
         pool,
-    );
+        None,
+    )?;
 
     arrow_iter.prepare();
     let py_rb_iter = PyRecordBatchIterator(unsafe {
@@ -130,6 +339,26 @@ pub fn get_arrow_rb_iter<'py>(
     obj.into_bound(py)
 }
 
+#[throws(ConnectorXPythonError)]
+pub fn write_parquet(
+    py: Python<'_>,
+    source_conn: &SourceConn,
+    queries: &[CXQuery<String>],
+    path: &str,
+    batch_size: usize,
+    pool: Option<&PoolVariant>,
+) -> usize {
+    py.detach(|| -> Result<usize, ConnectorXPythonError> {
+        Ok(get_arrow_to_parquet(
+            source_conn,
+            queries,
+            path,
+            batch_size,
+            pool,
+        )?)
+    })?
+}
+
 pub fn to_ptrs_rb(rb: RecordBatch) -> Vec<(uintptr_t, uintptr_t)> {
     let mut cols = vec![];
 
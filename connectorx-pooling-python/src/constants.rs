@@ -1,6 +1,9 @@
 // PyString buffer size in MB
 pub const PYSTRING_BUFFER_SIZE: usize = 4;
 
+// Rows `ConnectionPool.read_dicts` returns without an explicit limit
+pub const READ_DICTS_MAX_ROWS: usize = 100_000;
+
 #[cfg(not(debug_assertions))]
 pub const J4RS_BASE_PATH: &str = "./target/release";
 #[cfg(debug_assertions)]
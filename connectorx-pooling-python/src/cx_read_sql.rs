@@ -1,7 +1,9 @@
 use connectorx::{
+    params::ScalarValue,
     partition::{partition, PartitionQuery},
     pool::{PoolConfig, PoolVariant},
-    source_router::parse_source,
+    prelude::PartitionTiming,
+    source_router::{parse_source, SourceType},
     sql::CXQuery,
 };
 use fehler::throw;
@@ -10,7 +12,8 @@ use pyo3::{exceptions::PyValueError, PyResult};
 
 use crate::errors::ConnectorXPythonError;
 use crate::pool::PyConnectionPool;
-use pyo3::types::PyDict;
+use pyo3::types::{PyCFunction, PyDict};
+use std::collections::HashMap;
 
 #[derive(FromPyObject)]
 #[pyo3(from_item_all)]
@@ -34,6 +37,29 @@ impl Into<PartitionQuery> for PyPartitionQuery {
     }
 }
 
+/// A query parameter passed from Python. `bool` is tried before `int` since Python booleans
+/// are integers too; `None` is handled by the surrounding `Option`.
+#[derive(FromPyObject)]
+pub enum PyScalarValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<PyScalarValue> for ScalarValue {
+    fn from(value: PyScalarValue) -> Self {
+        match value {
+            PyScalarValue::Bool(v) => ScalarValue::Bool(v),
+            PyScalarValue::Int(v) => ScalarValue::Int(v),
+            PyScalarValue::Float(v) => ScalarValue::Float(v),
+            PyScalarValue::Text(v) => ScalarValue::Text(v),
+            PyScalarValue::Bytes(v) => ScalarValue::Bytes(v),
+        }
+    }
+}
+
 pub fn read_sql<'py>(
     py: Python<'py>,
     conn: Option<&str>,
@@ -43,6 +69,7 @@ pub fn read_sql<'py>(
     partition_query: Option<PyPartitionQuery>,
     pre_execution_queries: Option<Vec<String>>,
     pool: Option<&PyConnectionPool>,
+    params: Option<Vec<Option<PyScalarValue>>>,
     kwargs: Option<&Bound<PyDict>>,
 ) -> PyResult<Bound<'py, PyAny>> {
     let conn_str: &str = match (conn, pool) {
@@ -53,6 +80,74 @@ pub fn read_sql<'py>(
         )),
     };
     let source_conn = parse_source(conn_str, protocol).map_err(|e| ConnectorXPythonError::from(e))?;
+    let return_meta = kwargs
+        .and_then(|dict| dict.get_item("return_meta").ok().flatten())
+        .and_then(|obj| obj.extract::<bool>().ok())
+        .unwrap_or(false);
+    let collect_timings = kwargs
+        .and_then(|dict| dict.get_item("collect_timings").ok().flatten())
+        .and_then(|obj| obj.extract::<bool>().ok())
+        .unwrap_or(false);
+    let enum_dictionary = kwargs
+        .and_then(|dict| dict.get_item("enum_dictionary").ok().flatten())
+        .and_then(|obj| obj.extract::<bool>().ok())
+        .unwrap_or(true);
+    // the pool still names the database, but its connections are left alone
+    let bypass_pool = kwargs
+        .and_then(|dict| dict.get_item("bypass_pool").ok().flatten())
+        .and_then(|obj| obj.extract::<bool>().ok())
+        .unwrap_or(false);
+    let shared_pool = pool.filter(|_| !bypass_pool);
+    if collect_timings && (return_meta || params.is_some() || return_type == "arrow_stream") {
+        throw!(PyValueError::new_err(
+            "collect_timings cannot be combined with return_meta, params or arrow_stream",
+        ));
+    }
+
+    if let Some(params) = params {
+        let query = match (queries.as_deref(), &partition_query) {
+            (Some([query]), None) => query,
+            _ => throw!(PyValueError::new_err(
+                "params can only be used with a single, unpartitioned query",
+            )),
+        };
+        if return_type != "arrow" {
+            throw!(PyValueError::new_err(format!(
+                "params are only supported with return type 'arrow', got '{}'",
+                return_type
+            )));
+        }
+        let params: Vec<ScalarValue> = params
+            .into_iter()
+            .map(|p| p.map_or(ScalarValue::Null, ScalarValue::from))
+            .collect();
+        let inner_pool = shared_pool.and_then(|p| p.get_pool_variant());
+        let (result, null_counts) = crate::arrow::write_arrow_params(
+            py,
+            &source_conn,
+            query,
+            &params,
+            inner_pool.as_ref(),
+        )?;
+        if !return_meta {
+            return Ok(result);
+        }
+        // parameterized reads always run on a fixed protocol
+        let protocol = match source_conn.ty {
+            SourceType::Postgres => "cursor",
+            SourceType::MySQL => "binary",
+            _ => source_conn.proto.as_str(),
+        };
+        return with_meta(
+            py,
+            result,
+            protocol,
+            1,
+            inner_pool.is_some(),
+            Some(&null_counts),
+        );
+    }
+
     let (queries, origin_query) = match (queries, partition_query) {
         (Some(queries), None) => (queries.into_iter().map(CXQuery::Naked).collect(), None),
         (None, Some(part)) => {
@@ -71,7 +166,7 @@ pub fn read_sql<'py>(
 
     // Resolve to a single PoolVariant early: either extract from the provided PyConnectionPool,
     // or create one from the connection string (returns None for MSSQL/BigQuery/Trino).
-    let inner_pool: Option<PoolVariant> = match pool {
+    let inner_pool: Option<PoolVariant> = match shared_pool {
         Some(p) => p.get_pool_variant(),
         None => PoolVariant::from_source_conn(
             &source_conn,
@@ -80,44 +175,224 @@ pub fn read_sql<'py>(
             .map_err(ConnectorXPythonError::Other)?,
     };
     let pool_ref = inner_pool.as_ref();
+    let schema_cache = shared_pool.and_then(|p| p.schema_cache());
+    let partitions = queries.len();
 
-    match return_type {
-        "pandas" => Ok(crate::pandas::write_pandas(
-            py,
-            &source_conn,
-            origin_query,
-            &queries,
-            pre_execution_queries.as_deref(),
-            pool_ref,
-        )?),
-        "arrow" => Ok(crate::arrow::write_arrow(
-            py,
-            &source_conn,
-            origin_query,
-            &queries,
-            pre_execution_queries.as_deref(),
-            pool_ref,
-        )?),
-        "arrow_stream" => {
-            let batch_size = kwargs
-                .and_then(|dict| dict.get_item("batch_size").ok().flatten())
-                .and_then(|obj| obj.extract::<usize>().ok())
-                .unwrap_or(10000);
-
-            Ok(crate::arrow::get_arrow_rb_iter(
+    let (result, timings, null_counts) = match return_type {
+        "pandas" => {
+            let (result, timings) = crate::pandas::write_pandas(
+                py,
+                &source_conn,
+                origin_query,
+                &queries,
+                pre_execution_queries.as_deref(),
+                pool_ref,
+                schema_cache,
+            )?;
+            Ok((result, timings, None))
+        }
+        "arrow" => {
+            let (result, timings, null_counts) = crate::arrow::write_arrow(
                 py,
                 &source_conn,
                 origin_query,
                 &queries,
                 pre_execution_queries.as_deref(),
-                batch_size,
                 pool_ref,
-            )?)
+                schema_cache,
+                enum_dictionary,
+            )?;
+            Ok((result, timings, Some(null_counts)))
+        }
+        "arrow_stream" => {
+            let batch_size = kwargs
+                .and_then(|dict| dict.get_item("batch_size").ok().flatten())
+                .and_then(|obj| obj.extract::<usize>().ok())
+                .unwrap_or(10000);
+
+            Ok((
+                crate::arrow::get_arrow_rb_iter(
+                    py,
+                    &source_conn,
+                    origin_query,
+                    &queries,
+                    pre_execution_queries.as_deref(),
+                    batch_size,
+                    pool_ref,
+                )?,
+                vec![],
+                None,
+            ))
         }
 
         _ => Err(PyValueError::new_err(format!(
             "return type should be 'pandas' or 'arrow', got '{}'",
             return_type
         ))),
+    }?;
+
+    if return_meta {
+        with_meta(
+            py,
+            result,
+            &source_conn.proto,
+            partitions,
+            pool_ref.is_some(),
+            null_counts.as_ref(),
+        )
+    } else if collect_timings {
+        with_timings(py, result, &timings)
+    } else {
+        Ok(result)
+    }
+}
+
+/// Pairs a read result with one dict per partition saying where its time went, as returned
+/// by `read_sql(..., collect_timings=True)`.
+fn with_timings<'py>(
+    py: Python<'py>,
+    result: Bound<'py, PyAny>,
+    timings: &[PartitionTiming],
+) -> PyResult<Bound<'py, PyAny>> {
+    let timings = timings
+        .iter()
+        .map(|timing| {
+            let dict = PyDict::new(py);
+            dict.set_item("query", &timing.query)?;
+            dict.set_item("checkout_ms", timing.checkout_ms)?;
+            dict.set_item("exec_ms", timing.exec_ms)?;
+            dict.set_item("rows", timing.rows)?;
+            Ok(dict)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok((result, timings).into_pyobject(py)?.into_any())
+}
+
+/// Pairs a read result with a dict describing how it ran, as returned by
+/// `read_sql(..., return_meta=True)`. Reads that went through Arrow also report the NULLs
+/// found in each column.
+fn with_meta<'py>(
+    py: Python<'py>,
+    result: Bound<'py, PyAny>,
+    protocol: &str,
+    partitions: usize,
+    pooled: bool,
+    null_counts: Option<&HashMap<String, usize>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let meta = PyDict::new(py);
+    meta.set_item("protocol", protocol)?;
+    meta.set_item("partitions", partitions)?;
+    meta.set_item("pooled", pooled)?;
+    if let Some(null_counts) = null_counts {
+        meta.set_item("null_counts", null_counts)?;
     }
+    Ok((result, meta).into_pyobject(py)?.into_any())
+}
+
+/// Start a pandas read on a background thread and return an `asyncio.Future`, bound to the
+/// running event loop, that resolves to the same dataframe infos as `read_sql`. The GIL is
+/// only held while the result buffers are allocated; partitioning, connecting and fetching
+/// run with it released so the event loop keeps serving other tasks.
+pub fn read_sql_async<'py>(
+    py: Python<'py>,
+    conn: Option<&str>,
+    protocol: Option<&str>,
+    queries: Option<Vec<String>>,
+    partition_query: Option<PyPartitionQuery>,
+    pre_execution_queries: Option<Vec<String>>,
+    pool: Option<&PyConnectionPool>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let conn_str: &str = match (conn, pool) {
+        (_, Some(p)) => p.conn_str.as_str(),
+        (Some(c), None) => c,
+        (None, None) => throw!(PyValueError::new_err(
+            "either conn or pool must be provided",
+        )),
+    };
+    let source_conn =
+        parse_source(conn_str, protocol).map_err(|e| ConnectorXPythonError::from(e))?;
+    let queries: Result<Vec<CXQuery<String>>, (String, PartitionQuery)> =
+        match (queries, partition_query) {
+            (Some(queries), None) => Ok(queries.into_iter().map(CXQuery::Naked).collect()),
+            (None, Some(part)) => Err((part.query.clone(), part.into())),
+            (Some(_), Some(_)) => throw!(PyValueError::new_err(
+                "partition_query and queries cannot be both specified",
+            )),
+            (None, None) => throw!(PyValueError::new_err(
+                "partition_query and queries cannot be both None",
+            )),
+        };
+    let shared_pool = pool.and_then(|p| p.get_pool_variant());
+    let schema_cache = pool.and_then(|p| p.schema_cache());
+
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+    let future = event_loop.call_method0("create_future")?;
+    let (loop_obj, future_obj) = (event_loop.clone().unbind(), future.clone().unbind());
+
+    std::thread::spawn(move || {
+        Python::attach(|py| {
+            let result = (|| -> PyResult<Py<PyAny>> {
+                let (queries, origin_query) = match queries {
+                    Ok(queries) => (queries, None),
+                    Err((origin_query, part)) => (
+                        py.detach(|| partition(&part, &source_conn))
+                            .map_err(|e| ConnectorXPythonError::from(e))?,
+                        Some(origin_query),
+                    ),
+                };
+                let inner_pool = match shared_pool {
+                    Some(p) => Some(p),
+                    None => py
+                        .detach(|| {
+                            PoolVariant::from_source_conn(
+                                &source_conn,
+                                &PoolConfig {
+                                    max_size: queries.len() as u32,
+                                    ..Default::default()
+                                },
+                            )
+                        })
+                        .map_err(ConnectorXPythonError::Other)?,
+                };
+                let df = crate::pandas::write_pandas(
+                    py,
+                    &source_conn,
+                    origin_query,
+                    &queries,
+                    pre_execution_queries.as_deref(),
+                    inner_pool.as_ref(),
+                    schema_cache,
+                )?
+                .0;
+                Ok(df.unbind())
+            })();
+
+            // The future may only be resolved from the loop's own thread, and not at all if
+            // the awaiting task was cancelled in the meantime.
+            let resolve =
+                PyCFunction::new_closure(py, None, None, move |args, _kwargs| -> PyResult<()> {
+                    let py = args.py();
+                    let future = future_obj.bind(py);
+                    if future.call_method0("done")?.extract::<bool>()? {
+                        return Ok(());
+                    }
+                    match &result {
+                        Ok(df) => future.call_method1("set_result", (df.clone_ref(py),))?,
+                        Err(e) => future
+                            .call_method1("set_exception", (e.clone_ref(py).into_value(py),))?,
+                    };
+                    Ok(())
+                })
+                .and_then(|resolve| {
+                    loop_obj
+                        .bind(py)
+                        .call_method1("call_soon_threadsafe", (resolve,))
+                });
+            if let Err(e) = resolve {
+                e.write_unraisable(py, None);
+            }
+        })
+    });
+
+    Ok(future)
 }
@@ -1,7 +1,12 @@
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::PyErr;
 use thiserror::Error;
 
+// Raised when a ConnectionPool is requested for a backend without pool support
+// (MSSQL, BigQuery, Trino). Subclasses ValueError so existing handlers keep working.
+create_exception!(connectorx_pooling, UnsupportedPoolBackend, PyValueError);
+
 #[allow(unused)]
 pub type Result<T> = std::result::Result<T, ConnectorXPythonError>;
 
@@ -4,11 +4,20 @@ pub mod cx_read_sql;
 mod errors;
 pub mod pandas;
 pub mod pool;
+mod progress;
 
 use crate::constants::J4RS_BASE_PATH;
-use crate::pool::PyConnectionPool;
-use ::connectorx::{fed_dispatcher::run, partition::partition, source_router::parse_source};
-use pyo3::exceptions::PyRuntimeError;
+use crate::pool::{
+    get_default_pool_config, get_global_connection_budget, set_default_pool_config,
+    set_global_connection_budget, PyConnectionPool, PyReservation, PyTransaction,
+};
+use ::connectorx::{
+    fed_dispatcher::run,
+    partition::partition,
+    source_router::{normalize_conn_str, normalize_conn_str_redacted, parse_source, SourceType},
+    sql::CXQuery,
+};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::types::PyDict;
 use pyo3::{prelude::*, IntoPyObjectExt};
 use pyo3::{wrap_pyfunction, PyResult};
@@ -28,24 +37,42 @@ static START: Once = Once::new();
 // }
 
 #[pymodule]
-fn connectorx_pooling(_: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+fn connectorx_pooling(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     START.call_once(|| {
         let _ = env_logger::try_init();
     });
 
     m.add_wrapped(wrap_pyfunction!(read_sql))?;
+    m.add_wrapped(wrap_pyfunction!(read_sql_async))?;
     m.add_wrapped(wrap_pyfunction!(read_sql2))?;
     m.add_wrapped(wrap_pyfunction!(partition_sql))?;
+    m.add_wrapped(wrap_pyfunction!(parse_connection))?;
+    m.add_wrapped(wrap_pyfunction!(normalize_connection_string))?;
     m.add_wrapped(wrap_pyfunction!(get_meta))?;
+    m.add_wrapped(wrap_pyfunction!(to_parquet))?;
+    m.add_wrapped(wrap_pyfunction!(set_default_pool_config))?;
+    m.add_wrapped(wrap_pyfunction!(get_default_pool_config))?;
+    m.add_wrapped(wrap_pyfunction!(set_global_connection_budget))?;
+    m.add_wrapped(wrap_pyfunction!(get_global_connection_budget))?;
+    m.add_wrapped(wrap_pyfunction!(set_async_runtime_threads))?;
+    m.add_wrapped(wrap_pyfunction!(split_statements))?;
     m.add_class::<pandas::PandasBlockInfo>()?;
     m.add_class::<arrow::PyRecordBatch>()?;
     m.add_class::<arrow::PyRecordBatchIterator>()?;
+    m.add_class::<arrow::PyBatchStream>()?;
+    m.add_class::<arrow::PyBatchReader>()?;
     m.add_class::<PyConnectionPool>()?;
+    m.add_class::<PyTransaction>()?;
+    m.add_class::<PyReservation>()?;
+    m.add(
+        "UnsupportedPoolBackend",
+        py.get_type::<errors::UnsupportedPoolBackend>(),
+    )?;
     Ok(())
 }
 
 #[pyfunction]
-#[pyo3(signature = (conn=None, return_type="pandas", protocol=None, queries=None, partition_query=None, pre_execution_queries=None, pool=None, *, **kwargs))]
+#[pyo3(signature = (conn=None, return_type="pandas", protocol=None, queries=None, partition_query=None, pre_execution_queries=None, pool=None, params=None, *, **kwargs))]
 pub fn read_sql<'py>(
     py: Python<'py>,
     conn: Option<&str>,
@@ -55,6 +82,7 @@ pub fn read_sql<'py>(
     partition_query: Option<cx_read_sql::PyPartitionQuery>,
     pre_execution_queries: Option<Vec<String>>,
     pool: Option<&PyConnectionPool>,
+    params: Option<Vec<Option<cx_read_sql::PyScalarValue>>>,
     kwargs: Option<&Bound<PyDict>>,
 ) -> PyResult<Bound<'py, PyAny>> {
     cx_read_sql::read_sql(
@@ -66,10 +94,33 @@ pub fn read_sql<'py>(
         partition_query,
         pre_execution_queries,
         pool,
+        params,
         kwargs,
     )
 }
 
+#[pyfunction]
+#[pyo3(signature = (conn=None, protocol=None, queries=None, partition_query=None, pre_execution_queries=None, pool=None))]
+pub fn read_sql_async<'py>(
+    py: Python<'py>,
+    conn: Option<&str>,
+    protocol: Option<&str>,
+    queries: Option<Vec<String>>,
+    partition_query: Option<cx_read_sql::PyPartitionQuery>,
+    pre_execution_queries: Option<Vec<String>>,
+    pool: Option<&PyConnectionPool>,
+) -> PyResult<Bound<'py, PyAny>> {
+    cx_read_sql::read_sql_async(
+        py,
+        conn,
+        protocol,
+        queries,
+        partition_query,
+        pre_execution_queries,
+        pool,
+    )
+}
+
 #[pyfunction]
 pub fn partition_sql(
     conn: &str,
@@ -82,6 +133,53 @@ pub fn partition_sql(
     Ok(queries.into_iter().map(|q| q.to_string()).collect())
 }
 
+/// Describes a connection string without connecting: the backend it resolves to, whether
+/// `ConnectionPool` supports it, the database and host, and the string with its password masked.
+#[pyfunction]
+pub fn parse_connection<'py>(py: Python<'py>, conn: &str) -> PyResult<Bound<'py, PyDict>> {
+    let (rewritten, _) = crate::pool::rewrite_pool_conn(conn);
+    let source_conn =
+        parse_source(&rewritten, None).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let (source_type, poolable) = match source_conn.ty {
+        SourceType::Postgres => ("postgres", true),
+        SourceType::MySQL => ("mysql", true),
+        SourceType::SQLite => ("sqlite", true),
+        SourceType::Oracle => ("oracle", true),
+        SourceType::MsSQL => ("mssql", false),
+        SourceType::BigQuery => ("bigquery", false),
+        SourceType::DuckDB => ("duckdb", false),
+        SourceType::Trino => ("trino", false),
+        SourceType::Unknown => ("unknown", false),
+    };
+    let url = &source_conn.conn;
+    let database = match source_conn.ty {
+        // everything after `sqlite://` is the file path or `file:` uri
+        SourceType::SQLite => {
+            let path = &url.as_str()[9..];
+            let path = path.split('?').next().unwrap_or_default();
+            urlencoding::decode(path)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?
+                .into_owned()
+        }
+        _ => url.path().trim_start_matches('/').to_string(),
+    };
+    let redacted = match url::Url::parse(conn) {
+        Ok(mut parsed) if parsed.password().is_some() => {
+            let _ = parsed.set_password(Some("***"));
+            parsed.to_string()
+        }
+        _ => conn.to_string(),
+    };
+
+    let dict = PyDict::new(py);
+    dict.set_item("source_type", source_type)?;
+    dict.set_item("poolable", poolable)?;
+    dict.set_item("database", database)?;
+    dict.set_item("host", url.host_str())?;
+    dict.set_item("redacted", redacted)?;
+    Ok(dict)
+}
+
 #[pyfunction]
 #[pyo3(signature = (sql, db_map, strategy=None))]
 pub fn read_sql2<'py>(
@@ -106,6 +204,25 @@ pub fn read_sql2<'py>(
     Ok(obj.into_bound(py))
 }
 
+/// The canonical form of `conn`: query parameters sorted, scheme and host lowercased, and the
+/// password masked as `***` if `redact` is set.
+#[pyfunction]
+#[pyo3(signature = (conn, redact=false))]
+pub fn normalize_connection_string(conn: &str, redact: bool) -> PyResult<String> {
+    let normalized = if redact {
+        normalize_conn_str_redacted(conn)
+    } else {
+        normalize_conn_str(conn)
+    };
+    normalized.map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// The `;`-separated statements of an SQL script, as `read_sql_file` runs them.
+#[pyfunction]
+pub fn split_statements(sql: &str) -> Vec<String> {
+    ::connectorx::sql::split_statements(sql)
+}
+
 #[pyfunction]
 #[pyo3(signature = (conn, query, protocol=None))]
 pub fn get_meta<'py>(
@@ -117,3 +234,58 @@ pub fn get_meta<'py>(
     pandas::get_meta::get_meta(py, conn, protocol.unwrap_or("binary"), query)
         .map_err(|e| From::from(e))
 }
+
+#[pyfunction]
+#[pyo3(signature = (conn, queries, path, protocol=None, batch_size=10000, pool=None))]
+pub fn to_parquet(
+    py: Python<'_>,
+    conn: Option<&str>,
+    queries: Vec<String>,
+    path: &str,
+    protocol: Option<&str>,
+    batch_size: usize,
+    pool: Option<&PyConnectionPool>,
+) -> PyResult<usize> {
+    let conn_str: &str = match (conn, pool) {
+        (_, Some(p)) => p.conn_str.as_str(),
+        (Some(c), None) => c,
+        (None, None) => {
+            return Err(PyValueError::new_err(
+                "either conn or pool must be provided",
+            ))
+        }
+    };
+    let source_conn = parse_source(conn_str, protocol)
+        .map_err(|e| crate::errors::ConnectorXPythonError::from(e))?;
+    let queries: Vec<CXQuery<String>> = queries.into_iter().map(CXQuery::Naked).collect();
+    let inner_pool = pool.and_then(|p| p.get_pool_variant());
+
+    Ok(arrow::write_parquet(
+        py,
+        &source_conn,
+        &queries,
+        path,
+        batch_size,
+        inner_pool.as_ref(),
+    )?)
+}
+
+/// Set the number of worker threads of the runtime the async sources (MSSQL, BigQuery,
+/// Trino) and federated queries share. It has to be called before the first such query,
+/// as the runtime keeps the threads it was started with; by default it uses as many as
+/// the CPUs the process may use.
+#[pyfunction]
+pub fn set_async_runtime_threads(n: usize) -> PyResult<()> {
+    if n == 0 {
+        return Err(PyValueError::new_err("the runtime needs at least one thread"));
+    }
+    let config = ::connectorx::runtime::RuntimeConfig {
+        worker_threads: Some(n),
+    };
+    if !::connectorx::runtime::set_runtime_config(config) {
+        return Err(PyRuntimeError::new_err(
+            "the async runtime has already been started",
+        ));
+    }
+    Ok(())
+}
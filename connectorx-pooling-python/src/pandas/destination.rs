@@ -1,7 +1,9 @@
 use super::{
     pandas_columns::{
-        ArrayBlock, BooleanBlock, BytesBlock, DateTimeBlock, ExtractBlockFromBound, Float64Block,
+        ArrayBlock, BooleanBlock, BytesBlock, DateTimeBlock, DictBlock, ExtractBlockFromBound,
+        Float64Block,
         HasPandasColumn, Int64Block, PandasColumn, PandasColumnObject, PyBytes, StringBlock,
+        UIntBlock,
     },
     pystring::PyString,
     typesystem::{PandasArrayType, PandasBlockType, PandasTypeSystem},
@@ -46,6 +48,7 @@ pub struct PandasDestination<'py> {
     names: Vec<String>,
     block_datas: Vec<Bound<'py, PyAny>>, // either 2d array for normal blocks, or two 1d arrays for extension blocks
     block_infos: Vec<PandasBlockInfo>,
+    categories: Vec<Option<Vec<String>>>,
 }
 
 impl<'py> PandasDestination<'py> {
@@ -56,6 +59,7 @@ impl<'py> PandasDestination<'py> {
             names: vec![],
             block_datas: vec![],
             block_infos: vec![],
+            categories: vec![],
         }
     }
 
@@ -70,10 +74,21 @@ impl<'py> PandasDestination<'py> {
         let block_infos = to_list(py, self.block_infos)?;
         let names = to_list(py, self.names)?;
         let block_datas = to_list(py, self.block_datas)?;
+        let categories = to_list(py, self.categories)?;
+        let intervals: Vec<usize> = self
+            .schema
+            .iter()
+            .enumerate()
+            .filter(|(_, ty)| matches!(ty, PandasTypeSystem::Interval(_)))
+            .map(|(i, _)| i)
+            .collect();
+        let intervals = to_list(py, intervals)?;
         let result = [
             ("data", block_datas),
             ("headers", names),
             ("block_infos", block_infos),
+            ("categories", categories),
+            ("intervals", intervals),
         ]
         .into_py_dict(py)?;
         Ok(result.into_any())
@@ -154,6 +169,30 @@ impl<'py> PandasDestination<'py> {
                 PandasBlockType::Int64(false) => {
                     self.allocate_array::<i64>(py, dt, placement)?;
                 }
+                PandasBlockType::UInt8(true) => {
+                    self.allocate_masked_array::<u8>(py, dt, placement)?;
+                }
+                PandasBlockType::UInt8(false) => {
+                    self.allocate_array::<u8>(py, dt, placement)?;
+                }
+                PandasBlockType::UInt16(true) => {
+                    self.allocate_masked_array::<u16>(py, dt, placement)?;
+                }
+                PandasBlockType::UInt16(false) => {
+                    self.allocate_array::<u16>(py, dt, placement)?;
+                }
+                PandasBlockType::UInt32(true) => {
+                    self.allocate_masked_array::<u32>(py, dt, placement)?;
+                }
+                PandasBlockType::UInt32(false) => {
+                    self.allocate_array::<u32>(py, dt, placement)?;
+                }
+                PandasBlockType::UInt64(true) => {
+                    self.allocate_masked_array::<u64>(py, dt, placement)?;
+                }
+                PandasBlockType::UInt64(false) => {
+                    self.allocate_array::<u64>(py, dt, placement)?;
+                }
                 PandasBlockType::Float64 => {
                     self.allocate_array::<f64>(py, dt, placement)?;
                 }
@@ -166,6 +205,9 @@ impl<'py> PandasDestination<'py> {
                 PandasBlockType::Int64Array => {
                     self.allocate_array::<super::pandas_columns::PyList>(py, dt, placement)?;
                 }
+                PandasBlockType::StringArray => {
+                    self.allocate_array::<super::pandas_columns::PyList>(py, dt, placement)?;
+                }
                 PandasBlockType::String => {
                     self.allocate_array::<PyString>(py, dt, placement)?;
                 }
@@ -175,6 +217,9 @@ impl<'py> PandasDestination<'py> {
                 PandasBlockType::Bytes => {
                     self.allocate_array::<PyBytes>(py, dt, placement)?;
                 }
+                PandasBlockType::StrMap => {
+                    self.allocate_array::<super::pandas_columns::PyDict>(py, dt, placement)?;
+                }
             };
         }
     }
@@ -237,7 +282,7 @@ impl<'py> Destination for PandasDestination<'py> {
                     }
                 }
                 PandasBlockType::BooleanArray => {
-                    let bblock = ArrayBlock::<bool>::extract_block(buf)?;
+                    let bblock = ArrayBlock::<Option<bool>>::extract_block(buf)?;
                     let bcols = bblock.split()?;
                     for (&cid, bcol) in block.cids.iter().zip_eq(bcols) {
                         partitioned_columns[cid] = bcol
@@ -248,7 +293,7 @@ impl<'py> Destination for PandasDestination<'py> {
                     }
                 }
                 PandasBlockType::Float64Array => {
-                    let fblock = ArrayBlock::<f64>::extract_block(buf)?;
+                    let fblock = ArrayBlock::<Option<f64>>::extract_block(buf)?;
                     let fcols = fblock.split()?;
                     for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
                         partitioned_columns[cid] = fcol
@@ -259,7 +304,7 @@ impl<'py> Destination for PandasDestination<'py> {
                     }
                 }
                 PandasBlockType::Int64Array => {
-                    let fblock = ArrayBlock::<i64>::extract_block(buf)?;
+                    let fblock = ArrayBlock::<Option<i64>>::extract_block(buf)?;
                     let fcols = fblock.split()?;
                     for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
                         partitioned_columns[cid] = fcol
@@ -269,6 +314,17 @@ impl<'py> Destination for PandasDestination<'py> {
                             .collect()
                     }
                 }
+                PandasBlockType::StringArray => {
+                    let sblock = ArrayBlock::<Option<String>>::extract_block(buf)?;
+                    let scols = sblock.split()?;
+                    for (&cid, scol) in block.cids.iter().zip_eq(scols) {
+                        partitioned_columns[cid] = scol
+                            .partition(counts)
+                            .into_iter()
+                            .map(|c| Box::new(c) as _)
+                            .collect()
+                    }
+                }
                 PandasBlockType::Int64(_) => {
                     let ublock = Int64Block::extract_block(buf)?;
                     let ucols = ublock.split()?;
@@ -280,6 +336,50 @@ impl<'py> Destination for PandasDestination<'py> {
                             .collect()
                     }
                 }
+                PandasBlockType::UInt8(_) => {
+                    let ublock = UIntBlock::<u8>::extract_block(buf)?;
+                    let ucols = ublock.split()?;
+                    for (&cid, ucol) in block.cids.iter().zip_eq(ucols) {
+                        partitioned_columns[cid] = ucol
+                            .partition(counts)
+                            .into_iter()
+                            .map(|c| Box::new(c) as _)
+                            .collect()
+                    }
+                }
+                PandasBlockType::UInt16(_) => {
+                    let ublock = UIntBlock::<u16>::extract_block(buf)?;
+                    let ucols = ublock.split()?;
+                    for (&cid, ucol) in block.cids.iter().zip_eq(ucols) {
+                        partitioned_columns[cid] = ucol
+                            .partition(counts)
+                            .into_iter()
+                            .map(|c| Box::new(c) as _)
+                            .collect()
+                    }
+                }
+                PandasBlockType::UInt32(_) => {
+                    let ublock = UIntBlock::<u32>::extract_block(buf)?;
+                    let ucols = ublock.split()?;
+                    for (&cid, ucol) in block.cids.iter().zip_eq(ucols) {
+                        partitioned_columns[cid] = ucol
+                            .partition(counts)
+                            .into_iter()
+                            .map(|c| Box::new(c) as _)
+                            .collect()
+                    }
+                }
+                PandasBlockType::UInt64(_) => {
+                    let ublock = UIntBlock::<u64>::extract_block(buf)?;
+                    let ucols = ublock.split()?;
+                    for (&cid, ucol) in block.cids.iter().zip_eq(ucols) {
+                        partitioned_columns[cid] = ucol
+                            .partition(counts)
+                            .into_iter()
+                            .map(|c| Box::new(c) as _)
+                            .collect()
+                    }
+                }
                 PandasBlockType::String => {
                     let sblock = StringBlock::extract_block(buf)?;
                     let scols = sblock.split()?;
@@ -313,6 +413,17 @@ impl<'py> Destination for PandasDestination<'py> {
                             .collect()
                     }
                 }
+                PandasBlockType::StrMap => {
+                    let mblock = DictBlock::extract_block(buf)?;
+                    let mcols = mblock.split()?;
+                    for (&cid, mcol) in block.cids.iter().zip_eq(mcols) {
+                        partitioned_columns[cid] = mcol
+                            .partition(counts)
+                            .into_iter()
+                            .map(|c| Box::new(c) as _)
+                            .collect()
+                    }
+                }
             }
         }
 
@@ -341,6 +452,11 @@ impl<'py> Destination for PandasDestination<'py> {
     fn schema(&self) -> &[Self::TypeSystem] {
         self.schema.as_ref()
     }
+
+    /// Kept for the caller, which turns these columns into `pd.Categorical`.
+    fn set_enum_labels(&mut self, enum_labels: &[Option<Vec<String>>]) {
+        self.categories = enum_labels.to_vec();
+    }
 }
 pub struct PandasPartitionDestination<'py> {
     columns: Vec<Box<dyn PandasColumnObject + 'py>>,
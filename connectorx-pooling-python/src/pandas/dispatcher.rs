@@ -2,17 +2,21 @@ use super::{destination::PandasDestination, typesystem::PandasTypeSystem};
 use crate::errors::ConnectorXPythonError;
 use connectorx::errors::Result as CXResult;
 use connectorx::prelude::*;
+use connectorx::schema_cache::SchemaCache;
 use itertools::Itertools;
 use log::debug;
 use pyo3::prelude::*;
 use rayon::prelude::*;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Instant;
 
 pub struct PandasDispatcher<'py, S, TP> {
     src: S,
     dst: PandasDestination<'py>,
     queries: Vec<CXQuery<String>>,
     origin_query: Option<String>,
+    schema_cache: Option<Arc<SchemaCache>>,
     _phantom: PhantomData<TP>,
 }
 
@@ -37,6 +41,7 @@ where
             dst,
             queries: queries.iter().map(Into::into).collect(),
             origin_query,
+            schema_cache: None,
             _phantom: PhantomData,
         }
     }
@@ -45,8 +50,31 @@ where
         self.src.set_pre_execution_queries(pre_execution_queries);
     }
 
-    /// Start the data loading process.
-    pub fn run(mut self, py: Python<'py>) -> Result<Bound<'py, PyAny>, TP::Error> {
+    /// Take the metadata of the queries from `schema_cache` when it holds them, rather than
+    /// describing them to the database, and cache it otherwise.
+    pub fn set_schema_cache(&mut self, schema_cache: Option<Arc<SchemaCache>>) {
+        self.schema_cache = schema_cache;
+    }
+
+    /// Start the data loading process, reporting the time each partition took.
+    pub fn run(
+        self,
+        py: Python<'py>,
+    ) -> Result<(Bound<'py, PyAny>, Vec<PartitionTiming>), TP::Error> {
+        let schema_cache = self.schema_cache.clone();
+        let queries = self.queries.clone();
+        // the read may have failed as the schema changed since it was cached
+        self.read(py).inspect_err(|_| {
+            if let Some(cache) = schema_cache {
+                cache.invalidate::<S>(&queries);
+            }
+        })
+    }
+
+    fn read(
+        mut self,
+        py: Python<'py>,
+    ) -> Result<(Bound<'py, PyAny>, Vec<PartitionTiming>), TP::Error> {
         debug!("Run dispatcher");
 
         debug!("Prepare");
@@ -56,7 +84,8 @@ where
         self.src.set_origin_query(self.origin_query);
 
         debug!("Fetching metadata");
-        self.src.fetch_metadata()?;
+        SchemaCache::fetch_metadata(self.schema_cache.as_deref(), &mut self.src, &self.queries)?;
+        self.dst.set_enum_labels(&self.src.enum_labels());
         let src_schema = self.src.schema();
         let dst_schema = src_schema
             .iter()
@@ -113,13 +142,14 @@ where
         debug!("Start writing");
 
         // release GIL
-        py.detach(move || -> Result<(), TP::Error> {
+        let queries = &self.queries;
+        let timings = py.detach(move || -> Result<Vec<PartitionTiming>, TP::Error> {
             // parse and write
             dst_partitions
                 .into_par_iter()
                 .zip_eq(src_partitions)
                 .enumerate()
-                .try_for_each(|(i, (mut dst, mut src))| -> Result<(), TP::Error> {
+                .map(|(i, (mut dst, mut src))| -> Result<PartitionTiming, TP::Error> {
                     #[cfg(feature = "fptr")]
                     let f: Vec<_> = src_schema
                         .iter()
@@ -127,11 +157,15 @@ where
                         .map(|(&src_ty, &dst_ty)| TP::processor(src_ty, dst_ty))
                         .collect::<CXResult<Vec<_>>>()?;
 
+                    let checkout = src.checkout_time();
+                    let start = Instant::now();
+                    let mut rows = 0;
                     let mut parser = src.parser()?;
 
                     match dorder {
                         DataOrder::RowMajor => loop {
                             let (n, is_last) = parser.fetch_next()?;
+                            rows += n;
                             dst.aquire_row(n)?;
                             for _ in 0..n {
                                 #[allow(clippy::needless_range_loop)]
@@ -152,6 +186,7 @@ where
                         },
                         DataOrder::ColumnMajor => loop {
                             let (n, is_last) = parser.fetch_next()?;
+                            rows += n;
                             dst.aquire_row(n)?;
                             #[allow(clippy::needless_range_loop)]
                             for col in 0..dst.ncols() {
@@ -174,13 +209,18 @@ where
                     debug!("Finalize partition {}", i);
                     dst.finalize()?;
                     debug!("Partition {} finished", i);
-                    Ok(())
-                })?;
-            Ok(())
+                    Ok(PartitionTiming::new(
+                        &queries[i],
+                        checkout,
+                        start.elapsed(),
+                        rows,
+                    ))
+                })
+                .collect()
         })?;
         debug!("Writing finished");
 
-        Ok(self.dst.result(py).unwrap())
+        Ok((self.dst.result(py).unwrap(), timings))
     }
 
     /// Only fetch the metadata (header) of the destination.
@@ -190,6 +230,7 @@ where
         self.src.set_queries(self.queries.as_slice());
         self.src.set_origin_query(self.origin_query.clone());
         self.src.fetch_metadata()?;
+        self.dst.set_enum_labels(&self.src.enum_labels());
         let src_schema = self.src.schema();
         let dst_schema = src_schema
             .iter()
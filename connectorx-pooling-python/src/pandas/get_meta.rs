@@ -7,6 +7,7 @@ use super::{
     },
 };
 use crate::errors::ConnectorXPythonError;
+use connectorx::runtime::shared_runtime;
 use connectorx::source_router::{SourceConn, SourceType};
 use connectorx::{
     prelude::*,
@@ -29,7 +30,6 @@ use postgres::NoTls;
 use postgres_openssl::MakeTlsConnector;
 use pyo3::prelude::*;
 use std::convert::TryFrom;
-use std::sync::Arc;
 
 #[throws(ConnectorXPythonError)]
 pub fn get_meta<'py>(
@@ -182,7 +182,7 @@ pub fn get_meta<'py>(
             }
         }
         SourceType::MsSQL => {
-            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let rt = shared_runtime()?;
             let source = MsSQLSource::new(rt, &source_conn.conn[..], 1)?;
             let dispatcher = PandasDispatcher::<_, MsSQLPandasTransport>::new(
                 source,
@@ -205,7 +205,7 @@ pub fn get_meta<'py>(
             dispatcher.get_meta(py)?
         }
         SourceType::BigQuery => {
-            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let rt = shared_runtime()?;
             let source = BigQuerySource::new(rt, &source_conn.conn[..])?;
             let dispatcher = PandasDispatcher::<_, BigQueryPandasTransport>::new(
                 source,
@@ -217,7 +217,7 @@ pub fn get_meta<'py>(
             dispatcher.get_meta(py)?
         }
         SourceType::Trino => {
-            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let rt = shared_runtime()?;
             let source = TrinoSource::new(rt, &source_conn.conn[..])?;
             let dispatcher = PandasDispatcher::<_, TrinoPandasTransport>::new(
                 source,
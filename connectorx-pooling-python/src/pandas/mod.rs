@@ -15,6 +15,8 @@ pub use self::transports::{
 pub use self::typesystem::{PandasDType, PandasTypeSystem};
 use crate::errors::ConnectorXPythonError;
 use connectorx::pool::PoolVariant;
+use connectorx::runtime::shared_runtime;
+use connectorx::schema_cache::SchemaCache;
 use connectorx::source_router::{SourceConn, SourceType};
 use connectorx::sources::oracle::OracleSource;
 use connectorx::{
@@ -22,8 +24,8 @@ use connectorx::{
     sources::{
         mysql::{BinaryProtocol as MySQLBinaryProtocol, TextProtocol},
         postgres::{
-            rewrite_tls_args, BinaryProtocol as PgBinaryProtocol, CSVProtocol, CursorProtocol,
-            SimpleProtocol,
+            cursor_fetch_size, rewrite_tls_args, BinaryProtocol as PgBinaryProtocol, CSVProtocol,
+            CursorProtocol, SimpleProtocol,
         },
     },
     sql::CXQuery,
@@ -43,7 +45,8 @@ pub fn write_pandas<'a, 'py: 'a>(
     queries: &[CXQuery<String>],
     pre_execution_queries: Option<&[String]>,
     pool: Option<&PoolVariant>,
-) -> Bound<'py, PyAny> {
+    schema_cache: Option<Arc<SchemaCache>>,
+) -> (Bound<'py, PyAny>, Vec<PartitionTiming>) {
     let destination = PandasDestination::new();
     let protocol = source_conn.proto.as_str();
     debug!("Protocol: {}", protocol);
@@ -64,6 +67,7 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 ("csv", None) => {
@@ -78,6 +82,7 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 ("binary", Some(tls_conn)) => {
@@ -91,6 +96,7 @@ pub fn write_pandas<'a, 'py: 'a>(
                             PostgresPandasTransport<PgBinaryProtocol, MakeTlsConnector>,
                         >::new(sb, destination, queries, origin_query);
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 ("binary", None) => {
@@ -105,26 +111,30 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 ("cursor", Some(tls_conn)) => {
                     let pg_pool = pool.map(|p| p.postgres_tls_pool());
-                    let sb = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
+                    let mut sb = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
                         config, tls_conn, queries.len(), pg_pool,
                     )?;
+                    sb.set_cursor_fetch_size(cursor_fetch_size(&source_conn.conn)?);
                     let mut dispatcher =
                         PandasDispatcher::<
                             _,
                             PostgresPandasTransport<CursorProtocol, MakeTlsConnector>,
                         >::new(sb, destination, queries, origin_query);
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 ("cursor", None) => {
                     let pg_pool = pool.map(|p| p.postgres_notls_pool());
-                    let sb = PostgresSource::<CursorProtocol, NoTls>::new(
+                    let mut sb = PostgresSource::<CursorProtocol, NoTls>::new(
                         config, NoTls, queries.len(), pg_pool,
                     )?;
+                    sb.set_cursor_fetch_size(cursor_fetch_size(&source_conn.conn)?);
                     let mut dispatcher = PandasDispatcher::<
                         _,
                         PostgresPandasTransport<CursorProtocol, NoTls>,
@@ -132,6 +142,7 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 ("simple", Some(tls_conn)) => {
@@ -145,6 +156,7 @@ pub fn write_pandas<'a, 'py: 'a>(
                             PostgresPandasTransport<SimpleProtocol, MakeTlsConnector>,
                         >::new(sb, destination, queries, origin_query);
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 ("simple", None) => {
@@ -159,6 +171,7 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 _ => unimplemented!("{} protocol not supported", protocol),
@@ -169,12 +182,13 @@ pub fn write_pandas<'a, 'py: 'a>(
             let path = &source_conn.conn.as_str()[9..];
             let sqlite_pool = pool.map(|p| p.sqlite_pool());
             let source = SQLiteSource::new(path, queries.len(), sqlite_pool)?;
-            let dispatcher = PandasDispatcher::<_, SqlitePandasTransport>::new(
+            let mut dispatcher = PandasDispatcher::<_, SqlitePandasTransport>::new(
                 source,
                 destination,
                 queries,
                 origin_query,
             );
+            dispatcher.set_schema_cache(schema_cache);
             dispatcher.run(py)?
         }
         SourceType::MySQL => {
@@ -192,6 +206,7 @@ pub fn write_pandas<'a, 'py: 'a>(
                             origin_query,
                         );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 "text" => {
@@ -206,13 +221,14 @@ pub fn write_pandas<'a, 'py: 'a>(
                             origin_query,
                         );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_schema_cache(schema_cache);
                     dispatcher.run(py)?
                 }
                 _ => unimplemented!("{} protocol not supported", protocol),
             }
         }
         SourceType::MsSQL => {
-            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let rt = shared_runtime()?;
             let source = MsSQLSource::new(rt, &source_conn.conn[..], queries.len())?;
             let dispatcher = PandasDispatcher::<_, MsSQLPandasTransport>::new(
                 source,
@@ -225,16 +241,17 @@ pub fn write_pandas<'a, 'py: 'a>(
         SourceType::Oracle => {
             let oracle_pool = pool.map(|p| p.oracle_pool());
             let source = OracleSource::new(&source_conn.conn[..], queries.len(), oracle_pool)?;
-            let dispatcher = PandasDispatcher::<_, OraclePandasTransport>::new(
+            let mut dispatcher = PandasDispatcher::<_, OraclePandasTransport>::new(
                 source,
                 destination,
                 queries,
                 origin_query,
             );
+            dispatcher.set_schema_cache(schema_cache);
             dispatcher.run(py)?
         }
         SourceType::BigQuery => {
-            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let rt = shared_runtime()?;
             let source = BigQuerySource::new(rt, &source_conn.conn[..])?;
             let dispatcher = PandasDispatcher::<_, BigQueryPandasTransport>::new(
                 source,
@@ -245,7 +262,7 @@ pub fn write_pandas<'a, 'py: 'a>(
             dispatcher.run(py)?
         }
         SourceType::Trino => {
-            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let rt = shared_runtime()?;
             let source = TrinoSource::new(rt, &source_conn.conn[..])?;
             let dispatcher = PandasDispatcher::<TrinoSource, TrinoPandasTransport>::new(
                 source,
@@ -102,112 +102,51 @@ where
     }
 }
 
-impl PandasColumn<Vec<bool>> for ArrayColumn<bool> {
-    #[throws(ConnectorXPythonError)]
-    fn write(&mut self, val: Vec<bool>, row: usize) {
-        self.lengths.push(val.len());
-        self.buffer.extend_from_slice(&val[..]);
-        self.row_idx.push(row);
-        self.try_flush()?;
-    }
-}
-
-impl PandasColumn<Option<Vec<bool>>> for ArrayColumn<bool> {
-    #[throws(ConnectorXPythonError)]
-    fn write(&mut self, val: Option<Vec<bool>>, row: usize) {
-        match val {
-            Some(v) => {
-                self.lengths.push(v.len());
-                self.buffer.extend_from_slice(&v[..]);
-                self.row_idx.push(row);
-                self.try_flush()?;
-            }
-            None => {
-                self.lengths.push(usize::MAX);
-                self.row_idx.push(row);
+macro_rules! impl_array_column {
+    ($($t: ty,)+) => {
+        $(
+            impl PandasColumn<Vec<Option<$t>>> for ArrayColumn<Option<$t>> {
+                #[throws(ConnectorXPythonError)]
+                fn write(&mut self, val: Vec<Option<$t>>, row: usize) {
+                    self.lengths.push(val.len());
+                    self.buffer.extend(val);
+                    self.row_idx.push(row);
+                    self.try_flush()?;
+                }
             }
-        }
-    }
-}
-impl PandasColumn<Vec<f64>> for ArrayColumn<f64> {
-    #[throws(ConnectorXPythonError)]
-    fn write(&mut self, val: Vec<f64>, row: usize) {
-        self.lengths.push(val.len());
-        self.buffer.extend_from_slice(&val[..]);
-        self.row_idx.push(row);
-        self.try_flush()?;
-    }
-}
 
-impl PandasColumn<Option<Vec<f64>>> for ArrayColumn<f64> {
-    #[throws(ConnectorXPythonError)]
-    fn write(&mut self, val: Option<Vec<f64>>, row: usize) {
-        match val {
-            Some(v) => {
-                self.lengths.push(v.len());
-                self.buffer.extend_from_slice(&v[..]);
-                self.row_idx.push(row);
-                self.try_flush()?;
-            }
-            None => {
-                self.lengths.push(usize::MAX);
-                self.row_idx.push(row);
+            impl PandasColumn<Option<Vec<Option<$t>>>> for ArrayColumn<Option<$t>> {
+                #[throws(ConnectorXPythonError)]
+                fn write(&mut self, val: Option<Vec<Option<$t>>>, row: usize) {
+                    match val {
+                        Some(v) => {
+                            self.lengths.push(v.len());
+                            self.buffer.extend(v);
+                            self.row_idx.push(row);
+                            self.try_flush()?;
+                        }
+                        None => {
+                            self.lengths.push(usize::MAX);
+                            self.row_idx.push(row);
+                        }
+                    }
+                }
             }
-        }
-    }
-}
-
-impl PandasColumn<Vec<i64>> for ArrayColumn<i64> {
-    #[throws(ConnectorXPythonError)]
-    fn write(&mut self, val: Vec<i64>, row: usize) {
-        self.lengths.push(val.len());
-        self.buffer.extend_from_slice(&val[..]);
-        self.row_idx.push(row);
-        self.try_flush()?;
-    }
-}
 
-impl PandasColumn<Option<Vec<i64>>> for ArrayColumn<i64> {
-    #[throws(ConnectorXPythonError)]
-    fn write(&mut self, val: Option<Vec<i64>>, row: usize) {
-        match val {
-            Some(v) => {
-                self.lengths.push(v.len());
-                self.buffer.extend_from_slice(&v[..]);
-                self.row_idx.push(row);
-                self.try_flush()?;
-            }
-            None => {
-                self.lengths.push(usize::MAX);
-                self.row_idx.push(row);
+            impl HasPandasColumn for Vec<Option<$t>> {
+                type PandasColumn<'a> = ArrayColumn<Option<$t>>;
             }
-        }
-    }
-}
-
-impl HasPandasColumn for Vec<bool> {
-    type PandasColumn<'a> = ArrayColumn<bool>;
-}
-
-impl HasPandasColumn for Option<Vec<bool>> {
-    type PandasColumn<'a> = ArrayColumn<bool>;
-}
 
-impl HasPandasColumn for Vec<f64> {
-    type PandasColumn<'a> = ArrayColumn<f64>;
+            impl HasPandasColumn for Option<Vec<Option<$t>>> {
+                type PandasColumn<'a> = ArrayColumn<Option<$t>>;
+            }
+        )+
+    };
 }
 
-impl HasPandasColumn for Option<Vec<f64>> {
-    type PandasColumn<'a> = ArrayColumn<f64>;
-}
+// NULL elements become `None` inside the python list
+impl_array_column!(bool, f64, i64, String,);
 
-impl HasPandasColumn for Vec<i64> {
-    type PandasColumn<'a> = ArrayColumn<i64>;
-}
-
-impl HasPandasColumn for Option<Vec<i64>> {
-    type PandasColumn<'a> = ArrayColumn<i64>;
-}
 impl<V> ArrayColumn<V>
 where
     V: Send + for<'a> IntoPyObject<'a> + Clone,
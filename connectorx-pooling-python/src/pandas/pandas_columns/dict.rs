@@ -0,0 +1,162 @@
+use super::{
+    check_dtype, ExtractBlockFromBound, HasPandasColumn, PandasColumn, PandasColumnObject,
+    GIL_MUTEX,
+};
+use crate::errors::ConnectorXPythonError;
+use anyhow::anyhow;
+use fehler::throws;
+use ndarray::{ArrayViewMut2, Axis, Ix2};
+use numpy::{Element, PyArray, PyArrayDescr, PyArrayMethods};
+use pyo3::{Bound, IntoPyObject, Py, PyAny, PyResult, Python};
+use std::any::TypeId;
+use std::collections::HashMap;
+
+type StrMap = HashMap<String, Option<String>>;
+
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct PyDict(Py<PyAny>);
+
+// In order to put it into a numpy array
+unsafe impl Element for PyDict {
+    const IS_COPY: bool = false;
+    fn get_dtype(py: Python<'_>) -> Bound<'_, PyArrayDescr> {
+        PyArrayDescr::object(py)
+    }
+
+    fn clone_ref(&self, _py: Python<'_>) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+pub struct DictBlock<'a> {
+    data: ArrayViewMut2<'a, PyDict>,
+    buf_size: usize,
+}
+
+impl<'a> ExtractBlockFromBound<'a> for DictBlock<'a> {
+    fn extract_block<'b: 'a>(ob: &'b pyo3::Bound<'a, PyAny>) -> PyResult<Self> {
+        check_dtype(ob, "object")?;
+        let array = ob.cast::<PyArray<PyDict, Ix2>>()?;
+        let data = unsafe { array.as_array_mut() };
+        Ok(DictBlock {
+            data,
+            buf_size: 1 << 14, // in maps
+        })
+    }
+}
+
+impl<'a> DictBlock<'a> {
+    #[throws(ConnectorXPythonError)]
+    pub fn split(self) -> Vec<DictColumn> {
+        let mut ret = vec![];
+        let mut view = self.data;
+
+        let nrows = view.ncols();
+        while view.nrows() > 0 {
+            let (col, rest) = view.split_at(Axis(0), 1);
+            view = rest;
+            ret.push(DictColumn {
+                data: col
+                    .into_shape_with_order(nrows)?
+                    .into_slice()
+                    .ok_or_else(|| anyhow!("get None for splitted Dict data"))?
+                    .as_mut_ptr(),
+                buffer: Vec::with_capacity(self.buf_size),
+                buf_size: self.buf_size,
+            })
+        }
+        ret
+    }
+}
+
+/// A column of maps, such as Postgres `hstore`s, written as Python dicts, or `None`.
+pub struct DictColumn {
+    data: *mut PyDict,
+    buffer: Vec<(usize, Option<StrMap>)>,
+    buf_size: usize,
+}
+
+unsafe impl Send for DictColumn {}
+unsafe impl Sync for DictColumn {}
+
+impl PandasColumnObject for DictColumn {
+    fn typecheck(&self, id: TypeId) -> bool {
+        id == TypeId::of::<StrMap>() || id == TypeId::of::<Option<StrMap>>()
+    }
+
+    fn typename(&self) -> &'static str {
+        std::any::type_name::<StrMap>()
+    }
+
+    #[throws(ConnectorXPythonError)]
+    fn finalize(&mut self) {
+        self.flush()?;
+    }
+}
+
+impl PandasColumn<StrMap> for DictColumn {
+    #[throws(ConnectorXPythonError)]
+    fn write(&mut self, val: StrMap, row: usize) {
+        self.buffer.push((row, Some(val)));
+        self.try_flush()?;
+    }
+}
+
+impl PandasColumn<Option<StrMap>> for DictColumn {
+    #[throws(ConnectorXPythonError)]
+    fn write(&mut self, val: Option<StrMap>, row: usize) {
+        self.buffer.push((row, val));
+        self.try_flush()?;
+    }
+}
+
+impl HasPandasColumn for StrMap {
+    type PandasColumn<'a> = DictColumn;
+}
+
+impl HasPandasColumn for Option<StrMap> {
+    type PandasColumn<'a> = DictColumn;
+}
+
+impl DictColumn {
+    pub fn partition(self, counts: usize) -> Vec<DictColumn> {
+        let mut partitions = vec![];
+
+        for _ in 0..counts {
+            partitions.push(DictColumn {
+                data: self.data,
+                buffer: Vec::with_capacity(self.buf_size),
+                buf_size: self.buf_size,
+            });
+        }
+        partitions
+    }
+
+    #[throws(ConnectorXPythonError)]
+    pub fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            Python::attach(|py| -> Result<(), ConnectorXPythonError> {
+                // allocation in python is not thread safe
+                let _guard = GIL_MUTEX
+                    .lock()
+                    .map_err(|e| anyhow!("mutex poisoned {}", e))?;
+                for (row, map) in self.buffer.drain(..) {
+                    // NULL values within a map become `None`, as does a NULL map
+                    let obj = map.into_pyobject(py)?.into_any().unbind();
+                    unsafe {
+                        *self.data.add(row) = PyDict(obj);
+                    }
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    #[throws(ConnectorXPythonError)]
+    pub fn try_flush(&mut self) {
+        if self.buffer.len() >= self.buf_size {
+            self.flush()?;
+        }
+    }
+}
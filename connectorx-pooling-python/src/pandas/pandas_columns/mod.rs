@@ -2,9 +2,11 @@ mod array;
 mod boolean;
 mod bytes;
 mod datetime;
+mod dict;
 mod float64;
 mod int64;
 mod string;
+mod uint;
 // TODO: use macro for integers
 
 use crate::errors::Result;
@@ -12,6 +14,7 @@ pub use crate::pandas::pandas_columns::array::{ArrayBlock, PyList};
 pub use crate::pandas::pandas_columns::bytes::{BytesBlock, PyBytes};
 pub use boolean::BooleanBlock;
 pub use datetime::DateTimeBlock;
+pub use dict::{DictBlock, PyDict};
 use fehler::throw;
 pub use float64::Float64Block;
 pub use int64::Int64Block;
@@ -19,6 +22,7 @@ use pyo3::{exceptions::PyRuntimeError, intern, types::PyAnyMethods, PyAny, PyRes
 use std::any::TypeId;
 use std::sync::Mutex;
 pub use string::StringBlock;
+pub use uint::UIntBlock;
 
 // A global GIL lock for Python object allocations like string, bytes and list
 lazy_static! {
@@ -0,0 +1,164 @@
+use super::{
+    check_dtype, ExtractBlockFromBound, HasPandasColumn, PandasColumn, PandasColumnObject,
+};
+use crate::errors::ConnectorXPythonError;
+use anyhow::anyhow;
+use fehler::throws;
+use ndarray::{ArrayViewMut1, ArrayViewMut2, Axis, Ix2};
+use numpy::{Element, PyArray, PyArray1, PyArrayMethods};
+use pyo3::{
+    types::{PyTuple, PyTupleMethods},
+    PyAny, PyResult,
+};
+use std::any::TypeId;
+
+/// An unsigned integer type with a numpy dtype of its own, read into `uint8` to `uint64`
+/// blocks, or the matching nullable `UInt8` to `UInt64` arrays.
+pub trait UInt: Element + Copy + Send + 'static {
+    const DTYPE: &'static str;
+}
+
+macro_rules! impl_uint {
+    ($($t:ty => $dtype:literal),*) => {
+        $(
+            impl UInt for $t {
+                const DTYPE: &'static str = $dtype;
+            }
+
+            impl HasPandasColumn for $t {
+                type PandasColumn<'a> = UIntColumn<$t>;
+            }
+
+            impl HasPandasColumn for Option<$t> {
+                type PandasColumn<'a> = UIntColumn<$t>;
+            }
+        )*
+    };
+}
+
+impl_uint!(u8 => "uint8", u16 => "uint16", u32 => "uint32", u64 => "uint64");
+
+pub enum UIntBlock<'a, T> {
+    NumPy(ArrayViewMut2<'a, T>),
+    Extention(ArrayViewMut1<'a, T>, ArrayViewMut1<'a, bool>),
+}
+
+impl<'a, T: UInt> ExtractBlockFromBound<'a> for UIntBlock<'a, T> {
+    fn extract_block<'b: 'a>(ob: &'b pyo3::Bound<'a, PyAny>) -> PyResult<Self> {
+        if let Ok(array) = ob.cast::<PyArray<T, Ix2>>() {
+            check_dtype(ob, T::DTYPE)?;
+            let data = unsafe { array.as_array_mut() };
+            Ok(UIntBlock::NumPy(data))
+        } else {
+            let tuple = ob.cast::<PyTuple>()?;
+            let data = &tuple.as_slice()[0];
+            let mask = &tuple.as_slice()[1];
+            check_dtype(data, T::DTYPE)?;
+            check_dtype(mask, "bool")?;
+            Ok(UIntBlock::Extention(
+                unsafe { data.cast::<PyArray1<T>>()?.as_array_mut() },
+                unsafe { mask.cast::<PyArray1<bool>>()?.as_array_mut() },
+            ))
+        }
+    }
+}
+
+impl<'a, T: UInt> UIntBlock<'a, T> {
+    #[throws(ConnectorXPythonError)]
+    pub fn split(self) -> Vec<UIntColumn<T>> {
+        let mut ret = vec![];
+        match self {
+            UIntBlock::Extention(data, mask) => ret.push(UIntColumn {
+                data: data
+                    .into_slice()
+                    .ok_or_else(|| anyhow!("get None for {} data", T::DTYPE))?
+                    .as_mut_ptr(),
+                mask: Some(
+                    mask.into_slice()
+                        .ok_or_else(|| anyhow!("get None for {} mask", T::DTYPE))?
+                        .as_mut_ptr(),
+                ),
+            }),
+            UIntBlock::NumPy(mut view) => {
+                let nrows = view.ncols();
+                while view.nrows() > 0 {
+                    let (col, rest) = view.split_at(Axis(0), 1);
+                    view = rest;
+                    ret.push(UIntColumn {
+                        data: col
+                            .into_shape_with_order(nrows)?
+                            .into_slice()
+                            .ok_or_else(|| anyhow!("get None for splitted {} data", T::DTYPE))?
+                            .as_mut_ptr(),
+                        mask: None,
+                    })
+                }
+            }
+        }
+        ret
+    }
+}
+
+pub struct UIntColumn<T> {
+    data: *mut T,
+    mask: Option<*mut bool>,
+}
+
+unsafe impl<T> Send for UIntColumn<T> {}
+unsafe impl<T> Sync for UIntColumn<T> {}
+
+impl<T: UInt> PandasColumnObject for UIntColumn<T> {
+    fn typecheck(&self, id: TypeId) -> bool {
+        id == TypeId::of::<T>() || id == TypeId::of::<Option<T>>()
+    }
+
+    fn typename(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
+impl<T: UInt> PandasColumn<T> for UIntColumn<T> {
+    #[throws(ConnectorXPythonError)]
+    fn write(&mut self, val: T, row: usize) {
+        unsafe { *self.data.add(row) = val };
+        if let Some(mask) = self.mask.as_mut() {
+            unsafe { *mask.add(row) = false };
+        }
+    }
+}
+
+impl<T: UInt> PandasColumn<Option<T>> for UIntColumn<T> {
+    #[throws(ConnectorXPythonError)]
+    fn write(&mut self, val: Option<T>, row: usize) {
+        match val {
+            Some(val) => {
+                unsafe { *self.data.add(row) = val };
+                if let Some(mask) = self.mask.as_mut() {
+                    unsafe { *mask.add(row) = false };
+                }
+            }
+            None => {
+                if let Some(mask) = self.mask.as_mut() {
+                    unsafe { *mask.add(row) = true };
+                } else {
+                    panic!("Writing null {} to not null pandas array", T::DTYPE)
+                }
+            }
+        }
+    }
+}
+
+impl<T> UIntColumn<T> {
+    pub fn partition(self, counts: usize) -> Vec<UIntColumn<T>> {
+        let mut partitions = vec![];
+
+        for _ in 0..counts {
+            partitions.push(UIntColumn {
+                data: self.data,
+                mask: self.mask,
+            });
+        }
+
+        partitions
+    }
+}
@@ -27,11 +27,11 @@ impl_transport!(
         { Long[i32]                  => I64[i64]                | conversion auto }
         { Int24[i32]                 => I64[i64]                | conversion none }
         { LongLong[i64]              => I64[i64]                | conversion auto }
-        { UTiny[u8]                  => I64[i64]                | conversion auto }
-        { UShort[u16]                => I64[i64]                | conversion auto }
-        { ULong[u32]                 => I64[i64]                | conversion auto }
-        { UInt24[u32]                => I64[i64]                | conversion none }
-        { ULongLong[u64]             => F64[f64]                | conversion auto }
+        { UTiny[u8]                  => U8[u8]                  | conversion auto }
+        { UShort[u16]                => U16[u16]                | conversion auto }
+        { ULong[u32]                 => U32[u32]                | conversion auto }
+        { UInt24[u32]                => U32[u32]                | conversion none }
+        { ULongLong[u64]             => U64[u64]                | conversion auto }
         { Date[NaiveDate]            => DateTime[DateTime<Utc>] | conversion option }
         { Time[NaiveTime]            => String[String]          | conversion option }
         { Year[i16]                  => I64[i64]                | conversion none}
@@ -47,6 +47,9 @@ impl_transport!(
         { LongBlob[Vec<u8>]          => Bytes[Vec<u8>]          | conversion none }
         { Json[Value]                => String[String]          | conversion option }
         { Bit[Vec<u8>]               => Bytes[Vec<u8>]          | conversion none }
+        { BitFlag[bool]              => Bool[bool]              | conversion auto }
+        { Set[String]                => String[String]          | conversion none }
+        { SetList[Vec<Option<String>>] => StrArray[Vec<Option<String>>] | conversion auto }
     }
 );
 
@@ -63,11 +66,11 @@ impl_transport!(
         { Long[i32]                  => I64[i64]                | conversion auto }
         { Int24[i32]                 => I64[i64]                | conversion none }
         { LongLong[i64]              => I64[i64]                | conversion auto }
-        { UTiny[u8]                  => I64[i64]                | conversion auto }
-        { UShort[u16]                => I64[i64]                | conversion auto }
-        { ULong[u32]                 => I64[i64]                | conversion auto }
-        { UInt24[u32]                => I64[i64]                | conversion none }
-        { ULongLong[u64]             => F64[f64]                | conversion auto }
+        { UTiny[u8]                  => U8[u8]                  | conversion auto }
+        { UShort[u16]                => U16[u16]                | conversion auto }
+        { ULong[u32]                 => U32[u32]                | conversion auto }
+        { UInt24[u32]                => U32[u32]                | conversion none }
+        { ULongLong[u64]             => U64[u64]                | conversion auto }
         { Date[NaiveDate]            => DateTime[DateTime<Utc>] | conversion option }
         { Time[NaiveTime]            => String[String]          | conversion option }
         { Datetime[NaiveDateTime]    => DateTime[DateTime<Utc>] | conversion option }
@@ -83,6 +86,9 @@ impl_transport!(
         { LongBlob[Vec<u8>]          => Bytes[Vec<u8>]          | conversion none }
         { Json[Value]                => String[String]          | conversion option }
         { Bit[Vec<u8>]               => Bytes[Vec<u8>]          | conversion none }
+        { BitFlag[bool]              => Bool[bool]              | conversion auto }
+        { Set[String]                => String[String]          | conversion none }
+        { SetList[Vec<Option<String>>] => StrArray[Vec<Option<String>>] | conversion auto }
     }
 );
 
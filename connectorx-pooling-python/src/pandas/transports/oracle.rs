@@ -24,7 +24,9 @@ impl_transport!(
         { BinaryDouble[f64]                 => F64[f64]                     | conversion none }
         { NumInt[i64]                       => I64[i64]                     | conversion auto }
         { Blob[Vec<u8>]                     => Bytes[Vec<u8>]               | conversion auto }
+        { Raw[Vec<u8>]                      => Bytes[Vec<u8>]               | conversion none }
         { Clob[String]                      => String[String]               | conversion none }
+        { NClob[String]                     => String[String]               | conversion none }
         { VarChar[String]                   => String[String]               | conversion auto }
         { Char[String]                      => String[String]               | conversion none }
         { NVarChar[String]                  => String[String]               | conversion none }
@@ -35,6 +37,7 @@ impl_transport!(
         { TimestampTz[DateTime<Utc>]        => DateTime[DateTime<Utc>]      | conversion auto }
         { TimestampTzNano[DateTime<Utc>]    => DateTime[DateTime<Utc>]      | conversion none }
         { NumDecimal[Decimal]               => F64[f64]                     | conversion option }
+        { NumBigInt[Decimal]                => I64[i64]                     | conversion option }
     }
 );
 
@@ -49,4 +52,11 @@ impl<'py> TypeConversion<Decimal, f64> for OraclePandasTransport<'py> {
         val.to_f64()
             .unwrap_or_else(|| panic!("cannot convert decimal {:?} to float64", val))
     }
-}
\ No newline at end of file
+}
+
+impl<'py> TypeConversion<Decimal, i64> for OraclePandasTransport<'py> {
+    fn convert(val: Decimal) -> i64 {
+        val.to_i64()
+            .unwrap_or_else(|| panic!("cannot convert decimal {:?} to int64", val))
+    }
+}
@@ -1,7 +1,9 @@
 use crate::errors::ConnectorXPythonError;
 use crate::pandas::{destination::PandasDestination, typesystem::PandasTypeSystem};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
-use connectorx::sources::postgres::{Bit, HalfVector, IpInet, SparseVector, Vector};
+use connectorx::sources::postgres::{
+    Bit, HalfVector, Hstore, IpInet, PgInterval, SparseVector, Vector, Wkb,
+};
 use connectorx::{
     impl_transport,
     sources::postgres::{
@@ -36,17 +38,19 @@ macro_rules! impl_postgres_transport {
                 { Int4[i32]                                     => I64[i64]                               | conversion auto }
                 { Int8[i64]                                     => I64[i64]                               | conversion auto }
                 { UInt4[u32]                                    => I64[i64]                               | conversion auto }
-                { BoolArray[Vec<Option<bool>>]                  => BoolArray[Vec<bool>]                   | conversion option }
-                { Int2Array[Vec<Option<i16>>]                   => I64Array[Vec<i64>]                     | conversion option }
-                { Int4Array[Vec<Option<i32>>]                   => I64Array[Vec<i64>]                     | conversion option }
-                { Int8Array[Vec<Option<i64>>]                   => I64Array[Vec<i64>]                     | conversion option }
-                { Float4Array[Vec<Option<f32>>]                 => F64Array[Vec<f64>]                     | conversion option }
-                { Float8Array[Vec<Option<f64>>]                 => F64Array[Vec<f64>]                     | conversion option }
-                { NumericArray[Vec<Option<Decimal>>]            => F64Array[Vec<f64>]                     | conversion option }
-                { Vector[Vector]                                => F64Array[Vec<f64>]                     | conversion option   }
-                { HalfVec[HalfVector]                           => F64Array[Vec<f64>]                     | conversion option   }
+                { BoolArray[Vec<Option<bool>>]                  => BoolArray[Vec<Option<bool>>]           | conversion auto }
+                { Int2Array[Vec<Option<i16>>]                   => I64Array[Vec<Option<i64>>]             | conversion option }
+                { Int4Array[Vec<Option<i32>>]                   => I64Array[Vec<Option<i64>>]             | conversion option }
+                { Int8Array[Vec<Option<i64>>]                   => I64Array[Vec<Option<i64>>]             | conversion auto }
+                { Float4Array[Vec<Option<f32>>]                 => F64Array[Vec<Option<f64>>]             | conversion option }
+                { Float8Array[Vec<Option<f64>>]                 => F64Array[Vec<Option<f64>>]             | conversion auto }
+                { NumericArray[Vec<Option<Decimal>>]            => F64Array[Vec<Option<f64>>]             | conversion option }
+                { VarcharArray[Vec<Option<String>>]             => StrArray[Vec<Option<String>>]          | conversion auto }
+                { TextArray[Vec<Option<String>>]                => StrArray[Vec<Option<String>>]          | conversion none }
+                { Vector[Vector]                                => F64Array[Vec<Option<f64>>]             | conversion option   }
+                { HalfVec[HalfVector]                           => F64Array[Vec<Option<f64>>]             | conversion option   }
                 { Bit[Bit]                                      => Bytes[Vec<u8>]                         | conversion option   }
-                { SparseVec[SparseVector]                       => F64Array[Vec<f64>]                     | conversion option   }
+                { SparseVec[SparseVector]                       => F64Array[Vec<Option<f64>>]             | conversion option   }
                 { Bool[bool]                                    => Bool[bool]                             | conversion auto }
                 { Char[i8]                                      => Char[char]                             | conversion option }
                 { Text[&'r str]                                 => Str[&'r str]                           | conversion auto }
@@ -61,9 +65,12 @@ macro_rules! impl_postgres_transport {
                 { JSONB[Value]                                  => String[String]                         | conversion none }
                 { Inet[IpInet]                                  => String[String]                         | conversion option }
                 { Time[NaiveTime]                               => String[String]                         | conversion option }
+                { Interval[PgInterval]                          => Interval[String]                       | conversion option }
                 { ByteA[Vec<u8>]                                => Bytes[Vec<u8>]                         | conversion auto }
                 { Enum[&'r str]                                 => Str[&'r str]                           | conversion none }
-                { HSTORE[HashMap<String, Option<String>>]       => String[String]                         | conversion option }
+                { HSTORE[Hstore]                                => StrMap[HashMap<String, Option<String>>] | conversion option }
+                { Geometry[Wkb]                                 => Bytes[Vec<u8>]                         | conversion option }
+                { GeometryWkt[Wkb]                              => String[String]                         | conversion option }
             }
         );
     }
@@ -78,15 +85,19 @@ impl_postgres_transport!(CursorProtocol, MakeTlsConnector);
 impl_postgres_transport!(SimpleProtocol, NoTls);
 impl_postgres_transport!(SimpleProtocol, MakeTlsConnector);
 
-impl<'py, P, C> TypeConversion<Vector, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vector) -> Vec<f64> {
-        val.to_vec().into_iter().map(|v| v as f64).collect()
+impl<'py, P, C> TypeConversion<Vector, Vec<Option<f64>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: Vector) -> Vec<Option<f64>> {
+        val.to_vec().into_iter().map(|v| Some(v as f64)).collect()
     }
 }
 
-impl<'py, P, C> TypeConversion<HalfVector, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: HalfVector) -> Vec<f64> {
-        val.to_vec().into_iter().map(|v| v.to_f64()).collect()
+impl<'py, P, C> TypeConversion<HalfVector, Vec<Option<f64>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: HalfVector) -> Vec<Option<f64>> {
+        val.to_vec().into_iter().map(|v| Some(v.to_f64())).collect()
     }
 }
 
@@ -96,120 +107,82 @@ impl<'py, P, C> TypeConversion<Bit, Vec<u8>> for PostgresPandasTransport<'py, P,
     }
 }
 
-impl<'py, P, C> TypeConversion<SparseVector, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: SparseVector) -> Vec<f64> {
-        val.to_vec().into_iter().map(|v| v as f64).collect()
+impl<'py, P, C> TypeConversion<SparseVector, Vec<Option<f64>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: SparseVector) -> Vec<Option<f64>> {
+        val.to_vec().into_iter().map(|v| Some(v as f64)).collect()
     }
 }
 
-impl<'py, P, C> TypeConversion<IpInet, String> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: IpInet) -> String {
-        val.to_string()
+impl<'py, P, C> TypeConversion<Wkb, Vec<u8>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: Wkb) -> Vec<u8> {
+        val.into_bytes()
     }
 }
 
-impl<'py, P, C> TypeConversion<HashMap<String, Option<String>>, String>
-    for PostgresPandasTransport<'py, P, C>
-{
-    fn convert(val: HashMap<String, Option<String>>) -> String {
-        to_string(&val).unwrap()
+impl<'py, P, C> TypeConversion<Wkb, String> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: Wkb) -> String {
+        val.to_string()
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<Decimal>>, Vec<f64>>
-    for PostgresPandasTransport<'py, P, C>
-{
-    fn convert(val: Vec<Option<Decimal>>) -> Vec<f64> {
-        val.into_iter()
-            .map(|v| match v {
-                Some(v) => v
-                    .to_f64()
-                    .unwrap_or_else(|| panic!("cannot convert decimal {:?} to float64", v)),
-                None => {
-                    unimplemented!("In-array nullable not implemented for Vec<Decimal> for Pandas")
-                }
-            })
-            .collect()
+impl<'py, P, C> TypeConversion<PgInterval, String> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: PgInterval) -> String {
+        val.to_string()
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<bool>>, Vec<bool>>
-    for PostgresPandasTransport<'py, P, C>
-{
-    fn convert(val: Vec<Option<bool>>) -> Vec<bool> {
-        val.into_iter()
-            .map(|v| match v {
-                Some(v) => v,
-                None => {
-                    unimplemented!("In-array nullable not implemented for Vec<bool> for Pandas")
-                }
-            })
-            .collect()
+impl<'py, P, C> TypeConversion<IpInet, String> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: IpInet) -> String {
+        val.to_string()
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<f32>>, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<f32>>) -> Vec<f64> {
-        val.into_iter()
-            .map(|v| match v {
-                Some(v) => v as f64,
-                None => {
-                    unimplemented!("In-array nullable not implemented for Vec<f32> for Pandas")
-                }
-            })
-            .collect()
+impl<'py, P, C> TypeConversion<Hstore, HashMap<String, Option<String>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: Hstore) -> HashMap<String, Option<String>> {
+        val.into_inner()
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<f64>>, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<f64>>) -> Vec<f64> {
+impl<'py, P, C> TypeConversion<Vec<Option<Decimal>>, Vec<Option<f64>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: Vec<Option<Decimal>>) -> Vec<Option<f64>> {
         val.into_iter()
-            .map(|v| match v {
-                Some(v) => v,
-                None => {
-                    unimplemented!("In-array nullable not implemented for Vec<f64> for Pandas")
-                }
+            .map(|v| {
+                v.map(|v| {
+                    v.to_f64()
+                        .unwrap_or_else(|| panic!("cannot convert decimal {:?} to float64", v))
+                })
             })
             .collect()
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<i16>>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<i16>>) -> Vec<i64> {
-        val.into_iter()
-            .map(|v| match v {
-                Some(v) => v as i64,
-                None => {
-                    unimplemented!("In-array nullable not implemented for Vec<i16> for Pandas")
-                }
-            })
-            .collect()
+impl<'py, P, C> TypeConversion<Vec<Option<f32>>, Vec<Option<f64>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: Vec<Option<f32>>) -> Vec<Option<f64>> {
+        val.into_iter().map(|v| v.map(|v| v as f64)).collect()
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<i32>>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<i32>>) -> Vec<i64> {
-        val.into_iter()
-            .map(|v| match v {
-                Some(v) => v as i64,
-                None => {
-                    unimplemented!("In-array nullable not implemented for Vec<i32> for Pandas")
-                }
-            })
-            .collect()
+impl<'py, P, C> TypeConversion<Vec<Option<i16>>, Vec<Option<i64>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: Vec<Option<i16>>) -> Vec<Option<i64>> {
+        val.into_iter().map(|v| v.map(|v| v as i64)).collect()
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<i64>>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<i64>>) -> Vec<i64> {
-        val.into_iter()
-            .map(|v| match v {
-                Some(v) => v,
-                None => {
-                    unimplemented!("In-array nullable not implemented for Vec<i64> for Pandas")
-                }
-            })
-            .collect()
+impl<'py, P, C> TypeConversion<Vec<Option<i32>>, Vec<Option<i64>>>
+    for PostgresPandasTransport<'py, P, C>
+{
+    fn convert(val: Vec<Option<i32>>) -> Vec<Option<i64>> {
+        val.into_iter().map(|v| v.map(|v| v as i64)).collect()
     }
 }
 
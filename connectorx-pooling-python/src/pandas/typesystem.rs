@@ -1,15 +1,21 @@
 // Unfortunately, due to the orphan rule, typesystem implementation should be in this crate.
 use chrono::{DateTime, Utc};
 use connectorx::impl_typesystem;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum PandasTypeSystem {
     F64(bool),
     I64(bool),
+    U8(bool),
+    U16(bool),
+    U32(bool),
+    U64(bool),
     F64Array(bool),
     I64Array(bool),
     Bool(bool),
     BoolArray(bool),
+    StrArray(bool),
     Char(bool),
     Str(bool),
     BoxStr(bool),
@@ -17,19 +23,30 @@ pub enum PandasTypeSystem {
     Bytes(bool),
     ByteSlice(bool),
     DateTime(bool),
+    /// A Postgres `interval` in its text form, turned into `timedelta64` by the caller when
+    /// the column has no month components.
+    Interval(bool),
+    /// A map of strings to nullable strings, such as a Postgres `hstore`, as Python dicts.
+    StrMap(bool),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum PandasBlockType {
     Boolean(bool), // bool indicates nullablity
     Int64(bool),
+    UInt8(bool),
+    UInt16(bool),
+    UInt32(bool),
+    UInt64(bool),
     Float64,
     BooleanArray,
     Int64Array,
     Float64Array,
+    StringArray,
     String,
     DateTime,
     Bytes,
+    StrMap,
 }
 
 pub enum PandasArrayType {
@@ -43,7 +60,11 @@ impl From<PandasBlockType> for PandasArrayType {
     fn from(ty: PandasBlockType) -> PandasArrayType {
         match ty {
             PandasBlockType::Boolean(true) => PandasArrayType::BooleanArray,
-            PandasBlockType::Int64(true) => PandasArrayType::IntegerArray,
+            PandasBlockType::Int64(true)
+            | PandasBlockType::UInt8(true)
+            | PandasBlockType::UInt16(true)
+            | PandasBlockType::UInt32(true)
+            | PandasBlockType::UInt64(true) => PandasArrayType::IntegerArray,
             PandasBlockType::DateTime => PandasArrayType::DatetimeArray,
             _ => PandasArrayType::NumpyArray,
         }
@@ -55,16 +76,23 @@ impl From<PandasTypeSystem> for PandasBlockType {
         match ty {
             PandasTypeSystem::Bool(nullable) => PandasBlockType::Boolean(nullable),
             PandasTypeSystem::I64(nullable) => PandasBlockType::Int64(nullable),
+            PandasTypeSystem::U8(nullable) => PandasBlockType::UInt8(nullable),
+            PandasTypeSystem::U16(nullable) => PandasBlockType::UInt16(nullable),
+            PandasTypeSystem::U32(nullable) => PandasBlockType::UInt32(nullable),
+            PandasTypeSystem::U64(nullable) => PandasBlockType::UInt64(nullable),
             PandasTypeSystem::F64(_) => PandasBlockType::Float64,
             PandasTypeSystem::BoolArray(_) => PandasBlockType::BooleanArray,
             PandasTypeSystem::F64Array(_) => PandasBlockType::Float64Array,
             PandasTypeSystem::I64Array(_) => PandasBlockType::Int64Array,
+            PandasTypeSystem::StrArray(_) => PandasBlockType::StringArray,
             PandasTypeSystem::String(_)
             | PandasTypeSystem::BoxStr(_)
             | PandasTypeSystem::Str(_)
-            | PandasTypeSystem::Char(_) => PandasBlockType::String,
+            | PandasTypeSystem::Char(_)
+            | PandasTypeSystem::Interval(_) => PandasBlockType::String,
             PandasTypeSystem::Bytes(_) | PandasTypeSystem::ByteSlice(_) => PandasBlockType::Bytes,
             PandasTypeSystem::DateTime(_) => PandasBlockType::DateTime,
+            PandasTypeSystem::StrMap(_) => PandasBlockType::StrMap,
         }
     }
 }
@@ -74,17 +102,23 @@ impl_typesystem! {
     mappings = {
         { F64 => f64 }
         { I64 => i64 }
-        { F64Array => Vec<f64> }
-        { I64Array => Vec<i64> }
+        { U8 => u8 }
+        { U16 => u16 }
+        { U32 => u32 }
+        { U64 => u64 }
+        { F64Array => Vec<Option<f64>> }
+        { I64Array => Vec<Option<i64>> }
         { Bool => bool }
-        { BoolArray => Vec<bool> }
+        { BoolArray => Vec<Option<bool>> }
+        { StrArray => Vec<Option<String>> }
         { Char => char }
         { Str => &'r str }
         { BoxStr => Box<str> }
-        { String => String }
+        { String | Interval => String }
         { Bytes => Vec<u8> }
         { ByteSlice => &'r [u8] }
         { DateTime => DateTime<Utc> }
+        { StrMap => HashMap<String, Option<String>> }
     }
 }
 
@@ -98,14 +132,23 @@ impl PandasDType for PandasBlockType {
     fn is_masked(&self) -> bool {
         matches!(
             *self,
-            PandasBlockType::Boolean(true) | PandasBlockType::Int64(true)
+            PandasBlockType::Boolean(true)
+                | PandasBlockType::Int64(true)
+                | PandasBlockType::UInt8(true)
+                | PandasBlockType::UInt16(true)
+                | PandasBlockType::UInt32(true)
+                | PandasBlockType::UInt64(true)
         )
     }
 
     fn array_name(&self) -> &'static str {
         match *self {
             PandasBlockType::Boolean(true) => "BooleanArray",
-            PandasBlockType::Int64(true) => "IntegerArray",
+            PandasBlockType::Int64(true)
+            | PandasBlockType::UInt8(true)
+            | PandasBlockType::UInt16(true)
+            | PandasBlockType::UInt32(true)
+            | PandasBlockType::UInt64(true) => "IntegerArray",
             PandasBlockType::DateTime => "DatetimeArray",
             _ => "",
         }
@@ -1,81 +1,489 @@
-use parking_lot::Mutex;
+use parking_lot::{Condvar, Mutex, RwLock};
 use pyo3::prelude::*;
-use std::time::Duration;
+use pyo3::types::{PyDict, PyList};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Weak};
+use std::time::{Duration, Instant};
 
-use connectorx::pool::{PoolConfig, PoolVariant};
-use connectorx::source_router::parse_source;
+use arrow::record_batch::RecordBatch;
+use connectorx::arrow_batch_iter::RecordBatchIterator;
+use connectorx::errors::ConnectorXOutError;
+use connectorx::get_arrow::{
+    get_arrow_schema, get_arrow_single, new_record_batch_iter, ArrowQuery,
+};
+use connectorx::params::ScalarValue;
+use connectorx::pool::{CircuitBreakerConfig, PinnedVariant, PoolConfig, PoolEvent, PoolVariant};
+use connectorx::schema_cache::SchemaCache;
+use connectorx::source_router::{
+    normalize_conn_str, parse_source, resolve_conn, supported_protocols, SourceConn,
+};
+use connectorx::sql::CXQuery;
+use connectorx::transaction::Transaction;
 
-use crate::errors::ConnectorXPythonError;
+use crate::arrow::{PyBatchReader, PyBatchStream};
+use crate::constants::READ_DICTS_MAX_ROWS;
+use crate::cx_read_sql::PyScalarValue;
+use crate::errors::{ConnectorXPythonError, UnsupportedPoolBackend};
+use crate::progress::PyProgress;
 
 /// Python-exposed connection pool class
 #[pyclass]
 pub struct PyConnectionPool {
-    pool: Mutex<Option<PoolVariant>>,
+    pool: Mutex<Option<Arc<PoolVariant>>>,
     pub conn_str: String,
     max_size: u32,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
     #[pyo3(get)]
     pub default_protocol: String,
+    /// Reports the outcome of the last `warm_async`, until a `wait_warm` receives it.
+    warming: Mutex<Option<Warming>>,
+    /// Result schemas of earlier reads, kept when the pool is created with `schema_cache`.
+    schema_cache: Option<Arc<SchemaCache>>,
+}
+
+/// The channel the thread of a `warm_async` reports its outcome on. Its own lock is held by
+/// the `wait_warm` waiting on it, so that concurrent waits queue up behind that one.
+type Warming = Arc<Mutex<mpsc::Receiver<anyhow::Result<()>>>>;
+
+/// Redshift and ClickHouse are reached through the Postgres and MySQL drivers, with the
+/// protocol they support; other connection strings pass through unchanged.
+pub(crate) fn rewrite_pool_conn(conn: &str) -> (String, Option<&'static str>) {
+    let backend_split: Vec<&str> = conn.splitn(2, ':').collect();
+    if backend_split.len() == 2 {
+        if backend_split[0].contains("redshift") {
+            return (format!("postgresql:{}", backend_split[1]), Some("cursor"));
+        } else if backend_split[0].contains("clickhouse") {
+            return (format!("mysql:{}", backend_split[1]), Some("text"));
+        }
+    }
+    (conn.to_string(), None)
+}
+
+lazy_static! {
+    /// Pools handed out by `get_or_create`, by `registry_key`. An entry dies with the last
+    /// open `ConnectionPool` sharing it.
+    static ref POOL_REGISTRY: Mutex<HashMap<String, Slot>> = Mutex::new(HashMap::new());
+
+    /// Signalled as a pool `get_or_create` was building is registered, or fails to build.
+    static ref POOL_REGISTERED: Condvar = Condvar::new();
+
+    /// Settings a `ConnectionPool` falls back to for every option its caller leaves out.
+    static ref DEFAULT_POOL_CONFIG: RwLock<PoolConfig> = RwLock::new(PoolConfig::default());
+
+    /// The cap `set_global_connection_budget` puts on the pools of the process.
+    static ref CONNECTION_BUDGET: Mutex<ConnectionBudget> = Mutex::new(ConnectionBudget::default());
+}
+
+/// A `POOL_REGISTRY` entry: a pool being built, which later callers for the key wait for,
+/// or the one built.
+enum Slot {
+    Building,
+    Built(Weak<PoolVariant>),
+}
+
+/// The `Building` entry of `key`, removed unless replaced by the pool built by then.
+struct Building(String);
+
+impl Drop for Building {
+    fn drop(&mut self) {
+        let mut registry = POOL_REGISTRY.lock();
+        if let Some(Slot::Building) = registry.get(&self.0) {
+            registry.remove(&self.0);
+        }
+        POOL_REGISTERED.notify_all();
+    }
+}
+
+/// The live pools and the cap on the sum of their `max_size`. A pool stops counting once
+/// the last `ConnectionPool` sharing it is closed or dropped.
+#[derive(Default)]
+struct ConnectionBudget {
+    max: Option<u32>,
+    pools: Vec<Weak<PoolVariant>>,
+    /// The `max_size` of the pools being built.
+    reserved: u32,
+}
+
+impl ConnectionBudget {
+    fn used(&mut self) -> u32 {
+        self.pools.retain(|pool| pool.strong_count() > 0);
+        self.pools
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|pool| pool.max_size())
+            .sum::<u32>()
+            + self.reserved
+    }
+
+    /// Sets `max_size` aside for a pool about to be built, until the returned reservation
+    /// is dropped.
+    fn reserve(&mut self, max_size: u32) -> PyResult<Reservation> {
+        if let Some(max) = self.max {
+            let used = self.used();
+            if used + max_size > max {
+                return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "A pool of {} connections would exceed the global connection budget of \
+                     {}, of which open pools take {}",
+                    max_size, max, used
+                )));
+            }
+        }
+        self.reserved += max_size;
+        Ok(Reservation(max_size))
+    }
+
+    fn track(&mut self, pool: &Arc<PoolVariant>) {
+        if !self
+            .pools
+            .iter()
+            .any(|tracked| tracked.as_ptr() == Arc::as_ptr(pool))
+        {
+            self.pools.push(Arc::downgrade(pool));
+        }
+    }
+}
+
+/// The budget set aside by `ConnectionBudget::reserve`, given back when dropped.
+struct Reservation(u32);
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        CONNECTION_BUDGET.lock().reserved -= self.0;
+    }
+}
+
+/// Cap the connections all pools of the process may open together: creating a
+/// `ConnectionPool` whose `max_size` would take the sum over the open pools past `max`
+/// raises `RuntimeError`. `None` lifts the cap. Open pools are kept even if they already
+/// exceed a new cap.
+#[pyfunction]
+#[pyo3(signature = (max))]
+pub fn set_global_connection_budget(max: Option<u32>) {
+    CONNECTION_BUDGET.lock().max = max;
+}
+
+/// The cap set by `set_global_connection_budget`, as `max`, and how much of it the open
+/// pools take, as `used`.
+#[pyfunction]
+pub fn get_global_connection_budget(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let mut budget = CONNECTION_BUDGET.lock();
+    let dict = PyDict::new(py);
+    dict.set_item("max", budget.max)?;
+    dict.set_item("used", budget.used())?;
+    Ok(dict)
+}
+
+/// Replace the process-wide pool defaults. `config` holds any of the `ConnectionPool`
+/// options, timeouts in seconds; the options it leaves out get their built-in default.
+/// Pools created before the call keep their settings.
+#[pyfunction]
+pub fn set_default_pool_config(config: &Bound<'_, PyDict>) -> PyResult<()> {
+    let mut default = PoolConfig::default();
+    let (mut failure_threshold, mut open_duration) = (None, None);
+    for (key, value) in config.iter() {
+        let key: String = key.extract()?;
+        let secs = |value: &Bound<'_, PyAny>| -> PyResult<Option<Duration>> {
+            Ok(value.extract::<Option<u64>>()?.map(Duration::from_secs))
+        };
+        match key.as_str() {
+            "max_size" => default.max_size = value.extract()?,
+            "idle_timeout" => default.idle_timeout = secs(&value)?,
+            "max_lifetime" => default.max_lifetime = secs(&value)?,
+            "max_lifetime_jitter" => default.max_lifetime_jitter = secs(&value)?,
+            "connection_timeout" => {
+                default.connection_timeout = Duration::from_secs(value.extract()?)
+            }
+            "test_on_check_out" => default.test_on_check_out = value.extract()?,
+            "application_name" => default.application_name = value.extract()?,
+            "min_idle" => default.min_idle = value.extract()?,
+            "on_return_query" => default.on_return_query = value.extract()?,
+            "init_queries" => default.init_queries = value.extract()?,
+            "load_extensions" => default.load_extensions = value.extract()?,
+            "failure_threshold" => failure_threshold = value.extract()?,
+            "open_duration" => open_duration = value.extract()?,
+            "read_only" => default.read_only = value.extract()?,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown pool option '{}'",
+                    key
+                )))
+            }
+        }
+    }
+    default.circuit_breaker = circuit_breaker(failure_threshold, open_duration, None);
+    *DEFAULT_POOL_CONFIG.write() = default;
+    Ok(())
+}
+
+/// The process-wide pool defaults, in the form `set_default_pool_config` takes.
+#[pyfunction]
+pub fn get_default_pool_config(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let default = DEFAULT_POOL_CONFIG.read();
+    let secs = |d: Option<Duration>| d.map(|d| d.as_secs());
+    let dict = PyDict::new(py);
+    dict.set_item("max_size", default.max_size)?;
+    dict.set_item("idle_timeout", secs(default.idle_timeout))?;
+    dict.set_item("max_lifetime", secs(default.max_lifetime))?;
+    dict.set_item("max_lifetime_jitter", secs(default.max_lifetime_jitter))?;
+    dict.set_item("connection_timeout", default.connection_timeout.as_secs())?;
+    dict.set_item("test_on_check_out", default.test_on_check_out)?;
+    dict.set_item("application_name", default.application_name.as_deref())?;
+    dict.set_item("min_idle", default.min_idle)?;
+    dict.set_item("on_return_query", default.on_return_query.as_deref())?;
+    dict.set_item("init_queries", &default.init_queries)?;
+    dict.set_item("load_extensions", &default.load_extensions)?;
+    let breaker = default.circuit_breaker;
+    dict.set_item("failure_threshold", breaker.map(|b| b.failure_threshold))?;
+    dict.set_item("open_duration", breaker.map(|b| b.open_duration.as_secs()))?;
+    dict.set_item("read_only", default.read_only)?;
+    Ok(dict)
+}
+
+/// The circuit breaker `failure_threshold` and `open_duration` (in seconds) select, falling
+/// back to `default` for the one left out. Without a threshold the breaker stays off.
+fn circuit_breaker(
+    failure_threshold: Option<u32>,
+    open_duration: Option<u64>,
+    default: Option<CircuitBreakerConfig>,
+) -> Option<CircuitBreakerConfig> {
+    let failure_threshold =
+        failure_threshold.or_else(|| default.map(|config| config.failure_threshold))?;
+    let open_duration = open_duration
+        .map(Duration::from_secs)
+        .or_else(|| default.map(|config| config.open_duration))
+        .unwrap_or(CircuitBreakerConfig::DEFAULT_OPEN_DURATION);
+    Some(CircuitBreakerConfig {
+        failure_threshold,
+        open_duration,
+    })
+}
+
+/// The normalized `conn` with its password replaced by a hash, so urls differing only in
+/// parameter order or host case share a key and the key does not hold the password.
+fn registry_key(conn: &str) -> String {
+    let mut url = match normalize_conn_str(conn).map(|c| url::Url::parse(&c)) {
+        Ok(Ok(url)) => url,
+        _ => return conn.to_string(),
+    };
+    if let Some(password) = url.password() {
+        let mut hasher = DefaultHasher::new();
+        password.hash(&mut hasher);
+        let _ = url.set_password(Some(&format!("{:016x}", hasher.finish())));
+    }
+    url.to_string()
+}
+
+/// A `ConnectionPool` timeout in seconds, telling an option left out, which takes its
+/// default, from `None`, which turns the timeout off.
+#[derive(Clone, Copy)]
+pub enum Timeout {
+    Default,
+    Off,
+    Secs(u64),
+}
+
+impl Timeout {
+    fn or(self, default: Option<Duration>) -> Option<Duration> {
+        match self {
+            Timeout::Default => default,
+            Timeout::Off => None,
+            Timeout::Secs(secs) => Some(Duration::from_secs(secs)),
+        }
+    }
+}
+
+impl<'a, 'py> FromPyObject<'a, 'py> for Timeout {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        Ok(match obj.extract::<Option<u64>>()? {
+            Some(secs) => Timeout::Secs(secs),
+            None => Timeout::Off,
+        })
+    }
 }
 
 #[pymethods]
 impl PyConnectionPool {
-    /// Create a new connection pool
+    /// Create a new connection pool. Options left out, or passed as `None`, take their value
+    /// from `set_default_pool_config`, except `idle_timeout`, `max_lifetime` and
+    /// `max_lifetime_jitter`, which `None` turns off.
     #[new]
-    #[pyo3(signature = (conn, max_size=10, idle_timeout=300, max_lifetime=1800, connection_timeout=30, test_on_check_out=true))]
+    #[pyo3(signature = (conn, max_size=None, idle_timeout=Timeout::Default, max_lifetime=Timeout::Default, connection_timeout=None, test_on_check_out=None, max_lifetime_jitter=Timeout::Default, application_name=None, min_idle=None, on_return_query=None, init_queries=None, load_extensions=None, failure_threshold=None, open_duration=None, label=None, read_only=None, schema_cache=false))]
     pub fn new(
+        py: Python<'_>,
         conn: &str,
-        max_size: u32,
-        idle_timeout: Option<u64>,
-        max_lifetime: Option<u64>,
-        connection_timeout: u64,
-        test_on_check_out: bool,
+        max_size: Option<u32>,
+        idle_timeout: Timeout,
+        max_lifetime: Timeout,
+        connection_timeout: Option<u64>,
+        test_on_check_out: Option<bool>,
+        max_lifetime_jitter: Timeout,
+        application_name: Option<String>,
+        min_idle: Option<u32>,
+        on_return_query: Option<String>,
+        init_queries: Option<Vec<String>>,
+        load_extensions: Option<Vec<PathBuf>>,
+        failure_threshold: Option<u32>,
+        open_duration: Option<u64>,
+        label: Option<String>,
+        read_only: Option<bool>,
+        schema_cache: bool,
     ) -> PyResult<Self> {
+        let default = DEFAULT_POOL_CONFIG.read().clone();
         let config = PoolConfig {
-            max_size,
-            idle_timeout: idle_timeout.map(Duration::from_secs),
-            max_lifetime: max_lifetime.map(Duration::from_secs),
-            connection_timeout: Duration::from_secs(connection_timeout),
-            test_on_check_out,
+            max_size: max_size.unwrap_or(default.max_size),
+            idle_timeout: idle_timeout.or(default.idle_timeout),
+            max_lifetime: max_lifetime.or(default.max_lifetime),
+            max_lifetime_jitter: max_lifetime_jitter.or(default.max_lifetime_jitter),
+            connection_timeout: connection_timeout
+                .map(Duration::from_secs)
+                .unwrap_or(default.connection_timeout),
+            test_on_check_out: test_on_check_out.unwrap_or(default.test_on_check_out),
+            application_name: application_name.or(default.application_name),
+            min_idle: min_idle.or(default.min_idle),
+            on_return_query: on_return_query.or(default.on_return_query),
+            init_queries: init_queries.unwrap_or(default.init_queries),
+            load_extensions: load_extensions.unwrap_or(default.load_extensions),
+            circuit_breaker: circuit_breaker(
+                failure_threshold,
+                open_duration,
+                default.circuit_breaker,
+            ),
+            label: label.or(default.label),
+            read_only: read_only.unwrap_or(default.read_only),
+            ..default
         };
 
-        let mut rewritten_conn = conn.to_string();
-        let mut default_protocol = "binary".to_string();
-
-        let backend_split: Vec<&str> = conn.splitn(2, ':').collect();
-        if backend_split.len() == 2 {
-            if backend_split[0].contains("redshift") {
-                rewritten_conn = format!("postgresql:{}", backend_split[1]);
-                default_protocol = "cursor".to_string();
-            } else if backend_split[0].contains("clickhouse") {
-                rewritten_conn = format!("mysql:{}", backend_split[1]);
-                default_protocol = "text".to_string();
+        py.detach(|| Self::from_config(conn, config, schema_cache))
+    }
+
+    /// Return a `ConnectionPool` sharing the connections of an open pool created by an earlier
+    /// call for the same connection string, or build a new one. Query parameter order does not
+    /// matter. The pool settings only apply when a new pool is built.
+    #[staticmethod]
+    #[pyo3(signature = (conn, max_size=None, idle_timeout=Timeout::Default, max_lifetime=Timeout::Default, connection_timeout=None, test_on_check_out=None, max_lifetime_jitter=Timeout::Default, application_name=None, min_idle=None, on_return_query=None, init_queries=None, load_extensions=None, failure_threshold=None, open_duration=None, label=None, read_only=None, schema_cache=false))]
+    pub fn get_or_create(
+        py: Python<'_>,
+        conn: &str,
+        max_size: Option<u32>,
+        idle_timeout: Timeout,
+        max_lifetime: Timeout,
+        connection_timeout: Option<u64>,
+        test_on_check_out: Option<bool>,
+        max_lifetime_jitter: Timeout,
+        application_name: Option<String>,
+        min_idle: Option<u32>,
+        on_return_query: Option<String>,
+        init_queries: Option<Vec<String>>,
+        load_extensions: Option<Vec<PathBuf>>,
+        failure_threshold: Option<u32>,
+        open_duration: Option<u64>,
+        label: Option<String>,
+        read_only: Option<bool>,
+        schema_cache: bool,
+    ) -> PyResult<Self> {
+        let resolved = resolve_conn(conn).map_err(|e| ConnectorXPythonError::from(e))?;
+        let key = registry_key(&resolved);
+
+        // a concurrent caller for `key` waits for the pool the first one builds
+        let found = py.detach(|| {
+            let mut registry = POOL_REGISTRY.lock();
+            registry.retain(|_, slot| match slot {
+                Slot::Building => true,
+                Slot::Built(pool) => pool.strong_count() > 0,
+            });
+            while let Some(Slot::Building) = registry.get(&key) {
+                POOL_REGISTERED.wait(&mut registry);
+            }
+            if let Some(Slot::Built(pool)) = registry.get(&key) {
+                if let Some(pool_variant) = pool.upgrade() {
+                    return Ok(pool_variant);
+                }
             }
+            registry.insert(key.clone(), Slot::Building);
+            Err(Building(key.clone()))
+        });
+        let building = match found {
+            Ok(pool_variant) => {
+                let mut pool = Self::with_pool(&resolved, |_| Ok(pool_variant))?;
+                if schema_cache {
+                    pool.schema_cache = Some(Arc::new(SchemaCache::new()));
+                }
+                return Ok(pool);
+            }
+            Err(building) => building,
+        };
+        let pool = Self::new(
+            py,
+            &resolved,
+            max_size,
+            idle_timeout,
+            max_lifetime,
+            connection_timeout,
+            test_on_check_out,
+            max_lifetime_jitter,
+            application_name,
+            min_idle,
+            on_return_query,
+            init_queries,
+            load_extensions,
+            failure_threshold,
+            open_duration,
+            label,
+            read_only,
+            schema_cache,
+        )?;
+        if let Some(pool_variant) = pool.pool.lock().as_ref() {
+            let slot = Slot::Built(Arc::downgrade(pool_variant));
+            POOL_REGISTRY.lock().insert(key, slot);
         }
+        drop(building);
+        Ok(pool)
+    }
 
-        let source_conn = parse_source(&rewritten_conn, None)
+    /// Try a single connection and classify why it fails, without building a pool.
+    /// Returns a dict with the diagnosis (`Ok`, `HostUnreachable`, `AuthFailed`,
+    /// `DatabaseNotFound`, `TlsError` or `Other`) and the driver's error message.
+    #[staticmethod]
+    #[pyo3(signature = (conn, connection_timeout=30))]
+    pub fn diagnose<'py>(
+        py: Python<'py>,
+        conn: &str,
+        connection_timeout: u64,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let conn = resolve_conn(conn).map_err(|e| ConnectorXPythonError::from(e))?;
+        let (rewritten_conn, default_protocol) = rewrite_pool_conn(&conn);
+        let source_conn = parse_source(&rewritten_conn, default_protocol)
             .map_err(|e| ConnectorXPythonError::from(e))?;
+        let config = PoolConfig {
+            connection_timeout: Duration::from_secs(connection_timeout),
+            ..Default::default()
+        };
+        let diagnosis = py.detach(|| PoolVariant::diagnose(&source_conn, &config));
 
-        let pool_variant = PoolVariant::from_source_conn(&source_conn, &config)
-            .map_err(|e| ConnectorXPythonError::from(e))?
-            .ok_or_else(|| {
-                pyo3::exceptions::PyValueError::new_err(format!(
-                    "Unsupported database type for connection pooling: {:?}",
-                    source_conn.ty
-                ))
-            })?;
-
-        Ok(Self {
-            pool: Mutex::new(Some(pool_variant)),
-            conn_str: rewritten_conn,
-            max_size,
-            default_protocol,
-        })
+        let dict = PyDict::new(py);
+        dict.set_item("diagnosis", diagnosis.kind())?;
+        dict.set_item("message", diagnosis.message())?;
+        Ok(dict)
     }
 
     /// Close the pool and release all connections
     pub fn close(&self) {
         let mut pool = self.pool.lock();
-        *pool = None;
+        if let Some(variant) = pool.take() {
+            // the pool only closes along with the last wrapper sharing it
+            if Arc::strong_count(&variant) == 1 {
+                variant.emit_event(PoolEvent::Close {
+                    connections: variant.state().connections,
+                });
+                variant.set_event_handler(None);
+            }
+        }
     }
 
     /// Check if the pool is closed
@@ -90,6 +498,559 @@ impl PyConnectionPool {
         self.max_size
     }
 
+    /// Seconds the pool keeps an idle connection, `None` if it keeps them however long
+    #[getter]
+    pub fn idle_timeout(&self) -> Option<u64> {
+        self.idle_timeout.map(|d| d.as_secs())
+    }
+
+    /// Seconds the pool keeps a connection open, `None` if it keeps them however long
+    #[getter]
+    pub fn max_lifetime(&self) -> Option<u64> {
+        self.max_lifetime.map(|d| d.as_secs())
+    }
+
+    /// Number of connections the pool manages and how many of them are idle
+    pub fn state<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let pool = self.live_pool()?;
+        let state = pool.state();
+        let dict = PyDict::new(py);
+        dict.set_item("connections", state.connections)?;
+        dict.set_item("idle_connections", state.idle_connections)?;
+        Ok(dict)
+    }
+
+    /// State of the pool's circuit breaker: `closed`, `open` or `half_open`, or `None` if
+    /// the pool was created without a `failure_threshold`
+    pub fn circuit_state(&self) -> PyResult<Option<&'static str>> {
+        let pool = self.live_pool()?;
+        Ok(pool.circuit_state().map(|state| state.name()))
+    }
+
+    /// Forget the result schemas the pool cached, so the next read of every query describes
+    /// it to the database again. Does nothing for a pool created without `schema_cache`.
+    pub fn clear_schema_cache(&self) {
+        if let Some(cache) = &self.schema_cache {
+            cache.clear();
+        }
+    }
+
+    /// The reads that took their schema from the cache, as `hits`, the reads that described
+    /// their query, as `misses`, and the queries cached, as `entries`; `None` for a pool
+    /// created without `schema_cache`.
+    pub fn schema_cache_info<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let cache = match &self.schema_cache {
+            Some(cache) => cache,
+            None => return Ok(None),
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("hits", cache.hits())?;
+        dict.set_item("misses", cache.misses())?;
+        dict.set_item("entries", cache.len())?;
+        Ok(Some(dict))
+    }
+
+    /// What the pool's backend supports: `supports_transactions`, `supports_prepared`,
+    /// `supports_copy`, `supports_partition` and `supports_tls`, each a bool
+    pub fn capabilities<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let pool = self.live_pool()?;
+        let capabilities = pool.capabilities();
+        let dict = PyDict::new(py);
+        dict.set_item("supports_transactions", capabilities.supports_transactions)?;
+        dict.set_item("supports_prepared", capabilities.supports_prepared)?;
+        dict.set_item("supports_copy", capabilities.supports_copy)?;
+        dict.set_item("supports_partition", capabilities.supports_partition)?;
+        dict.set_item("supports_tls", capabilities.supports_tls)?;
+        Ok(dict)
+    }
+
+    /// Column names and Arrow type names a query would return, resolved through the pool.
+    /// Raises `ValueError` if the query cannot be described, e.g. as it is invalid or the
+    /// connection failed.
+    #[pyo3(signature = (query, protocol=None))]
+    pub fn get_meta(
+        &self,
+        py: Python<'_>,
+        query: String,
+        protocol: Option<&str>,
+    ) -> PyResult<Vec<(String, String)>> {
+        let pool = self.live_pool()?;
+        let source_conn = parse_source(
+            &self.conn_str,
+            Some(protocol.unwrap_or(&self.default_protocol)),
+        )
+        .map_err(|e| ConnectorXPythonError::from(e))?;
+        let query = CXQuery::Naked(query);
+
+        let schema = py
+            .detach(|| get_arrow_schema(&source_conn, &query, Some(&pool)))
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().clone(), f.data_type().to_string()))
+            .collect())
+    }
+
+    /// Read `query`, or a list of queries read as one partition each, on pooled connections.
+    /// `progress_callback(completed, total)` is called under the GIL each time a partition is
+    /// read, e.g. with a tqdm bar's `update`-style counts; an exception it raises aborts the
+    /// read and propagates as is.
+    #[pyo3(signature = (query, return_type="pandas", protocol=None, progress_callback=None))]
+    pub fn read_sql<'py>(
+        &self,
+        py: Python<'py>,
+        query: &Bound<'py, PyAny>,
+        return_type: &str,
+        protocol: Option<&str>,
+        progress_callback: Option<Py<PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        check_return_type(return_type)?;
+        let pool = self.live_pool()?;
+        let source_conn = parse_source(
+            &self.conn_str,
+            Some(protocol.unwrap_or(&self.default_protocol)),
+        )
+        .map_err(|e| ConnectorXPythonError::from(e))?;
+        let queries: Vec<CXQuery<String>> = match query.extract::<String>() {
+            Ok(query) => vec![CXQuery::Naked(query)],
+            Err(_) => query
+                .extract::<Vec<String>>()?
+                .into_iter()
+                .map(CXQuery::Naked)
+                .collect(),
+        };
+        let schema_cache = self.schema_cache.clone();
+        let progress = progress_callback.map(PyProgress::new);
+
+        let rbs = py
+            .detach(|| -> Result<_, ConnectorXOutError> {
+                let mut read = ArrowQuery::new(&source_conn).queries(&queries).pool(&pool);
+                if let Some(cache) = &schema_cache {
+                    read = read.schema_cache(cache);
+                }
+                if let Some(progress) = &progress {
+                    read = read.progress(progress.to_callback());
+                }
+                Ok(read.run()?.arrow()?)
+            })
+            .map_err(|e| {
+                progress
+                    .as_ref()
+                    .and_then(PyProgress::take_raised)
+                    .unwrap_or_else(|| ConnectorXPythonError::from(e).into())
+            })?;
+        to_table(py, rbs, return_type)
+    }
+
+    /// Read `query` lazily on a pooled connection, as an iterator of `pyarrow.RecordBatch`es
+    /// of at most `batch_size` rows. The connection goes back to the pool once the iterator
+    /// is exhausted or garbage-collected. `progress_callback(rows, None)` is called with the
+    /// rows streamed so far after every batch; an exception it raises ends the stream.
+    #[pyo3(signature = (query, batch_size=10000, protocol=None, progress_callback=None))]
+    pub fn stream_sql(
+        &self,
+        py: Python<'_>,
+        query: String,
+        batch_size: usize,
+        protocol: Option<&str>,
+        progress_callback: Option<Py<PyAny>>,
+    ) -> PyResult<PyBatchStream> {
+        let iter = self.batch_iter(py, query, batch_size, protocol)?;
+        let stream = PyBatchStream::new(iter);
+        Ok(match progress_callback {
+            Some(callback) => stream.with_progress(PyProgress::new(callback)),
+            None => stream,
+        })
+    }
+
+    /// Read `query` lazily on a pooled connection, as a `pyarrow.RecordBatchReader` of
+    /// batches of at most `batch_size` rows, which pyarrow pulls straight from connectorx
+    /// through the Arrow C stream interface. The connection goes back to the pool once the
+    /// reader is exhausted or closed.
+    #[pyo3(signature = (query, batch_size=10000, protocol=None))]
+    pub fn stream_reader<'py>(
+        &self,
+        py: Python<'py>,
+        query: String,
+        batch_size: usize,
+        protocol: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let iter = self.batch_iter(py, query, batch_size, protocol)?;
+        py.import("pyarrow")?
+            .getattr("RecordBatchReader")?
+            .call_method1("from_stream", (PyBatchReader::new(iter),))
+    }
+
+    /// Read `query` into a list of `{column: value}` dicts, one per row, converted by pyarrow
+    /// rather than through pandas. Meant for small results: without `limit`, a result of more
+    /// than 100,000 rows raises `ValueError` as soon as the read gets past them, instead of
+    /// being buffered whole; with it, only the first `limit` rows are read.
+    #[pyo3(signature = (query, limit=None, protocol=None))]
+    pub fn read_dicts<'py>(
+        &self,
+        py: Python<'py>,
+        query: String,
+        limit: Option<usize>,
+        protocol: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let pool = self.live_pool()?;
+        let source_conn = parse_source(
+            &self.conn_str,
+            Some(protocol.unwrap_or(&self.default_protocol)),
+        )
+        .map_err(|e| ConnectorXPythonError::from(e))?;
+        if limit == Some(0) {
+            return Ok(PyList::empty(py).into_any());
+        }
+        let queries = [CXQuery::Naked(query)];
+        let max_rows = limit.unwrap_or(READ_DICTS_MAX_ROWS);
+
+        let rbs = py
+            .detach(|| -> Result<_, ConnectorXOutError> {
+                let mut iter = new_record_batch_iter(
+                    &source_conn,
+                    None,
+                    &queries,
+                    max_rows.min(10000),
+                    None,
+                    Some(&pool),
+                    None,
+                )?;
+                iter.prepare();
+                let mut rbs = vec![];
+                let mut rows = 0;
+                while let Some(rb) = iter.try_next_batch()? {
+                    if rows + rb.num_rows() > max_rows {
+                        if limit.is_none() {
+                            return Ok(None);
+                        }
+                        rbs.push(rb.slice(0, max_rows - rows));
+                        break;
+                    }
+                    rows += rb.num_rows();
+                    rbs.push(rb);
+                }
+                Ok(Some(rbs))
+            })
+            .map_err(|e| ConnectorXPythonError::from(e))?
+            .ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "The result holds more than {} rows; pass limit to read only the first ones",
+                    READ_DICTS_MAX_ROWS
+                ))
+            })?;
+        if rbs.is_empty() {
+            return Ok(PyList::empty(py).into_any());
+        }
+        to_table(py, rbs, "arrow")?.call_method0("to_pylist")
+    }
+
+    /// Time `query` under each of `protocols`, `repeat` reads each on the pool's connections,
+    /// to pick the fastest for a workload. Returns a dict by protocol holding `median_ms`,
+    /// `rows` and `runs`, or for a protocol the backend does not support, why it was
+    /// `skipped`.
+    #[pyo3(signature = (query, protocols=None, repeat=3))]
+    pub fn benchmark_protocols<'py>(
+        &self,
+        py: Python<'py>,
+        query: String,
+        protocols: Option<Vec<String>>,
+        repeat: usize,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        if repeat == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "repeat must be at least 1",
+            ));
+        }
+        let pool = self.live_pool()?;
+        let ty = parse_source(&self.conn_str, None)
+            .map_err(|e| ConnectorXPythonError::from(e))?
+            .ty;
+        let protocols =
+            protocols.unwrap_or_else(|| vec!["binary".into(), "csv".into(), "cursor".into()]);
+        let queries = [CXQuery::Naked(query)];
+
+        let results = PyDict::new(py);
+        for protocol in protocols {
+            let result = PyDict::new(py);
+            if !supported_protocols(&ty).contains(&protocol.as_str()) {
+                result.set_item(
+                    "skipped",
+                    format!("{:?} does not support the {} protocol", ty, protocol),
+                )?;
+                results.set_item(protocol, result)?;
+                continue;
+            }
+            let source_conn = parse_source(&self.conn_str, Some(&protocol))
+                .map_err(|e| ConnectorXPythonError::from(e))?;
+            let (mut elapsed, rows) = py
+                .detach(|| -> Result<_, ConnectorXOutError> {
+                    let mut elapsed = vec![];
+                    let mut rows = 0;
+                    for _ in 0..repeat {
+                        let start = Instant::now();
+                        let destination = ArrowQuery::new(&source_conn)
+                            .queries(&queries)
+                            .pool(&pool)
+                            .run()?;
+                        elapsed.push(start.elapsed().as_secs_f64() * 1000.0);
+                        rows = destination.arrow()?.iter().map(|rb| rb.num_rows()).sum();
+                    }
+                    Ok((elapsed, rows))
+                })
+                .map_err(|e| ConnectorXPythonError::from(e))?;
+            elapsed.sort_by(f64::total_cmp);
+            let mid = elapsed.len() / 2;
+            let median = match elapsed.len() % 2 {
+                0 => (elapsed[mid - 1] + elapsed[mid]) / 2.0,
+                _ => elapsed[mid],
+            };
+            result.set_item("median_ms", median)?;
+            result.set_item("rows", rows)?;
+            result.set_item("runs", repeat)?;
+            results.set_item(protocol, result)?;
+        }
+        Ok(results)
+    }
+
+    /// Execute a statement on a pooled connection without the test_on_check_out validation.
+    /// The connection may be stale; only use this when its health is already known.
+    pub fn execute_unchecked(&self, py: Python<'_>, sql: &str) -> PyResult<()> {
+        let pool = self.live_pool()?;
+        py.detach(|| pool.execute_unchecked(sql))
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        Ok(())
+    }
+
+    /// Run the parameterized statement `sql` once per tuple in `rows` on one connection, in a
+    /// single transaction, and return the total number of rows affected. Placeholders follow
+    /// the backend: `?` for SQLite and MySQL, `$1` for Postgres and `:1` for Oracle. Any
+    /// failure rolls the whole batch back and raises.
+    pub fn execute_many(
+        &self,
+        py: Python<'_>,
+        sql: &str,
+        rows: Vec<Vec<Option<PyScalarValue>>>,
+    ) -> PyResult<u64> {
+        let pool = self.live_pool()?;
+        let rows: Vec<Vec<ScalarValue>> = rows
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|v| v.map_or(ScalarValue::Null, ScalarValue::from))
+                    .collect()
+            })
+            .collect();
+        Ok(py
+            .detach(|| pool.execute_many(sql, &rows))
+            .map_err(|e| ConnectorXPythonError::from(e))?)
+    }
+
+    /// The raw version string of the server behind the pool, queried once and then cached
+    pub fn server_version(&self, py: Python<'_>) -> PyResult<String> {
+        let pool = self.live_pool()?;
+        Ok(py
+            .detach(|| pool.server_version())
+            .map_err(|e| ConnectorXPythonError::from(e))?)
+    }
+
+    /// Qualified `schema.table` names of the tables reachable through the pool, sorted,
+    /// optionally only those in `schema`. Views and system schemas are not listed.
+    #[pyo3(signature = (schema=None))]
+    pub fn list_tables(&self, py: Python<'_>, schema: Option<&str>) -> PyResult<Vec<String>> {
+        let pool = self.live_pool()?;
+        Ok(py
+            .detach(|| pool.list_tables(schema))
+            .map_err(|e| ConnectorXPythonError::from(e))?)
+    }
+
+    /// Names of the schemas reachable through the pool, without system schemas. For SQLite
+    /// these are the attached databases, e.g. `main`.
+    pub fn list_schemas(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        let pool = self.live_pool()?;
+        Ok(py
+            .detach(|| pool.list_schemas())
+            .map_err(|e| ConnectorXPythonError::from(e))?)
+    }
+
+    /// Check a connection out and start a transaction on it. Use the returned object as a
+    /// context manager: it commits on a clean exit and rolls back if an exception escapes.
+    pub fn transaction(&self, py: Python<'_>) -> PyResult<PyTransaction> {
+        let pool = self.live_pool()?;
+        let source_conn = parse_source(&self.conn_str, Some(&self.default_protocol))
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        let txn = py
+            .detach(|| Transaction::begin(&source_conn, &pool))
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        Ok(PyTransaction {
+            txn: Mutex::new(Some(txn)),
+        })
+    }
+
+    /// Check `n` connections out up front for a bulk job, so it never waits for one midway.
+    /// They go back to the pool together when the returned reservation is released, e.g. on
+    /// leaving it as a context manager. Raises if `n` exceeds the pool size.
+    pub fn reserve(&self, py: Python<'_>, n: usize) -> PyResult<PyReservation> {
+        if n > self.max_size as usize {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Cannot reserve {} connections from a pool of at most {}",
+                n, self.max_size
+            )));
+        }
+        let pool = self.live_pool()?;
+        let source_conn = parse_source(&self.conn_str, Some(&self.default_protocol))
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        let conns = py
+            .detach(|| pool.reserve(n))
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        Ok(PyReservation {
+            source_conn,
+            pool,
+            conns: Mutex::new(Some(conns)),
+        })
+    }
+
+    /// Open up to `n` connections on a background thread, by checking `n` out at once and
+    /// returning them, so they are idle in the pool by the time the first reads need them.
+    /// Returns right away; `wait_warm` waits for the warming to finish. Raises if `n`
+    /// exceeds the pool size.
+    pub fn warm_async(&self, n: usize) -> PyResult<()> {
+        if n > self.max_size as usize {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Cannot warm {} connections in a pool of at most {}",
+                n, self.max_size
+            )));
+        }
+        let pool = self.live_pool()?;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(pool.reserve(n).map(drop));
+        });
+        *self.warming.lock() = Some(Arc::new(Mutex::new(rx)));
+        Ok(())
+    }
+
+    /// Wait up to `timeout` seconds, or without limit if `None`, for the last `warm_async` to
+    /// finish. Returns `False` if it is still running, and `True` once it is done or if none
+    /// was started. Raises the error that failed the warming, e.g. an unreachable database.
+    #[pyo3(signature = (timeout=None))]
+    pub fn wait_warm(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<bool> {
+        // No lock is held while the GIL is released: a thread blocked on it while holding
+        // the GIL would keep this one from taking the GIL back.
+        let warming = match self.warming.lock().clone() {
+            Some(warming) => warming,
+            None => return Ok(true),
+        };
+        let deadline = timeout.map(|secs| Instant::now() + Duration::from_secs_f64(secs.max(0.0)));
+        let is_current = |current: &Option<Warming>| {
+            current
+                .as_ref()
+                .is_some_and(|current| Arc::ptr_eq(current, &warming))
+        };
+        let result = py.detach(|| {
+            let rx = match deadline {
+                Some(deadline) => warming
+                    .try_lock_until(deadline)
+                    .ok_or(mpsc::RecvTimeoutError::Timeout)?,
+                None => warming.lock(),
+            };
+            if !is_current(&self.warming.lock()) {
+                // a concurrent wait already received the outcome, or a newer warming started
+                return Ok(Ok(()));
+            }
+            let result = match deadline {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            };
+            if !matches!(result, Err(mpsc::RecvTimeoutError::Timeout)) {
+                let mut current = self.warming.lock();
+                if is_current(&current) {
+                    *current = None;
+                }
+            }
+            result
+        });
+        match result {
+            Ok(result) => result.map_err(|e| ConnectorXPythonError::from(e))?,
+            Err(mpsc::RecvTimeoutError::Timeout) => return Ok(false),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                    "The thread warming the pool panicked",
+                ))
+            }
+        }
+        Ok(true)
+    }
+
+    /// Close idle connections until at most `keep` are left and return how many were closed.
+    /// The pool stays open and reopens connections as needed. It also reopens them right
+    /// away up to `min_idle`, so `keep` cannot be lower; without `min_idle` the pool keeps
+    /// all `max_size` connections open.
+    pub fn trim_idle(&self, py: Python<'_>, keep: u32) -> PyResult<u32> {
+        let pool = self.live_pool()?;
+        Ok(py
+            .detach(|| pool.trim_idle(keep))
+            .map_err(|e| ConnectorXPythonError::from(e))?)
+    }
+
+    /// Call `callback` with a dict for every event of the pool, e.g.
+    /// `{"event": "acquire", "latency_ms": 3.1}`, replacing the callback set before; `None`
+    /// removes it. Events are `connect`, `acquire` (`latency_ms`), `release` (`held_ms`),
+    /// `timeout` (`waited_ms`), `disconnect` (`age_ms`) and `close` (`connections`), the last
+    /// one raised by `close()`. The dicts of a pool built with a `label` also carry it under
+    /// `"label"`. Callbacks run in order on a background thread, and exceptions they raise are
+    /// reported as unraisable.
+    #[pyo3(signature = (callback))]
+    pub fn set_event_callback(&self, callback: Option<Py<PyAny>>) -> PyResult<()> {
+        let pool = self.live_pool()?;
+        let callback = match callback {
+            Some(callback) => callback,
+            None => {
+                pool.set_event_handler(None);
+                return Ok(());
+            }
+        };
+
+        // r2d2 raises events on whichever thread touches the pool, some of them under its
+        // lock, so they are queued here and the callback runs on a thread of its own, which
+        // ends once the handler sending to it is dropped
+        let (tx, rx) = mpsc::channel::<PoolEvent>();
+        let label = pool.label();
+        std::thread::spawn(move || {
+            for event in rx {
+                Python::attach(|py| {
+                    let result = event_dict(py, event, label.as_deref())
+                        .and_then(|dict| callback.bind(py).call1((dict,)).map(|_| ()));
+                    if let Err(e) = result {
+                        e.write_unraisable(py, Some(callback.bind(py)));
+                    }
+                });
+            }
+        });
+        pool.set_event_handler(Some(Arc::new(move |event| {
+            let _ = tx.send(event);
+        })));
+        Ok(())
+    }
+
+    /// Render pool metrics in Prometheus text exposition format
+    #[pyo3(signature = (name_prefix="connectorx_pool"))]
+    pub fn metrics(&self, name_prefix: &str) -> PyResult<String> {
+        let pool = self.pool.lock();
+        let pool = pool.as_ref().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(
+                "Cannot read metrics of a closed ConnectionPool",
+            )
+        })?;
+        Ok(pool.metrics_text(name_prefix))
+    }
+
     /// Context manager support: __enter__
     pub fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
@@ -117,8 +1078,352 @@ impl PyConnectionPool {
 
 // Internal accessor for Rust code — returns an owned clone of the pool variant.
 impl PyConnectionPool {
+    /// Build a pool from a complete `config`, the defaults of `set_default_pool_config` left
+    /// aside, checked against the global connection budget as `ConnectionPool()` is.
+    pub fn from_config(conn: &str, config: PoolConfig, schema_cache: bool) -> PyResult<Self> {
+        // `env:VARNAME` is resolved first so a Redshift or ClickHouse url is still rewritten
+        let conn = resolve_conn(conn).map_err(|e| ConnectorXPythonError::from(e))?;
+
+        // set aside while building, so concurrent callers cannot both take the last of it
+        let mut reservation = CONNECTION_BUDGET.lock().reserve(config.max_size)?;
+        let mut pool = Self::with_pool(&conn, |source_conn| {
+            let pool_variant = PoolVariant::from_source_conn(source_conn, &config)
+                .map_err(|e| ConnectorXPythonError::from(e))?
+                .ok_or_else(|| {
+                    UnsupportedPoolBackend::new_err(format!(
+                        "Unsupported database type for connection pooling: {:?}",
+                        source_conn.ty
+                    ))
+                })?;
+            Ok(Arc::new(pool_variant))
+        })?;
+        if let Some(pool_variant) = pool.pool.lock().as_ref() {
+            let mut budget = CONNECTION_BUDGET.lock();
+            budget.track(pool_variant);
+            // the pool now counts itself
+            budget.reserved -= std::mem::take(&mut reservation.0);
+        }
+        if schema_cache {
+            pool.schema_cache = Some(Arc::new(SchemaCache::new()));
+        }
+        Ok(pool)
+    }
+
+    /// Wrap a pool built elsewhere, e.g. by a Rust extension that already manages one, so
+    /// reads share its connections rather than opening a second pool against the same
+    /// database. `conn_str` is the connection string of `variant` and sets the default
+    /// protocol; `max_size` reports the size `variant` was configured with.
+    pub fn from_pool_variant(variant: PoolVariant, conn_str: String) -> PyResult<Self> {
+        let conn = resolve_conn(&conn_str).map_err(|e| ConnectorXPythonError::from(e))?;
+        let pool = Self::with_pool(&conn, |_| Ok(Arc::new(variant)))?;
+        // counts against the global budget, though it is not checked for a pool built elsewhere
+        if let Some(pool_variant) = pool.pool.lock().as_ref() {
+            CONNECTION_BUDGET.lock().track(pool_variant);
+        }
+        Ok(pool)
+    }
+
     pub fn get_pool_variant(&self) -> Option<PoolVariant> {
         let pool = self.pool.lock();
-        pool.as_ref().cloned()
+        pool.as_deref().cloned()
+    }
+
+    /// The pool, or a `ValueError` once it has been closed.
+    fn live_pool(&self) -> PyResult<PoolVariant> {
+        self.get_pool_variant().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("Cannot use a closed ConnectionPool")
+        })
+    }
+
+    pub fn schema_cache(&self) -> Option<Arc<SchemaCache>> {
+        self.schema_cache.clone()
+    }
+
+    /// A prepared stream of `query`, read on a pooled connection in batches of at most
+    /// `batch_size` rows.
+    fn batch_iter(
+        &self,
+        py: Python<'_>,
+        query: String,
+        batch_size: usize,
+        protocol: Option<&str>,
+    ) -> PyResult<Box<dyn RecordBatchIterator>> {
+        let pool = self.live_pool()?;
+        let source_conn = parse_source(
+            &self.conn_str,
+            Some(protocol.unwrap_or(&self.default_protocol)),
+        )
+        .map_err(|e| ConnectorXPythonError::from(e))?;
+        let queries = [CXQuery::Naked(query)];
+
+        let iter = py
+            .detach(|| -> Result<_, ConnectorXOutError> {
+                let mut iter = new_record_batch_iter(
+                    &source_conn,
+                    None,
+                    &queries,
+                    batch_size,
+                    None,
+                    Some(&pool),
+                    None,
+                )?;
+                iter.prepare();
+                Ok(iter)
+            })
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        Ok(iter)
+    }
+
+    /// Wraps the pool `build` returns for the already `env:`-resolved `conn`.
+    fn with_pool(
+        conn: &str,
+        build: impl FnOnce(&SourceConn) -> PyResult<Arc<PoolVariant>>,
+    ) -> PyResult<Self> {
+        let (rewritten_conn, default_protocol) = rewrite_pool_conn(conn);
+
+        // otherwise a `cxprotocol` url parameter, or binary, is the pool's default protocol
+        let source_conn = parse_source(&rewritten_conn, default_protocol)
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        let pool_variant = build(&source_conn)?;
+
+        Ok(Self {
+            max_size: pool_variant.max_size(),
+            idle_timeout: pool_variant.idle_timeout(),
+            max_lifetime: pool_variant.max_lifetime(),
+            pool: Mutex::new(Some(pool_variant)),
+            conn_str: rewritten_conn,
+            default_protocol: source_conn.proto,
+            warming: Mutex::new(None),
+            schema_cache: None,
+        })
+    }
+}
+
+/// The dict `set_event_callback` passes for `event`, durations in milliseconds.
+fn event_dict<'py>(
+    py: Python<'py>,
+    event: PoolEvent,
+    label: Option<&str>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("event", event.name())?;
+    if let Some(label) = label {
+        dict.set_item("label", label)?;
+    }
+    match event {
+        PoolEvent::Connect => {}
+        PoolEvent::Acquire { latency } => {
+            dict.set_item("latency_ms", latency.as_secs_f64() * 1000.0)?
+        }
+        PoolEvent::Release { held } => dict.set_item("held_ms", held.as_secs_f64() * 1000.0)?,
+        PoolEvent::Timeout { waited } => {
+            dict.set_item("waited_ms", waited.as_secs_f64() * 1000.0)?
+        }
+        PoolEvent::Disconnect { age } => dict.set_item("age_ms", age.as_secs_f64() * 1000.0)?,
+        PoolEvent::Close { connections } => dict.set_item("connections", connections)?,
+    }
+    Ok(dict)
+}
+
+/// The `return_type`s a pinned connection can read into.
+fn check_return_type(return_type: &str) -> PyResult<()> {
+    if !matches!(return_type, "pandas" | "arrow") {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "return type should be 'pandas' or 'arrow', got '{}'",
+            return_type
+        )));
+    }
+    Ok(())
+}
+
+/// Hands record batches to Python as a pyarrow table, converted to pandas if asked.
+fn to_table<'py>(
+    py: Python<'py>,
+    rbs: Vec<RecordBatch>,
+    return_type: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    let module = py.import("connectorx_pooling")?;
+    let table = module.call_method1("reconstruct_arrow", (crate::arrow::to_ptrs(rbs),))?;
+    match return_type {
+        "pandas" => module.call_method1("arrow_to_pandas", (table,)),
+        _ => Ok(table),
+    }
+}
+
+/// A transaction holding one pooled connection, returned by `ConnectionPool.transaction()`
+#[pyclass]
+pub struct PyTransaction {
+    txn: Mutex<Option<Transaction>>,
+}
+
+impl PyTransaction {
+    /// Run `f` on the transaction with the GIL released. The lock is only taken once the
+    /// GIL is released, so a `commit` waiting on it from another thread never holds the GIL
+    /// this thread needs back.
+    fn with_txn<T: Send>(
+        &self,
+        py: Python<'_>,
+        f: impl FnOnce(&Transaction) -> Result<T, ConnectorXPythonError> + Send,
+    ) -> PyResult<T> {
+        py.detach(|| {
+            let txn = self.txn.lock();
+            let txn = txn.as_ref().ok_or_else(already_finished)?;
+            Ok(f(txn)?)
+        })
+    }
+
+    /// Commit or roll back the transaction with the GIL released, as `with_txn` does.
+    /// Returns `false` if it was already finished.
+    fn finish(&self, py: Python<'_>, commit: bool) -> PyResult<bool> {
+        py.detach(|| {
+            let txn = match self.txn.lock().take() {
+                Some(txn) => txn,
+                None => return Ok(false),
+            };
+            let finished = if commit { txn.commit() } else { txn.rollback() };
+            finished.map_err(|e| ConnectorXPythonError::from(e))?;
+            Ok(true)
+        })
+    }
+}
+
+fn already_finished() -> PyErr {
+    pyo3::exceptions::PyValueError::new_err("The transaction is already finished")
+}
+
+#[pymethods]
+impl PyTransaction {
+    /// Execute a statement on the transaction's connection
+    pub fn execute(&self, py: Python<'_>, sql: &str) -> PyResult<()> {
+        self.with_txn(py, |txn| Ok(txn.execute(sql)?))
+    }
+
+    /// Read a query on the transaction's connection, seeing its uncommitted writes
+    #[pyo3(signature = (query, return_type="pandas"))]
+    pub fn read_sql<'py>(
+        &self,
+        py: Python<'py>,
+        query: &str,
+        return_type: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        check_return_type(return_type)?;
+        let rbs = self.with_txn(py, |txn| Ok(txn.read_arrow(query)?.arrow()?))?;
+        to_table(py, rbs, return_type)
+    }
+
+    /// Commit the transaction and return its connection to the pool
+    pub fn commit(&self, py: Python<'_>) -> PyResult<()> {
+        if !self.finish(py, true)? {
+            return Err(already_finished());
+        }
+        Ok(())
+    }
+
+    /// Roll the transaction back and return its connection to the pool
+    pub fn rollback(&self, py: Python<'_>) -> PyResult<()> {
+        if !self.finish(py, false)? {
+            return Err(already_finished());
+        }
+        Ok(())
+    }
+
+    /// Context manager support: __enter__
+    pub fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Context manager support: __exit__, committing unless an exception is propagating.
+    /// A transaction already finished explicitly inside the block is left alone.
+    pub fn __exit__(
+        &self,
+        py: Python<'_>,
+        exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.finish(py, exc_type.is_none())?;
+        Ok(false)
+    }
+}
+
+/// Connections checked out together by `ConnectionPool.reserve`. Different connections can
+/// be used from different threads at the same time.
+#[pyclass]
+pub struct PyReservation {
+    source_conn: SourceConn,
+    pool: PoolVariant,
+    conns: Mutex<Option<Vec<PinnedVariant>>>,
+}
+
+impl PyReservation {
+    /// A handle on connection `index`; the lock is not held while it is in use.
+    fn conn(&self, index: usize) -> PyResult<PinnedVariant> {
+        let conns = self.conns.lock();
+        let conns = conns.as_ref().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("The reservation is already released")
+        })?;
+        conns.get(index).cloned().ok_or_else(|| {
+            pyo3::exceptions::PyIndexError::new_err(format!(
+                "connection index {} out of range for a reservation of {}",
+                index,
+                conns.len()
+            ))
+        })
+    }
+}
+
+#[pymethods]
+impl PyReservation {
+    /// Number of reserved connections, 0 once released
+    pub fn __len__(&self) -> usize {
+        self.conns.lock().as_ref().map_or(0, Vec::len)
+    }
+
+    /// Execute a statement on reserved connection `index`
+    pub fn execute(&self, py: Python<'_>, index: usize, sql: &str) -> PyResult<()> {
+        let conn = self.conn(index)?;
+        py.detach(|| conn.execute(sql))
+            .map_err(|e| ConnectorXPythonError::from(e))?;
+        Ok(())
+    }
+
+    /// Read a query on reserved connection `index`
+    #[pyo3(signature = (index, query, return_type="pandas"))]
+    pub fn read_sql<'py>(
+        &self,
+        py: Python<'py>,
+        index: usize,
+        query: &str,
+        return_type: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        check_return_type(return_type)?;
+        let conn = self.conn(index)?;
+        let rbs = py.detach(|| -> Result<_, ConnectorXPythonError> {
+            Ok(
+                get_arrow_single(&self.source_conn, query, &[], Some(&self.pool), Some(&conn))?
+                    .arrow()?,
+            )
+        })?;
+        to_table(py, rbs, return_type)
+    }
+
+    /// Return every reserved connection to the pool. Releasing twice is a no-op.
+    pub fn release(&self) {
+        self.conns.lock().take();
+    }
+
+    /// Context manager support: __enter__
+    pub fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Context manager support: __exit__
+    pub fn __exit__(
+        &self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) {
+        self.release();
     }
 }
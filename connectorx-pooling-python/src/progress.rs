@@ -0,0 +1,47 @@
+use anyhow::anyhow;
+use connectorx::prelude::ProgressCallback;
+use parking_lot::Mutex;
+use pyo3::prelude::*;
+use std::sync::Arc;
+
+/// A Python `progress_callback(completed, total)`, called under the GIL like tqdm's
+/// `update`. The first exception it raises is kept, so that the read it aborted re-raises
+/// that exception rather than a `RuntimeError` describing it.
+#[derive(Clone)]
+pub struct PyProgress {
+    callback: Arc<Py<PyAny>>,
+    raised: Arc<Mutex<Option<PyErr>>>,
+}
+
+impl PyProgress {
+    pub fn new(callback: Py<PyAny>) -> Self {
+        PyProgress {
+            callback: Arc::new(callback),
+            raised: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Report `completed` out of `total`, `None` when the total is not known.
+    pub fn call(&self, py: Python<'_>, completed: usize, total: Option<usize>) -> PyResult<()> {
+        self.callback.call1(py, (completed, total))?;
+        Ok(())
+    }
+
+    /// The callback for `ArrowQuery::progress`, taking the GIL from whichever thread
+    /// finished a partition.
+    pub fn to_callback(&self) -> ProgressCallback {
+        let progress = self.clone();
+        Arc::new(move |completed, total| {
+            Python::attach(|py| progress.call(py, completed, total)).map_err(|e| {
+                let message = anyhow!("progress_callback raised {}", e);
+                progress.raised.lock().get_or_insert(e);
+                message
+            })
+        })
+    }
+
+    /// The exception that aborted a read, if the callback raised one.
+    pub fn take_raised(&self) -> Option<PyErr> {
+        self.raised.lock().take()
+    }
+}
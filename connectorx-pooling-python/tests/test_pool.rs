@@ -0,0 +1,41 @@
+//! Rust-side tests of the pool wrapper. The `extension` feature leaves libpython unlinked,
+//! so run them without it:
+//! `cargo test --no-default-features --features srcs,dsts,fptr,nbstr,federation,fed_exec`.
+
+use connectorx::pool::{PoolConfig, PoolVariant};
+use connectorx::source_router::SourceConn;
+use connectorx_pooling::pool::PyConnectionPool;
+use std::convert::TryFrom;
+use std::env;
+
+#[test]
+fn test_pool_from_pool_variant() {
+    let path = env::temp_dir().join(format!("cx_from_pool_variant_{}.db", std::process::id()));
+    let conn = format!("sqlite://{}", path.display());
+    let variant = PoolVariant::from_source_conn(
+        &SourceConn::try_from(conn.as_str()).unwrap(),
+        &PoolConfig {
+            max_size: 3,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .unwrap();
+    variant
+        .execute_unchecked("CREATE TABLE test_table(test_int INTEGER)")
+        .unwrap();
+
+    let pool = PyConnectionPool::from_pool_variant(variant, conn).unwrap();
+    assert_eq!(3, pool.max_size());
+    assert!(!pool.is_closed());
+    assert_eq!("binary", pool.default_protocol);
+    // the wrapped pool is the one read through
+    pool.get_pool_variant()
+        .unwrap()
+        .execute_unchecked("INSERT INTO test_table VALUES (1)")
+        .unwrap();
+
+    pool.close();
+    assert!(pool.get_pool_variant().is_none());
+    let _ = std::fs::remove_file(&path);
+}
@@ -1,9 +1,13 @@
+use crate::destinations::arrowstream::ArrowDestinationError as ArrowStreamDestinationError;
 use crate::prelude::*;
+use anyhow::anyhow;
+use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 use itertools::Itertools;
 use log::debug;
 use rayon::prelude::*;
 use std::marker::PhantomData;
+use std::thread::JoinHandle;
 
 pub fn set_global_num_thread(num: usize) {
     rayon::ThreadPoolBuilder::new()
@@ -32,6 +36,7 @@ where
     dorder: DataOrder,
     src_schema: Vec<S::TypeSystem>,
     dst_schema: Vec<ArrowStreamTypeSystem>,
+    worker: Option<JoinHandle<Result<(), TP::Error>>>,
     _phantom: PhantomData<TP>,
 }
 
@@ -61,6 +66,7 @@ where
             dorder,
             src_schema,
             dst_schema,
+            worker: None,
             _phantom: PhantomData,
         })
     }
@@ -72,7 +78,7 @@ where
         let dst_partitions = self.dst_parts.take().unwrap();
         let dorder = self.dorder;
 
-        std::thread::spawn(move || -> Result<(), TP::Error> {
+        let worker = std::thread::spawn(move || -> Result<(), TP::Error> {
             let schemas: Vec<_> = src_schema
                 .iter()
                 .zip_eq(&dst_schema)
@@ -133,6 +139,7 @@ where
 
             Ok(())
         });
+        self.worker = Some(worker);
     }
 }
 
@@ -145,18 +152,45 @@ where
         S = S,
         D = ArrowStreamDestination,
     >,
+    ConnectorXOutError: From<TP::Error>,
 {
-    type Item = RecordBatch;
+    type Item = Result<RecordBatch, ConnectorXOutError>;
     /// NOTE: not thread safe
     fn next(&mut self) -> Option<Self::Item> {
-        self.dst.record_batch().ok().flatten()
+        match self.dst.record_batch() {
+            Ok(Some(rb)) => Some(Ok(rb)),
+            // every partition writer is gone: the stream either ended or a partition failed
+            Ok(None) => match self.worker.take()?.join() {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(Err(e.into())),
+                Err(_) => Some(Err(anyhow!("record batch worker panicked").into())),
+            },
+            Err(ArrowStreamDestinationError::BatchTimeout(timeout)) => {
+                Some(Err(ConnectorXOutError::BatchTimeout(timeout)))
+            }
+            Err(e) => Some(Err(e.into())),
+        }
     }
 }
 
 pub trait RecordBatchIterator: Send {
     fn get_schema(&self) -> (RecordBatch, &[String]);
+    /// The Arrow schema of the stream, resolved before any row is read, so it is known even
+    /// for a result without rows, whose stream ends without a batch.
+    fn schema(&self) -> SchemaRef;
     fn prepare(&mut self);
-    fn next_batch(&mut self) -> Option<RecordBatch>;
+    /// The next batch, or `None` once the stream is exhausted. A partition that failed to
+    /// read fails the stream with its error once the batches before it are consumed, and a
+    /// wait exceeding the batch timeout fails with `ConnectorXOutError::BatchTimeout`. The
+    /// iterator stays usable after a timeout: calling it again keeps waiting for the same
+    /// batch. Sources that also bound their fetches by the timeout (Postgres, MsSQL,
+    /// BigQuery) abandon a stalled query meanwhile, so the wait then ends with its error.
+    fn try_next_batch(&mut self) -> Result<Option<RecordBatch>, ConnectorXOutError>;
+    /// The next batch, or `None` once the stream is exhausted or has failed; see
+    /// [`Self::try_next_batch`] to tell the two apart.
+    fn next_batch(&mut self) -> Option<RecordBatch> {
+        self.try_next_batch().ok().flatten()
+    }
 }
 
 impl<'a, S, TP> RecordBatchIterator for ArrowBatchIter<S, TP>
@@ -168,16 +202,21 @@ where
             S = S,
             D = ArrowStreamDestination,
         > + std::marker::Send,
+    ConnectorXOutError: From<TP::Error>,
 {
     fn get_schema(&self) -> (RecordBatch, &[String]) {
         (self.dst.empty_batch(), self.dst.names())
     }
 
+    fn schema(&self) -> SchemaRef {
+        self.dst.arrow_schema()
+    }
+
     fn prepare(&mut self) {
         self.run();
     }
 
-    fn next_batch(&mut self) -> Option<RecordBatch> {
-        self.next()
+    fn try_next_batch(&mut self) -> Result<Option<RecordBatch>, ConnectorXOutError> {
+        self.next().transpose()
     }
 }
@@ -20,6 +20,11 @@ const KILO: usize = 1 << 10;
 #[cfg(feature = "dst_arrow")]
 pub const RECORD_BATCH_SIZE: usize = 64 * KILO;
 
+/// Under `DictPolicy::Auto`, a string column is dictionary-encoded when its first batch
+/// holds at most this many distinct values per non-null value.
+#[cfg(feature = "dst_arrow")]
+pub const DICTIONARY_AUTO_MAX_RATIO: f64 = 0.1;
+
 #[cfg(any(
     feature = "src_postgres",
     feature = "src_mysql",
@@ -31,6 +36,13 @@ pub const DB_BUFFER_SIZE: usize = 32;
 #[cfg(any(feature = "src_oracle"))]
 pub const ORACLE_ARRAY_SIZE: u32 = KILO as u32;
 
+#[cfg(feature = "src_oracle")]
+pub const ORACLE_MAX_ARRAY_SIZE: u32 = 100 * KILO as u32;
+
+/// Bytes read from a CLOB or NCLOB locator at a time.
+#[cfg(feature = "src_oracle")]
+pub const ORACLE_LOB_CHUNK_SIZE: usize = 256 * KILO;
+
 #[cfg(all(not(debug_assertions), feature = "federation"))]
 pub const J4RS_BASE_PATH: &str = "../target/release";
 
@@ -51,3 +63,6 @@ pub const MYSQL_JDBC_DRIVER: &str = "com.mysql.cj.jdbc.Driver";
 pub const DUCKDB_JDBC_DRIVER: &str = "org.duckdb.DuckDBDriver";
 
 pub const CONNECTORX_PROTOCOL: &str = "cxprotocol";
+
+/// Prefix of a connection string naming the environment variable that holds the real one.
+pub const ENV_CONN_PREFIX: &str = "env:";
@@ -1,22 +1,33 @@
 use super::{
     errors::{ArrowDestinationError, Result},
-    typesystem::{DateTimeWrapperMicro, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro},
+    typesystem::{
+        DateTimeWrapperMicro, JsonString, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro,
+        WkbBinary,
+    },
 };
 use crate::{
-    constants::{DEFAULT_ARROW_DECIMAL, DEFAULT_ARROW_DECIMAL_SCALE, SECONDS_IN_DAY},
+    constants::{
+        DEFAULT_ARROW_DECIMAL, DEFAULT_ARROW_DECIMAL_PRECISION, DEFAULT_ARROW_DECIMAL_SCALE,
+        SECONDS_IN_DAY,
+    },
     utils::decimal_to_i128,
 };
 use arrow::array::{
-    ArrayBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float32Builder, Float64Builder,
-    Int16Builder, Int32Builder, Int64Builder, LargeBinaryBuilder, LargeListBuilder, StringBuilder,
-    Time64MicrosecondBuilder, Time64NanosecondBuilder, TimestampMicrosecondBuilder,
-    TimestampNanosecondBuilder, UInt16Builder, UInt32Builder, UInt64Builder,
+    ArrayBuilder, ArrayRef, BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder,
+    Float32Builder, Float64Builder, Int16Builder, Int32Builder, Int64Builder,
+    IntervalMonthDayNanoBuilder, LargeBinaryBuilder, LargeListBuilder, LargeStringBuilder,
+    MapBuilder, StringArray, StringBuilder, StringDictionaryBuilder, Time64MicrosecondBuilder,
+    Time64NanosecondBuilder, TimestampMicrosecondBuilder, TimestampNanosecondBuilder,
+    UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder,
 };
-use arrow::datatypes::Field;
-use arrow::datatypes::{DataType as ArrowDataType, TimeUnit};
+use arrow::datatypes::{
+    DataType as ArrowDataType, Int32Type, IntervalMonthDayNano, IntervalUnit, TimeUnit,
+};
+use arrow::datatypes::{Field, Fields};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
-use fehler::throws;
-use rust_decimal::Decimal;
+use fehler::{throw, throws};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use std::{any::Any, collections::HashMap};
 
 /// Associate arrow builder with native type
 pub trait ArrowAssoc {
@@ -65,6 +76,7 @@ macro_rules! impl_arrow_assoc {
     };
 }
 
+impl_arrow_assoc!(u8, ArrowDataType::UInt8, UInt8Builder);
 impl_arrow_assoc!(u16, ArrowDataType::UInt16, UInt16Builder);
 impl_arrow_assoc!(u32, ArrowDataType::UInt32, UInt32Builder);
 impl_arrow_assoc!(u64, ArrowDataType::UInt64, UInt64Builder);
@@ -74,17 +86,125 @@ impl_arrow_assoc!(i64, ArrowDataType::Int64, Int64Builder);
 impl_arrow_assoc!(f32, ArrowDataType::Float32, Float32Builder);
 impl_arrow_assoc!(f64, ArrowDataType::Float64, Float64Builder);
 impl_arrow_assoc!(bool, ArrowDataType::Boolean, BooleanBuilder);
+impl_arrow_assoc!(
+    IntervalMonthDayNano,
+    ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+    IntervalMonthDayNanoBuilder
+);
+
+/// How the values of a decimal column are stored in Arrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalLayout {
+    /// `Decimal128(precision, scale)`, exact as long as the values fit.
+    Decimal128(u8, i8),
+    Float64,
+    /// The decimal's text representation, e.g. `-12.3400`.
+    Utf8,
+}
+
+impl Default for DecimalLayout {
+    fn default() -> Self {
+        DecimalLayout::Decimal128(DEFAULT_ARROW_DECIMAL_PRECISION, DEFAULT_ARROW_DECIMAL_SCALE)
+    }
+}
+
+impl DecimalLayout {
+    pub fn data_type(&self) -> ArrowDataType {
+        match self {
+            DecimalLayout::Decimal128(precision, scale) => {
+                ArrowDataType::Decimal128(*precision, *scale)
+            }
+            DecimalLayout::Float64 => ArrowDataType::Float64,
+            DecimalLayout::Utf8 => ArrowDataType::Utf8,
+        }
+    }
+}
+
+/// Builder of a decimal column, writing every value in the column's [`DecimalLayout`].
+pub enum DecimalBuilder {
+    Decimal128(Decimal128Builder, u32),
+    Float64(Float64Builder),
+    Utf8(StringBuilder),
+}
+
+impl DecimalBuilder {
+    pub fn new(layout: DecimalLayout, nrows: usize) -> Self {
+        match layout {
+            DecimalLayout::Decimal128(_, scale) => DecimalBuilder::Decimal128(
+                Decimal128Builder::with_capacity(nrows).with_data_type(layout.data_type()),
+                scale as u32,
+            ),
+            DecimalLayout::Float64 => DecimalBuilder::Float64(Float64Builder::with_capacity(nrows)),
+            DecimalLayout::Utf8 => DecimalBuilder::Utf8(StringBuilder::with_capacity(1024, nrows)),
+        }
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append_option(&mut self, value: Option<Decimal>) {
+        match (self, value) {
+            (DecimalBuilder::Decimal128(builder, scale), Some(v)) => {
+                builder.append_value(decimal_to_i128(v, *scale)?)
+            }
+            (DecimalBuilder::Float64(builder), Some(v)) => builder.append_option(v.to_f64()),
+            (DecimalBuilder::Utf8(builder), Some(v)) => builder.append_value(v.to_string()),
+            (DecimalBuilder::Decimal128(builder, _), None) => builder.append_null(),
+            (DecimalBuilder::Float64(builder), None) => builder.append_null(),
+            (DecimalBuilder::Utf8(builder), None) => builder.append_null(),
+        }
+    }
+
+    fn inner(&self) -> &dyn ArrayBuilder {
+        match self {
+            DecimalBuilder::Decimal128(builder, _) => builder,
+            DecimalBuilder::Float64(builder) => builder,
+            DecimalBuilder::Utf8(builder) => builder,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn ArrayBuilder {
+        match self {
+            DecimalBuilder::Decimal128(builder, _) => builder,
+            DecimalBuilder::Float64(builder) => builder,
+            DecimalBuilder::Utf8(builder) => builder,
+        }
+    }
+}
+
+impl ArrayBuilder for DecimalBuilder {
+    fn len(&self) -> usize {
+        self.inner().len()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        self.inner_mut().finish()
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        self.inner().finish_cloned()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
 
 impl ArrowAssoc for Decimal {
-    type Builder = Decimal128Builder;
+    type Builder = DecimalBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        Decimal128Builder::with_capacity(nrows).with_data_type(DEFAULT_ARROW_DECIMAL)
+        DecimalBuilder::new(DecimalLayout::default(), nrows)
     }
 
     fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
-        builder.append_value(decimal_to_i128(value, DEFAULT_ARROW_DECIMAL_SCALE as u32)?);
-        Ok(())
+        builder.append_option(Some(value))
     }
 
     fn field(header: &str) -> Field {
@@ -93,21 +213,14 @@ impl ArrowAssoc for Decimal {
 }
 
 impl ArrowAssoc for Option<Decimal> {
-    type Builder = Decimal128Builder;
+    type Builder = DecimalBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        Decimal128Builder::with_capacity(nrows).with_data_type(DEFAULT_ARROW_DECIMAL)
+        DecimalBuilder::new(DecimalLayout::default(), nrows)
     }
 
     fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
-        match value {
-            Some(v) => builder.append_option(Some(decimal_to_i128(
-                v,
-                DEFAULT_ARROW_DECIMAL_SCALE as u32,
-            )?)),
-            None => builder.append_null(),
-        }
-        Ok(())
+        builder.append_option(value)
     }
 
     fn field(header: &str) -> Field {
@@ -152,16 +265,160 @@ impl ArrowAssoc for Option<&str> {
     }
 }
 
+/// Arrow type of string columns. `Utf8` addresses its values with 32-bit offsets, so a
+/// batch holds at most 2 GiB of text per column; `LargeUtf8` uses 64-bit offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrowStringType {
+    #[default]
+    Utf8,
+    LargeUtf8,
+}
+
+impl ArrowStringType {
+    pub fn data_type(&self) -> ArrowDataType {
+        match self {
+            ArrowStringType::Utf8 => ArrowDataType::Utf8,
+            ArrowStringType::LargeUtf8 => ArrowDataType::LargeUtf8,
+        }
+    }
+}
+
+/// Timezone of timestamp columns. `Preserve` keeps each column as its source types it:
+/// zone-aware columns, such as a Postgres `timestamptz`, are tagged `+00:00` and naive ones
+/// carry no timezone. `ForceUtc` tags every timestamp column `+00:00`, reading naive values
+/// as UTC; `ForceNaive` strips the timezone, leaving zone-aware values as UTC wall-clock
+/// times. The values themselves are the same under every policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampPolicy {
+    #[default]
+    Preserve,
+    ForceUtc,
+    ForceNaive,
+}
+
+impl TimestampPolicy {
+    /// `data_type` with its timezone set by this policy, if it is a timestamp.
+    pub fn data_type(&self, data_type: &ArrowDataType) -> ArrowDataType {
+        match (self, data_type) {
+            (TimestampPolicy::ForceUtc, ArrowDataType::Timestamp(unit, _)) => {
+                ArrowDataType::Timestamp(*unit, Some("+00:00".into()))
+            }
+            (TimestampPolicy::ForceNaive, ArrowDataType::Timestamp(unit, _)) => {
+                ArrowDataType::Timestamp(*unit, None)
+            }
+            _ => data_type.clone(),
+        }
+    }
+}
+
+/// Whether the `Utf8` columns of a result are dictionary-encoded as `Dictionary(Int32, Utf8)`,
+/// which saves memory when a column repeats a few values, such as country codes. `Auto`
+/// encodes the columns whose first batch is of low cardinality, see
+/// [`DICTIONARY_AUTO_MAX_RATIO`](crate::constants::DICTIONARY_AUTO_MAX_RATIO). Enum columns
+/// are left to `enum_dictionary` and `LargeUtf8` columns are never encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DictPolicy {
+    #[default]
+    Never,
+    Always,
+    Auto,
+}
+
+/// Builder of a string column: plain `Utf8` or `LargeUtf8`, or `Dictionary(Int32, Utf8)`
+/// seeded with the column's known values, e.g. the labels of a Postgres enum, in their
+/// declared order.
+pub enum Utf8Builder {
+    Plain(StringBuilder),
+    Large(LargeStringBuilder),
+    Dictionary(StringDictionaryBuilder<Int32Type>),
+}
+
+impl Utf8Builder {
+    pub fn large(nrows: usize) -> Self {
+        Utf8Builder::Large(LargeStringBuilder::with_capacity(1024, nrows))
+    }
+
+    #[throws(ArrowDestinationError)]
+    pub fn dictionary(values: &StringArray, nrows: usize) -> Self {
+        Utf8Builder::Dictionary(StringDictionaryBuilder::new_with_dictionary(nrows, values)?)
+    }
+
+    pub fn dictionary_type() -> ArrowDataType {
+        ArrowDataType::Dictionary(
+            Box::new(ArrowDataType::Int32),
+            Box::new(ArrowDataType::Utf8),
+        )
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append_option(&mut self, value: Option<&str>) {
+        match self {
+            Utf8Builder::Plain(builder) => {
+                // the builder panics once the offsets no longer fit in an i32
+                let len = builder.values_slice().len() + value.map_or(0, str::len);
+                if len > i32::MAX as usize {
+                    throw!(ArrowDestinationError::Utf8OffsetOverflow);
+                }
+                builder.append_option(value)
+            }
+            Utf8Builder::Large(builder) => builder.append_option(value),
+            Utf8Builder::Dictionary(builder) => builder.append_option(value),
+        }
+    }
+
+    fn inner(&self) -> &dyn ArrayBuilder {
+        match self {
+            Utf8Builder::Plain(builder) => builder,
+            Utf8Builder::Large(builder) => builder,
+            Utf8Builder::Dictionary(builder) => builder,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut dyn ArrayBuilder {
+        match self {
+            Utf8Builder::Plain(builder) => builder,
+            Utf8Builder::Large(builder) => builder,
+            Utf8Builder::Dictionary(builder) => builder,
+        }
+    }
+}
+
+impl ArrayBuilder for Utf8Builder {
+    fn len(&self) -> usize {
+        self.inner().len()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        self.inner_mut().finish()
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        self.inner().finish_cloned()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
 impl ArrowAssoc for String {
-    type Builder = StringBuilder;
+    type Builder = Utf8Builder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        StringBuilder::with_capacity(1024, nrows)
+        Utf8Builder::Plain(StringBuilder::with_capacity(1024, nrows))
     }
 
     #[throws(ArrowDestinationError)]
     fn append(builder: &mut Self::Builder, value: String) {
-        builder.append_value(value.as_str());
+        builder.append_option(Some(value.as_str()))?;
     }
 
     fn field(header: &str) -> Field {
@@ -170,18 +427,15 @@ impl ArrowAssoc for String {
 }
 
 impl ArrowAssoc for Option<String> {
-    type Builder = StringBuilder;
+    type Builder = Utf8Builder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        StringBuilder::with_capacity(1024, nrows)
+        Utf8Builder::Plain(StringBuilder::with_capacity(1024, nrows))
     }
 
     #[throws(ArrowDestinationError)]
     fn append(builder: &mut Self::Builder, value: Self) {
-        match value {
-            Some(s) => builder.append_value(s.as_str()),
-            None => builder.append_null(),
-        }
+        builder.append_option(value.as_deref())?;
     }
 
     fn field(header: &str) -> Field {
@@ -189,6 +443,144 @@ impl ArrowAssoc for Option<String> {
     }
 }
 
+impl ArrowAssoc for JsonString {
+    type Builder = Utf8Builder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        <String as ArrowAssoc>::builder(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: JsonString) {
+        <String as ArrowAssoc>::append(builder, value.0)?;
+    }
+
+    fn field(header: &str) -> Field {
+        json_field(<String as ArrowAssoc>::field(header))
+    }
+}
+
+impl ArrowAssoc for Option<JsonString> {
+    type Builder = Utf8Builder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        <Option<String> as ArrowAssoc>::builder(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Option<JsonString>) {
+        <Option<String> as ArrowAssoc>::append(builder, value.map(|json| json.0))?;
+    }
+
+    fn field(header: &str) -> Field {
+        json_field(<Option<String> as ArrowAssoc>::field(header))
+    }
+}
+
+/// `field` flagged as holding JSON documents, for readers to parse the strings back.
+fn json_field(field: Field) -> Field {
+    field.with_metadata(HashMap::from([("json".to_string(), "true".to_string())]))
+}
+
+impl ArrowAssoc for WkbBinary {
+    type Builder = BinaryBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BinaryBuilder::with_capacity(nrows, 1024)
+    }
+
+    fn append(builder: &mut Self::Builder, value: WkbBinary) -> Result<()> {
+        builder.append_value(value.0);
+        Ok(())
+    }
+
+    fn field(header: &str) -> Field {
+        wkb_field(Field::new(header, ArrowDataType::Binary, false))
+    }
+}
+
+impl ArrowAssoc for Option<WkbBinary> {
+    type Builder = BinaryBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BinaryBuilder::with_capacity(nrows, 1024)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Option<WkbBinary>) -> Result<()> {
+        builder.append_option(value.map(|wkb| wkb.0));
+        Ok(())
+    }
+
+    fn field(header: &str) -> Field {
+        wkb_field(Field::new(header, ArrowDataType::Binary, true))
+    }
+}
+
+/// `field` flagged as holding WKB geometries, for GeoArrow-aware readers.
+fn wkb_field(field: Field) -> Field {
+    field.with_metadata(HashMap::from([("geoarrow".to_string(), "wkb".to_string())]))
+}
+
+impl ArrowAssoc for HashMap<String, Option<String>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        MapBuilder::with_capacity(None, StringBuilder::new(), StringBuilder::new(), nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        append_map(builder, Some(value))
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(), false)
+    }
+}
+
+impl ArrowAssoc for Option<HashMap<String, Option<String>>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        MapBuilder::with_capacity(None, StringBuilder::new(), StringBuilder::new(), nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        append_map(builder, value)
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(), true)
+    }
+}
+
+/// Appends the entries of `map` sorted by key, so equal maps are written alike.
+fn append_map(
+    builder: &mut MapBuilder<StringBuilder, StringBuilder>,
+    map: Option<HashMap<String, Option<String>>>,
+) -> Result<()> {
+    let is_valid = map.is_some();
+    let mut entries: Vec<_> = map.into_iter().flatten().collect();
+    entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    for (key, value) in entries {
+        builder.keys().append_value(key);
+        builder.values().append_option(value);
+    }
+    builder.append(is_valid)?;
+    Ok(())
+}
+
+/// The type `MapBuilder` gives a map of strings to nullable strings.
+fn map_type() -> ArrowDataType {
+    let entries = Fields::from(vec![
+        Field::new("keys", ArrowDataType::Utf8, false),
+        Field::new("values", ArrowDataType::Utf8, true),
+    ]);
+    ArrowDataType::Map(
+        std::sync::Arc::new(Field::new("entries", ArrowDataType::Struct(entries), false)),
+        false,
+    )
+}
+
 impl ArrowAssoc for DateTime<Utc> {
     type Builder = TimestampNanosecondBuilder;
 
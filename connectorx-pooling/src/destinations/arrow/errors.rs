@@ -7,6 +7,12 @@ pub enum ArrowDestinationError {
     #[error(transparent)]
     ArrowError(#[from] arrow::error::ArrowError),
 
+    #[error("Result exceeds the memory limit of {limit} bytes ({used} bytes used)")]
+    MemoryLimitExceeded { limit: usize, used: usize },
+
+    #[error("String column exceeds the 2 GiB offset limit of Utf8, read it as LargeUtf8")]
+    Utf8OffsetOverflow,
+
     #[error(transparent)]
     ConnectorXError(#[from] crate::errors::ConnectorXError),
 
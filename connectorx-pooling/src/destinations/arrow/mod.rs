@@ -5,21 +5,32 @@ mod errors;
 mod funcs;
 pub mod typesystem;
 
+pub use self::arrow_assoc::{ArrowStringType, DecimalLayout, DictPolicy, TimestampPolicy};
 pub use self::errors::{ArrowDestinationError, Result};
 pub use self::typesystem::ArrowTypeSystem;
 use super::{Consume, Destination, DestinationPartition};
-use crate::constants::RECORD_BATCH_SIZE;
+use crate::constants::{DICTIONARY_AUTO_MAX_RATIO, RECORD_BATCH_SIZE};
 use crate::data_order::DataOrder;
 use crate::typesystem::{Realize, TypeAssoc, TypeSystem};
 use anyhow::anyhow;
-use arrow::{datatypes::Schema, record_batch::RecordBatch};
-use arrow_assoc::ArrowAssoc;
+use arrow::{
+    array::{
+        make_array, Array, ArrayData, ArrayRef, AsArray, StringArray, StringDictionaryBuilder,
+    },
+    datatypes::{DataType, Field, Int32Type, Schema},
+    record_batch::RecordBatch,
+};
+use arrow_assoc::{ArrowAssoc, DecimalBuilder, Utf8Builder};
 use fehler::{throw, throws};
 use funcs::{FFinishBuilder, FNewBuilder, FNewField};
 use itertools::Itertools;
 use std::{
     any::Any,
-    sync::{Arc, Mutex},
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
 #[cfg(feature = "dst_polars")]
@@ -40,6 +51,32 @@ pub struct ArrowDestination {
     data: Arc<Mutex<Vec<RecordBatch>>>,
     arrow_schema: Arc<Schema>,
     batch_size: usize,
+    max_bytes: Option<usize>,
+    used_bytes: Arc<AtomicUsize>,
+    decimal_types: Vec<Option<(u8, i8)>>,
+    decimal_fallback: DecimalLayout,
+    enum_labels: Vec<Option<Vec<String>>>,
+    enum_dictionary: bool,
+    column_comments: Vec<Option<String>>,
+    string_type: ArrowStringType,
+    timestamp_policy: TimestampPolicy,
+    layouts: Vec<ColumnLayout>,
+    utf8_overflow: Arc<Mutex<Option<String>>>,
+    /// Batches written by each partition so far, if batches are kept in partition order.
+    partition_batches: Option<Arc<Mutex<Vec<usize>>>>,
+}
+
+/// How a column is built when it differs from its type's [`ArrowAssoc`] default.
+#[derive(Clone)]
+enum ColumnLayout {
+    Default,
+    Decimal(DecimalLayout),
+    /// A string column with 64-bit offsets.
+    LargeUtf8,
+    /// A string column dictionary-encoded over these values.
+    Dictionary(Arc<StringArray>),
+    /// A timestamp column whose timezone is set by the policy.
+    Timestamp(TimestampPolicy),
 }
 
 impl Default for ArrowDestination {
@@ -50,6 +87,18 @@ impl Default for ArrowDestination {
             data: Arc::new(Mutex::new(vec![])),
             arrow_schema: Arc::new(Schema::empty()),
             batch_size: RECORD_BATCH_SIZE,
+            max_bytes: None,
+            used_bytes: Arc::new(AtomicUsize::new(0)),
+            decimal_types: vec![],
+            decimal_fallback: DecimalLayout::default(),
+            enum_labels: vec![],
+            enum_dictionary: true,
+            column_comments: vec![],
+            string_type: ArrowStringType::default(),
+            timestamp_policy: TimestampPolicy::default(),
+            layouts: vec![],
+            utf8_overflow: Arc::new(Mutex::new(None)),
+            partition_batches: None,
         }
     }
 }
@@ -66,8 +115,73 @@ impl ArrowDestination {
             data: Arc::new(Mutex::new(vec![])),
             arrow_schema: Arc::new(Schema::empty()),
             batch_size,
+            max_bytes: None,
+            used_bytes: Arc::new(AtomicUsize::new(0)),
+            decimal_types: vec![],
+            decimal_fallback: DecimalLayout::default(),
+            enum_labels: vec![],
+            enum_dictionary: true,
+            column_comments: vec![],
+            string_type: ArrowStringType::default(),
+            timestamp_policy: TimestampPolicy::default(),
+            layouts: vec![],
+            utf8_overflow: Arc::new(Mutex::new(None)),
+            partition_batches: None,
         }
     }
+
+    /// Caps the buffer bytes of the batches written to this destination. A partition
+    /// flushing a batch that crosses the limit fails with `MemoryLimitExceeded`.
+    pub fn set_max_bytes(&mut self, max_bytes: Option<usize>) {
+        self.max_bytes = max_bytes;
+    }
+
+    /// Layout of the decimal columns whose source reports no precision and scale, e.g. an
+    /// unconstrained Postgres `NUMERIC`. Defaults to `Decimal128(38, 10)`.
+    pub fn set_decimal_fallback(&mut self, layout: DecimalLayout) {
+        self.decimal_fallback = layout;
+    }
+
+    /// Whether enum columns, see [`Source::enum_labels`](crate::sources::Source::enum_labels),
+    /// are written as `Dictionary(Int32, Utf8)` holding every label in declared order, or as
+    /// plain strings. Defaults to `true`.
+    pub fn set_enum_dictionary(&mut self, enabled: bool) {
+        self.enum_dictionary = enabled;
+    }
+
+    /// Arrow type of the string columns. Defaults to `Utf8`, whose batches hold at most
+    /// 2 GiB of text per column: a partition crossing that fails with `Utf8OffsetOverflow`.
+    pub fn set_string_type(&mut self, string_type: ArrowStringType) {
+        self.string_type = string_type;
+    }
+
+    /// Timezone of the timestamp columns, see [`TimestampPolicy`]. Defaults to `Preserve`.
+    pub fn set_timestamp_policy(&mut self, policy: TimestampPolicy) {
+        self.timestamp_policy = policy;
+    }
+
+    /// Whether the batches are kept in the order of the partitions that wrote them, rather
+    /// than the order the partitions flushed them in. Within a partition the order is always
+    /// kept. Defaults to `false`.
+    pub fn set_preserve_order(&mut self, enabled: bool) {
+        self.partition_batches = enabled.then(|| Arc::new(Mutex::new(vec![])));
+    }
+
+    /// The column whose text overflowed the `Utf8` offsets, if any did.
+    pub fn utf8_overflow(&self) -> Option<String> {
+        self.utf8_overflow
+            .lock()
+            .ok()
+            .and_then(|column| column.clone())
+    }
+
+    /// The limit and the bytes used so far, if the memory limit has been crossed.
+    pub fn memory_limit_exceeded(&self) -> Option<(usize, usize)> {
+        let used = self.used_bytes.load(Ordering::Relaxed);
+        self.max_bytes
+            .filter(|&limit| used > limit)
+            .map(|limit| (limit, used))
+    }
 }
 
 impl Destination for ArrowDestination {
@@ -98,25 +212,77 @@ impl Destination for ArrowDestination {
         // parse the metadata
         self.schema = schema.to_vec();
         self.names = names.iter().map(|n| n.as_ref().to_string()).collect();
+        self.layouts = self
+            .schema
+            .iter()
+            .enumerate()
+            .map(|(i, dt)| match (dt, self.enum_labels.get(i)) {
+                (ArrowTypeSystem::Decimal(_), _) => {
+                    ColumnLayout::Decimal(match self.decimal_types.get(i).copied().flatten() {
+                        Some((precision, scale)) => DecimalLayout::Decimal128(precision, scale),
+                        None => self.decimal_fallback,
+                    })
+                }
+                (ArrowTypeSystem::LargeUtf8(_), Some(Some(labels))) if self.enum_dictionary => {
+                    ColumnLayout::Dictionary(Arc::new(StringArray::from_iter_values(labels)))
+                }
+                (ArrowTypeSystem::LargeUtf8(_) | ArrowTypeSystem::Json(_), _)
+                    if self.string_type == ArrowStringType::LargeUtf8 =>
+                {
+                    ColumnLayout::LargeUtf8
+                }
+                (
+                    ArrowTypeSystem::Date64(_)
+                    | ArrowTypeSystem::Date64Micro(_)
+                    | ArrowTypeSystem::DateTimeTz(_)
+                    | ArrowTypeSystem::DateTimeTzMicro(_),
+                    _,
+                ) if self.timestamp_policy != TimestampPolicy::Preserve => {
+                    ColumnLayout::Timestamp(self.timestamp_policy)
+                }
+                _ => ColumnLayout::Default,
+            })
+            .collect();
         let fields = self
             .schema
             .iter()
             .zip_eq(&self.names)
-            .map(|(&dt, h)| Ok(Realize::<FNewField>::realize(dt)?(h.as_str())))
+            .zip_eq(&self.layouts)
+            .enumerate()
+            .map(|(i, ((&dt, h), layout))| {
+                let mut field = Realize::<FNewField>::realize(dt)?(h.as_str());
+                let data_type = match layout {
+                    ColumnLayout::Default => None,
+                    ColumnLayout::Decimal(layout) => Some(layout.data_type()),
+                    ColumnLayout::LargeUtf8 => Some(ArrowStringType::LargeUtf8.data_type()),
+                    ColumnLayout::Dictionary(_) => Some(Utf8Builder::dictionary_type()),
+                    ColumnLayout::Timestamp(policy) => Some(policy.data_type(field.data_type())),
+                };
+                if let Some(data_type) = data_type {
+                    field = Field::new(field.name(), data_type, field.is_nullable())
+                        .with_metadata(field.metadata().clone());
+                }
+                if let Some(Some(comment)) = self.column_comments.get(i) {
+                    let mut metadata = field.metadata().clone();
+                    metadata.insert("comment".to_string(), comment.clone());
+                    field = field.with_metadata(metadata);
+                }
+                Ok(field)
+            })
             .collect::<Result<Vec<_>>>()?;
         self.arrow_schema = Arc::new(Schema::new(fields));
     }
 
     #[throws(ArrowDestinationError)]
     fn partition(&mut self, counts: usize) -> Vec<Self::Partition<'_>> {
+        if let Some(partition_batches) = &self.partition_batches {
+            *partition_batches
+                .lock()
+                .map_err(|e| anyhow!("mutex poisoned {}", e))? = vec![0; counts];
+        }
         let mut partitions = vec![];
-        for _ in 0..counts {
-            partitions.push(ArrowPartitionWriter::new(
-                self.schema.clone(),
-                Arc::clone(&self.data),
-                Arc::clone(&self.arrow_schema),
-                self.batch_size,
-            )?);
+        for i in 0..counts {
+            partitions.push(ArrowPartitionWriter::new(self, i)?);
         }
         partitions
     }
@@ -124,6 +290,18 @@ impl Destination for ArrowDestination {
     fn schema(&self) -> &[ArrowTypeSystem] {
         self.schema.as_slice()
     }
+
+    fn set_decimal_types(&mut self, decimal_types: &[Option<(u8, i8)>]) {
+        self.decimal_types = decimal_types.to_vec();
+    }
+
+    fn set_enum_labels(&mut self, enum_labels: &[Option<Vec<String>>]) {
+        self.enum_labels = enum_labels.to_vec();
+    }
+
+    fn set_column_comments(&mut self, comments: &[Option<String>]) {
+        self.column_comments = comments.to_vec();
+    }
 }
 
 impl ArrowDestination {
@@ -189,6 +367,17 @@ impl ArrowDestination {
         concat(lf_vec, union_args)?.collect()?
     }
 
+    /// Moves all record batches of `other` into this destination. The caller is responsible
+    /// for making sure both destinations share a compatible schema.
+    #[throws(ArrowDestinationError)]
+    pub fn append(&mut self, other: ArrowDestination) {
+        let batches = other.arrow()?;
+        self.data
+            .lock()
+            .map_err(|e| anyhow!("mutex poisoned {}", e))?
+            .extend(batches);
+    }
+
     #[throws(ArrowDestinationError)]
     pub fn record_batch(&mut self) -> Option<RecordBatch> {
         let mut guard = self
@@ -198,6 +387,38 @@ impl ArrowDestination {
         (*guard).pop()
     }
 
+    /// NULLs in each column of the batches written so far, by column name. Arrow keeps the
+    /// count for every array, so this is a sum over the batches rather than a scan.
+    #[throws(ArrowDestinationError)]
+    pub fn null_counts(&self) -> HashMap<String, usize> {
+        let batches = self
+            .data
+            .lock()
+            .map_err(|e| anyhow!("mutex poisoned {}", e))?;
+        let mut counts: HashMap<String, usize> =
+            self.names.iter().map(|name| (name.clone(), 0)).collect();
+        for batch in batches.iter() {
+            for (name, column) in self.names.iter().zip(batch.columns()) {
+                *counts.entry(name.clone()).or_default() += column.null_count();
+            }
+        }
+        counts
+    }
+
+    /// The last row written so far, as a batch of one row, or `None` if no row was written.
+    #[throws(ArrowDestinationError)]
+    pub fn last_row(&self) -> Option<RecordBatch> {
+        let batches = self
+            .data
+            .lock()
+            .map_err(|e| anyhow!("mutex poisoned {}", e))?;
+        batches
+            .iter()
+            .rev()
+            .find(|batch| batch.num_rows() > 0)
+            .map(|batch| batch.slice(batch.num_rows() - 1, 1))
+    }
+
     pub fn empty_batch(&self) -> RecordBatch {
         RecordBatch::new_empty(self.arrow_schema.clone())
     }
@@ -209,36 +430,121 @@ impl ArrowDestination {
     pub fn names(&self) -> &[String] {
         self.names.as_slice()
     }
+
+    /// Dictionary-encodes the `Utf8` columns of the batches written so far as `policy` asks,
+    /// changing the schema to match. Each column is encoded over all batches at once, so
+    /// every batch holds the same dictionary and they concatenate or stream as they are.
+    #[throws(ArrowDestinationError)]
+    pub fn encode_dictionaries(&mut self, policy: DictPolicy) {
+        if policy == DictPolicy::Never {
+            return;
+        }
+        let mut batches = self
+            .data
+            .lock()
+            .map_err(|e| anyhow!("mutex poisoned {}", e))?;
+        let sample = batches.iter().find(|batch| batch.num_rows() > 0);
+        let encoded: Vec<usize> = self
+            .arrow_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.data_type() == &DataType::Utf8)
+            .filter(|(i, _)| match policy {
+                DictPolicy::Always => true,
+                _ => sample.is_some_and(|batch| low_cardinality(batch.column(*i).as_ref())),
+            })
+            .map(|(i, _)| i)
+            .collect();
+        if encoded.is_empty() {
+            return;
+        }
+
+        let mut columns: Vec<Vec<ArrayRef>> = batches
+            .iter()
+            .map(|batch| batch.columns().to_vec())
+            .collect();
+        for &i in &encoded {
+            let mut builder = StringDictionaryBuilder::<Int32Type>::new();
+            for batch in batches.iter() {
+                for value in batch.column(i).as_string::<i32>() {
+                    builder.append_option(value);
+                }
+            }
+            let dictionary = builder.finish();
+            let mut offset = 0;
+            for (batch, columns) in batches.iter().zip(&mut columns) {
+                // the slices share the dictionary's values
+                columns[i] = Arc::new(dictionary.slice(offset, batch.num_rows()));
+                offset += batch.num_rows();
+            }
+        }
+
+        let fields: Vec<Field> = self
+            .arrow_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let field = field.as_ref().clone();
+                if encoded.contains(&i) {
+                    field.with_data_type(Utf8Builder::dictionary_type())
+                } else {
+                    field
+                }
+            })
+            .collect();
+        self.arrow_schema = Arc::new(Schema::new(fields));
+        *batches = columns
+            .into_iter()
+            .map(|columns| RecordBatch::try_new(Arc::clone(&self.arrow_schema), columns))
+            .collect::<std::result::Result<_, _>>()?;
+    }
+}
+
+/// Whether the `Utf8` `column` has few enough distinct values to be worth a dictionary.
+fn low_cardinality(column: &dyn Array) -> bool {
+    let values = column.as_string::<i32>();
+    let non_null = values.len() - values.null_count();
+    let distinct: HashSet<&str> = values.iter().flatten().collect();
+    non_null > 0 && distinct.len() as f64 <= non_null as f64 * DICTIONARY_AUTO_MAX_RATIO
 }
 
 pub struct ArrowPartitionWriter {
     schema: Vec<ArrowTypeSystem>,
+    layouts: Vec<ColumnLayout>,
     builders: Option<Builders>,
     current_row: usize,
     current_col: usize,
     data: Arc<Mutex<Vec<RecordBatch>>>,
     arrow_schema: Arc<Schema>,
     batch_size: usize,
+    max_bytes: Option<usize>,
+    used_bytes: Arc<AtomicUsize>,
+    utf8_overflow: Arc<Mutex<Option<String>>>,
+    partition: usize,
+    partition_batches: Option<Arc<Mutex<Vec<usize>>>>,
 }
 
 // unsafe impl Sync for ArrowPartitionWriter {}
 
 impl ArrowPartitionWriter {
     #[throws(ArrowDestinationError)]
-    fn new(
-        schema: Vec<ArrowTypeSystem>,
-        data: Arc<Mutex<Vec<RecordBatch>>>,
-        arrow_schema: Arc<Schema>,
-        batch_size: usize,
-    ) -> Self {
+    fn new(destination: &ArrowDestination, partition: usize) -> Self {
         let mut pw = ArrowPartitionWriter {
-            schema,
+            schema: destination.schema.clone(),
+            layouts: destination.layouts.clone(),
             builders: None,
             current_row: 0,
             current_col: 0,
-            data,
-            arrow_schema,
-            batch_size,
+            data: Arc::clone(&destination.data),
+            arrow_schema: Arc::clone(&destination.arrow_schema),
+            batch_size: destination.batch_size,
+            max_bytes: destination.max_bytes,
+            used_bytes: Arc::clone(&destination.used_bytes),
+            utf8_overflow: Arc::clone(&destination.utf8_overflow),
+            partition,
+            partition_batches: destination.partition_batches.clone(),
         };
         pw.allocate()?;
         pw
@@ -249,7 +555,21 @@ impl ArrowPartitionWriter {
         let builders = self
             .schema
             .iter()
-            .map(|dt| Ok(Realize::<FNewBuilder>::realize(*dt)?(self.batch_size)))
+            .zip_eq(&self.layouts)
+            .map(|(dt, layout)| match layout {
+                ColumnLayout::Default | ColumnLayout::Timestamp(_) => {
+                    Ok(Realize::<FNewBuilder>::realize(*dt)?(self.batch_size))
+                }
+                ColumnLayout::Decimal(layout) => {
+                    Ok(Box::new(DecimalBuilder::new(*layout, self.batch_size)) as Builder)
+                }
+                ColumnLayout::LargeUtf8 => {
+                    Ok(Box::new(Utf8Builder::large(self.batch_size)) as Builder)
+                }
+                ColumnLayout::Dictionary(values) => {
+                    Ok(Box::new(Utf8Builder::dictionary(values, self.batch_size)?) as Builder)
+                }
+            })
             .collect::<Result<Vec<_>>>()?;
         self.builders.replace(builders);
     }
@@ -263,16 +583,49 @@ impl ArrowPartitionWriter {
         let columns = builders
             .into_iter()
             .zip(self.schema.iter())
-            .map(|(builder, &dt)| Realize::<FFinishBuilder>::realize(dt)?(builder))
-            .collect::<std::result::Result<Vec<_>, crate::errors::ConnectorXError>>()?;
+            .zip(&self.layouts)
+            .enumerate()
+            .map(|(i, ((builder, &dt), layout))| {
+                let column = Realize::<FFinishBuilder>::realize(dt)?(builder)?;
+                Ok(match layout {
+                    // only the timezone differs, so the values are kept as they are
+                    ColumnLayout::Timestamp(_) => make_array(
+                        column
+                            .to_data()
+                            .into_builder()
+                            .data_type(self.arrow_schema.field(i).data_type().clone())
+                            .build()?,
+                    ),
+                    _ => column,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
         let rb = RecordBatch::try_new(Arc::clone(&self.arrow_schema), columns)?;
+        if let Some(limit) = self.max_bytes {
+            let bytes: usize = rb.columns().iter().map(|c| data_bytes(&c.to_data())).sum();
+            let used = self.used_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+            if used > limit {
+                throw!(ArrowDestinationError::MemoryLimitExceeded { limit, used });
+            }
+        }
         {
             let mut guard = self
                 .data
                 .lock()
                 .map_err(|e| anyhow!("mutex poisoned {}", e))?;
             let inner_data = &mut *guard;
-            inner_data.push(rb);
+            match &self.partition_batches {
+                // the batches stay grouped by partition, in partition order
+                Some(partition_batches) => {
+                    let mut counts = partition_batches
+                        .lock()
+                        .map_err(|e| anyhow!("mutex poisoned {}", e))?;
+                    let at = counts[..=self.partition].iter().sum();
+                    counts[self.partition] += 1;
+                    inner_data.insert(at, rb);
+                }
+                None => inner_data.push(rb),
+            }
         }
 
         self.current_row = 0;
@@ -280,6 +633,14 @@ impl ArrowPartitionWriter {
     }
 }
 
+/// Bytes used by the buffers of `data` and its children. Unlike the buffers' capacity this
+/// does not count the room builders reserve for a whole batch.
+fn data_bytes(data: &ArrayData) -> usize {
+    data.buffers().iter().map(|b| b.len()).sum::<usize>()
+        + data.nulls().map_or(0, |n| n.buffer().len())
+        + data.child_data().iter().map(data_bytes).sum::<usize>()
+}
+
 impl<'a> DestinationPartition<'a> for ArrowPartitionWriter {
     type TypeSystem = ArrowTypeSystem;
     type Error = ArrowDestinationError;
@@ -316,12 +677,20 @@ where
         loop {
             match &mut self.builders {
                 Some(builders) => {
-                    <T as ArrowAssoc>::append(
+                    let appended = <T as ArrowAssoc>::append(
                         builders[col]
                             .downcast_mut::<T::Builder>()
                             .ok_or_else(|| anyhow!("cannot cast arrow builder for append"))?,
                         value,
-                    )?;
+                    );
+                    if let Err(ArrowDestinationError::Utf8OffsetOverflow) = appended {
+                        // kept so the reader can name the column, whichever error wraps this
+                        if let Ok(mut column) = self.utf8_overflow.lock() {
+                            column
+                                .get_or_insert_with(|| self.arrow_schema.field(col).name().clone());
+                        }
+                    }
+                    appended?;
                     break;
                 }
                 None => self.allocate()?, // allocate if builders are not initialized
@@ -1,6 +1,8 @@
 use crate::impl_typesystem;
+use arrow::datatypes::IntervalMonthDayNano;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy)]
 pub struct DateTimeWrapperMicro(pub DateTime<Utc>);
@@ -11,11 +13,22 @@ pub struct NaiveTimeWrapperMicro(pub NaiveTime);
 #[derive(Debug, Clone, Copy)]
 pub struct NaiveDateTimeWrapperMicro(pub NaiveDateTime);
 
+/// A JSON document, such as a Postgres `jsonb` value, written as a string column flagged
+/// `{"json": "true"}` in its field metadata.
+#[derive(Debug, Clone)]
+pub struct JsonString(pub String);
+
+/// A geometry as WKB, such as a PostGIS `geometry` value, written as a binary column flagged
+/// `{"geoarrow": "wkb"}` in its field metadata.
+#[derive(Debug, Clone)]
+pub struct WkbBinary(pub Vec<u8>);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ArrowTypeSystem {
     Int16(bool),
     Int32(bool),
     Int64(bool),
+    UInt8(bool),
     UInt16(bool),
     UInt32(bool),
     UInt64(bool),
@@ -25,11 +38,14 @@ pub enum ArrowTypeSystem {
     Boolean(bool),
     LargeUtf8(bool),
     LargeBinary(bool),
+    Json(bool),
+    Wkb(bool),
     Date32(bool),
     Date64(bool),
     Date64Micro(bool),
     Time64(bool),
     Time64Micro(bool),
+    Interval(bool),
     DateTimeTz(bool),
     DateTimeTzMicro(bool),
     BoolArray(bool),
@@ -43,6 +59,7 @@ pub enum ArrowTypeSystem {
     Float32Array(bool),
     Float64Array(bool),
     DecimalArray(bool),
+    Map(bool),
 }
 
 impl_typesystem! {
@@ -51,6 +68,7 @@ impl_typesystem! {
         { Int16           => i16                       }
         { Int32           => i32                       }
         { Int64           => i64                       }
+        { UInt8           => u8                        }
         { UInt16          => u16                       }
         { UInt32          => u32                       }
         { UInt64          => u64                       }
@@ -60,11 +78,14 @@ impl_typesystem! {
         { Boolean         => bool                      }
         { LargeUtf8       => String                    }
         { LargeBinary     => Vec<u8>                   }
+        { Json            => JsonString                }
+        { Wkb             => WkbBinary                 }
         { Date32          => NaiveDate                 }
         { Date64          => NaiveDateTime             }
         { Date64Micro     => NaiveDateTimeWrapperMicro }
         { Time64          => NaiveTime                 }
         { Time64Micro     => NaiveTimeWrapperMicro     }
+        { Interval        => IntervalMonthDayNano      }
         { DateTimeTz      => DateTime<Utc>             }
         { DateTimeTzMicro => DateTimeWrapperMicro      }
         { BoolArray       => Vec<Option<bool>>         }
@@ -78,5 +99,6 @@ impl_typesystem! {
         { Float32Array    => Vec<Option<f32>>          }
         { Float64Array    => Vec<Option<f64>>          }
         { DecimalArray    => Vec<Option<Decimal>>      }
+        { Map             => HashMap<String, Option<String>> }
     }
 }
@@ -1,20 +1,25 @@
 use super::{
     errors::{ArrowDestinationError, Result},
-    typesystem::{DateTimeWrapperMicro, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro},
+    typesystem::{
+        DateTimeWrapperMicro, JsonString, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro,
+        WkbBinary,
+    },
 };
 use crate::constants::{DEFAULT_ARROW_DECIMAL, DEFAULT_ARROW_DECIMAL_SCALE, SECONDS_IN_DAY};
 use crate::utils::decimal_to_i128;
 use arrow::array::{
-    ArrayBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float32Builder, Float64Builder,
-    Int16Builder, Int32Builder, Int64Builder, LargeBinaryBuilder, LargeListBuilder, StringBuilder,
-    Time64MicrosecondBuilder, Time64NanosecondBuilder, TimestampMicrosecondBuilder,
-    TimestampNanosecondBuilder, UInt16Builder, UInt32Builder, UInt64Builder,
+    ArrayBuilder, BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float32Builder,
+    Float64Builder, Int16Builder, Int32Builder, Int64Builder, IntervalMonthDayNanoBuilder,
+    LargeBinaryBuilder, LargeListBuilder, MapBuilder, StringBuilder, Time64MicrosecondBuilder,
+    Time64NanosecondBuilder, TimestampMicrosecondBuilder, TimestampNanosecondBuilder,
+    UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder,
 };
-use arrow::datatypes::Field;
-use arrow::datatypes::{DataType as ArrowDataType, TimeUnit};
+use arrow::datatypes::{DataType as ArrowDataType, IntervalMonthDayNano, IntervalUnit, TimeUnit};
+use arrow::datatypes::{Field, Fields};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use fehler::throws;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 /// Associate arrow builder with native type
 pub trait ArrowAssoc {
     type Builder: ArrayBuilder + Send;
@@ -62,6 +67,7 @@ macro_rules! impl_arrow_assoc {
     };
 }
 
+impl_arrow_assoc!(u8, ArrowDataType::UInt8, UInt8Builder);
 impl_arrow_assoc!(u16, ArrowDataType::UInt16, UInt16Builder);
 impl_arrow_assoc!(u32, ArrowDataType::UInt32, UInt32Builder);
 impl_arrow_assoc!(u64, ArrowDataType::UInt64, UInt64Builder);
@@ -71,6 +77,11 @@ impl_arrow_assoc!(i64, ArrowDataType::Int64, Int64Builder);
 impl_arrow_assoc!(f32, ArrowDataType::Float32, Float32Builder);
 impl_arrow_assoc!(f64, ArrowDataType::Float64, Float64Builder);
 impl_arrow_assoc!(bool, ArrowDataType::Boolean, BooleanBuilder);
+impl_arrow_assoc!(
+    IntervalMonthDayNano,
+    ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+    IntervalMonthDayNanoBuilder
+);
 
 impl ArrowAssoc for Decimal {
     type Builder = Decimal128Builder;
@@ -186,6 +197,144 @@ impl ArrowAssoc for Option<String> {
     }
 }
 
+impl ArrowAssoc for JsonString {
+    type Builder = StringBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        <String as ArrowAssoc>::builder(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: JsonString) {
+        <String as ArrowAssoc>::append(builder, value.0)?;
+    }
+
+    fn field(header: &str) -> Field {
+        json_field(<String as ArrowAssoc>::field(header))
+    }
+}
+
+impl ArrowAssoc for Option<JsonString> {
+    type Builder = StringBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        <Option<String> as ArrowAssoc>::builder(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Option<JsonString>) {
+        <Option<String> as ArrowAssoc>::append(builder, value.map(|json| json.0))?;
+    }
+
+    fn field(header: &str) -> Field {
+        json_field(<Option<String> as ArrowAssoc>::field(header))
+    }
+}
+
+/// `field` flagged as holding JSON documents, for readers to parse the strings back.
+fn json_field(field: Field) -> Field {
+    field.with_metadata(HashMap::from([("json".to_string(), "true".to_string())]))
+}
+
+impl ArrowAssoc for WkbBinary {
+    type Builder = BinaryBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BinaryBuilder::with_capacity(nrows, 1024)
+    }
+
+    fn append(builder: &mut Self::Builder, value: WkbBinary) -> Result<()> {
+        builder.append_value(value.0);
+        Ok(())
+    }
+
+    fn field(header: &str) -> Field {
+        wkb_field(Field::new(header, ArrowDataType::Binary, false))
+    }
+}
+
+impl ArrowAssoc for Option<WkbBinary> {
+    type Builder = BinaryBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BinaryBuilder::with_capacity(nrows, 1024)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Option<WkbBinary>) -> Result<()> {
+        builder.append_option(value.map(|wkb| wkb.0));
+        Ok(())
+    }
+
+    fn field(header: &str) -> Field {
+        wkb_field(Field::new(header, ArrowDataType::Binary, true))
+    }
+}
+
+/// `field` flagged as holding WKB geometries, for GeoArrow-aware readers.
+fn wkb_field(field: Field) -> Field {
+    field.with_metadata(HashMap::from([("geoarrow".to_string(), "wkb".to_string())]))
+}
+
+impl ArrowAssoc for HashMap<String, Option<String>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        MapBuilder::with_capacity(None, StringBuilder::new(), StringBuilder::new(), nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        append_map(builder, Some(value))
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(), false)
+    }
+}
+
+impl ArrowAssoc for Option<HashMap<String, Option<String>>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        MapBuilder::with_capacity(None, StringBuilder::new(), StringBuilder::new(), nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        append_map(builder, value)
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(), true)
+    }
+}
+
+/// Appends the entries of `map` sorted by key, so equal maps are written alike.
+fn append_map(
+    builder: &mut MapBuilder<StringBuilder, StringBuilder>,
+    map: Option<HashMap<String, Option<String>>>,
+) -> Result<()> {
+    let is_valid = map.is_some();
+    let mut entries: Vec<_> = map.into_iter().flatten().collect();
+    entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    for (key, value) in entries {
+        builder.keys().append_value(key);
+        builder.values().append_option(value);
+    }
+    builder.append(is_valid)?;
+    Ok(())
+}
+
+/// The type `MapBuilder` gives a map of strings to nullable strings.
+fn map_type() -> ArrowDataType {
+    let entries = Fields::from(vec![
+        Field::new("keys", ArrowDataType::Utf8, false),
+        Field::new("values", ArrowDataType::Utf8, true),
+    ]);
+    ArrowDataType::Map(
+        std::sync::Arc::new(Field::new("entries", ArrowDataType::Struct(entries), false)),
+        false,
+    )
+}
+
 impl ArrowAssoc for DateTime<Utc> {
     type Builder = TimestampNanosecondBuilder;
 
@@ -1,3 +1,4 @@
+use std::time::Duration;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, ArrowDestinationError>;
@@ -7,6 +8,12 @@ pub enum ArrowDestinationError {
     #[error(transparent)]
     ArrowError(#[from] arrow::error::ArrowError),
 
+    #[error("No record batch arrived within {0:?}")]
+    BatchTimeout(Duration),
+
+    #[error("The record batch stream was dropped before it was exhausted")]
+    StreamDropped,
+
     #[error(transparent)]
     ConnectorXError(#[from] crate::errors::ConnectorXError),
 
@@ -20,9 +20,10 @@ use itertools::Itertools;
 use std::{
     any::Any,
     sync::{
-        mpsc::{channel, Receiver, Sender},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
         Arc,
     },
+    time::Duration,
 };
 
 type Builder = Box<dyn Any + Send>;
@@ -35,6 +36,7 @@ pub struct ArrowDestination {
     batch_size: usize,
     sender: Option<Sender<RecordBatch>>,
     receiver: Receiver<RecordBatch>,
+    batch_timeout: Option<Duration>,
 }
 
 impl Default for ArrowDestination {
@@ -47,6 +49,7 @@ impl Default for ArrowDestination {
             batch_size: RECORD_BATCH_SIZE,
             sender: Some(tx),
             receiver: rx,
+            batch_timeout: None,
         }
     }
 }
@@ -65,6 +68,7 @@ impl ArrowDestination {
             batch_size,
             sender: Some(tx),
             receiver: rx,
+            batch_timeout: None,
         }
     }
 }
@@ -145,11 +149,24 @@ impl ArrowDestination {
         data
     }
 
+    /// Bounds how long `record_batch` waits for the next batch; `None` waits indefinitely.
+    pub fn set_batch_timeout(&mut self, timeout: Option<Duration>) {
+        self.batch_timeout = timeout;
+    }
+
+    /// The next batch, or `None` once every partition is finished. A timed out wait leaves
+    /// the stream intact: the batch is still returned by a later call once it arrives.
     #[throws(ArrowDestinationError)]
     pub fn record_batch(&mut self) -> Option<RecordBatch> {
-        match self.receiver.recv() {
-            Ok(rb) => Some(rb),
-            Err(_) => None,
+        match self.batch_timeout {
+            Some(timeout) => match self.receiver.recv_timeout(timeout) {
+                Ok(rb) => Some(rb),
+                Err(RecvTimeoutError::Timeout) => {
+                    throw!(ArrowDestinationError::BatchTimeout(timeout))
+                }
+                Err(RecvTimeoutError::Disconnected) => None,
+            },
+            None => self.receiver.recv().ok(),
         }
     }
 
@@ -221,7 +238,12 @@ impl ArrowPartitionWriter {
             .map(|(builder, &dt)| Realize::<FFinishBuilder>::realize(dt)?(builder))
             .collect::<std::result::Result<Vec<_>, crate::errors::ConnectorXError>>()?;
         let rb = RecordBatch::try_new(Arc::clone(&self.arrow_schema), columns)?;
-        self.sender.as_ref().and_then(|s| s.send(rb).ok());
+        // stop reading once nobody receives the batches, which frees the connection
+        if let Some(sender) = &self.sender {
+            if sender.send(rb).is_err() {
+                throw!(ArrowDestinationError::StreamDropped);
+            }
+        }
 
         self.current_row = 0;
         self.current_col = 0;
@@ -39,6 +39,18 @@ pub trait Destination: Sized {
     fn partition(&mut self, counts: usize) -> Result<Vec<Self::Partition<'_>>, Self::Error>;
     /// Return the schema of the destination.
     fn schema(&self) -> &[Self::TypeSystem];
+
+    /// Receives [`Source::decimal_types`](crate::sources::Source::decimal_types) before
+    /// `allocate`. Destinations with a fixed decimal representation ignore it.
+    fn set_decimal_types(&mut self, _decimal_types: &[Option<(u8, i8)>]) {}
+
+    /// Receives [`Source::enum_labels`](crate::sources::Source::enum_labels) before
+    /// `allocate`. Destinations without a categorical representation ignore it.
+    fn set_enum_labels(&mut self, _enum_labels: &[Option<Vec<String>>]) {}
+
+    /// Receives [`Source::column_comments`](crate::sources::Source::column_comments) before
+    /// `allocate`, if they were asked for. Destinations without field metadata ignore them.
+    fn set_column_comments(&mut self, _comments: &[Option<String>]) {}
 }
 
 /// `PartitionDestination` writes values to its own region. `PartitionDestination` is parameterized
@@ -0,0 +1,160 @@
+//! Classification of connection failures, used by [`PoolVariant::diagnose`](crate::pool::PoolVariant::diagnose)
+//! to tell a bad password from an unreachable host.
+
+use crate::source_router::SourceType;
+use std::fmt;
+
+/// Outcome of a preflight connection attempt. Failures keep the driver's error message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnosis {
+    Ok,
+    /// The server could not be reached: bad host name, nothing listening, firewall, timeout.
+    HostUnreachable(String),
+    /// The server rejected the user or password.
+    AuthFailed(String),
+    /// The server was reached and accepted the login, but the database does not exist.
+    DatabaseNotFound(String),
+    /// Setting up TLS failed, e.g. an untrusted certificate or a server without TLS.
+    TlsError(String),
+    /// A failure none of the above patterns matched.
+    Other(String),
+}
+
+impl Diagnosis {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Diagnosis::Ok)
+    }
+
+    /// The variant name, e.g. `AuthFailed`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Diagnosis::Ok => "Ok",
+            Diagnosis::HostUnreachable(_) => "HostUnreachable",
+            Diagnosis::AuthFailed(_) => "AuthFailed",
+            Diagnosis::DatabaseNotFound(_) => "DatabaseNotFound",
+            Diagnosis::TlsError(_) => "TlsError",
+            Diagnosis::Other(_) => "Other",
+        }
+    }
+
+    /// The driver error behind a failed diagnosis.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Diagnosis::Ok => None,
+            Diagnosis::HostUnreachable(m)
+            | Diagnosis::AuthFailed(m)
+            | Diagnosis::DatabaseNotFound(m)
+            | Diagnosis::TlsError(m)
+            | Diagnosis::Other(m) => Some(m),
+        }
+    }
+
+    /// Classifies the error message of a failed connection attempt to a `ty` server. The
+    /// patterns are checked from the most to the least specific failure: an authentication
+    /// error may mention SSL (`no pg_hba.conf entry ..., SSL off`) and a TLS error the host.
+    pub fn classify(ty: &SourceType, message: &str) -> Self {
+        let lower = message.to_ascii_lowercase();
+        let any = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+        let (tls, auth, database): (&[&str], &[&str], &[&str]) = match ty {
+            SourceType::Postgres => (
+                &["tls", "ssl", "certificate", "server does not support"],
+                &[
+                    "password authentication failed",
+                    "authentication failed",
+                    "no pg_hba.conf entry",
+                    "role \"",
+                    "password missing",
+                ],
+                &["database \""],
+            ),
+            SourceType::MySQL => (
+                &["tls", "ssl", "certificate", "secure connection"],
+                &[
+                    "access denied",
+                    "error 1045",
+                    "error 1698",
+                    "authentication plugin",
+                ],
+                &["unknown database", "error 1049", "error 1044"],
+            ),
+            SourceType::Oracle => (
+                &[
+                    "ora-28759",
+                    "ora-28860",
+                    "ora-29024",
+                    "ora-29106",
+                    "certificate",
+                    "ssl",
+                ],
+                &[
+                    "ora-01017",
+                    "ora-28000",
+                    "ora-01005",
+                    "invalid username/password",
+                ],
+                &["ora-12514", "ora-12505", "ora-01034"],
+            ),
+            SourceType::SQLite => (
+                &[],
+                &[],
+                &[
+                    "unable to open database file",
+                    "file is not a database",
+                    "no such file",
+                ],
+            ),
+            _ => (
+                &["tls", "ssl", "certificate"],
+                &["authentication", "access denied"],
+                &[],
+            ),
+        };
+        let host: &[&str] = match ty {
+            SourceType::Oracle => &[
+                "ora-12541",
+                "ora-12545",
+                "ora-12170",
+                "ora-12154",
+                "ora-12543",
+                "ora-12537",
+                "tns:",
+            ],
+            _ => &[
+                "connection refused",
+                "timed out",
+                "timeout",
+                "failed to lookup address",
+                "name or service not known",
+                "nodename nor servname",
+                "no route to host",
+                "network is unreachable",
+                "host is unreachable",
+                "connection reset",
+                "could not connect",
+                "error connecting to server",
+            ],
+        };
+
+        let message = message.to_string();
+        if any(auth) {
+            Diagnosis::AuthFailed(message)
+        } else if any(tls) {
+            Diagnosis::TlsError(message)
+        } else if any(database) {
+            Diagnosis::DatabaseNotFound(message)
+        } else if any(host) {
+            Diagnosis::HostUnreachable(message)
+        } else {
+            Diagnosis::Other(message)
+        }
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.message() {
+            Some(message) => write!(f, "{}: {}", self.kind(), message),
+            None => f.write_str(self.kind()),
+        }
+    }
+}
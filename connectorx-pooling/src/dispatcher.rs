@@ -4,7 +4,8 @@ use crate::{
     data_order::{coordinate, DataOrder},
     destinations::{Destination, DestinationPartition},
     errors::{ConnectorXError, Result as CXResult},
-    sources::{PartitionParser, Source, SourcePartition},
+    schema_cache::SchemaCache,
+    sources::{InvalidUtf8Policy, PartitionParser, Source, SourcePartition},
     sql::CXQuery,
     typesystem::Transport,
 };
@@ -12,6 +13,35 @@ use itertools::Itertools;
 use log::debug;
 use rayon::prelude::*;
 use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Told how far a read got: with the partitions finished so far and their total as each one
+/// finishes, or with the rows so far and `None` where the total is unknown, as for a stream.
+/// Calls never overlap and the counts never go down. An error aborts the read.
+pub type ProgressCallback = Arc<dyn Fn(usize, Option<usize>) -> anyhow::Result<()> + Send + Sync>;
+
+/// Where the time of one partition went, as collected by [`Dispatcher::run_timed`].
+#[derive(Debug, Clone)]
+pub struct PartitionTiming {
+    pub query: String,
+    /// Time spent waiting for a connection, so pool contention shows apart from the query.
+    pub checkout_ms: f64,
+    /// Time from issuing the query until the partition is written to the destination.
+    pub exec_ms: f64,
+    pub rows: usize,
+}
+
+impl PartitionTiming {
+    pub fn new(query: &CXQuery<String>, checkout: Duration, exec: Duration, rows: usize) -> Self {
+        Self {
+            query: query.to_string(),
+            checkout_ms: checkout.as_secs_f64() * 1000.0,
+            exec_ms: exec.as_secs_f64() * 1000.0,
+            rows,
+        }
+    }
+}
 
 /// A dispatcher takes a `S: Source`, a `D: Destination`, a `TP: Transport` and a vector of `queries` as input to
 /// load data from `S` to `D` using the queries.
@@ -20,6 +50,9 @@ pub struct Dispatcher<'a, S, D, TP> {
     dst: &'a mut D,
     queries: Vec<CXQuery<String>>,
     origin_query: Option<String>,
+    include_comments: bool,
+    schema_cache: Option<&'a SchemaCache>,
+    progress: Option<ProgressCallback>,
     _phantom: PhantomData<TP>,
 }
 
@@ -39,6 +72,9 @@ where
             dst,
             queries: queries.iter().map(Into::into).collect(),
             origin_query,
+            include_comments: false,
+            schema_cache: None,
+            progress: None,
             _phantom: PhantomData,
         }
     }
@@ -47,6 +83,28 @@ where
         self.src.set_pre_execution_queries(pre_execution_queries);
     }
 
+    /// How the source decodes text holding invalid UTF-8. Fails the read by default.
+    pub fn set_invalid_utf8(&mut self, policy: InvalidUtf8Policy) {
+        self.src.set_invalid_utf8(policy);
+    }
+
+    /// Pass the column comments of the source on to the destination, at the cost of a catalog
+    /// query. Off by default.
+    pub fn set_include_comments(&mut self, include_comments: bool) {
+        self.include_comments = include_comments;
+    }
+
+    /// Take the metadata of the queries from `schema_cache` when it holds them, rather than
+    /// describing them to the database, and cache it otherwise.
+    pub fn set_schema_cache(&mut self, schema_cache: Option<&'w SchemaCache>) {
+        self.schema_cache = schema_cache;
+    }
+
+    /// Report each finished partition to `progress`, see [`ProgressCallback`].
+    pub fn set_progress(&mut self, progress: Option<ProgressCallback>) {
+        self.progress = progress;
+    }
+
     pub fn prepare(
         mut self,
     ) -> Result<
@@ -66,7 +124,13 @@ where
         self.src.set_origin_query(self.origin_query);
 
         debug!("Fetching metadata");
-        self.src.fetch_metadata()?;
+        SchemaCache::fetch_metadata(self.schema_cache, &mut self.src, &self.queries)?;
+        self.dst.set_decimal_types(&self.src.decimal_types());
+        self.dst.set_enum_labels(&self.src.enum_labels());
+        if self.include_comments {
+            debug!("Fetching column comments");
+            self.dst.set_column_comments(&self.src.column_comments()?);
+        }
         let src_schema = self.src.schema();
         let dst_schema = src_schema
             .iter()
@@ -120,8 +184,27 @@ where
 
     /// Start the data loading process.
     pub fn run(self) -> Result<(), TP::Error> {
+        self.run_timed()?;
+        Ok(())
+    }
+
+    /// Same as [`run`](Self::run), also reporting the time each partition took.
+    pub fn run_timed(self) -> Result<Vec<PartitionTiming>, TP::Error> {
         debug!("Run dispatcher");
-        let (dorder, src_partitions, dst_partitions, src_schema, dst_schema) = self.prepare()?;
+        let queries = self.queries.clone();
+        let schema_cache = self.schema_cache;
+        let progress = self.progress.clone();
+        // held while `progress` runs, so the partitions report one at a time and in order
+        let completed = Mutex::new(0);
+        // the read may have failed as the schema changed since it was cached
+        let invalidate = |e: TP::Error| {
+            if let Some(cache) = schema_cache {
+                cache.invalidate::<S>(&queries);
+            }
+            e
+        };
+        let (dorder, src_partitions, dst_partitions, src_schema, dst_schema) =
+            self.prepare().map_err(&invalidate)?;
 
         #[cfg(all(not(feature = "branch"), not(feature = "fptr")))]
         compile_error!("branch or fptr, pick one");
@@ -135,71 +218,102 @@ where
 
         debug!("Start writing");
         // parse and write
-        dst_partitions
+        let timings = dst_partitions
             .into_par_iter()
             .zip_eq(src_partitions)
             .enumerate()
-            .try_for_each(|(i, (mut dst, mut src))| -> Result<(), TP::Error> {
-                #[cfg(feature = "fptr")]
-                let f: Vec<_> = src_schema
-                    .iter()
-                    .zip_eq(&dst_schema)
-                    .map(|(&src_ty, &dst_ty)| TP::processor(src_ty, dst_ty))
-                    .collect::<CXResult<Vec<_>>>()?;
-
-                let mut parser = src.parser()?;
-
-                match dorder {
-                    DataOrder::RowMajor => loop {
-                        let (n, is_last) = parser.fetch_next()?;
-                        dst.aquire_row(n)?;
-                        for _ in 0..n {
-                            #[allow(clippy::needless_range_loop)]
-                            for col in 0..dst.ncols() {
-                                #[cfg(feature = "fptr")]
-                                f[col](&mut parser, &mut dst)?;
-
-                                #[cfg(feature = "branch")]
-                                {
-                                    let (s1, s2) = schemas[col];
-                                    TP::process(s1, s2, &mut parser, &mut dst)?;
+            .map(
+                |(i, (mut dst, mut src))| -> Result<PartitionTiming, TP::Error> {
+                    let checkout = src.checkout_time();
+                    let start = Instant::now();
+                    // a connection failed mid-read may be left unusable for the next borrower
+                    let read = (|| -> Result<usize, TP::Error> {
+                        #[cfg(feature = "fptr")]
+                        let f: Vec<_> = src_schema
+                            .iter()
+                            .zip_eq(&dst_schema)
+                            .map(|(&src_ty, &dst_ty)| TP::processor(src_ty, dst_ty))
+                            .collect::<CXResult<Vec<_>>>()?;
+
+                        let mut rows = 0;
+                        let mut parser = src.parser()?;
+
+                        match dorder {
+                            DataOrder::RowMajor => loop {
+                                let (n, is_last) = parser.fetch_next()?;
+                                rows += n;
+                                dst.aquire_row(n)?;
+                                for _ in 0..n {
+                                    #[allow(clippy::needless_range_loop)]
+                                    for col in 0..dst.ncols() {
+                                        #[cfg(feature = "fptr")]
+                                        f[col](&mut parser, &mut dst)?;
+
+                                        #[cfg(feature = "branch")]
+                                        {
+                                            let (s1, s2) = schemas[col];
+                                            TP::process(s1, s2, &mut parser, &mut dst)?;
+                                        }
+                                    }
                                 }
-                            }
-                        }
-                        if is_last {
-                            break;
-                        }
-                    },
-                    DataOrder::ColumnMajor => loop {
-                        let (n, is_last) = parser.fetch_next()?;
-                        dst.aquire_row(n)?;
-                        #[allow(clippy::needless_range_loop)]
-                        for col in 0..dst.ncols() {
-                            for _ in 0..n {
-                                #[cfg(feature = "fptr")]
-                                f[col](&mut parser, &mut dst)?;
-                                #[cfg(feature = "branch")]
-                                {
-                                    let (s1, s2) = schemas[col];
-                                    TP::process(s1, s2, &mut parser, &mut dst)?;
+                                if is_last {
+                                    break;
                                 }
-                            }
+                            },
+                            DataOrder::ColumnMajor => loop {
+                                let (n, is_last) = parser.fetch_next()?;
+                                rows += n;
+                                dst.aquire_row(n)?;
+                                #[allow(clippy::needless_range_loop)]
+                                for col in 0..dst.ncols() {
+                                    for _ in 0..n {
+                                        #[cfg(feature = "fptr")]
+                                        f[col](&mut parser, &mut dst)?;
+                                        #[cfg(feature = "branch")]
+                                        {
+                                            let (s1, s2) = schemas[col];
+                                            TP::process(s1, s2, &mut parser, &mut dst)?;
+                                        }
+                                    }
+                                }
+                                if is_last {
+                                    break;
+                                }
+                            },
                         }
-                        if is_last {
-                            break;
+                        Ok(rows)
+                    })();
+                    let rows = match read {
+                        Ok(rows) => rows,
+                        Err(e) => {
+                            src.release_after_error();
+                            return Err(e);
                         }
-                    },
-                }
+                    };
 
-                debug!("Finalize partition {}", i);
-                dst.finalize()?;
-                debug!("Partition {} finished", i);
-                Ok(())
-            })?;
+                    debug!("Finalize partition {}", i);
+                    dst.finalize()?;
+                    debug!("Partition {} finished", i);
+                    if let Some(progress) = &progress {
+                        let mut completed = completed.lock().unwrap();
+                        *completed += 1;
+                        progress(*completed, Some(queries.len()))
+                            .map_err(ConnectorXError::Other)?;
+                    }
+                    Ok(PartitionTiming::new(
+                        &queries[i],
+                        checkout,
+                        start.elapsed(),
+                        rows,
+                    ))
+                },
+            )
+            .collect::<Result<Vec<_>, TP::Error>>()
+            .map_err(&invalidate)?;
 
         debug!("Writing finished");
 
-        Ok(())
+        Ok(timings)
     }
 
     /// Only fetch the metadata (header) of the destination.
@@ -209,6 +323,8 @@ where
         self.src.set_queries(self.queries.as_slice());
         self.src.set_origin_query(self.origin_query.clone());
         self.src.fetch_metadata()?;
+        self.dst.set_decimal_types(&self.src.decimal_types());
+        self.dst.set_enum_labels(&self.src.enum_labels());
         let src_schema = self.src.schema();
         let dst_schema = src_schema
             .iter()
@@ -1,4 +1,5 @@
 use crate::data_order::DataOrder;
+use crate::source_router::SourceType;
 use std::any::type_name;
 use std::fmt;
 use thiserror::Error;
@@ -14,9 +15,38 @@ pub enum ConnectorXOutError {
     #[error("Source {0} not supported.")]
     SourceNotSupport(String),
 
+    #[error("Shard {0} failed: {1}")]
+    ShardError(usize, #[source] Box<ConnectorXOutError>),
+
+    #[error("Shard {0} schema is incompatible with shard 0: expected {1}, found {2}.")]
+    ShardSchemaMismatch(usize, String, String),
+
+    #[error("No record batch arrived within {0:?}")]
+    BatchTimeout(std::time::Duration),
+
+    #[error("Result exceeds the memory limit of {limit} bytes ({used} bytes used)")]
+    MemoryLimitExceeded { limit: usize, used: usize },
+
+    #[error("Column {column} exceeds the 2 GiB offset limit of Utf8 strings, read it with ArrowStringType::LargeUtf8")]
+    Utf8OffsetOverflow { column: String },
+
+    #[error("{count} partitions exceed the limit of {max}")]
+    TooManyPartitions { count: usize, max: usize },
+
+    /// Every connection of the pool stayed in use until the checkout timed out.
+    #[error("Timed out waiting for a pooled connection, all of them are in use")]
+    PoolTimeout(#[source] Box<ConnectorXOutError>),
+
+    /// The pool could not open a connection, e.g. as the database is down.
+    #[error("The pool could not connect: {0}")]
+    PoolConnectionFailed(#[source] Box<ConnectorXOutError>),
+
     #[error(transparent)]
     IOError(#[from] std::io::Error),
 
+    #[error("Failed to create tokio runtime: {0}")]
+    RuntimeInit(#[source] std::io::Error),
+
     #[error(transparent)]
     JsonError(#[from] serde_json::Error),
 
@@ -94,6 +124,14 @@ pub enum ConnectorXOutError {
     #[error(transparent)]
     ArrowStreamError(#[from] crate::destinations::arrowstream::ArrowDestinationError),
 
+    #[cfg(feature = "dst_parquet")]
+    #[error(transparent)]
+    ParquetError(#[from] parquet::errors::ParquetError),
+
+    #[cfg(feature = "flight")]
+    #[error(transparent)]
+    FlightError(#[from] arrow_flight::error::FlightError),
+
     #[cfg(all(feature = "src_postgres", feature = "dst_arrow"))]
     #[error(transparent)]
     PostgresArrowTransportError(#[from] crate::transports::PostgresArrowTransportError),
@@ -122,6 +160,34 @@ pub enum ConnectorXOutError {
     #[error(transparent)]
     TrinoArrowTransportError(#[from] crate::transports::TrinoArrowTransportError),
 
+    #[cfg(all(feature = "src_postgres", feature = "dst_arrow"))]
+    #[error(transparent)]
+    PostgresArrowStreamTransportError(#[from] crate::transports::PostgresArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_mysql", feature = "dst_arrow"))]
+    #[error(transparent)]
+    MySQLArrowStreamTransportError(#[from] crate::transports::MySQLArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_sqlite", feature = "dst_arrow"))]
+    #[error(transparent)]
+    SQLiteArrowStreamTransportError(#[from] crate::transports::SQLiteArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_mssql", feature = "dst_arrow"))]
+    #[error(transparent)]
+    MsSQLArrowStreamTransportError(#[from] crate::transports::MsSQLArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_oracle", feature = "dst_arrow"))]
+    #[error(transparent)]
+    OracleArrowStreamTransportError(#[from] crate::transports::OracleArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_bigquery", feature = "dst_arrow"))]
+    #[error(transparent)]
+    BigQueryArrowStreamTransportError(#[from] crate::transports::BigQueryArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_trino", feature = "dst_arrow"))]
+    #[error(transparent)]
+    TrinoArrowStreamTransportError(#[from] crate::transports::TrinoArrowStreamTransportError),
+
     /// Any other errors that are too trivial to be put here explicitly.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
@@ -152,6 +218,18 @@ pub enum ConnectorXError {
     #[error("Cannot get total number of rows in advance.")]
     CountError(),
 
+    #[error("Unsupported connection scheme {0:?}, expected one of: {1}.")]
+    UnsupportedScheme(String, String),
+
+    #[error("Unsupported protocol {0:?} for {1:?}, expected one of: {2}.")]
+    UnsupportedProtocol(String, SourceType, String),
+
+    #[error("Environment variable {0:?} named by the connection string is not set.")]
+    ConnEnvVarNotSet(String),
+
+    #[error("Text column holds invalid UTF-8: {0}.")]
+    InvalidUtf8(std::str::Utf8Error),
+
     #[error(transparent)]
     SQLParserError(#[from] sqlparser::parser::ParserError),
 
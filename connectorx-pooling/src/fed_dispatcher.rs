@@ -51,7 +51,8 @@ pub fn run(
                         .as_ref()
                         .unwrap();
 
-                    let destination = get_arrow(source_conn, None, queries.as_slice(), None, None)?;
+                    let destination =
+                        get_arrow(source_conn, None, queries.as_slice(), None, None, None)?;
                     let rbs = destination.arrow()?;
 
                     let provider = MemTable::try_new(rbs[0].schema(), vec![rbs])?;
@@ -82,7 +83,7 @@ pub fn run(
         })?;
 
     debug!("\nexecute query final...\n{}\n", local_sql);
-    let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+    let rt = crate::runtime::shared_runtime()?;
     // until datafusion fix the bug: https://github.com/apache/arrow-datafusion/issues/2147
     for alias in alias_names {
         local_sql = local_sql.replace(format!("\"{}\"", alias).as_str(), alias.as_str());
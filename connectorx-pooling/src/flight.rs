@@ -0,0 +1,100 @@
+//! Stream query results to an Arrow Flight endpoint with `DoPut`, batch by batch, so a
+//! large result never has to be held in memory before it is forwarded.
+
+use crate::{
+    constants::RECORD_BATCH_SIZE, errors::ConnectorXOutError, get_arrow::new_record_batch_iter,
+    pool::PoolVariant, source_router::SourceConn, sql::CXQuery,
+};
+use arrow::record_batch::RecordBatch;
+use arrow_flight::{
+    encode::FlightDataEncoderBuilder, error::FlightError, FlightClient, FlightDescriptor,
+};
+use futures::{
+    channel::{mpsc, oneshot},
+    executor::block_on,
+    SinkExt, TryStreamExt,
+};
+use log::debug;
+
+/// Number of record batches read ahead of the Flight stream. Once this many are waiting,
+/// reading from the source pauses until the client has sent one of them.
+pub const FLIGHT_BUFFERED_BATCHES: usize = 1;
+
+/// Stream the result of `queries` to `flight_client` as a single `DoPut` for `descriptor`.
+/// Returns the number of rows sent once the server has acknowledged the upload.
+///
+/// Batches are read on a dedicated thread and handed over through a bounded channel, so a
+/// Flight server that applies backpressure also slows the reads from the source down
+/// instead of letting the batches pile up in memory. A failed read aborts the upload, and
+/// the error from the source is returned in preference to the one reported by the server.
+pub async fn get_arrow_to_flight(
+    source_conn: &SourceConn,
+    queries: &[CXQuery<String>],
+    flight_client: &mut FlightClient,
+    descriptor: FlightDescriptor,
+    pool: Option<&PoolVariant>,
+) -> Result<usize, ConnectorXOutError> {
+    let mut batch_iter = new_record_batch_iter(
+        source_conn,
+        None,
+        queries,
+        RECORD_BATCH_SIZE,
+        None,
+        pool,
+        None,
+    )?;
+    let schema = batch_iter.schema();
+
+    let (mut tx, rx) = mpsc::channel::<Result<RecordBatch, FlightError>>(FLIGHT_BUFFERED_BATCHES);
+    let (done_tx, done_rx) = oneshot::channel::<Result<usize, ConnectorXOutError>>();
+    std::thread::spawn(move || {
+        batch_iter.prepare();
+        let mut nrows = 0;
+        let result = loop {
+            match batch_iter.try_next_batch() {
+                Ok(Some(rb)) => {
+                    if rb.num_rows() == 0 {
+                        continue;
+                    }
+                    let n = rb.num_rows();
+                    // The receiver is gone once the upload failed; its error is reported
+                    // by the response stream.
+                    if block_on(tx.send(Ok(rb))).is_err() {
+                        break Ok(nrows);
+                    }
+                    nrows += n;
+                }
+                Ok(None) => break Ok(nrows),
+                Err(e) => {
+                    let _ = block_on(tx.send(Err(FlightError::ExternalError(
+                        format!("reading from the source failed: {}", e).into(),
+                    ))));
+                    break Err(e);
+                }
+            }
+        };
+        drop(tx);
+        let _ = done_tx.send(result);
+    });
+
+    let data = FlightDataEncoderBuilder::new()
+        .with_schema(schema)
+        .with_flight_descriptor(Some(descriptor))
+        .build(rx);
+    let uploaded = match flight_client.do_put(data).await {
+        Ok(responses) => responses.try_for_each(|_| async { Ok(()) }).await,
+        Err(e) => Err(e),
+    };
+
+    let nrows = match done_rx.await {
+        Ok(result) => result?,
+        Err(_) => {
+            return Err(ConnectorXOutError::FlightError(FlightError::ExternalError(
+                "the thread reading from the source panicked".into(),
+            )))
+        }
+    };
+    uploaded?;
+    debug!("Sent {} rows to the Flight endpoint", nrows);
+    Ok(nrows)
+}
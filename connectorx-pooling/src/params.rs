@@ -0,0 +1,140 @@
+//! Values bound to prepared-statement placeholders by [`get_arrow_params`](crate::get_arrow::get_arrow_params).
+//!
+//! Parameters are sent to the server separately from the SQL text, so they are never
+//! interpolated into the query. Placeholder syntax follows the backend: `?` for SQLite and
+//! MySQL, `$1` for Postgres and `:1` for Oracle.
+
+/// A single query parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarValue {
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bool(bool),
+    Null,
+    Bytes(Vec<u8>),
+}
+
+#[cfg(feature = "src_sqlite")]
+impl rusqlite::ToSql for ScalarValue {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        use rusqlite::types::{ToSqlOutput, ValueRef};
+        Ok(match self {
+            ScalarValue::Int(v) => ToSqlOutput::Borrowed(ValueRef::Integer(*v)),
+            ScalarValue::Float(v) => ToSqlOutput::Borrowed(ValueRef::Real(*v)),
+            ScalarValue::Text(v) => ToSqlOutput::Borrowed(ValueRef::Text(v.as_bytes())),
+            ScalarValue::Bool(v) => ToSqlOutput::Borrowed(ValueRef::Integer(*v as i64)),
+            ScalarValue::Null => ToSqlOutput::Borrowed(ValueRef::Null),
+            ScalarValue::Bytes(v) => ToSqlOutput::Borrowed(ValueRef::Blob(v)),
+        })
+    }
+}
+
+#[cfg(feature = "src_mysql")]
+impl From<&ScalarValue> for r2d2_mysql::mysql::Value {
+    fn from(value: &ScalarValue) -> Self {
+        use r2d2_mysql::mysql::Value;
+        match value {
+            ScalarValue::Int(v) => Value::Int(*v),
+            ScalarValue::Float(v) => Value::Double(*v),
+            ScalarValue::Text(v) => Value::Bytes(v.clone().into_bytes()),
+            ScalarValue::Bool(v) => Value::Int(*v as i64),
+            ScalarValue::Null => Value::NULL,
+            ScalarValue::Bytes(v) => Value::Bytes(v.clone()),
+        }
+    }
+}
+
+/// Converts parameters into the positional form the MySQL driver expects.
+#[cfg(feature = "src_mysql")]
+pub fn mysql_params(params: &[ScalarValue]) -> r2d2_mysql::mysql::Params {
+    if params.is_empty() {
+        r2d2_mysql::mysql::Params::Empty
+    } else {
+        r2d2_mysql::mysql::Params::Positional(params.iter().map(Into::into).collect())
+    }
+}
+
+// Postgres checks each parameter against the placeholder type the server inferred, so the
+// value is converted here into that type (e.g. `Int` into an `int4` or `numeric`
+// placeholder) instead of relying on the fixed mapping of the underlying Rust types. A value
+// that has no conversion into the placeholder's type fails with `WrongType` before anything
+// is sent, rather than being written in a representation the server misreads.
+#[cfg(feature = "src_postgres")]
+impl postgres::types::ToSql for ScalarValue {
+    fn to_sql(
+        &self,
+        ty: &postgres::types::Type,
+        out: &mut bytes::BytesMut,
+    ) -> Result<postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        use postgres::types::{IsNull, Type, WrongType};
+        use rust_decimal::Decimal;
+        use std::convert::TryFrom;
+        let is_text = <String as postgres::types::ToSql>::accepts(ty);
+        match (self, ty) {
+            (ScalarValue::Int(v), &Type::INT2) => i16::try_from(*v)?.to_sql(ty, out),
+            (ScalarValue::Int(v), &Type::INT4) => i32::try_from(*v)?.to_sql(ty, out),
+            (ScalarValue::Int(v), &Type::INT8) => v.to_sql(ty, out),
+            (ScalarValue::Int(v), &Type::OID) => u32::try_from(*v)?.to_sql(ty, out),
+            (ScalarValue::Int(v), &Type::FLOAT4) => (*v as f32).to_sql(ty, out),
+            (ScalarValue::Int(v), &Type::FLOAT8) => (*v as f64).to_sql(ty, out),
+            (ScalarValue::Int(v), &Type::NUMERIC) => Decimal::from(*v).to_sql(ty, out),
+            (ScalarValue::Int(v), _) if is_text => v.to_string().to_sql(ty, out),
+            (ScalarValue::Float(v), &Type::FLOAT4) => (*v as f32).to_sql(ty, out),
+            (ScalarValue::Float(v), &Type::FLOAT8) => v.to_sql(ty, out),
+            (ScalarValue::Float(v), &Type::NUMERIC) => Decimal::try_from(*v)?.to_sql(ty, out),
+            (ScalarValue::Float(v), _) if is_text => v.to_string().to_sql(ty, out),
+            (ScalarValue::Text(v), _) if is_text => v.to_sql(ty, out),
+            (ScalarValue::Bool(v), &Type::BOOL) => v.to_sql(ty, out),
+            (ScalarValue::Bool(v), _) if is_text => v.to_string().to_sql(ty, out),
+            (ScalarValue::Bytes(v), &Type::BYTEA) => v.to_sql(ty, out),
+            (ScalarValue::Null, _) => Ok(IsNull::Yes),
+            _ => Err(Box::new(WrongType::new::<ScalarValue>(ty.clone()))),
+        }
+    }
+
+    fn accepts(ty: &postgres::types::Type) -> bool {
+        use postgres::types::Type;
+        matches!(
+            *ty,
+            Type::INT2
+                | Type::INT4
+                | Type::INT8
+                | Type::OID
+                | Type::FLOAT4
+                | Type::FLOAT8
+                | Type::NUMERIC
+                | Type::BOOL
+                | Type::BYTEA
+        ) || <String as postgres::types::ToSql>::accepts(ty)
+    }
+
+    // a NULL binds to a placeholder of any type, so it skips the `accepts` check
+    fn to_sql_checked(
+        &self,
+        ty: &postgres::types::Type,
+        out: &mut bytes::BytesMut,
+    ) -> Result<postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        match self {
+            ScalarValue::Null => Ok(postgres::types::IsNull::Yes),
+            _ if !<Self as postgres::types::ToSql>::accepts(ty) => Err(Box::new(
+                postgres::types::WrongType::new::<ScalarValue>(ty.clone()),
+            )),
+            _ => self.to_sql(ty, out),
+        }
+    }
+}
+
+/// Converts a parameter into a value the Oracle driver can bind. Oracle has no SQL boolean
+/// before 23c, so booleans are bound as 0/1.
+#[cfg(feature = "src_oracle")]
+pub fn oracle_param(value: &ScalarValue) -> Box<dyn oracle::sql_type::ToSql> {
+    match value {
+        ScalarValue::Int(v) => Box::new(*v),
+        ScalarValue::Float(v) => Box::new(*v),
+        ScalarValue::Text(v) => Box::new(v.clone()),
+        ScalarValue::Bool(v) => Box::new(*v as i64),
+        ScalarValue::Null => Box::new(Option::<String>::None),
+        ScalarValue::Bytes(v) => Box::new(v.clone()),
+    }
+}
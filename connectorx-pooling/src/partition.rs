@@ -1,32 +1,49 @@
 use crate::errors::{ConnectorXOutError, OutResult};
+use crate::params::ScalarValue;
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+use crate::pool::checkout;
+use crate::pool::PoolVariant;
+#[cfg(any(feature = "src_bigquery", feature = "src_mssql", feature = "src_trino"))]
+use crate::runtime::shared_runtime;
 use crate::source_router::{SourceConn, SourceType};
 #[cfg(feature = "src_bigquery")]
 use crate::sources::bigquery::BigQueryDialect;
 #[cfg(feature = "src_mssql")]
 use crate::sources::mssql::{mssql_config, FloatN, IntN, MsSQLTypeSystem};
 #[cfg(feature = "src_mysql")]
-use crate::sources::mysql::{MySQLSourceError, MySQLTypeSystem};
+use crate::sources::mysql::{mysql_opts, MySQLTypeSystem};
 #[cfg(feature = "src_oracle")]
 use crate::sources::oracle::{OracleDialect, OracleSource};
 #[cfg(feature = "src_postgres")]
 use crate::sources::postgres::{rewrite_tls_args, PostgresTypeSystem};
+#[cfg(feature = "src_sqlite")]
+use crate::sources::sqlite::sqlite_target;
 #[cfg(feature = "src_trino")]
 use crate::sources::trino::TrinoDialect;
 #[cfg(feature = "src_sqlite")]
 use crate::sql::get_partition_range_query_sep;
-use crate::sql::{get_partition_range_query, single_col_partition_query, CXQuery};
+use crate::sql::{
+    get_partition_range_query, predicate_partition_query, range_partition_query,
+    range_partition_template, CXQuery,
+};
 use anyhow::anyhow;
 use fehler::{throw, throws};
 #[cfg(feature = "src_bigquery")]
 use gcp_bigquery_client;
 #[cfg(feature = "src_mysql")]
-use r2d2_mysql::mysql::{prelude::Queryable, Opts, Pool, Row};
+use r2d2_mysql::mysql::{prelude::Queryable, Pool, Row};
 #[cfg(feature = "src_sqlite")]
 use rusqlite::{types::Type, Connection};
 #[cfg(feature = "src_postgres")]
 use rust_decimal::{prelude::ToPrimitive, Decimal};
 #[cfg(feature = "src_postgres")]
 use rust_decimal_macros::dec;
+use sqlparser::ast::Value;
 #[cfg(feature = "src_mssql")]
 use sqlparser::dialect::MsSqlDialect;
 #[cfg(feature = "src_mysql")]
@@ -38,7 +55,7 @@ use sqlparser::dialect::SQLiteDialect;
 #[cfg(feature = "src_mssql")]
 use tiberius::Client;
 #[cfg(any(feature = "src_bigquery", feature = "src_mssql", feature = "src_trino"))]
-use tokio::{net::TcpStream, runtime::Runtime};
+use tokio::net::TcpStream;
 #[cfg(feature = "src_mssql")]
 use tokio_util::compat::TokioAsyncWriteCompatExt;
 use url::Url;
@@ -63,6 +80,125 @@ impl PartitionQuery {
     }
 }
 
+/// How a query is split into partitions that are read in parallel. Every strategy keeps the
+/// rows whose partition column is NULL, reading them in the first partition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartitionStrategy {
+    /// `n` ranges of equal width between the MIN and MAX of the integer column `col`.
+    NumericRange { col: String, n: usize },
+    /// The ranges `bounds` splits `col` into, for columns of any type the database can
+    /// compare, e.g. text. The ascending `bounds` are split points, so `k` of them make
+    /// `k + 1` partitions: `col < bounds[0]`, `bounds[0] <= col < bounds[1]`, and so on up to
+    /// `col >= bounds[k - 1]`.
+    ExplicitRanges {
+        col: String,
+        bounds: Vec<ScalarValue>,
+    },
+    /// `n` partitions by a hash of `col` modulo `n`, for columns of any type. Supported for
+    /// Postgres (`hashtext`), MySQL (`crc32`), MsSQL (`checksum`) and Oracle (`ora_hash`).
+    Hash { col: String, n: usize },
+}
+
+impl PartitionStrategy {
+    /// The column the partitions are split on.
+    pub fn column(&self) -> &str {
+        match self {
+            PartitionStrategy::NumericRange { col, .. }
+            | PartitionStrategy::ExplicitRanges { col, .. }
+            | PartitionStrategy::Hash { col, .. } => col,
+        }
+    }
+}
+
+/// Split `query` into the partitions `strategy` describes, probing the range of a
+/// [`PartitionStrategy::NumericRange`] through `pool` when one is given.
+pub fn partition_by(
+    query: &str,
+    strategy: &PartitionStrategy,
+    source_conn: &SourceConn,
+    pool: Option<&PoolVariant>,
+) -> OutResult<Vec<CXQuery>> {
+    match strategy {
+        PartitionStrategy::NumericRange { col, n } => {
+            let part = PartitionQuery::new(query, col, None, None, *n);
+            partition_with_pool(&part, source_conn, pool)
+        }
+        PartitionStrategy::ExplicitRanges { col, bounds } => {
+            if bounds.is_empty() {
+                throw!(anyhow!("explicit partition ranges need at least one bound"));
+            }
+            let bounds = bounds
+                .iter()
+                .map(sql_literal)
+                .collect::<OutResult<Vec<_>>>()?;
+            (0..=bounds.len())
+                .map(|i| {
+                    let lower = i
+                        .checked_sub(1)
+                        .map(|j| format!("CXTMPTAB_PART.{} >= {}", col, bounds[j]));
+                    let upper = bounds
+                        .get(i)
+                        .map(|bound| format!("CXTMPTAB_PART.{} < {}", col, bound));
+                    let mut predicate = lower
+                        .into_iter()
+                        .chain(upper)
+                        .collect::<Vec<_>>()
+                        .join(" AND ");
+                    if i == 0 {
+                        predicate = format!("({}) OR CXTMPTAB_PART.{} IS NULL", predicate, col);
+                    }
+                    filtered_query(source_conn, query, &predicate)
+                })
+                .collect()
+        }
+        PartitionStrategy::Hash { col, n } => {
+            let n = (*n).max(1);
+            (0..n)
+                .map(|i| {
+                    let mut predicate = hash_predicate(source_conn, col, n, i)?;
+                    if i == 0 {
+                        predicate = format!("({}) OR CXTMPTAB_PART.{} IS NULL", predicate, col);
+                    }
+                    filtered_query(source_conn, query, &predicate)
+                })
+                .collect()
+        }
+    }
+}
+
+/// `value` as an SQL literal, for the bounds of a [`PartitionStrategy::ExplicitRanges`].
+#[throws(ConnectorXOutError)]
+fn sql_literal(value: &ScalarValue) -> Value {
+    match value {
+        ScalarValue::Int(v) => Value::Number(v.to_string(), false),
+        ScalarValue::Float(v) => Value::Number(v.to_string(), false),
+        ScalarValue::Text(v) => Value::SingleQuotedString(v.clone()),
+        ScalarValue::Bool(v) => Value::Boolean(*v),
+        ScalarValue::Null | ScalarValue::Bytes(_) => {
+            throw!(anyhow!("{:?} can not be a partition bound", value))
+        }
+    }
+}
+
+/// The predicate selecting the rows of `col` whose hash modulo `n` is `i`.
+#[throws(ConnectorXOutError)]
+fn hash_predicate(source_conn: &SourceConn, col: &str, n: usize, i: usize) -> String {
+    match source_conn.ty {
+        // hashtext is signed, and so is the remainder of a negative hash
+        SourceType::Postgres => format!(
+            "abs(mod(hashtext(CAST(CXTMPTAB_PART.{} AS TEXT)), {})) = {}",
+            col, n, i
+        ),
+        SourceType::MySQL => format!("crc32(CXTMPTAB_PART.{}) % {} = {}", col, n, i),
+        SourceType::MsSQL => format!("abs(checksum(CXTMPTAB_PART.{}) % {}) = {}", col, n, i),
+        SourceType::Oracle => format!("ora_hash(CXTMPTAB_PART.{}, {}) = {}", col, n - 1, i),
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?} does not support hash partitioning",
+            source_conn.ty
+        ))),
+    }
+}
+
 pub fn partition(part: &PartitionQuery, source_conn: &SourceConn) -> OutResult<Vec<CXQuery>> {
     let mut queries = vec![];
     let num = part.num as i64;
@@ -108,6 +244,143 @@ pub fn get_col_range(source_conn: &SourceConn, query: &str, col: &str) -> OutRes
     }
 }
 
+/// Like [`get_col_range`], but runs the MIN/MAX probe on a connection checked out of `pool`
+/// instead of opening a new one.
+#[allow(unreachable_patterns)]
+pub fn get_col_range_pooled(
+    source_conn: &SourceConn,
+    pool: &PoolVariant,
+    query: &str,
+    col: &str,
+) -> OutResult<(i64, i64)> {
+    match pool {
+        #[cfg(feature = "src_postgres")]
+        PoolVariant::PostgresNoTls(p) => {
+            let mut conn = checkout(p).map_err(|e| anyhow!(e))?;
+            pg_col_range(&mut conn, query, col)
+        }
+        #[cfg(feature = "src_postgres")]
+        PoolVariant::PostgresTls(p) => {
+            let mut conn = checkout(p).map_err(|e| anyhow!(e))?;
+            pg_col_range(&mut conn, query, col)
+        }
+        #[cfg(feature = "src_sqlite")]
+        PoolVariant::SQLite(p) => {
+            let conn = checkout(p).map_err(|e| anyhow!(e))?;
+            sqlite_col_range(&conn, query, col)
+        }
+        #[cfg(feature = "src_mysql")]
+        PoolVariant::MySQL(p) => {
+            let mut conn = checkout(p).map_err(|e| anyhow!(e))?;
+            mysql_col_range(&mut *conn, query, col)
+        }
+        #[cfg(feature = "src_oracle")]
+        PoolVariant::Oracle(p) => {
+            let source = OracleSource::new(source_conn.conn.as_str(), 1, Some(p.clone()))?;
+            oracle_col_range(&source, query, col)
+        }
+        _ => get_col_range(source_conn, query, col),
+    }
+}
+
+/// Split `part.query` into range partitions on `part.column`, probing the range through
+/// `pool` when one is given. Unlike [`partition`], rows whose partition column is NULL are
+/// kept (they are read by the first partition), and a range holding fewer distinct values
+/// than `part.num` (including `min == max`) yields fewer partitions.
+pub fn partition_with_pool(
+    part: &PartitionQuery,
+    source_conn: &SourceConn,
+    pool: Option<&PoolVariant>,
+) -> OutResult<Vec<CXQuery>> {
+    partition_ranges(part, source_conn, pool)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, (lower, upper))| {
+            part_query(source_conn, &part.query, &part.column, lower, upper, i == 0)
+        })
+        .collect()
+}
+
+/// Split `part.query` into the same partitions as [`partition_with_pool`], but as a single
+/// query template with the range left to placeholders, and the values each partition binds
+/// to them: its lower and upper bounds, and whether it keeps the NULL rows. Supported for
+/// Postgres and SQLite.
+pub fn partition_template(
+    part: &PartitionQuery,
+    source_conn: &SourceConn,
+    pool: Option<&PoolVariant>,
+) -> OutResult<(CXQuery, Vec<Vec<ScalarValue>>)> {
+    let template = match source_conn.ty {
+        #[cfg(feature = "src_postgres")]
+        SourceType::Postgres => range_partition_template(
+            &part.query,
+            &part.column,
+            "CAST($1 AS BIGINT)",
+            "CAST($2 AS BIGINT)",
+            "CAST($3 AS BOOLEAN)",
+            &PostgreSqlDialect {},
+        )?,
+        #[cfg(feature = "src_sqlite")]
+        SourceType::SQLite => range_partition_template(
+            &part.query,
+            &part.column,
+            "?1",
+            "?2",
+            "?3",
+            &SQLiteDialect {},
+        )?,
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?} does not support prepared partition queries",
+            source_conn.ty
+        ))),
+    };
+    let params = partition_ranges(part, source_conn, pool)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, (lower, upper))| {
+            vec![
+                ScalarValue::Int(lower),
+                ScalarValue::Int(upper),
+                ScalarValue::Bool(i == 0),
+            ]
+        })
+        .collect();
+    Ok((CXQuery::Wrapped(template), params))
+}
+
+/// The `[lower, upper)` ranges [`partition_with_pool`] splits `part.column` into.
+fn partition_ranges(
+    part: &PartitionQuery,
+    source_conn: &SourceConn,
+    pool: Option<&PoolVariant>,
+) -> OutResult<Vec<(i64, i64)>> {
+    let (min, max) = match (part.min, part.max, pool) {
+        (None, None, Some(pool)) => {
+            get_col_range_pooled(source_conn, pool, &part.query, &part.column)?
+        }
+        (None, None, None) => get_col_range(source_conn, &part.query, &part.column)?,
+        (Some(min), Some(max), _) => (min, max),
+        _ => throw!(anyhow!(
+            "partition_query range can not be partially specified",
+        )),
+    };
+
+    let num = (part.num as i64).clamp(1, (max - min).saturating_add(1).max(1));
+    let partition_size = (max - min + 1) / num;
+
+    let ranges = (0..num)
+        .map(|i| {
+            let lower = min + i * partition_size;
+            let upper = match i == num - 1 {
+                true => max + 1,
+                false => min + (i + 1) * partition_size,
+            };
+            (lower, upper)
+        })
+        .collect();
+    Ok(ranges)
+}
+
 #[throws(ConnectorXOutError)]
 pub fn get_part_query(
     source_conn: &SourceConn,
@@ -115,41 +388,81 @@ pub fn get_part_query(
     col: &str,
     lower: i64,
     upper: i64,
+) -> CXQuery<String> {
+    part_query(source_conn, query, col, lower, upper, false)?
+}
+
+#[throws(ConnectorXOutError)]
+fn part_query(
+    source_conn: &SourceConn,
+    query: &str,
+    col: &str,
+    lower: i64,
+    upper: i64,
+    include_nulls: bool,
 ) -> CXQuery<String> {
     let query = match source_conn.ty {
         #[cfg(feature = "src_postgres")]
-        SourceType::Postgres => {
-            single_col_partition_query(query, col, lower, upper, &PostgreSqlDialect {})?
-        }
+        SourceType::Postgres => range_partition_query(
+            query,
+            col,
+            lower,
+            upper,
+            include_nulls,
+            &PostgreSqlDialect {},
+        )?,
         #[cfg(feature = "src_sqlite")]
         SourceType::SQLite => {
-            single_col_partition_query(query, col, lower, upper, &SQLiteDialect {})?
+            range_partition_query(query, col, lower, upper, include_nulls, &SQLiteDialect {})?
         }
         #[cfg(feature = "src_mysql")]
         SourceType::MySQL => {
-            single_col_partition_query(query, col, lower, upper, &MySqlDialect {})?
+            range_partition_query(query, col, lower, upper, include_nulls, &MySqlDialect {})?
         }
         #[cfg(feature = "src_mssql")]
         SourceType::MsSQL => {
-            single_col_partition_query(query, col, lower, upper, &MsSqlDialect {})?
+            range_partition_query(query, col, lower, upper, include_nulls, &MsSqlDialect {})?
         }
         #[cfg(feature = "src_oracle")]
         SourceType::Oracle => {
-            single_col_partition_query(query, col, lower, upper, &OracleDialect {})?
+            range_partition_query(query, col, lower, upper, include_nulls, &OracleDialect {})?
         }
         #[cfg(feature = "src_bigquery")]
         SourceType::BigQuery => {
-            single_col_partition_query(query, col, lower, upper, &BigQueryDialect {})?
+            range_partition_query(query, col, lower, upper, include_nulls, &BigQueryDialect {})?
         }
         #[cfg(feature = "src_trino")]
         SourceType::Trino => {
-            single_col_partition_query(query, col, lower, upper, &TrinoDialect {})?
+            range_partition_query(query, col, lower, upper, include_nulls, &TrinoDialect {})?
         }
         _ => unimplemented!("{:?} not implemented!", source_conn.ty),
     };
     CXQuery::Wrapped(query)
 }
 
+/// Wraps `query` so it only returns the rows matching `predicate`.
+#[throws(ConnectorXOutError)]
+fn filtered_query(source_conn: &SourceConn, query: &str, predicate: &str) -> CXQuery<String> {
+    let query = match source_conn.ty {
+        #[cfg(feature = "src_postgres")]
+        SourceType::Postgres => predicate_partition_query(query, predicate, &PostgreSqlDialect {})?,
+        #[cfg(feature = "src_sqlite")]
+        SourceType::SQLite => predicate_partition_query(query, predicate, &SQLiteDialect {})?,
+        #[cfg(feature = "src_mysql")]
+        SourceType::MySQL => predicate_partition_query(query, predicate, &MySqlDialect {})?,
+        #[cfg(feature = "src_mssql")]
+        SourceType::MsSQL => predicate_partition_query(query, predicate, &MsSqlDialect {})?,
+        #[cfg(feature = "src_oracle")]
+        SourceType::Oracle => predicate_partition_query(query, predicate, &OracleDialect {})?,
+        #[cfg(feature = "src_bigquery")]
+        SourceType::BigQuery => predicate_partition_query(query, predicate, &BigQueryDialect {})?,
+        #[cfg(feature = "src_trino")]
+        SourceType::Trino => predicate_partition_query(query, predicate, &TrinoDialect {})?,
+        _ => unimplemented!("{:?} not implemented!", source_conn.ty),
+    };
+    CXQuery::Wrapped(query)
+}
+
 #[cfg(feature = "src_postgres")]
 #[throws(ConnectorXOutError)]
 fn pg_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
@@ -158,6 +471,12 @@ fn pg_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
         None => config.connect(postgres::NoTls)?,
         Some(tls_conn) => config.connect(tls_conn)?,
     };
+    pg_col_range(&mut client, query, col)?
+}
+
+#[cfg(feature = "src_postgres")]
+#[throws(ConnectorXOutError)]
+fn pg_col_range(client: &mut postgres::Client, query: &str, col: &str) -> (i64, i64) {
     let range_query = get_partition_range_query(query, col, &PostgreSqlDialect {})?;
     let row = client.query_one(range_query.as_str(), &[])?;
 
@@ -208,7 +527,14 @@ fn pg_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
 #[throws(ConnectorXOutError)]
 fn sqlite_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
     // remove the first "sqlite://" manually since url.path is not correct for windows and for relative path
-    let conn = Connection::open(&conn.as_str()[9..])?;
+    let target = sqlite_target(&conn.as_str()[9..])?;
+    let conn = Connection::open_with_flags(&target.path, target.flags)?;
+    sqlite_col_range(&conn, query, col)?
+}
+
+#[cfg(feature = "src_sqlite")]
+#[throws(ConnectorXOutError)]
+fn sqlite_col_range(conn: &Connection, query: &str, col: &str) -> (i64, i64) {
     // SQLite only optimize min max queries when there is only one aggregation
     // https://www.sqlite.org/optoverview.html#minmax
     let (min_query, max_query) = get_partition_range_query_sep(query, col, &SQLiteDialect {})?;
@@ -259,8 +585,16 @@ fn sqlite_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64)
 #[cfg(feature = "src_mysql")]
 #[throws(ConnectorXOutError)]
 fn mysql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
-    let pool = Pool::new(Opts::from_url(conn.as_str()).map_err(MySQLSourceError::MySQLUrlError)?)?;
+    // keeps the client certificate until the connection is open
+    let opts = mysql_opts(conn.as_str())?;
+    let pool = Pool::new(opts.opts.clone())?;
     let mut conn = pool.get_conn()?;
+    mysql_col_range(&mut conn, query, col)?
+}
+
+#[cfg(feature = "src_mysql")]
+#[throws(ConnectorXOutError)]
+fn mysql_col_range<Q: Queryable>(conn: &mut Q, query: &str, col: &str) -> (i64, i64) {
     let range_query = get_partition_range_query(query, col, &MySqlDialect {})?;
     let row: Row = conn
         .query_first(range_query)?
@@ -387,7 +721,7 @@ fn mysql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
 #[cfg(feature = "src_mssql")]
 #[throws(ConnectorXOutError)]
 fn mssql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
-    let rt = Runtime::new().expect("Failed to create runtime");
+    let rt = shared_runtime()?;
     let config = mssql_config(conn)?;
     let tcp = rt.block_on(TcpStream::connect(config.get_addr()))?;
     tcp.set_nodelay(true)?;
@@ -452,6 +786,12 @@ fn mssql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
 #[throws(ConnectorXOutError)]
 fn oracle_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
     let source = OracleSource::new(conn.as_str(), 1, None)?;
+    oracle_col_range(&source, query, col)?
+}
+
+#[cfg(feature = "src_oracle")]
+#[throws(ConnectorXOutError)]
+fn oracle_col_range(source: &OracleSource, query: &str, col: &str) -> (i64, i64) {
     let conn = source.get_conn()?;
     let range_query = get_partition_range_query(query, col, &OracleDialect {})?;
     let row = conn.query_row(range_query.as_str(), &[])?;
@@ -463,7 +803,7 @@ fn oracle_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64)
 #[cfg(feature = "src_bigquery")]
 #[throws(ConnectorXOutError)] // TODO
 fn bigquery_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
-    let rt = Runtime::new().expect("Failed to create runtime");
+    let rt = shared_runtime()?;
     let url = Url::parse(conn.as_str())?;
     let sa_key_path = url.path();
     let client = rt.block_on(gcp_bigquery_client::Client::from_service_account_key_file(
@@ -500,7 +840,7 @@ fn trino_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
 
     use crate::sources::trino::{TrinoDialect, TrinoPartitionQueryResult};
 
-    let rt = Runtime::new().expect("Failed to create runtime");
+    let rt = shared_runtime()?;
 
     let username = match conn.username() {
         "" => "connectorx",
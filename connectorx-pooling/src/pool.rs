@@ -1,3 +1,11 @@
+use crate::diagnosis::Diagnosis;
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+use crate::params::ScalarValue;
 use crate::source_router::SourceConn;
 #[cfg(any(
     feature = "src_mysql",
@@ -6,6 +14,8 @@ use crate::source_router::SourceConn;
     feature = "src_oracle"
 ))]
 use crate::source_router::SourceType;
+#[cfg(feature = "dst_arrow")]
+use crate::{destinations::arrow::ArrowDestination, get_arrow::get_arrow_single};
 use anyhow::Result;
 #[cfg(any(
     feature = "src_mysql",
@@ -13,143 +23,2618 @@ use anyhow::Result;
     feature = "src_sqlite",
     feature = "src_oracle"
 ))]
-use r2d2::Pool;
+use log::warn;
 #[cfg(any(
     feature = "src_mysql",
     feature = "src_postgres",
     feature = "src_sqlite",
     feature = "src_oracle"
 ))]
-use std::sync::Arc;
+use r2d2::{ManageConnection, Pool, PooledConnection};
+use std::path::PathBuf;
 use std::time::Duration;
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+use std::{
+    cell::Cell,
+    collections::{hash_map::RandomState, VecDeque},
+    hash::{BuildHasher, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock, RwLock,
+    },
+    time::Instant,
+};
+use thiserror::Error;
+
+#[cfg(feature = "src_mysql")]
+use crate::params::mysql_params;
+#[cfg(feature = "src_mysql")]
+use crate::sources::mysql::{mysql_opts, MySQLSourceError};
+#[cfg(feature = "src_mysql")]
+use r2d2_mysql::mysql::OptsBuilder;
+#[cfg(feature = "src_mysql")]
+use r2d2_mysql::MySqlConnectionManager;
+#[cfg(feature = "src_mysql")]
+use std::{any::Any, collections::HashMap};
+
+#[cfg(feature = "src_postgres")]
+use postgres::NoTls;
+#[cfg(feature = "src_postgres")]
+use postgres_openssl::MakeTlsConnector;
+#[cfg(feature = "src_postgres")]
+use r2d2_postgres::PostgresConnectionManager;
+
+#[cfg(feature = "src_sqlite")]
+use r2d2_sqlite::SqliteConnectionManager;
+
+#[cfg(feature = "src_oracle")]
+use crate::params::oracle_param;
+#[cfg(feature = "src_oracle")]
+use r2d2_oracle::OracleConnectionManager;
+
+/// Configuration for connection pool construction.
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub max_size: u32,
+    /// Closes connections left idle for longer. `None` keeps them however long they idle.
+    pub idle_timeout: Option<Duration>,
+    /// Closes connections open for longer. `None` keeps them however long they live.
+    pub max_lifetime: Option<Duration>,
+    /// Shortens each connection's `max_lifetime` by a random amount up to this value, so
+    /// connections opened together are not all replaced at once. See [`ConnectionExpiry`].
+    pub max_lifetime_jitter: Option<Duration>,
+    pub connection_timeout: Duration,
+    pub test_on_check_out: bool,
+    pub reuse_policy: ReusePolicy,
+    /// Idle connections r2d2 keeps open, reopening them in the background as they are
+    /// closed. `None` keeps the pool full at `max_size`. [`PoolVariant::trim_idle`] cannot
+    /// go below it.
+    pub min_idle: Option<u32>,
+    /// Tags every connection for server-side monitoring: Postgres `application_name`, the
+    /// MySQL `program_name` connection attribute and the Oracle `MODULE` and `CLIENT_INFO`.
+    /// SQLite ignores it.
+    pub application_name: Option<String>,
+    /// Statement resetting the session state a borrower may have left behind: `DISCARD ALL`
+    /// for Postgres, `RESET ALL` behind a proxy such as pgbouncer that manages the server
+    /// session itself, and `BEGIN DBMS_SESSION.RESET_PACKAGE; END;` for Oracle. MySQL has no
+    /// statement resetting a whole session, so name the settings, e.g. `SET SESSION sql_mode =
+    /// DEFAULT`. SQLite connections keep no server session and need none.
+    ///
+    /// [`PoolGuard`] runs it as the connection goes back to the pool, followed by the pool's
+    /// session setup such as `application_name`. A failing statement is logged and the
+    /// connection returned as is.
+    pub on_return_query: Option<String>,
+    /// Statements run once on every connection the pool opens, after the session setup such
+    /// as `application_name`, e.g. to create temporary functions or tables. Unlike
+    /// pre-execution queries they do not run again when a connection is checked out.
+    pub init_queries: Vec<String>,
+    /// Shared libraries loaded as SQLite extensions into every connection the pool opens,
+    /// before `init_queries`, so those can use them. The other backends ignore it.
+    pub load_extensions: Vec<PathBuf>,
+    /// Fails checkouts fast while the database looks down instead of waiting out
+    /// `connection_timeout` each time. `None` turns the breaker off.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Names the pool in [`PoolVariant::metrics_text`], e.g. the tenant it serves when there
+    /// is one pool per tenant. Unlike `application_name` it never reaches the server.
+    pub label: Option<String>,
+    /// Makes every connection read-only, e.g. for a reporting replica, so the server rejects
+    /// writes: Postgres sets `default_transaction_read_only`, MySQL the session's transaction
+    /// access mode and Oracle the session's `READ_ONLY`, and SQLite opens the database with
+    /// `SQLITE_OPEN_READ_ONLY`. It is set again after `on_return_query`, though a borrower
+    /// can still turn it off for its own session. Oracle only has `READ_ONLY` from 23ai on,
+    /// so building a read-only pool against an older server fails.
+    pub read_only: bool,
+    /// Hard upper bound on the time a [`checkout`] takes. r2d2 keeps retrying a failing
+    /// connect, backing off, for up to `connection_timeout`; past this deadline the checkout
+    /// gives up with [`PoolError::DeadlineExceeded`] wrapping the last error instead, even if
+    /// `connection_timeout` is longer. `None` leaves checkouts bounded by `connection_timeout`.
+    /// r2d2 waits up to half of `connection_timeout` between two attempts to connect, so the
+    /// first checkouts after an outage may still run into the deadline once the database is
+    /// back.
+    pub acquire_deadline: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            idle_timeout: Some(Duration::from_secs(300)), // 5 minutes
+            max_lifetime: Some(Duration::from_secs(1800)), // 30 minutes
+            max_lifetime_jitter: None,
+            connection_timeout: Duration::from_secs(30),
+            test_on_check_out: true,
+            reuse_policy: ReusePolicy::Lifo,
+            min_idle: None,
+            application_name: None,
+            on_return_query: None,
+            init_queries: vec![],
+            load_extensions: vec![],
+            circuit_breaker: None,
+            label: None,
+            read_only: false,
+            acquire_deadline: None,
+        }
+    }
+}
+
+/// Settings of a pool's circuit breaker, see [`PoolConfig::circuit_breaker`].
+///
+/// After `failure_threshold` consecutive failed checkouts the breaker opens and every
+/// checkout fails at once with [`PoolError::CircuitOpen`] for `open_duration`. The next
+/// checkout after that is let through as a trial: the breaker closes again if it succeeds
+/// and stays open for another `open_duration` if it fails. A checkout timing out on an
+/// exhausted pool counts as a failure too, as r2d2 does not tell the two apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: u32,
+    pub open_duration: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// How long the breaker stays open unless configured otherwise.
+    pub const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+    pub fn new(failure_threshold: u32) -> Self {
+        Self {
+            failure_threshold,
+            open_duration: Self::DEFAULT_OPEN_DURATION,
+        }
+    }
+}
+
+/// State of a pool's circuit breaker, as reported by [`PoolVariant::circuit_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Checkouts go through.
+    Closed,
+    /// Checkouts fail at once.
+    Open,
+    /// `open_duration` is over and the next checkout is let through as a trial.
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Lowercase name of the state, e.g. `half_open`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Why [`checkout`] or [`checkout_unchecked`] handed out no connection.
+#[derive(Error, Debug)]
+pub enum PoolError {
+    #[error(transparent)]
+    Checkout(#[from] r2d2::Error),
+
+    #[error("Every connection of the pool stayed in use: {0}")]
+    Saturated(r2d2::Error),
+
+    #[error("Circuit breaker open after {failures} consecutive checkout failures, retrying in {retry_in:?}")]
+    CircuitOpen { failures: u32, retry_in: Duration },
+
+    #[error("Checkout gave up after its deadline of {deadline:?}: {source}")]
+    DeadlineExceeded {
+        deadline: Duration,
+        source: r2d2::Error,
+    },
+
+    #[error("Failed to replace a pooled connection that failed validation: {0}")]
+    Reconnect(String),
+}
+
+impl PoolError {
+    /// Whether the checkout timed out with every connection in use, rather than because no
+    /// connection could be opened. An open circuit breaker counts as the latter.
+    pub fn is_saturated(&self) -> bool {
+        matches!(self, PoolError::Saturated(_))
+    }
+}
+
+/// Order in which idle connections are handed back out by the pool.
+///
+/// r2d2 itself always reuses the most recently returned connection (`Lifo`). `Fifo` is
+/// emulated by [`checkout`], which the MySQL, Postgres, SQLite and Oracle sources use, so
+/// every connection in the pool gets exercised and none sits idle long enough to be closed
+/// by the server. MSSQL, BigQuery and Trino are not pooled and ignore the policy.
+///
+/// A `Fifo` pool queues the connections handed back through [`PoolGuard`] itself, oldest
+/// first, as r2d2 only ever hands out its newest idle connection. r2d2 counts the queued
+/// connections as checked out: [`PoolVariant::state`] adds them back to the idle ones and
+/// [`PoolVariant::trim_idle`] closes them, but r2d2's reaper does not close them for
+/// `idle_timeout` or `max_lifetime`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReusePolicy {
+    Lifo,
+    Fifo,
+}
+
+/// Applies `PoolConfig` settings to an r2d2 builder.
+pub fn configure_builder<M: r2d2::ManageConnection>(
+    builder: r2d2::Builder<M>,
+    config: &PoolConfig,
+) -> r2d2::Builder<M> {
+    builder
+        .max_size(config.max_size)
+        .connection_timeout(config.connection_timeout)
+        .test_on_check_out(config.test_on_check_out)
+        .min_idle(config.min_idle)
+        .idle_timeout(config.idle_timeout)
+        .max_lifetime(config.max_lifetime)
+}
+
+/// An r2d2 pool built by [`build_pool`] or [`ManagedPool::new`], owning the settings and
+/// state r2d2 has no room for. It derefs to the r2d2 pool; [`checkout`] is what honors the
+/// extras.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub struct ManagedPool<M: ManageConnection> {
+    pool: Pool<Managed<M>>,
+    /// The manager r2d2 was given, for the connections a `Fifo` pool keeps out of its sight.
+    manager: Managed<M>,
+    reuse_policy: ReusePolicy,
+    /// The `Returns` of a `Fifo` pool.
+    returns: Option<Mutex<Returns<M>>>,
+    recycle: Option<Recycle>,
+    /// `PoolConfig::on_return_query` and the session setup, as a connection customizer.
+    on_return: Option<Customizer<M>>,
+    events: EventBridge,
+    breaker: Option<CircuitBreaker>,
+    label: Option<String>,
+    acquire_deadline: Option<Duration>,
+    /// The connection the pool was built from by [`PoolVariant::from_source_conn`], which
+    /// [`PoolConnection::query_arrow`](GenericConnection::query_arrow) reads with.
+    source_conn: OnceLock<SourceConn>,
+    /// Looked up by [`PoolVariant::server_version`] the first time it is asked for.
+    server_version: OnceLock<String>,
+    /// Kept for as long as the pool: the client certificate its MySQL connections read on
+    /// connect.
+    #[cfg(feature = "src_mysql")]
+    keepalive: OnceLock<Box<dyn Any + Send + Sync>>,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> ManagedPool<M> {
+    /// A pool of at most `max_size` connections with r2d2's defaults and none of the
+    /// [`PoolConfig`] extras, as a source builds when it is given no pool.
+    pub fn new(manager: M, max_size: u32) -> std::result::Result<Arc<Self>, r2d2::Error> {
+        let events = EventBridge::default();
+        let manager = Managed::new(manager, &events);
+        let pool = Pool::builder()
+            .max_size(max_size)
+            .event_handler(Box::new(events.clone()))
+            .build(manager.clone())?;
+        Ok(Arc::new(Self::bare(pool, manager, events)))
+    }
+
+    fn bare(pool: Pool<Managed<M>>, manager: Managed<M>, events: EventBridge) -> Self {
+        Self {
+            pool,
+            manager,
+            reuse_policy: ReusePolicy::Lifo,
+            returns: None,
+            recycle: None,
+            on_return: None,
+            events,
+            breaker: None,
+            label: None,
+            acquire_deadline: None,
+            source_conn: OnceLock::new(),
+            server_version: OnceLock::new(),
+            #[cfg(feature = "src_mysql")]
+            keepalive: OnceLock::new(),
+        }
+    }
+
+    /// Keeps `value` alive until the pool is dropped.
+    #[cfg(feature = "src_mysql")]
+    pub(crate) fn hold(&self, value: impl Any + Send + Sync) {
+        let _ = self.keepalive.set(Box::new(value));
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> std::ops::Deref for ManagedPool<M> {
+    type Target = Pool<Managed<M>>;
+
+    fn deref(&self) -> &Pool<Managed<M>> {
+        &self.pool
+    }
+}
+
+/// The manager r2d2 is given by a [`ManagedPool`], wrapping the backend's own so that
+/// [`checkout_unchecked`] can skip the `test_on_check_out` validation and [`discard`] can
+/// have r2d2 close a connection.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub struct Managed<M> {
+    inner: Arc<M>,
+    counters: Arc<PoolCounters>,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M> Clone for Managed<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            counters: self.counters.clone(),
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M> Managed<M> {
+    fn new(manager: M, events: &EventBridge) -> Self {
+        Self {
+            inner: Arc::new(manager),
+            counters: events.counters.clone(),
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+thread_local! {
+    /// Set while [`checkout_unchecked`] checks a connection out, which then goes unvalidated.
+    static UNCHECKED: Cell<bool> = const { Cell::new(false) };
+    /// Set while [`discard`] hands a connection back, which r2d2 then closes as broken.
+    static DISCARDING: Cell<bool> = const { Cell::new(false) };
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> ManageConnection for Managed<M> {
+    type Connection = M::Connection;
+    type Error = M::Error;
+
+    fn connect(&self) -> std::result::Result<M::Connection, M::Error> {
+        self.inner.connect()
+    }
+
+    fn is_valid(&self, conn: &mut M::Connection) -> std::result::Result<(), M::Error> {
+        if UNCHECKED.with(Cell::get) {
+            return Ok(());
+        }
+        self.inner.is_valid(conn).map_err(|e| {
+            self.counters
+                .validation_failures
+                .fetch_add(1, Ordering::Relaxed);
+            warn!("closing a pooled connection that failed validation: {}", e);
+            e
+        })
+    }
+
+    fn has_broken(&self, conn: &mut M::Connection) -> bool {
+        DISCARDING.with(Cell::get) || self.inner.has_broken(conn)
+    }
+}
+
+/// Closes `conn` instead of returning it to its pool. r2d2 stops counting it right away and
+/// opens another in its place as `min_idle` asks, whose `max_lifetime` starts afresh.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn discard<M: ManageConnection>(conn: PooledConnection<Managed<M>>) {
+    DISCARDING.with(|discarding| discarding.set(true));
+    drop(conn);
+    DISCARDING.with(|discarding| discarding.set(false));
+}
+
+/// The connections handed back to a `Fifo` pool, kept out of r2d2 in the order they were
+/// returned so that [`checkout`] can take the one idle the longest.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+struct Returns<M: ManageConnection> {
+    queue: VecDeque<PooledConnection<Managed<M>>>,
+    /// Checkouts blocked on r2d2, to which returned connections go straight back instead.
+    waiting: usize,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Debug)]
+struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Debug, Default)]
+struct BreakerState {
+    failures: u32,
+    opened_at: Option<Instant>,
+    /// A half-open trial checkout is under way; other checkouts keep failing meanwhile.
+    trial: bool,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl CircuitBreaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Fails with `CircuitOpen` unless a checkout may go ahead, taking the trial slot if
+    /// the breaker is half-open.
+    fn admit(&self) -> std::result::Result<(), PoolError> {
+        let mut state = self.state.lock().unwrap();
+        let opened_at = match state.opened_at {
+            Some(opened_at) => opened_at,
+            None => return Ok(()),
+        };
+        let open_for = opened_at.elapsed();
+        if open_for < self.config.open_duration || state.trial {
+            return Err(PoolError::CircuitOpen {
+                failures: state.failures,
+                retry_in: self.config.open_duration.saturating_sub(open_for),
+            });
+        }
+        state.trial = true;
+        Ok(())
+    }
+
+    fn record(&self, success: bool) {
+        let mut state = self.state.lock().unwrap();
+        if success {
+            *state = BreakerState::default();
+            return;
+        }
+        state.failures = state.failures.saturating_add(1);
+        if state.trial || state.failures >= self.config.failure_threshold {
+            if state.opened_at.is_none() {
+                warn!(
+                    "circuit breaker opened after {} consecutive checkout failures",
+                    state.failures
+                );
+            }
+            state.opened_at = Some(Instant::now());
+            state.trial = false;
+        }
+    }
+
+    fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if state.trial || opened_at.elapsed() < self.config.open_duration => {
+                CircuitState::Open
+            }
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// Jittered lifetimes enforced by [`checkout`] on top of r2d2's `max_lifetime`, closing
+/// expired connections through [`discard`].
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Clone)]
+struct Recycle {
+    max_lifetime: Duration,
+    jitter: Duration,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl Recycle {
+    fn expiry(&self, now: Instant) -> ConnectionExpiry {
+        // a fresh RandomState is randomly keyed, which is all the randomness needed here
+        let unit = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        let cut = self.jitter.min(self.max_lifetime).mul_f64(unit);
+        ConnectionExpiry {
+            created: now,
+            expires: now + (self.max_lifetime - cut),
+        }
+    }
+}
+
+/// When a connection of a pool built with `max_lifetime_jitter` is due to be replaced,
+/// kept in the connection's r2d2 extensions. r2d2 gives no access to a connection before it
+/// is checked out, so the clock starts when [`checkout`] first hands the connection out;
+/// r2d2's own `max_lifetime`, counted from when the connection was opened, still closes any
+/// connection idle past it.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionExpiry {
+    pub created: Instant,
+    pub expires: Instant,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+/// What happened in a pool, as passed to the handler set with
+/// [`PoolVariant::set_event_handler`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoolEvent {
+    /// A new connection was opened.
+    Connect,
+    /// A connection was checked out after waiting `latency`.
+    Acquire { latency: Duration },
+    /// A connection went back to the pool after being held for `held`.
+    Release { held: Duration },
+    /// A checkout gave up after waiting `waited`.
+    Timeout { waited: Duration },
+    /// A connection was closed, `age` after it was opened.
+    Disconnect { age: Duration },
+    /// The pool was closed while it held `connections` connections. r2d2 reports no
+    /// `Disconnect` for the connections a dropped pool closes, so wrappers closing a pool
+    /// raise this one with [`PoolVariant::emit_event`].
+    Close { connections: u32 },
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl PoolEvent {
+    /// Short name of the event, e.g. `acquire`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PoolEvent::Connect => "connect",
+            PoolEvent::Acquire { .. } => "acquire",
+            PoolEvent::Release { .. } => "release",
+            PoolEvent::Timeout { .. } => "timeout",
+            PoolEvent::Disconnect { .. } => "disconnect",
+            PoolEvent::Close { .. } => "close",
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub type PoolEventHandler = Arc<dyn Fn(PoolEvent) + Send + Sync>;
+
+/// Passes r2d2's events on to the handler currently set on the pool, if any. Installed by
+/// [`build_pool`], so a handler can be set or replaced once the pool is built.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Clone, Default)]
+struct EventBridge {
+    handler: Arc<RwLock<Option<PoolEventHandler>>>,
+    /// Leaves checkouts and check-ins to [`checkout`] and [`PoolGuard`], for a `Fifo` pool
+    /// whose queued connections r2d2 never sees go back.
+    own_checkouts: bool,
+    counters: Arc<PoolCounters>,
+}
+
+/// Running totals of a pool, rendered as counters by [`PoolVariant::metrics_text`].
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Debug, Default)]
+struct PoolCounters {
+    checkouts: AtomicU64,
+    /// The time the checkouts waited for a connection, in nanoseconds.
+    checkout_nanos: AtomicU64,
+    /// Connections that failed the `test_on_check_out` validation.
+    validation_failures: AtomicU64,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl EventBridge {
+    fn emit(&self, event: PoolEvent) {
+        if let PoolEvent::Acquire { latency } = &event {
+            let nanos = latency.as_nanos().min(u64::MAX as u128) as u64;
+            self.counters.checkouts.fetch_add(1, Ordering::Relaxed);
+            self.counters
+                .checkout_nanos
+                .fetch_add(nanos, Ordering::Relaxed);
+        }
+        // cloned out so the handler runs without the lock held
+        let handler = self.handler.read().unwrap().clone();
+        if let Some(handler) = handler {
+            handler(event);
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl std::fmt::Debug for EventBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EventBridge")
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl r2d2::HandleEvent for EventBridge {
+    fn handle_acquire(&self, _event: r2d2::event::AcquireEvent) {
+        self.emit(PoolEvent::Connect);
+    }
+
+    fn handle_release(&self, event: r2d2::event::ReleaseEvent) {
+        self.emit(PoolEvent::Disconnect { age: event.age() });
+    }
+
+    fn handle_checkout(&self, event: r2d2::event::CheckoutEvent) {
+        if !self.own_checkouts {
+            self.emit(PoolEvent::Acquire {
+                latency: event.duration(),
+            });
+        }
+    }
+
+    fn handle_timeout(&self, event: r2d2::event::TimeoutEvent) {
+        self.emit(PoolEvent::Timeout {
+            waited: event.timeout(),
+        });
+    }
+
+    fn handle_checkin(&self, event: r2d2::event::CheckinEvent) {
+        if !self.own_checkouts {
+            self.emit(PoolEvent::Release {
+                held: event.duration(),
+            });
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+/// Statements run on every connection a pool opens, before it is first handed out, as
+/// required by [`PoolConfig::application_name`] and [`PoolConfig::read_only`], followed by the
+/// [`PoolConfig::init_queries`] and any statements added by [`SessionInit::pin`]. Installed as
+/// the pool's r2d2 connection customizer by [`build_pool`]; r2d2 only calls it for a newly
+/// opened connection, so nothing runs again when the connection is checked out.
+#[derive(Debug, Clone, Default)]
+pub struct SessionInit {
+    statements: Vec<String>,
+    init_queries: Vec<String>,
+    pinned: Vec<String>,
+    extensions: Vec<PathBuf>,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl SessionInit {
+    pub fn new(ty: &SourceType, config: &PoolConfig) -> Self {
+        let mut statements = vec![];
+        if let Some(name) = &config.application_name {
+            let name = name.replace('\'', "''");
+            match ty {
+                SourceType::Postgres => {
+                    statements.push(format!("SET application_name = '{}'", name))
+                }
+                SourceType::Oracle => statements.push(format!(
+                    "BEGIN DBMS_APPLICATION_INFO.SET_MODULE('{0}', NULL); \
+                     DBMS_APPLICATION_INFO.SET_CLIENT_INFO('{0}'); END;",
+                    name
+                )),
+                // MySQL only takes the name as a connection attribute, see `mysql_connect_attrs`
+                _ => {}
+            }
+        }
+        if config.read_only {
+            match ty {
+                SourceType::Postgres => {
+                    statements.push("SET default_transaction_read_only = on".to_string())
+                }
+                SourceType::MySQL => {
+                    statements.push("SET SESSION TRANSACTION READ ONLY".to_string())
+                }
+                SourceType::Oracle => {
+                    statements.push("ALTER SESSION SET READ_ONLY = TRUE".to_string())
+                }
+                // SQLite opens the database read-only instead, see `SQLiteTarget::set_read_only`
+                _ => {}
+            }
+        }
+        Self {
+            statements,
+            init_queries: config.init_queries.clone(),
+            pinned: vec![],
+            extensions: match ty {
+                SourceType::SQLite => config.load_extensions.clone(),
+                _ => vec![],
+            },
+        }
+    }
+
+    /// Adds a statement run after the init queries, and again after
+    /// [`PoolConfig::on_return_query`], so neither can undo it, such as the Postgres
+    /// `client_encoding`, see [`crate::sources::postgres::client_encoding`].
+    pub fn pin(mut self, statement: String) -> Self {
+        self.pinned.push(statement);
+        self
+    }
+
+    pub fn statements(&self) -> &[String] {
+        &self.statements
+    }
+
+    /// `query` followed by these statements, which it may have undone, e.g. `DISCARD ALL`
+    /// resetting `application_name`. The init queries are left out, as they only run once
+    /// per connection.
+    fn after(&self, query: &str) -> Self {
+        let mut statements = vec![query.to_string()];
+        statements.extend(self.statements.iter().cloned());
+        Self {
+            statements,
+            pinned: self.pinned.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// The session statements followed by the init queries and the pinned statements.
+    fn all_statements(&self) -> impl Iterator<Item = &String> {
+        self.statements
+            .iter()
+            .chain(&self.init_queries)
+            .chain(&self.pinned)
+    }
+}
+
+#[cfg(feature = "src_postgres")]
+impl r2d2::CustomizeConnection<postgres::Client, postgres::Error> for SessionInit {
+    fn on_acquire(&self, conn: &mut postgres::Client) -> std::result::Result<(), postgres::Error> {
+        for statement in self.all_statements() {
+            conn.batch_execute(statement)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "src_mysql")]
+impl r2d2::CustomizeConnection<r2d2_mysql::mysql::Conn, r2d2_mysql::mysql::Error> for SessionInit {
+    fn on_acquire(
+        &self,
+        conn: &mut r2d2_mysql::mysql::Conn,
+    ) -> std::result::Result<(), r2d2_mysql::mysql::Error> {
+        use r2d2_mysql::mysql::prelude::Queryable;
+        for statement in self.all_statements() {
+            conn.query_drop(statement)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "src_sqlite")]
+impl r2d2::CustomizeConnection<rusqlite::Connection, rusqlite::Error> for SessionInit {
+    fn on_acquire(
+        &self,
+        conn: &mut rusqlite::Connection,
+    ) -> std::result::Result<(), rusqlite::Error> {
+        for extension in &self.extensions {
+            // loading runs the library's code, which the pool's owner chose to trust
+            unsafe {
+                let _guard = rusqlite::LoadExtensionGuard::new(conn)?;
+                conn.load_extension(extension, None)?;
+            }
+        }
+        for statement in self.all_statements() {
+            conn.execute_batch(statement)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "src_oracle")]
+impl r2d2::CustomizeConnection<oracle::Connection, oracle::Error> for SessionInit {
+    fn on_acquire(&self, conn: &mut oracle::Connection) -> std::result::Result<(), oracle::Error> {
+        for statement in self.all_statements() {
+            conn.execute(statement, &[])?;
+        }
+        Ok(())
+    }
+}
+
+/// Connection attributes announcing [`PoolConfig::application_name`] as the MySQL
+/// `program_name`, visible in `performance_schema.session_connect_attrs`.
+#[cfg(feature = "src_mysql")]
+pub fn mysql_connect_attrs(config: &PoolConfig) -> Option<HashMap<String, String>> {
+    config
+        .application_name
+        .as_ref()
+        .map(|name| HashMap::from([("program_name".to_string(), name.clone())]))
+}
+
+/// Builds a pool of `manager`'s connections configured by `config`.
+///
+/// r2d2 validates the connections it hands out when `test_on_check_out` is set, except to
+/// [`checkout_unchecked`], and closes the ones failing. With a jitter, [`checkout`] closes
+/// connections before r2d2's `max_lifetime` is up.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub fn build_pool<M: ManageConnection>(
+    manager: M,
+    config: &PoolConfig,
+    session: SessionInit,
+) -> Result<Arc<ManagedPool<M>>>
+where
+    SessionInit: r2d2::CustomizeConnection<M::Connection, M::Error>,
+{
+    let recycle = match (config.max_lifetime, config.max_lifetime_jitter) {
+        (Some(max_lifetime), Some(jitter)) if !jitter.is_zero() => Some(Recycle {
+            max_lifetime,
+            jitter,
+        }),
+        _ => None,
+    };
+    let fifo = config.reuse_policy == ReusePolicy::Fifo;
+    let events = EventBridge {
+        own_checkouts: fifo,
+        ..EventBridge::default()
+    };
+    let manager = Managed::new(manager, &events);
+    let pool = configure_builder(Pool::builder(), config)
+        .connection_customizer(customizer::<M>(&session))
+        .event_handler(Box::new(events.clone()))
+        .build(manager.clone())?;
+
+    Ok(Arc::new(ManagedPool {
+        reuse_policy: config.reuse_policy,
+        returns: fifo.then(|| {
+            Mutex::new(Returns {
+                queue: VecDeque::new(),
+                waiting: 0,
+            })
+        }),
+        recycle,
+        on_return: config
+            .on_return_query
+            .as_deref()
+            .map(|query| customizer::<M>(&session.after(query))),
+        breaker: config.circuit_breaker.map(CircuitBreaker::new),
+        label: config.label.clone(),
+        acquire_deadline: config.acquire_deadline,
+        ..ManagedPool::bare(pool, manager, events)
+    }))
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+type Customizer<M> = Box<
+    dyn r2d2::CustomizeConnection<
+        <M as ManageConnection>::Connection,
+        <M as ManageConnection>::Error,
+    >,
+>;
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn customizer<M: ManageConnection>(session: &SessionInit) -> Customizer<M>
+where
+    SessionInit: r2d2::CustomizeConnection<M::Connection, M::Error>,
+{
+    Box::new(session.clone())
+}
+
+/// The reuse policy `pool` was built with. Pools built by [`ManagedPool::new`] are always
+/// `Lifo`.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub fn reuse_policy<M: ManageConnection>(pool: &ManagedPool<M>) -> ReusePolicy {
+    pool.reuse_policy
+}
+
+/// The state of `pool`'s circuit breaker, `None` if it was built without one.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub fn circuit_state<M: ManageConnection>(pool: &ManagedPool<M>) -> Option<CircuitState> {
+    pool.breaker.as_ref().map(CircuitBreaker::state)
+}
+
+/// Runs `get` unless the pool's circuit breaker is open, and counts its outcome against the
+/// breaker.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn guarded<M: ManageConnection>(
+    pool: &ManagedPool<M>,
+    get: impl FnOnce() -> std::result::Result<PooledConnection<Managed<M>>, PoolError>,
+) -> std::result::Result<PooledConnection<Managed<M>>, PoolError> {
+    let breaker = match &pool.breaker {
+        Some(breaker) => breaker,
+        None => return get(),
+    };
+    breaker.admit()?;
+    let conn = get();
+    breaker.record(conn.is_ok());
+    conn
+}
+
+/// Checks a connection out of `pool`, honoring the pool's [`ReusePolicy`]. A connection
+/// failing the `test_on_check_out` validation or past its jittered lifetime is closed and
+/// another one checked out instead. Fails at once while the pool's circuit breaker is open,
+/// and once `PoolConfig::acquire_deadline` has passed.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub fn checkout<M: ManageConnection>(
+    pool: &Arc<ManagedPool<M>>,
+) -> std::result::Result<PoolGuard<M>, PoolError> {
+    let deadline = Deadline::of(pool);
+    let conn = guarded(pool, || checkout_unexpired(pool, deadline))
+        .map_err(|err| Deadline::check(deadline, err))?;
+    Ok(PoolGuard::new(conn, pool.clone()))
+}
+
+/// Names of the databases attached to a SQLite connection, as `PRAGMA database_list` orders them.
+#[cfg(feature = "src_sqlite")]
+fn sqlite_databases(conn: &rusqlite::Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA database_list")?;
+    let names = stmt.query_map([], |r| r.get::<_, String>(1))?;
+    names.collect()
+}
+
+/// Checks connections out until one is within its jittered lifetime, closing the others.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn checkout_unexpired<M: ManageConnection>(
+    pool: &ManagedPool<M>,
+    deadline: Option<Deadline>,
+) -> std::result::Result<PooledConnection<Managed<M>>, PoolError> {
+    loop {
+        let mut conn = checkout_by_policy(pool, deadline)?;
+        match expired(pool, &mut conn) {
+            true => discard(conn),
+            false => return Ok(conn),
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn checkout_by_policy<M: ManageConnection>(
+    pool: &ManagedPool<M>,
+    deadline: Option<Deadline>,
+) -> std::result::Result<PooledConnection<Managed<M>>, PoolError> {
+    let returns = match &pool.returns {
+        Some(returns) => returns,
+        None => return Deadline::get(deadline, pool),
+    };
+    let start = Instant::now();
+    let conn = loop {
+        // what r2d2 holds idle is either new or was put back while checkouts were waiting,
+        // before anything now queued was returned
+        if let Some(conn) = pool.try_get() {
+            break Ok(conn);
+        }
+        let mut queued = returns.lock().unwrap();
+        let mut conn = match queued.queue.pop_front() {
+            Some(conn) => conn,
+            None => {
+                queued.waiting += 1;
+                drop(queued);
+                let conn = Deadline::get(deadline, pool);
+                returns.lock().unwrap().waiting -= 1;
+                break conn;
+            }
+        };
+        drop(queued);
+        // r2d2 only validates the connections it hands out itself
+        match pool.test_on_check_out() && pool.manager.is_valid(&mut conn).is_err() {
+            true => discard(conn),
+            false => break Ok(conn),
+        }
+    }?;
+    pool.events.emit(PoolEvent::Acquire {
+        latency: start.elapsed(),
+    });
+    Ok(conn)
+}
+
+/// When a checkout of a pool built with `acquire_deadline` has to give up.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Debug, Clone, Copy)]
+struct Deadline {
+    limit: Duration,
+    at: Instant,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl Deadline {
+    /// The deadline of a checkout starting now, if its pool has one.
+    fn of<M: ManageConnection>(pool: &ManagedPool<M>) -> Option<Self> {
+        let limit = pool.acquire_deadline?;
+        Some(Self {
+            limit,
+            at: Instant::now() + limit,
+        })
+    }
+
+    /// Waits for a connection of `pool` no longer than `connection_timeout` and `deadline`.
+    /// Timing out with as many connections open as the pool may hold, all of them in use,
+    /// fails with `Saturated`, and with fewer, which r2d2 could not add to, with `Checkout`.
+    fn get<M: ManageConnection>(
+        deadline: Option<Self>,
+        pool: &ManagedPool<M>,
+    ) -> std::result::Result<PooledConnection<Managed<M>>, PoolError> {
+        let conn = match deadline {
+            Some(deadline) => {
+                let left = deadline.at.saturating_duration_since(Instant::now());
+                pool.get_timeout(left.min(pool.connection_timeout()))
+            }
+            None => pool.get(),
+        };
+        conn.map_err(|e| match pool.state().connections >= pool.max_size() {
+            true => PoolError::Saturated(e),
+            false => PoolError::Checkout(e),
+        })
+    }
+
+    /// `e` as `DeadlineExceeded` if the checkout it ended ran into `deadline` unable to
+    /// connect. A saturated pool stays `Saturated`.
+    fn check(deadline: Option<Self>, e: PoolError) -> PoolError {
+        match (deadline, e) {
+            (Some(deadline), PoolError::Checkout(source)) if Instant::now() >= deadline.at => {
+                PoolError::DeadlineExceeded {
+                    deadline: deadline.limit,
+                    source,
+                }
+            }
+            (_, e) => e,
+        }
+    }
+}
+
+/// Whether the [`ConnectionExpiry`] of `conn` has passed, starting its clock if it has none
+/// yet.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn expired<M: ManageConnection>(
+    pool: &ManagedPool<M>,
+    conn: &mut PooledConnection<Managed<M>>,
+) -> bool {
+    let recycle = match &pool.recycle {
+        Some(recycle) => recycle,
+        None => return false,
+    };
+    let now = Instant::now();
+    match PooledConnection::extensions(conn).get::<ConnectionExpiry>() {
+        Some(expiry) => expiry.expires <= now,
+        None => {
+            PooledConnection::extensions_mut(conn).insert(recycle.expiry(now));
+            false
+        }
+    }
+}
+
+/// Runs the pool's `on_return_query` on `conn` as it goes back to the pool.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn reset_returned<M: ManageConnection>(pool: &ManagedPool<M>, conn: &mut M::Connection) {
+    if let Some(reset) = &pool.on_return {
+        if let Err(e) = reset.on_acquire(conn) {
+            warn!("failed to reset a returned pooled connection: {}", e);
+        }
+    }
+}
+
+/// Checks a connection out of `pool` like [`checkout`], but without running the
+/// `test_on_check_out` validation query on it.
+///
+/// The connection may have been closed by the server or the network since it was last used,
+/// and the first statement run on it will fail in that case rather than being retried on a
+/// fresh connection. Only use this for tight loops over a connection you know is healthy.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub fn checkout_unchecked<M: ManageConnection>(
+    pool: &Arc<ManagedPool<M>>,
+) -> std::result::Result<PoolGuard<M>, PoolError> {
+    UNCHECKED.with(|unchecked| unchecked.set(true));
+    let conn = checkout(pool);
+    UNCHECKED.with(|unchecked| unchecked.set(false));
+    conn
+}
+
+/// Closes idle connections of `pool` until at most `keep` are left, starting with those
+/// queued by a `Fifo` pool, returning how many were closed.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn trim_idle<M: ManageConnection>(pool: &ManagedPool<M>, keep: u32) -> Result<u32> {
+    // r2d2 reopens every closed connection right away while fewer than `min_idle` are idle
+    let min_idle = pool.min_idle().unwrap_or_else(|| pool.max_size());
+    if keep < min_idle {
+        anyhow::bail!(
+            "cannot trim a pool to {} idle connections while it keeps {} open, as \
+             PoolConfig::min_idle asks",
+            keep,
+            min_idle
+        );
+    }
+    let mut excess = vec![];
+    let mut queued = 0;
+    if let Some(returns) = &pool.returns {
+        let mut returns = returns.lock().unwrap();
+        while pool.state().idle_connections + returns.queue.len() as u32 > keep {
+            match returns.queue.pop_back() {
+                Some(conn) => excess.push(conn),
+                None => break,
+            }
+        }
+        queued = returns.queue.len() as u32;
+    }
+    while pool.state().idle_connections + queued > keep {
+        match pool.try_get() {
+            Some(conn) => excess.push(conn),
+            None => break,
+        }
+    }
+    let trimmed = excess.len() as u32;
+    excess.into_iter().for_each(discard);
+    Ok(trimmed)
+}
+
+/// Hands the connections a `Fifo` pool has queued back to r2d2, which closes them once
+/// `idle_timeout` or `max_lifetime` is up, or along with the pool.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+fn flush_returns<M: ManageConnection>(pool: &ManagedPool<M>) -> u32 {
+    let queue = match &pool.returns {
+        Some(returns) => std::mem::take(&mut returns.lock().unwrap().queue),
+        None => return 0,
+    };
+    // dropped outside the lock, as r2d2 takes its own to put each one back
+    queue.len() as u32
+}
+
+/// A connection checked out with [`checkout`], which goes back to its pool when dropped,
+/// after `PoolConfig::on_return_query` has reset its session. A `Fifo` pool queues it behind
+/// the connections returned before it.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub struct PoolGuard<M: ManageConnection> {
+    conn: Option<PooledConnection<Managed<M>>>,
+    pool: Arc<ManagedPool<M>>,
+    checkout: Instant,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> PoolGuard<M> {
+    fn new(conn: PooledConnection<Managed<M>>, pool: Arc<ManagedPool<M>>) -> Self {
+        Self {
+            conn: Some(conn),
+            pool,
+            checkout: Instant::now(),
+        }
+    }
+
+    /// Values kept alongside the connection in the pool, which outlive this checkout.
+    pub fn extensions(this: &Self) -> &r2d2::Extensions {
+        PooledConnection::extensions(this.conn.as_ref().unwrap())
+    }
+
+    pub fn extensions_mut(this: &mut Self) -> &mut r2d2::Extensions {
+        PooledConnection::extensions_mut(this.conn.as_mut().unwrap())
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> std::fmt::Debug for PoolGuard<M>
+where
+    M::Connection: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.conn.as_ref().unwrap().fmt(f)
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> std::ops::Deref for PoolGuard<M> {
+    type Target = M::Connection;
+
+    fn deref(&self) -> &M::Connection {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> std::ops::DerefMut for PoolGuard<M> {
+    fn deref_mut(&mut self) -> &mut M::Connection {
+        self.conn.as_mut().unwrap()
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> Drop for PoolGuard<M> {
+    fn drop(&mut self) {
+        let mut conn = match self.conn.take() {
+            Some(conn) => conn,
+            None => return,
+        };
+        reset_returned(&self.pool, &mut conn);
+        let returns = match &self.pool.returns {
+            Some(returns) => returns,
+            None => return,
+        };
+        self.pool.events.emit(PoolEvent::Release {
+            held: self.checkout.elapsed(),
+        });
+        // r2d2 discards a broken connection as it is put back, which a queued one never is
+        if self.pool.manager.has_broken(&mut conn) {
+            return;
+        }
+        let mut returns = returns.lock().unwrap();
+        if returns.waiting == 0 {
+            returns.queue.push_back(conn);
+        }
+    }
+}
+
+/// A connection checked out of a pool and kept aside, e.g. for the length of a transaction,
+/// so that sources can borrow it for a read (see [`PinnedConnection::lease`]) instead of
+/// checking out another one. It goes back to its pool once the last clone is dropped.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub struct PinnedConnection<M: ManageConnection>(Arc<Mutex<Option<PoolGuard<M>>>>);
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> Clone for PinnedConnection<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> PinnedConnection<M> {
+    pub fn new(conn: PoolGuard<M>) -> Self {
+        Self(Arc::new(Mutex::new(Some(conn))))
+    }
+
+    /// Lends the connection out until the returned guard is dropped. Fails while it is
+    /// already lent, so a pinned connection serves one partition at a time.
+    pub fn lease(&self) -> Result<LeasedConnection<M>> {
+        let conn = self.0.lock().unwrap().take().ok_or_else(|| {
+            anyhow::anyhow!("the pinned connection is already in use by another partition")
+        })?;
+        Ok(LeasedConnection {
+            conn: Some(conn),
+            home: Some(self.0.clone()),
+        })
+    }
+}
+
+/// A connection used by a source partition: either checked out of the pool, or lent by a
+/// [`PinnedConnection`] and handed back to it on drop.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub struct LeasedConnection<M: ManageConnection> {
+    conn: Option<PoolGuard<M>>,
+    home: Option<Arc<Mutex<Option<PoolGuard<M>>>>>,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> From<PoolGuard<M>> for LeasedConnection<M> {
+    fn from(conn: PoolGuard<M>) -> Self {
+        Self {
+            conn: Some(conn),
+            home: None,
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> std::ops::Deref for LeasedConnection<M> {
+    type Target = M::Connection;
+
+    fn deref(&self) -> &M::Connection {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> std::ops::DerefMut for LeasedConnection<M> {
+    fn deref_mut(&mut self) -> &mut M::Connection {
+        self.conn.as_mut().unwrap()
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> LeasedConnection<M> {
+    /// Whether the connection is lent by a [`PinnedConnection`] rather than checked out of
+    /// the pool.
+    pub fn is_pinned(&self) -> bool {
+        self.home.is_some()
+    }
+
+    /// Values kept alongside the connection in the pool, which outlive this checkout, such
+    /// as the statements prepared on it.
+    pub fn extensions_mut(&mut self) -> &mut r2d2::Extensions {
+        PoolGuard::extensions_mut(self.conn.as_mut().unwrap())
+    }
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> Drop for LeasedConnection<M> {
+    fn drop(&mut self) {
+        if let Some(home) = &self.home {
+            *home.lock().unwrap() = self.conn.take();
+        }
+    }
+}
+
+/// How many connections a pool manages and how many of them are idle, as returned by
+/// [`PoolVariant::state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolState {
+    pub connections: u32,
+    pub idle_connections: u32,
+}
+
+/// What a pool's backend supports, as returned by [`PoolVariant::capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Multi-statement transactions through [`crate::transaction::Transaction`].
+    pub supports_transactions: bool,
+    /// Queries with bound parameters.
+    pub supports_prepared: bool,
+    /// Whole-table reads through `COPY`, see `get_arrow_copy`.
+    pub supports_copy: bool,
+    /// Reads split into partitions running on separate connections.
+    pub supports_partition: bool,
+    /// Connections encrypted with TLS.
+    pub supports_tls: bool,
+}
+
+/// A unified pool type covering all pool-supported database backends.
+/// Arms are feature-gated so only compiled when the relevant source is enabled.
+#[derive(Clone)]
+pub enum PoolVariant {
+    #[cfg(feature = "src_mysql")]
+    MySQL(Arc<ManagedPool<MySqlConnectionManager>>),
+    #[cfg(feature = "src_postgres")]
+    PostgresNoTls(Arc<ManagedPool<PostgresConnectionManager<NoTls>>>),
+    #[cfg(feature = "src_postgres")]
+    PostgresTls(Arc<ManagedPool<PostgresConnectionManager<MakeTlsConnector>>>),
+    #[cfg(feature = "src_sqlite")]
+    SQLite(Arc<ManagedPool<SqliteConnectionManager>>),
+    #[cfg(feature = "src_oracle")]
+    Oracle(Arc<ManagedPool<OracleConnectionManager>>),
+}
+
+impl PoolVariant {
+    /// Builds a pool from a parsed connection, returning `None` for backends that do not
+    /// support connection pooling (MSSQL, BigQuery, Trino). The r2d2 backends connect on the
+    /// calling thread and never create a tokio runtime; see [`Self::is_async`].
+    pub fn from_source_conn(source_conn: &SourceConn, config: &PoolConfig) -> Result<Option<Self>> {
+        let pool = Self::open(source_conn, config)?;
+        #[cfg(any(
+            feature = "src_mysql",
+            feature = "src_postgres",
+            feature = "src_sqlite",
+            feature = "src_oracle"
+        ))]
+        if let Some(pool) = &pool {
+            pool.set_source_conn(source_conn);
+        }
+        Ok(pool)
+    }
+
+    #[allow(unused_variables)]
+    fn open(source_conn: &SourceConn, config: &PoolConfig) -> Result<Option<Self>> {
+        match source_conn.ty {
+            #[cfg(feature = "src_mysql")]
+            SourceType::MySQL => {
+                let mysql_opts = mysql_opts(source_conn.conn.as_str())?;
+                let opts = OptsBuilder::from_opts(mysql_opts.opts)
+                    .connect_attrs(mysql_connect_attrs(config));
+                let pool = build_pool(
+                    MySqlConnectionManager::new(opts),
+                    config,
+                    SessionInit::new(&source_conn.ty, config),
+                )
+                .map_err(|e| {
+                    // the driver's wording does not say which setting asked for TLS
+                    match e
+                        .to_string()
+                        .contains("server does not have this capability")
+                    {
+                        true => MySQLSourceError::TlsNotSupported.into(),
+                        false => e,
+                    }
+                })?;
+                if let Some(identity) = mysql_opts.identity {
+                    pool.hold(identity);
+                }
+                Ok(Some(PoolVariant::MySQL(pool)))
+            }
+            #[cfg(feature = "src_postgres")]
+            SourceType::Postgres => {
+                use crate::sources::postgres::{client_encoding, rewrite_tls_args};
+                let (pg_config, tls) = rewrite_tls_args(&source_conn.conn)?;
+                let mut session = SessionInit::new(&source_conn.ty, config);
+                if let Some(encoding) = client_encoding(&source_conn.conn)? {
+                    session = session.pin(format!("SET client_encoding = '{}'", encoding));
+                }
+                match tls {
+                    Some(tls_conn) => {
+                        let pool = build_pool(
+                            PostgresConnectionManager::new(pg_config, tls_conn),
+                            config,
+                            session,
+                        )?;
+                        Ok(Some(PoolVariant::PostgresTls(pool)))
+                    }
+                    None => {
+                        let pool = build_pool(
+                            PostgresConnectionManager::new(pg_config, NoTls),
+                            config,
+                            session,
+                        )?;
+                        Ok(Some(PoolVariant::PostgresNoTls(pool)))
+                    }
+                }
+            }
+            #[cfg(feature = "src_sqlite")]
+            SourceType::SQLite => {
+                use crate::sources::sqlite::{sqlite_manager, sqlite_target};
+                // Strip the "sqlite://" prefix (9 chars) the same way get_arrow.rs does.
+                let mut target = sqlite_target(&source_conn.conn.as_str()[9..])?;
+                if config.read_only {
+                    target.set_read_only();
+                }
+                let pool = build_pool(
+                    sqlite_manager(&target)?,
+                    config,
+                    SessionInit::new(&source_conn.ty, config),
+                )?;
+                Ok(Some(PoolVariant::SQLite(pool)))
+            }
+            #[cfg(feature = "src_oracle")]
+            SourceType::Oracle => {
+                use crate::sources::oracle::{
+                    connect_oracle, oracle_array_size, oracle_max_lob_size, OracleSourceError,
+                };
+                // Reject a bad arraysize or max_lob_size up front rather than on the first query.
+                oracle_array_size(&source_conn.conn)?;
+                oracle_max_lob_size(&source_conn.conn)?;
+                let connector = connect_oracle(&source_conn.conn).map_err(anyhow::Error::from)?;
+                if config.read_only {
+                    // otherwise every connection fails on `ALTER SESSION SET READ_ONLY`, and
+                    // the build only reports it as a timeout
+                    let (version, _) = connector.connect()?.server_version()?;
+                    if version.major() < 23 {
+                        return Err(OracleSourceError::ReadOnlyUnsupported(version).into());
+                    }
+                }
+                let pool = build_pool(
+                    OracleConnectionManager::from_connector(connector),
+                    config,
+                    SessionInit::new(&source_conn.ty, config),
+                )?;
+                Ok(Some(PoolVariant::Oracle(pool)))
+            }
+            // MSSQL, BigQuery, Trino, and any other type: no pool support. An async arm takes
+            // `runtime::shared_runtime()` inside the arm itself, so that only pools which
+            // need a runtime create one, and reports `true` from `is_async`.
+            _ => Ok(None),
+        }
+    }
+
+    /// Opens a single connection the way [`Self::from_source_conn`] would and classifies
+    /// the failure, if any. Unlike building a pool, which retries until
+    /// `connection_timeout` and then reports the last error as a string, this makes one
+    /// attempt, bounded by `config.connection_timeout` where the driver supports it.
+    #[allow(unused_variables)]
+    pub fn diagnose(source_conn: &SourceConn, config: &PoolConfig) -> Diagnosis {
+        let attempt: Result<()> = (|| {
+            match source_conn.ty {
+                #[cfg(feature = "src_mysql")]
+                SourceType::MySQL => {
+                    // keeps the client certificate until the connection is open
+                    let mysql_opts = mysql_opts(source_conn.conn.as_str())?;
+                    let opts = OptsBuilder::from_opts(mysql_opts.opts.clone())
+                        .tcp_connect_timeout(Some(config.connection_timeout));
+                    MySqlConnectionManager::new(opts).connect()?;
+                }
+                #[cfg(feature = "src_postgres")]
+                SourceType::Postgres => {
+                    use crate::sources::postgres::rewrite_tls_args;
+                    let (mut pg_config, tls) = rewrite_tls_args(&source_conn.conn)?;
+                    pg_config.connect_timeout(config.connection_timeout);
+                    match tls {
+                        Some(tls_conn) => {
+                            PostgresConnectionManager::new(pg_config, tls_conn).connect()?;
+                        }
+                        None => {
+                            PostgresConnectionManager::new(pg_config, NoTls).connect()?;
+                        }
+                    }
+                }
+                #[cfg(feature = "src_sqlite")]
+                SourceType::SQLite => {
+                    use crate::sources::sqlite::{sqlite_manager, sqlite_target};
+                    let target = sqlite_target(&source_conn.conn.as_str()[9..])?;
+                    let conn = sqlite_manager(&target)?.connect()?;
+                    // opening is lazy: touch the file so a non-database is reported here
+                    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))?;
+                }
+                #[cfg(feature = "src_oracle")]
+                SourceType::Oracle => {
+                    use crate::sources::oracle::connect_oracle;
+                    let connector =
+                        connect_oracle(&source_conn.conn).map_err(anyhow::Error::from)?;
+                    OracleConnectionManager::from_connector(connector).connect()?;
+                }
+                _ => anyhow::bail!(
+                    "connection pooling is not supported for {:?}",
+                    source_conn.ty
+                ),
+            }
+            Ok(())
+        })();
+        match attempt {
+            Ok(()) => Diagnosis::Ok,
+            Err(e) => Diagnosis::classify(&source_conn.ty, &format!("{:#}", e)),
+        }
+    }
+
+    /// Snapshot of the underlying r2d2 pool state, with the connections a `Fifo` pool has
+    /// queued counted as idle.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn state(&self) -> PoolState {
+        fn state<M: ManageConnection>(pool: &ManagedPool<M>) -> PoolState {
+            let state = pool.state();
+            let queued = pool
+                .returns
+                .as_ref()
+                .map_or(0, |returns| returns.lock().unwrap().queue.len() as u32);
+            PoolState {
+                connections: state.connections,
+                idle_connections: state.idle_connections + queued,
+            }
+        }
+
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => state(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => state(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => state(p),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => state(p),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => state(p),
+        }
+    }
+
+    /// Maximum number of connections the pool will maintain.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn max_size(&self) -> u32 {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => p.max_size(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => p.max_size(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => p.max_size(),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => p.max_size(),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => p.max_size(),
+        }
+    }
+
+    /// How long the pool keeps an idle connection, see [`PoolConfig::idle_timeout`].
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => p.idle_timeout(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => p.idle_timeout(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => p.idle_timeout(),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => p.idle_timeout(),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => p.idle_timeout(),
+        }
+    }
+
+    /// How long the pool keeps a connection open, see [`PoolConfig::max_lifetime`].
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn max_lifetime(&self) -> Option<Duration> {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => p.max_lifetime(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => p.max_lifetime(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => p.max_lifetime(),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => p.max_lifetime(),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => p.max_lifetime(),
+        }
+    }
+
+    /// State of the pool's circuit breaker, `None` if [`PoolConfig::circuit_breaker`] is
+    /// off.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => circuit_state(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => circuit_state(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => circuit_state(p),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => circuit_state(p),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => circuit_state(p),
+        }
+    }
+
+    /// The pool's [`PoolConfig::label`], if it was built with one.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn label(&self) -> Option<String> {
+        fn label<M: ManageConnection>(pool: &ManagedPool<M>) -> Option<String> {
+            pool.label.clone()
+        }
+
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => label(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => label(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => label(p),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => label(p),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => label(p),
+        }
+    }
+
+    /// Runs `sql` on a connection acquired through [`checkout_unchecked`], skipping the
+    /// `test_on_check_out` validation round-trip. See there for the risks.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn execute_unchecked(&self, sql: &str) -> Result<()> {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => {
+                use r2d2_mysql::mysql::prelude::Queryable;
+                checkout_unchecked(p)?.query_drop(sql)?;
+            }
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => checkout_unchecked(p)?.batch_execute(sql)?,
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => checkout_unchecked(p)?.batch_execute(sql)?,
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => checkout_unchecked(p)?.execute_batch(sql)?,
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => {
+                let conn = checkout_unchecked(p)?;
+                conn.execute(sql, &[])?;
+                conn.commit()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the parameterized statement `sql` once per row of `rows` on one connection, inside
+    /// a single transaction that is committed at the end. The statement is prepared once and
+    /// uses the backend's placeholders (see [`crate::params`]). On any failure the transaction
+    /// is rolled back and nothing is written. Returns the total number of rows affected.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn execute_many(&self, sql: &str, rows: &[Vec<ScalarValue>]) -> Result<u64> {
+        #[cfg(feature = "src_postgres")]
+        fn postgres_execute_many(
+            conn: &mut postgres::Client,
+            sql: &str,
+            rows: &[Vec<ScalarValue>],
+        ) -> Result<u64> {
+            // dropping the transaction without committing rolls it back
+            let mut txn = conn.transaction()?;
+            let stmt = txn.prepare(sql)?;
+            let mut affected = 0;
+            for row in rows {
+                let params: Vec<&(dyn postgres::types::ToSql + Sync)> =
+                    row.iter().map(|v| v as _).collect();
+                affected += txn.execute(&stmt, &params)?;
+            }
+            txn.commit()?;
+            Ok(affected)
+        }
+
+        Ok(match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => {
+                use r2d2_mysql::mysql::{prelude::Queryable, TxOpts};
+                let mut conn = checkout(p)?;
+                let mut txn = conn.start_transaction(TxOpts::default())?;
+                let stmt = txn.prep(sql)?;
+                let mut affected = 0;
+                for row in rows {
+                    txn.exec_drop(&stmt, mysql_params(row))?;
+                    affected += txn.affected_rows();
+                }
+                txn.commit()?;
+                affected
+            }
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => postgres_execute_many(&mut *checkout(p)?, sql, rows)?,
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => postgres_execute_many(&mut *checkout(p)?, sql, rows)?,
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => {
+                let mut conn = checkout(p)?;
+                let txn = conn.transaction()?;
+                let mut affected = 0;
+                {
+                    let mut stmt = txn.prepare(sql)?;
+                    for row in rows {
+                        affected += stmt.execute(rusqlite::params_from_iter(row))? as u64;
+                    }
+                }
+                txn.commit()?;
+                affected
+            }
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => {
+                let conn = checkout(p)?;
+                let run = || -> Result<u64> {
+                    let mut stmt = conn.statement(sql).build()?;
+                    let mut affected = 0;
+                    for row in rows {
+                        let binds: Vec<Box<dyn oracle::sql_type::ToSql>> =
+                            row.iter().map(oracle_param).collect();
+                        let binds: Vec<&dyn oracle::sql_type::ToSql> =
+                            binds.iter().map(|b| b.as_ref()).collect();
+                        stmt.execute(&binds)?;
+                        affected += stmt.row_count()?;
+                    }
+                    conn.commit()?;
+                    Ok(affected)
+                };
+                match run() {
+                    Ok(affected) => affected,
+                    Err(e) => {
+                        let _ = conn.rollback();
+                        return Err(e);
+                    }
+                }
+            }
+        })
+    }
+
+    /// The raw version string reported by the server behind the pool. It is queried on a
+    /// pooled connection the first time and cached for the pool's lifetime afterwards.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn server_version(&self) -> Result<String> {
+        let cache = match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => &p.server_version,
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => &p.server_version,
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => &p.server_version,
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => &p.server_version,
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => &p.server_version,
+        };
+        if let Some(version) = cache.get() {
+            return Ok(version.clone());
+        }
+
+        let version: String = match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => {
+                use r2d2_mysql::mysql::prelude::Queryable;
+                checkout(p)?
+                    .query_first("SELECT VERSION()")?
+                    .ok_or_else(|| anyhow::anyhow!("SELECT VERSION() returned no row"))?
+            }
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => {
+                checkout(p)?.query_one("SHOW server_version", &[])?.get(0)
+            }
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => {
+                checkout(p)?.query_one("SHOW server_version", &[])?.get(0)
+            }
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => {
+                checkout(p)?.query_row("select sqlite_version()", [], |r| r.get(0))?
+            }
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => {
+                checkout(p)?.query_row_as("SELECT banner FROM v$version WHERE ROWNUM = 1", &[])?
+            }
+        };
+
+        Ok(cache.get_or_init(|| version).clone())
+    }
+
+    /// The tables reachable through the pool as `schema.table`, sorted, optionally only those
+    /// of `schema`. Views and the backend's own system schemas are left out. For SQLite the
+    /// schemas are the attached databases, e.g. `main`.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn list_tables(&self, schema: Option<&str>) -> Result<Vec<String>> {
+        const POSTGRES_TABLES: &str = "SELECT table_schema || '.' || table_name \
+            FROM information_schema.tables WHERE table_type = 'BASE TABLE' \
+            AND table_schema NOT IN ('pg_catalog', 'information_schema') \
+            AND ($1::text IS NULL OR table_schema = $1) ORDER BY 1";
+
+        Ok(match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => {
+                use r2d2_mysql::mysql::prelude::Queryable;
+                checkout(p)?.exec(
+                    "SELECT CONCAT(table_schema, '.', table_name) \
+                     FROM information_schema.tables WHERE table_type = 'BASE TABLE' \
+                     AND table_schema NOT IN ('mysql', 'information_schema', 'performance_schema', 'sys') \
+                     AND (? IS NULL OR table_schema = ?) ORDER BY 1",
+                    (schema, schema),
+                )?
+            }
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => checkout(p)?
+                .query(POSTGRES_TABLES, &[&schema])?
+                .iter()
+                .map(|r| r.get(0))
+                .collect(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => checkout(p)?
+                .query(POSTGRES_TABLES, &[&schema])?
+                .iter()
+                .map(|r| r.get(0))
+                .collect(),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => {
+                let conn = checkout(p)?;
+                let mut tables = vec![];
+                for db in sqlite_databases(&conn)? {
+                    if schema.is_some_and(|s| s != db) {
+                        continue;
+                    }
+                    let mut stmt = conn.prepare(&format!(
+                        "SELECT name FROM \"{}\".sqlite_master \
+                         WHERE type = 'table' AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\'",
+                        db.replace('"', "\"\"")
+                    ))?;
+                    let names = stmt.query_map([], |r| r.get::<_, String>(0))?;
+                    for name in names {
+                        tables.push(format!("{}.{}", db, name?));
+                    }
+                }
+                tables.sort();
+                tables
+            }
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => checkout(p)?
+                .query_as::<String>(
+                    "SELECT owner || '.' || table_name FROM all_tables \
+                     WHERE (:1 IS NULL AND owner IN \
+                       (SELECT username FROM all_users WHERE oracle_maintained = 'N')) \
+                     OR owner = :2 ORDER BY 1",
+                    &[&schema, &schema],
+                )?
+                .collect::<std::result::Result<_, _>>()?,
+        })
+    }
+
+    /// The schemas reachable through the pool, leaving out the backend's own system schemas.
+    /// Sorted, except for SQLite, where they are the attached databases in the order
+    /// `PRAGMA database_list` reports them, `main` first.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn list_schemas(&self) -> Result<Vec<String>> {
+        const POSTGRES_SCHEMAS: &str = "SELECT schema_name FROM information_schema.schemata \
+            WHERE schema_name NOT IN ('pg_catalog', 'information_schema') \
+            AND schema_name NOT LIKE 'pg\\_toast%' AND schema_name NOT LIKE 'pg\\_temp\\_%' \
+            ORDER BY 1";
+
+        Ok(match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => {
+                use r2d2_mysql::mysql::prelude::Queryable;
+                checkout(p)?.query(
+                    "SELECT schema_name FROM information_schema.schemata \
+                     WHERE schema_name NOT IN ('mysql', 'information_schema', 'performance_schema', 'sys') \
+                     ORDER BY 1",
+                )?
+            }
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => checkout(p)?
+                .query(POSTGRES_SCHEMAS, &[])?
+                .iter()
+                .map(|r| r.get(0))
+                .collect(),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => checkout(p)?
+                .query(POSTGRES_SCHEMAS, &[])?
+                .iter()
+                .map(|r| r.get(0))
+                .collect(),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => sqlite_databases(&*checkout(p)?)?,
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => checkout(p)?
+                .query_as::<String>(
+                    "SELECT username FROM all_users WHERE oracle_maintained = 'N' ORDER BY 1",
+                    &[],
+                )?
+                .collect::<std::result::Result<_, _>>()?,
+        })
+    }
+
+    /// Checks a connection out and keeps it aside, e.g. to run a transaction on it. The
+    /// connection returns to the pool once the [`PinnedVariant`] and its clones are dropped.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn pin(&self) -> Result<PinnedVariant> {
+        Ok(match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => PinnedVariant::MySQL(PinnedConnection::new(checkout(p)?)),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => {
+                PinnedVariant::PostgresNoTls(PinnedConnection::new(checkout(p)?))
+            }
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => {
+                PinnedVariant::PostgresTls(PinnedConnection::new(checkout(p)?))
+            }
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => PinnedVariant::SQLite(PinnedConnection::new(checkout(p)?)),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => PinnedVariant::Oracle(PinnedConnection::new(checkout(p)?)),
+        })
+    }
+
+    /// Checks a connection out behind a [`GenericConnection`], for code that runs SQL the same
+    /// way whichever backend the pool serves. The connection returns to the pool once the box
+    /// is dropped.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn checkout(&self) -> Result<Box<dyn GenericConnection>> {
+        fn boxed<M: ManageConnection>(pool: &Arc<ManagedPool<M>>) -> Result<PoolConnection<M>> {
+            Ok(PoolConnection {
+                conn: PinnedConnection::new(checkout(pool)?),
+                pool: pool.clone(),
+                source_conn: pool.source_conn.get().cloned(),
+            })
+        }
+
+        Ok(match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => Box::new(boxed(p)?),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => Box::new(boxed(p)?),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => Box::new(boxed(p)?),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => Box::new(boxed(p)?),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => Box::new(boxed(p)?),
+        })
+    }
+
+    /// Checks `n` connections out up front, e.g. so a bulk load cannot stall halfway waiting
+    /// for one. Each returns to the pool once its [`PinnedVariant`] is dropped, and the ones
+    /// already checked out are returned if a later checkout fails.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn reserve(&self, n: usize) -> Result<Vec<PinnedVariant>> {
+        if n > self.max_size() as usize {
+            anyhow::bail!(
+                "cannot reserve {} connections from a pool of at most {}",
+                n,
+                self.max_size()
+            );
+        }
+        (0..n).map(|_| self.pin()).collect()
+    }
+
+    /// Closes idle connections until at most `keep` are left, e.g. to shrink the server-side
+    /// footprint during a quiet period without dropping the pool. r2d2 reopens connections
+    /// lazily as they are needed again. Returns the number of connections closed.
+    ///
+    /// r2d2 also reopens connections right away while fewer than `PoolConfig::min_idle` are
+    /// idle, so trimming below it fails. That includes any `keep` short of `max_size` for
+    /// the default `min_idle` of `None`, which keeps the pool full.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn trim_idle(&self, keep: u32) -> Result<u32> {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => trim_idle(p, keep),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => trim_idle(p, keep),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => trim_idle(p, keep),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => trim_idle(p, keep),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => trim_idle(p, keep),
+        }
+    }
+
+    /// Hands the connections a `Fifo` pool has queued back to r2d2, e.g. as the pool is
+    /// closed, so they are closed with it rather than waiting in the queue. Returns the
+    /// number handed back; other reuse policies queue none.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn flush_returns(&self) -> u32 {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => flush_returns(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => flush_returns(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => flush_returns(p),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => flush_returns(p),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => flush_returns(p),
+        }
+    }
 
-#[cfg(feature = "src_mysql")]
-use r2d2_mysql::mysql::{Opts, OptsBuilder};
-#[cfg(feature = "src_mysql")]
-use r2d2_mysql::MySqlConnectionManager;
+    /// Whether the pool's connections are driven by a tokio runtime, which it then holds
+    /// for its lifetime. `false` for every r2d2 backend (MySQL, Postgres, SQLite and Oracle),
+    /// whose pools never create one, so callers may use them where no runtime is wanted.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn is_async(&self) -> bool {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(_) => false,
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(_) | PoolVariant::PostgresTls(_) => false,
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(_) => false,
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(_) => false,
+        }
+    }
 
-#[cfg(feature = "src_postgres")]
-use postgres::NoTls;
-#[cfg(feature = "src_postgres")]
-use postgres_openssl::MakeTlsConnector;
-#[cfg(feature = "src_postgres")]
-use r2d2_postgres::PostgresConnectionManager;
+    /// Backend name used as the `backend` label in exported metrics.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn backend_label(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(_) => "mysql",
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(_) | PoolVariant::PostgresTls(_) => "postgres",
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(_) => "sqlite",
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(_) => "oracle",
+        }
+    }
 
-#[cfg(feature = "src_sqlite")]
-use r2d2_sqlite::SqliteConnectionManager;
+    /// The features the pool's backend supports, so callers can branch on them rather than
+    /// on the backend itself.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn capabilities(&self) -> Capabilities {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(_) => Capabilities {
+                supports_transactions: true,
+                supports_prepared: true,
+                supports_copy: false,
+                supports_partition: true,
+                supports_tls: true,
+            },
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(_) | PoolVariant::PostgresTls(_) => Capabilities {
+                supports_transactions: true,
+                supports_prepared: true,
+                supports_copy: true,
+                supports_partition: true,
+                supports_tls: true,
+            },
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(_) => Capabilities {
+                supports_transactions: true,
+                supports_prepared: true,
+                supports_copy: false,
+                supports_partition: true,
+                supports_tls: false,
+            },
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(_) => Capabilities {
+                supports_transactions: true,
+                supports_prepared: true,
+                supports_copy: false,
+                supports_partition: true,
+                supports_tls: true,
+            },
+        }
+    }
 
-#[cfg(feature = "src_oracle")]
-use r2d2_oracle::OracleConnectionManager;
+    /// Renders the current pool state in the Prometheus text exposition format, with every
+    /// metric name prefixed by `name_prefix` (e.g. `connectorx_pool`), followed by counters of
+    /// the checkouts, the time they waited and the connections failing validation.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn metrics_text(&self, name_prefix: &str) -> String {
+        let state = self.state();
+        let mut labels = format!("backend=\"{}\"", self.backend_label());
+        if let Some(label) = self.label() {
+            // escaped as the exposition format requires for label values
+            let label = label
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            labels.push_str(&format!(",label=\"{}\"", label));
+        }
+        let gauges = [
+            (
+                "connections",
+                "Number of connections currently managed by the pool.",
+                state.connections,
+            ),
+            (
+                "idle_connections",
+                "Number of idle connections in the pool.",
+                state.idle_connections,
+            ),
+            (
+                "active_connections",
+                "Number of connections currently checked out of the pool.",
+                // `state` reads r2d2 and a `Fifo` queue apart, so a return in between can
+                // count a connection as idle that r2d2 still counts as checked out
+                state.connections.saturating_sub(state.idle_connections),
+            ),
+            (
+                "max_size",
+                "Maximum number of connections the pool will maintain.",
+                self.max_size(),
+            ),
+        ];
 
-/// Configuration for connection pool construction.
-#[derive(Debug, Clone)]
-pub struct PoolConfig {
-    pub max_size: u32,
-    pub idle_timeout: Option<Duration>,
-    pub max_lifetime: Option<Duration>,
-    pub connection_timeout: Duration,
-    pub test_on_check_out: bool,
-}
+        let counters = self.event_bridge().counters;
+        let counters = [
+            (
+                "checkouts_total",
+                "Number of connections checked out of the pool.",
+                counters.checkouts.load(Ordering::Relaxed).to_string(),
+            ),
+            (
+                "checkout_wait_seconds_total",
+                "Total time checkouts waited for a connection.",
+                Duration::from_nanos(counters.checkout_nanos.load(Ordering::Relaxed))
+                    .as_secs_f64()
+                    .to_string(),
+            ),
+            (
+                "validation_failures_total",
+                "Number of checked out connections that failed validation.",
+                counters
+                    .validation_failures
+                    .load(Ordering::Relaxed)
+                    .to_string(),
+            ),
+        ];
 
-impl Default for PoolConfig {
-    fn default() -> Self {
-        Self {
-            max_size: 10,
-            idle_timeout: Some(Duration::from_secs(300)), // 5 minutes
-            max_lifetime: Some(Duration::from_secs(1800)), // 30 minutes
-            connection_timeout: Duration::from_secs(30),
-            test_on_check_out: true,
+        let mut out = String::new();
+        let mut push = |kind: &str, name: &str, help: &str, value: &dyn std::fmt::Display| {
+            out.push_str(&format!("# HELP {}_{} {}\n", name_prefix, name, help));
+            out.push_str(&format!("# TYPE {}_{} {}\n", name_prefix, name, kind));
+            out.push_str(&format!(
+                "{}_{}{{{}}} {}\n",
+                name_prefix, name, labels, value
+            ));
+        };
+        for (name, help, value) in gauges {
+            push("gauge", name, help, &value);
         }
+        for (name, help, value) in counters {
+            push("counter", name, help, &value);
+        }
+        out
     }
-}
 
-/// Applies `PoolConfig` settings to an r2d2 builder.
-pub fn configure_builder<M: r2d2::ManageConnection>(
-    builder: r2d2::Builder<M>,
-    config: &PoolConfig,
-) -> r2d2::Builder<M> {
-    let mut builder = builder
-        .max_size(config.max_size)
-        .connection_timeout(config.connection_timeout);
-    if let Some(timeout) = config.idle_timeout {
-        builder = builder.idle_timeout(Some(timeout));
+    /// Sets the handler called with every [`PoolEvent`] of this pool, replacing the one set
+    /// before; `None` removes it. The handler runs on the thread the event happens on, a
+    /// timeout while the pool's lock is held, so it should hand the event off rather than
+    /// block.
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn set_event_handler(&self, handler: Option<PoolEventHandler>) {
+        *self.event_bridge().handler.write().unwrap() = handler;
     }
-    if let Some(lifetime) = config.max_lifetime {
-        builder = builder.max_lifetime(Some(lifetime));
+
+    /// Passes `event` to the handler set with [`PoolVariant::set_event_handler`], for events
+    /// r2d2 does not report itself, such as [`PoolEvent::Close`].
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    pub fn emit_event(&self, event: PoolEvent) {
+        self.event_bridge().emit(event);
     }
-    if config.test_on_check_out {
-        builder = builder.test_on_check_out(true);
+
+    #[cfg(any(
+        feature = "src_mysql",
+        feature = "src_postgres",
+        feature = "src_sqlite",
+        feature = "src_oracle"
+    ))]
+    fn set_source_conn(&self, source_conn: &SourceConn) {
+        fn set<M: ManageConnection>(pool: &ManagedPool<M>, source_conn: &SourceConn) {
+            let _ = pool.source_conn.set(source_conn.clone());
+        }
+
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PoolVariant::MySQL(p) => set(p, source_conn),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresNoTls(p) => set(p, source_conn),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => set(p, source_conn),
+            #[cfg(feature = "src_sqlite")]
+            PoolVariant::SQLite(p) => set(p, source_conn),
+            #[cfg(feature = "src_oracle")]
+            PoolVariant::Oracle(p) => set(p, source_conn),
+        }
     }
-    builder
-}
 
-/// A unified pool type covering all pool-supported database backends.
-/// Arms are feature-gated so only compiled when the relevant source is enabled.
-#[derive(Clone)]
-pub enum PoolVariant {
-    #[cfg(feature = "src_mysql")]
-    MySQL(Arc<Pool<MySqlConnectionManager>>),
-    #[cfg(feature = "src_postgres")]
-    PostgresNoTls(Arc<Pool<PostgresConnectionManager<NoTls>>>),
-    #[cfg(feature = "src_postgres")]
-    PostgresTls(Arc<Pool<PostgresConnectionManager<MakeTlsConnector>>>),
-    #[cfg(feature = "src_sqlite")]
-    SQLite(Arc<Pool<SqliteConnectionManager>>),
-    #[cfg(feature = "src_oracle")]
-    Oracle(Arc<Pool<OracleConnectionManager>>),
-}
+    fn event_bridge(&self) -> EventBridge {
+        fn bridge<M: ManageConnection>(pool: &ManagedPool<M>) -> EventBridge {
+            pool.events.clone()
+        }
 
-impl PoolVariant {
-    /// Builds a pool from a parsed connection, returning `None` for backends that do not
-    /// support connection pooling (MSSQL, BigQuery, Trino).
-    #[allow(unused_variables)]
-    pub fn from_source_conn(source_conn: &SourceConn, config: &PoolConfig) -> Result<Option<Self>> {
-        match source_conn.ty {
+        match self {
             #[cfg(feature = "src_mysql")]
-            SourceType::MySQL => {
-                let manager = MySqlConnectionManager::new(OptsBuilder::from_opts(Opts::from_url(
-                    source_conn.conn.as_str(),
-                )?));
-                let pool = configure_builder(Pool::builder(), config).build(manager)?;
-                Ok(Some(PoolVariant::MySQL(Arc::new(pool))))
-            }
+            PoolVariant::MySQL(p) => bridge(p),
             #[cfg(feature = "src_postgres")]
-            SourceType::Postgres => {
-                use crate::sources::postgres::rewrite_tls_args;
-                let (pg_config, tls) = rewrite_tls_args(&source_conn.conn)?;
-                match tls {
-                    Some(tls_conn) => {
-                        let manager = PostgresConnectionManager::new(pg_config, tls_conn);
-                        let pool = configure_builder(Pool::builder(), config).build(manager)?;
-                        Ok(Some(PoolVariant::PostgresTls(Arc::new(pool))))
-                    }
-                    None => {
-                        let manager = PostgresConnectionManager::new(pg_config, NoTls);
-                        let pool = configure_builder(Pool::builder(), config).build(manager)?;
-                        Ok(Some(PoolVariant::PostgresNoTls(Arc::new(pool))))
-                    }
-                }
-            }
+            PoolVariant::PostgresNoTls(p) => bridge(p),
+            #[cfg(feature = "src_postgres")]
+            PoolVariant::PostgresTls(p) => bridge(p),
             #[cfg(feature = "src_sqlite")]
-            SourceType::SQLite => {
-                use urlencoding::decode;
-                // Strip the "sqlite://" prefix (9 chars) the same way get_arrow.rs does.
-                let path = &source_conn.conn.as_str()[9..];
-                let decoded = decode(path)?.into_owned();
-                let manager = SqliteConnectionManager::file(decoded);
-                let pool = configure_builder(Pool::builder(), config).build(manager)?;
-                Ok(Some(PoolVariant::SQLite(Arc::new(pool))))
-            }
+            PoolVariant::SQLite(p) => bridge(p),
             #[cfg(feature = "src_oracle")]
-            SourceType::Oracle => {
-                use crate::sources::oracle::connect_oracle;
-                let connector = connect_oracle(&source_conn.conn).map_err(anyhow::Error::from)?;
-                let manager = OracleConnectionManager::from_connector(connector);
-                let pool = configure_builder(Pool::builder(), config).build(manager)?;
-                Ok(Some(PoolVariant::Oracle(Arc::new(pool))))
-            }
-            // MSSQL, BigQuery, Trino, and any other type: no pool support
-            _ => Ok(None),
+            PoolVariant::Oracle(p) => bridge(p),
         }
     }
 
@@ -178,7 +2663,7 @@ impl PoolVariant {
     }
 
     #[cfg(feature = "src_mysql")]
-    pub fn mysql_pool(&self) -> Arc<Pool<MySqlConnectionManager>> {
+    pub fn mysql_pool(&self) -> Arc<ManagedPool<MySqlConnectionManager>> {
         match self {
             PoolVariant::MySQL(p) => Arc::clone(p),
             #[allow(unreachable_patterns)]
@@ -190,7 +2675,7 @@ impl PoolVariant {
     }
 
     #[cfg(feature = "src_postgres")]
-    pub fn postgres_notls_pool(&self) -> Arc<Pool<PostgresConnectionManager<NoTls>>> {
+    pub fn postgres_notls_pool(&self) -> Arc<ManagedPool<PostgresConnectionManager<NoTls>>> {
         match self {
             PoolVariant::PostgresNoTls(p) => Arc::clone(p),
             #[allow(unreachable_patterns)]
@@ -202,7 +2687,9 @@ impl PoolVariant {
     }
 
     #[cfg(feature = "src_postgres")]
-    pub fn postgres_tls_pool(&self) -> Arc<Pool<PostgresConnectionManager<MakeTlsConnector>>> {
+    pub fn postgres_tls_pool(
+        &self,
+    ) -> Arc<ManagedPool<PostgresConnectionManager<MakeTlsConnector>>> {
         match self {
             PoolVariant::PostgresTls(p) => Arc::clone(p),
             #[allow(unreachable_patterns)]
@@ -214,7 +2701,7 @@ impl PoolVariant {
     }
 
     #[cfg(feature = "src_sqlite")]
-    pub fn sqlite_pool(&self) -> Arc<Pool<SqliteConnectionManager>> {
+    pub fn sqlite_pool(&self) -> Arc<ManagedPool<SqliteConnectionManager>> {
         match self {
             PoolVariant::SQLite(p) => Arc::clone(p),
             #[allow(unreachable_patterns)]
@@ -226,7 +2713,7 @@ impl PoolVariant {
     }
 
     #[cfg(feature = "src_oracle")]
-    pub fn oracle_pool(&self) -> Arc<Pool<OracleConnectionManager>> {
+    pub fn oracle_pool(&self) -> Arc<ManagedPool<OracleConnectionManager>> {
         match self {
             PoolVariant::Oracle(p) => Arc::clone(p),
             #[allow(unreachable_patterns)]
@@ -237,3 +2724,212 @@ impl PoolVariant {
         }
     }
 }
+
+/// A connection kept aside by [`PoolVariant::pin`], one arm per pooled backend.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+#[derive(Clone)]
+pub enum PinnedVariant {
+    #[cfg(feature = "src_mysql")]
+    MySQL(PinnedConnection<MySqlConnectionManager>),
+    #[cfg(feature = "src_postgres")]
+    PostgresNoTls(PinnedConnection<PostgresConnectionManager<NoTls>>),
+    #[cfg(feature = "src_postgres")]
+    PostgresTls(PinnedConnection<PostgresConnectionManager<MakeTlsConnector>>),
+    #[cfg(feature = "src_sqlite")]
+    SQLite(PinnedConnection<SqliteConnectionManager>),
+    #[cfg(feature = "src_oracle")]
+    Oracle(PinnedConnection<OracleConnectionManager>),
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl PinnedVariant {
+    /// Runs `sql` on the connection, discarding any result set.
+    pub fn execute(&self, sql: &str) -> Result<()> {
+        match self {
+            #[cfg(feature = "src_mysql")]
+            PinnedVariant::MySQL(c) => {
+                use r2d2_mysql::mysql::prelude::Queryable;
+                c.lease()?.query_drop(sql)?;
+            }
+            #[cfg(feature = "src_postgres")]
+            PinnedVariant::PostgresNoTls(c) => c.lease()?.batch_execute(sql)?,
+            #[cfg(feature = "src_postgres")]
+            PinnedVariant::PostgresTls(c) => c.lease()?.batch_execute(sql)?,
+            #[cfg(feature = "src_sqlite")]
+            PinnedVariant::SQLite(c) => c.lease()?.execute_batch(sql)?,
+            #[cfg(feature = "src_oracle")]
+            PinnedVariant::Oracle(c) => {
+                c.lease()?.execute(sql, &[])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A connection checked out with [`PoolVariant::checkout`], running SQL without the caller
+/// matching on the backend.
+pub trait GenericConnection {
+    /// Runs `sql`, discarding any result set, and returns the number of rows it changed, or
+    /// the last statement changed when `sql` holds several.
+    fn execute(&mut self, sql: &str) -> Result<u64>;
+
+    /// Reads the result of `sql` into Arrow on this connection, as a single partition, so it
+    /// sees the session state left by [`execute`](Self::execute), e.g. temporary tables.
+    #[cfg(feature = "dst_arrow")]
+    fn query_arrow(&mut self, sql: &str) -> Result<ArrowDestination>;
+}
+
+/// The [`GenericConnection`] of each pooled backend, holding its pool for the reads.
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+pub struct PoolConnection<M: ManageConnection> {
+    conn: PinnedConnection<M>,
+    pool: Arc<ManagedPool<M>>,
+    source_conn: Option<SourceConn>,
+}
+
+#[cfg(any(
+    feature = "src_mysql",
+    feature = "src_postgres",
+    feature = "src_sqlite",
+    feature = "src_oracle"
+))]
+impl<M: ManageConnection> PoolConnection<M> {
+    #[cfg(feature = "dst_arrow")]
+    fn read_arrow(
+        &self,
+        sql: &str,
+        pool: PoolVariant,
+        pinned: PinnedVariant,
+    ) -> Result<ArrowDestination> {
+        // the source reads settings such as `cursor_fetch_size` from the connection url
+        let source_conn = self.source_conn.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("query_arrow needs a pool built by PoolVariant::from_source_conn")
+        })?;
+        Ok(get_arrow_single(
+            source_conn,
+            sql,
+            &[],
+            Some(&pool),
+            Some(&pinned),
+        )?)
+    }
+}
+
+#[cfg(feature = "src_mysql")]
+impl GenericConnection for PoolConnection<MySqlConnectionManager> {
+    fn execute(&mut self, sql: &str) -> Result<u64> {
+        use r2d2_mysql::mysql::prelude::Queryable;
+        let mut conn = self.conn.lease()?;
+        conn.query_drop(sql)?;
+        Ok(conn.affected_rows())
+    }
+
+    #[cfg(feature = "dst_arrow")]
+    fn query_arrow(&mut self, sql: &str) -> Result<ArrowDestination> {
+        self.read_arrow(
+            sql,
+            PoolVariant::MySQL(self.pool.clone()),
+            PinnedVariant::MySQL(self.conn.clone()),
+        )
+    }
+}
+
+#[cfg(feature = "src_postgres")]
+fn postgres_execute(conn: &mut postgres::Client, sql: &str) -> Result<u64> {
+    use postgres::SimpleQueryMessage;
+    Ok(conn
+        .simple_query(sql)?
+        .iter()
+        .filter_map(|message| match message {
+            SimpleQueryMessage::CommandComplete(rows) => Some(*rows),
+            _ => None,
+        })
+        .last()
+        .unwrap_or(0))
+}
+
+#[cfg(feature = "src_postgres")]
+impl GenericConnection for PoolConnection<PostgresConnectionManager<NoTls>> {
+    fn execute(&mut self, sql: &str) -> Result<u64> {
+        postgres_execute(&mut *self.conn.lease()?, sql)
+    }
+
+    #[cfg(feature = "dst_arrow")]
+    fn query_arrow(&mut self, sql: &str) -> Result<ArrowDestination> {
+        self.read_arrow(
+            sql,
+            PoolVariant::PostgresNoTls(self.pool.clone()),
+            PinnedVariant::PostgresNoTls(self.conn.clone()),
+        )
+    }
+}
+
+#[cfg(feature = "src_postgres")]
+impl GenericConnection for PoolConnection<PostgresConnectionManager<MakeTlsConnector>> {
+    fn execute(&mut self, sql: &str) -> Result<u64> {
+        postgres_execute(&mut *self.conn.lease()?, sql)
+    }
+
+    #[cfg(feature = "dst_arrow")]
+    fn query_arrow(&mut self, sql: &str) -> Result<ArrowDestination> {
+        self.read_arrow(
+            sql,
+            PoolVariant::PostgresTls(self.pool.clone()),
+            PinnedVariant::PostgresTls(self.conn.clone()),
+        )
+    }
+}
+
+#[cfg(feature = "src_sqlite")]
+impl GenericConnection for PoolConnection<SqliteConnectionManager> {
+    fn execute(&mut self, sql: &str) -> Result<u64> {
+        let conn = self.conn.lease()?;
+        conn.execute_batch(sql)?;
+        Ok(conn.changes())
+    }
+
+    #[cfg(feature = "dst_arrow")]
+    fn query_arrow(&mut self, sql: &str) -> Result<ArrowDestination> {
+        self.read_arrow(
+            sql,
+            PoolVariant::SQLite(self.pool.clone()),
+            PinnedVariant::SQLite(self.conn.clone()),
+        )
+    }
+}
+
+/// Oracle sessions are always inside a transaction, which `execute` commits like
+/// [`PoolVariant::execute_unchecked`].
+#[cfg(feature = "src_oracle")]
+impl GenericConnection for PoolConnection<OracleConnectionManager> {
+    fn execute(&mut self, sql: &str) -> Result<u64> {
+        let conn = self.conn.lease()?;
+        let rows = conn.execute(sql, &[])?.row_count()?;
+        conn.commit()?;
+        Ok(rows)
+    }
+
+    #[cfg(feature = "dst_arrow")]
+    fn query_arrow(&mut self, sql: &str) -> Result<ArrowDestination> {
+        self.read_arrow(
+            sql,
+            PoolVariant::Oracle(self.pool.clone()),
+            PinnedVariant::Oracle(self.conn.clone()),
+        )
+    }
+}
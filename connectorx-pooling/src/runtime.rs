@@ -0,0 +1,97 @@
+//! The tokio runtime shared by the async sources (MSSQL, BigQuery, Trino) and federated
+//! execution, so that each query no longer spins up and tears down its own thread pool.
+
+use crate::errors::ConnectorXOutError;
+use fehler::throws;
+use std::future::Future;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex, OnceLock,
+};
+use std::time::Duration;
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::time::error::Elapsed;
+
+static SHARED_RUNTIME: OnceLock<Arc<Runtime>> = OnceLock::new();
+static RUNTIME_CONFIG: Mutex<RuntimeConfig> = Mutex::new(RuntimeConfig {
+    worker_threads: None,
+});
+static RUNTIMES_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Settings the shared runtime is created with, see [`set_runtime_config`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    /// Number of worker threads. Defaults to the CPUs the process may use, which accounts
+    /// for the CPU quota of the container it runs in, rather than every core of the host.
+    pub worker_threads: Option<usize>,
+}
+
+impl RuntimeConfig {
+    /// Builds a multi-threaded runtime with these settings.
+    pub fn build(&self) -> std::io::Result<Runtime> {
+        let worker_threads = match self.worker_threads {
+            Some(n) => n.max(1),
+            None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        };
+        Builder::new_multi_thread()
+            .worker_threads(worker_threads)
+            .enable_all()
+            .build()
+    }
+}
+
+/// Sets the config the shared runtime is created with on first use. Returns `false`, and
+/// changes nothing, once the runtime has been created.
+pub fn set_runtime_config(config: RuntimeConfig) -> bool {
+    let mut current = RUNTIME_CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+    if SHARED_RUNTIME.get().is_some() {
+        return false;
+    }
+    *current = config;
+    true
+}
+
+/// Builds a tokio runtime with `build`, returning `ConnectorXOutError::RuntimeInit` instead of
+/// panicking when it cannot be created (e.g. when a container's thread limit is reached).
+#[throws(ConnectorXOutError)]
+pub fn init_runtime<F: FnOnce() -> std::io::Result<Runtime>>(build: F) -> Arc<Runtime> {
+    let rt = build().map_err(ConnectorXOutError::RuntimeInit)?;
+    RUNTIMES_CREATED.fetch_add(1, Ordering::Relaxed);
+    Arc::new(rt)
+}
+
+/// Returns the process-wide multi-threaded runtime, creating it on first use. Concurrent
+/// queries can share it since `Runtime::block_on` may be called from several threads at once.
+#[throws(ConnectorXOutError)]
+pub fn shared_runtime() -> Arc<Runtime> {
+    if let Some(rt) = SHARED_RUNTIME.get() {
+        return rt.clone();
+    }
+    let config = RUNTIME_CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+    match SHARED_RUNTIME.get() {
+        Some(rt) => rt.clone(),
+        None => {
+            let rt = init_runtime(|| config.build())?;
+            SHARED_RUNTIME.get_or_init(|| rt).clone()
+        }
+    }
+}
+
+/// Number of runtimes created through [`init_runtime`] so far in this process.
+pub fn runtimes_created() -> usize {
+    RUNTIMES_CREATED.load(Ordering::Relaxed)
+}
+
+/// Runs `fut` to completion on `handle` like [`Handle::block_on`], giving up once `timeout`
+/// elapses, e.g. on a fetch from a server that stopped answering. Dropping the future
+/// abandons the request.
+pub fn block_on_timeout<F: Future>(
+    handle: &Handle,
+    timeout: Option<Duration>,
+    fut: F,
+) -> Result<F::Output, Elapsed> {
+    match timeout {
+        Some(timeout) => handle.block_on(async { tokio::time::timeout(timeout, fut).await }),
+        None => Ok(handle.block_on(fut)),
+    }
+}
@@ -0,0 +1,94 @@
+//! Keeps what sources resolved about the result of a query, so reading the same query again
+//! skips describing it to the database.
+
+use crate::sources::{Source, SourceMetadata};
+use crate::sql::CXQuery;
+use log::debug;
+use std::any::type_name;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Result metadata of earlier reads, by the queries read, with runs of whitespace collapsed,
+/// and the type of the source reading them. An entry is kept until [`clear`](Self::clear),
+/// or until a read that used it fails, as the schema it holds may have changed since.
+#[derive(Default)]
+pub struct SchemaCache {
+    entries: Mutex<HashMap<(String, &'static str), SourceMetadata>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl SchemaCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve the metadata of `src`, whose queries are set to `queries`, from `cache` when
+    /// it holds them, or else from the database, caching what the source resolved. Without a
+    /// cache, or for a source that does not support it, this is `src.fetch_metadata()`.
+    pub fn fetch_metadata<S: Source>(
+        cache: Option<&Self>,
+        src: &mut S,
+        queries: &[CXQuery<String>],
+    ) -> Result<(), S::Error> {
+        let cache = match cache {
+            Some(cache) => cache,
+            None => return src.fetch_metadata(),
+        };
+        let key = Self::key::<S>(queries);
+        let cached = cache.entries.lock().unwrap().get(&key).cloned();
+        if let Some(metadata) = cached {
+            if src.set_metadata(&metadata) {
+                debug!("Reusing the cached metadata");
+                cache.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(());
+            }
+        }
+        cache.misses.fetch_add(1, Ordering::Relaxed);
+        src.fetch_metadata()?;
+        if let Some(metadata) = src.metadata() {
+            cache.entries.lock().unwrap().insert(key, metadata);
+        }
+        Ok(())
+    }
+
+    /// Forget the metadata of `queries` as read by a source of type `S`.
+    pub fn invalidate<S: Source>(&self, queries: &[CXQuery<String>]) {
+        self.entries
+            .lock()
+            .unwrap()
+            .remove(&Self::key::<S>(queries));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads that took their metadata from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Reads that described their queries to the database.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn key<S: Source>(queries: &[CXQuery<String>]) -> (String, &'static str) {
+        let queries = queries
+            .iter()
+            .map(|q| q.as_str().split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join(";\n");
+        (queries, type_name::<S>())
+    }
+}
@@ -1,7 +1,8 @@
-use crate::constants::CONNECTORX_PROTOCOL;
+use crate::constants::{CONNECTORX_PROTOCOL, ENV_CONN_PREFIX};
 use crate::errors::{ConnectorXError, Result};
 use anyhow::anyhow;
-use fehler::throws;
+use fehler::{throw, throws};
+use std::borrow::Cow;
 use std::convert::TryFrom;
 use url::Url;
 
@@ -29,7 +30,8 @@ impl TryFrom<&str> for SourceConn {
     type Error = ConnectorXError;
 
     fn try_from(conn: &str) -> Result<SourceConn> {
-        let old_url = Url::parse(conn).map_err(|e| anyhow!("parse error: {}", e))?;
+        let old_url =
+            Url::parse(&escape_sqlite_uri(conn)).map_err(|e| anyhow!("parse error: {}", e))?;
 
         // parse connectorx protocol
         let proto = match old_url.query_pairs().find(|p| p.0 == CONNECTORX_PROTOCOL) {
@@ -51,17 +53,93 @@ impl TryFrom<&str> for SourceConn {
 
         // users from sqlalchemy may set engine in connection url (e.g. mssql+pymssql://...)
         // only for compatablility, we don't use the same engine
-        match url.scheme().split('+').collect::<Vec<&str>>()[0] {
-            "postgres" | "postgresql" => Ok(SourceConn::new(SourceType::Postgres, url, proto)),
-            "sqlite" => Ok(SourceConn::new(SourceType::SQLite, url, proto)),
-            "mysql" => Ok(SourceConn::new(SourceType::MySQL, url, proto)),
-            "mssql" => Ok(SourceConn::new(SourceType::MsSQL, url, proto)),
-            "oracle" => Ok(SourceConn::new(SourceType::Oracle, url, proto)),
-            "bigquery" => Ok(SourceConn::new(SourceType::BigQuery, url, proto)),
-            "duckdb" => Ok(SourceConn::new(SourceType::DuckDB, url, proto)),
-            "trino" => Ok(SourceConn::new(SourceType::Trino, url, proto)),
-            _ => Ok(SourceConn::new(SourceType::Unknown, url, proto)),
+        let scheme = url.scheme().split('+').collect::<Vec<&str>>()[0].to_ascii_lowercase();
+        let (ty, rewrite) = resolve_scheme(&scheme).ok_or_else(|| {
+            ConnectorXError::UnsupportedScheme(scheme.clone(), ACCEPTED_SCHEMES.join(", "))
+        })?;
+        if let Some(canonical) = rewrite {
+            url.set_scheme(canonical)
+                .map_err(|_| anyhow!("cannot rewrite scheme {} to {}", scheme, canonical))?;
         }
+        check_protocol(&ty, &proto)?;
+        Ok(SourceConn::new(ty, url, proto))
+    }
+}
+
+/// `sqlite://:memory:` and `sqlite://file:...` are not valid url authorities, so the colons
+/// of their path are escaped before parsing. The SQLite source decodes them again.
+fn escape_sqlite_uri(conn: &str) -> Cow<'_, str> {
+    for prefix in ["sqlite://", "sqlite3://"] {
+        let rest = match conn.get(..prefix.len()) {
+            Some(p) if p.eq_ignore_ascii_case(prefix) => &conn[prefix.len()..],
+            _ => continue,
+        };
+        if rest.starts_with(":memory:") || rest.starts_with("file:") {
+            let (path, query) = rest.split_at(rest.find('?').unwrap_or(rest.len()));
+            return Cow::Owned(format!(
+                "{}{}{}",
+                &conn[..prefix.len()],
+                path.replace(':', "%3A"),
+                query
+            ));
+        }
+    }
+    Cow::Borrowed(conn)
+}
+
+/// Connection-string schemes accepted by `SourceConn`, matched case-insensitively.
+pub const ACCEPTED_SCHEMES: &[&str] = &[
+    "postgres",
+    "postgresql",
+    "sqlite",
+    "sqlite3",
+    "mysql",
+    "mysql2",
+    "mssql",
+    "oracle",
+    "bigquery",
+    "duckdb",
+    "trino",
+];
+
+/// Maps a lowercased scheme to its source type. Aliases the underlying driver would not
+/// understand also return the canonical scheme the url should be rewritten to.
+fn resolve_scheme(scheme: &str) -> Option<(SourceType, Option<&'static str>)> {
+    let resolved = match scheme {
+        "postgres" | "postgresql" => (SourceType::Postgres, None),
+        "sqlite" => (SourceType::SQLite, None),
+        "sqlite3" => (SourceType::SQLite, Some("sqlite")),
+        "mysql" => (SourceType::MySQL, None),
+        "mysql2" => (SourceType::MySQL, Some("mysql")),
+        "mssql" => (SourceType::MsSQL, None),
+        "oracle" => (SourceType::Oracle, None),
+        "bigquery" => (SourceType::BigQuery, None),
+        "duckdb" => (SourceType::DuckDB, None),
+        "trino" => (SourceType::Trino, None),
+        _ => return None,
+    };
+    Some(resolved)
+}
+
+/// Transfer protocols understood by each source type. Sources with a single way of
+/// fetching data only accept the default, `binary`.
+pub fn supported_protocols(ty: &SourceType) -> &'static [&'static str] {
+    match ty {
+        SourceType::Postgres => &["binary", "csv", "cursor", "simple"],
+        SourceType::MySQL => &["binary", "text"],
+        _ => &["binary"],
+    }
+}
+
+#[throws(ConnectorXError)]
+fn check_protocol(ty: &SourceType, protocol: &str) {
+    let supported = supported_protocols(ty);
+    if !supported.contains(&protocol) {
+        throw!(ConnectorXError::UnsupportedProtocol(
+            protocol.to_string(),
+            ty.clone(),
+            supported.join(", ")
+        ));
     }
 }
 
@@ -74,12 +152,65 @@ impl SourceConn {
     }
 }
 
+/// Resolves `env:VARNAME` to the value of the environment variable `VARNAME`, so the
+/// connection string itself never appears in code or configuration. Any other connection
+/// string is returned unchanged.
+#[throws(ConnectorXError)]
+pub fn resolve_conn(conn: &str) -> Cow<'_, str> {
+    match conn.strip_prefix(ENV_CONN_PREFIX) {
+        Some(var) => Cow::Owned(
+            std::env::var(var).map_err(|_| ConnectorXError::ConnEnvVarNotSet(var.to_string()))?,
+        ),
+        None => Cow::Borrowed(conn),
+    }
+}
+
 #[throws(ConnectorXError)]
 pub fn parse_source(conn: &str, protocol: Option<&str>) -> SourceConn {
-    let mut source_conn = SourceConn::try_from(conn)?;
+    let mut source_conn = SourceConn::try_from(&*resolve_conn(conn)?)?;
     match protocol {
-        Some(p) => source_conn.set_protocol(p),
+        Some(p) => {
+            check_protocol(&source_conn.ty, p)?;
+            source_conn.set_protocol(p)
+        }
         None => {}
     }
     source_conn
 }
+
+/// A canonical form of `conn`, so connection strings that differ only in the order of their
+/// query parameters or the case of their scheme and host compare equal. Parameters are sorted
+/// by name, then value. The password is kept, so the result can still be connected with;
+/// use [`normalize_conn_str_redacted`] for anything that is logged or displayed.
+#[throws(ConnectorXError)]
+pub fn normalize_conn_str(conn: &str) -> String {
+    let mut url =
+        Url::parse(&escape_sqlite_uri(conn)).map_err(|e| anyhow!("parse error: {}", e))?;
+    let scheme = url.scheme().to_ascii_lowercase();
+    // the "host" of a relative SQLite path is part of the file name
+    if !scheme.starts_with("sqlite") {
+        if let Some(host) = url.host_str().map(|h| h.to_ascii_lowercase()) {
+            url.set_host(Some(&host))
+                .map_err(|e| anyhow!("parse error: {}", e))?;
+        }
+    }
+    let mut params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    if params.is_empty() {
+        url.set_query(None);
+    } else {
+        params.sort();
+        url.query_pairs_mut().clear().extend_pairs(params);
+    }
+    url.to_string()
+}
+
+/// [`normalize_conn_str`] with the password masked as `***`.
+#[throws(ConnectorXError)]
+pub fn normalize_conn_str_redacted(conn: &str) -> String {
+    let normalized = normalize_conn_str(conn)?;
+    let mut url = Url::parse(&normalized).map_err(|e| anyhow!("parse error: {}", e))?;
+    if url.password().is_some() {
+        let _ = url.set_password(Some("***"));
+    }
+    url.to_string()
+}
@@ -10,6 +10,9 @@ pub enum BigQuerySourceError {
     #[error(transparent)]
     BQError(#[from] BQError),
 
+    #[error("Fetch did not complete within the batch timeout")]
+    FetchTimeout(#[from] tokio::time::error::Elapsed),
+
     #[error(transparent)]
     BigQueryUrlError(#[from] url::ParseError),
 
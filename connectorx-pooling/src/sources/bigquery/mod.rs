@@ -7,6 +7,7 @@ pub use self::errors::BigQuerySourceError;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
+    runtime::block_on_timeout,
     sources::{PartitionParser, Produce, Source, SourcePartition},
     sql::{count_query, limit1_query, CXQuery},
 };
@@ -23,6 +24,7 @@ use gcp_bigquery_client::{
 };
 use sqlparser::dialect::Dialect;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 pub use typesystem::BigQueryTypeSystem;
 use url::Url;
@@ -53,6 +55,7 @@ pub struct BigQuerySource {
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<BigQueryTypeSystem>,
+    batch_timeout: Option<Duration>,
 }
 
 impl BigQuerySource {
@@ -79,8 +82,16 @@ impl BigQuerySource {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            batch_timeout: None,
         }
     }
+
+    /// Abandons a partition's query once a single request for its results has waited
+    /// longer than `timeout`, failing its read instead of leaving the worker to a request
+    /// that may never return. `None` lets requests wait indefinitely.
+    pub fn set_batch_timeout(&mut self, timeout: Option<Duration>) {
+        self.batch_timeout = timeout;
+    }
 }
 
 impl Source for BigQuerySource
@@ -170,13 +181,15 @@ where
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
         for query in self.queries {
-            ret.push(BigQuerySourcePartition::new(
+            let mut partition = BigQuerySourcePartition::new(
                 self.rt.clone(),
                 self.client.clone(),
                 self.project_id.clone(),
                 &query,
                 &self.schema,
-            ));
+            );
+            partition.batch_timeout = self.batch_timeout;
+            ret.push(partition);
         }
         ret
     }
@@ -190,6 +203,7 @@ pub struct BigQuerySourcePartition {
     schema: Vec<BigQueryTypeSystem>,
     nrows: usize,
     ncols: usize,
+    batch_timeout: Option<Duration>,
 }
 
 impl BigQuerySourcePartition {
@@ -208,6 +222,7 @@ impl BigQuerySourcePartition {
             schema: schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            batch_timeout: None,
         }
     }
 }
@@ -235,10 +250,14 @@ impl SourcePartition for BigQuerySourcePartition {
     #[throws(BigQuerySourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
         let job = self.client.job();
-        let qry = self.rt.block_on(job.query(
-            self.project_id.as_str(),
-            QueryRequest::new(self.query.as_str()),
-        ))?;
+        let qry = block_on_timeout(
+            self.rt.handle(),
+            self.batch_timeout,
+            job.query(
+                self.project_id.as_str(),
+                QueryRequest::new(self.query.as_str()),
+            ),
+        )??;
         let job_info = qry
             .job_reference
             .as_ref()
@@ -251,7 +270,9 @@ impl SourcePartition for BigQuerySourcePartition {
             start_index: None,
             timeout_ms: None,
         };
-        let rs = self.rt.block_on(
+        let rs = block_on_timeout(
+            self.rt.handle(),
+            self.batch_timeout,
             job.get_query_results(
                 self.project_id.as_str(),
                 job_info
@@ -261,8 +282,14 @@ impl SourcePartition for BigQuerySourcePartition {
                     .as_str(),
                 params,
             ),
-        )?;
-        BigQuerySourceParser::new(self.rt.clone(), self.client.clone(), rs, &self.schema)
+        )??;
+        BigQuerySourceParser::new(
+            self.rt.clone(),
+            self.client.clone(),
+            rs,
+            &self.schema,
+            self.batch_timeout,
+        )
     }
 
     fn nrows(&self) -> usize {
@@ -282,6 +309,8 @@ pub struct BigQuerySourceParser {
     current_col: usize,
     current_row: usize,
     nrows: Option<usize>,
+    /// Bounds each page fetch, see [`BigQuerySource::set_batch_timeout`].
+    fetch_timeout: Option<Duration>,
 }
 
 impl<'a> BigQuerySourceParser {
@@ -290,11 +319,13 @@ impl<'a> BigQuerySourceParser {
         client: Arc<Client>,
         response: GetQueryResultsResponse,
         schema: &[BigQueryTypeSystem],
+        fetch_timeout: Option<Duration>,
     ) -> Self {
         Self {
             rt,
             client,
             response,
+            fetch_timeout,
             ncols: schema.len(),
             current_row: 0,
             current_col: 0,
@@ -348,13 +379,13 @@ macro_rules! impl_produce {
                         let job = self.client.job();
                         let job_info = self.response.job_reference.as_ref().ok_or_else(|| anyhow!("job_reference is none"))?;
                         let params = GetQueryResultsParameters { format_options: None, location: job_info.location.clone(), max_results: None, page_token: self.response.page_token.clone(), start_index: None, timeout_ms: None };
-                        self.response = self.rt.block_on(
+                        self.response = block_on_timeout(self.rt.handle(), self.fetch_timeout,
                             job.get_query_results(
                                 job_info.project_id.as_ref().ok_or_else(|| anyhow!("project_id is none"))?.as_str(),
                                 job_info.job_id.as_ref().ok_or_else(|| anyhow!("job_id is none"))?.as_str(),
                                 params,
                             ),
-                        )?;
+                        )??;
                         self.current_row = 0;
                         ridx = 0;
                     }
@@ -381,13 +412,13 @@ macro_rules! impl_produce {
                         let job = self.client.job();
                         let job_info = self.response.job_reference.as_ref().ok_or_else(|| anyhow!("job_reference is none"))?;
                         let params = GetQueryResultsParameters { format_options: None, location: job_info.location.clone(), max_results: None, page_token: self.response.page_token.clone(), start_index: None, timeout_ms: None };
-                        self.response = self.rt.block_on(
+                        self.response = block_on_timeout(self.rt.handle(), self.fetch_timeout,
                             job.get_query_results(
                                 job_info.project_id.as_ref().ok_or_else(|| anyhow!("project_id is none"))?.as_str(),
                                 job_info.job_id.as_ref().ok_or_else(|| anyhow!("job_id is none"))?.as_str(),
                                 params,
                             ),
-                        )?;
+                        )??;
                         self.current_row = 0;
                         ridx = 0;
                     }
@@ -437,7 +468,9 @@ impl<'r, 'a> Produce<'r, bool> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -451,7 +484,7 @@ impl<'r, 'a> Produce<'r, bool> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -511,7 +544,9 @@ impl<'r, 'a> Produce<'r, Option<bool>> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -525,7 +560,7 @@ impl<'r, 'a> Produce<'r, Option<bool>> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -587,7 +622,9 @@ impl<'r, 'a> Produce<'r, NaiveDate> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -601,7 +638,7 @@ impl<'r, 'a> Produce<'r, NaiveDate> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -656,7 +693,9 @@ impl<'r, 'a> Produce<'r, Option<NaiveDate>> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -670,7 +709,7 @@ impl<'r, 'a> Produce<'r, Option<NaiveDate>> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -731,7 +770,9 @@ impl<'r, 'a> Produce<'r, NaiveDateTime> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -745,7 +786,7 @@ impl<'r, 'a> Produce<'r, NaiveDateTime> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -801,7 +842,9 @@ impl<'r, 'a> Produce<'r, Option<NaiveDateTime>> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -815,7 +858,7 @@ impl<'r, 'a> Produce<'r, Option<NaiveDateTime>> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -878,7 +921,9 @@ impl<'r, 'a> Produce<'r, NaiveTime> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -892,7 +937,7 @@ impl<'r, 'a> Produce<'r, NaiveTime> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -947,7 +992,9 @@ impl<'r, 'a> Produce<'r, Option<NaiveTime>> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -961,7 +1008,7 @@ impl<'r, 'a> Produce<'r, Option<NaiveTime>> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -1022,7 +1069,9 @@ impl<'r, 'a> Produce<'r, DateTime<Utc>> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -1036,7 +1085,7 @@ impl<'r, 'a> Produce<'r, DateTime<Utc>> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
@@ -1095,7 +1144,9 @@ impl<'r, 'a> Produce<'r, Option<DateTime<Utc>>> for BigQuerySourceParser {
                 start_index: None,
                 timeout_ms: None,
             };
-            self.response = self.rt.block_on(
+            self.response = block_on_timeout(
+                self.rt.handle(),
+                self.fetch_timeout,
                 job.get_query_results(
                     job_info
                         .project_id
@@ -1109,7 +1160,7 @@ impl<'r, 'a> Produce<'r, Option<DateTime<Utc>>> for BigQuerySourceParser {
                         .as_str(),
                     params,
                 ),
-            )?;
+            )??;
             self.current_row = 0;
             ridx = 0;
         }
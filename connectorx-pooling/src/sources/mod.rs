@@ -24,7 +24,42 @@ use crate::data_order::DataOrder;
 use crate::errors::ConnectorXError;
 use crate::sql::CXQuery;
 use crate::typesystem::{TypeAssoc, TypeSystem};
+use std::any::Any;
+use std::borrow::Cow;
 use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How text columns holding bytes that are not valid UTF-8 are decoded. `Error` fails the
+/// read with `InvalidUtf8`; `Replace` reads each invalid value as a single U+FFFD; `Lossy`
+/// keeps the valid text and replaces every invalid sequence with U+FFFD, as
+/// [`String::from_utf8_lossy`] does. Only sources handing text over as raw bytes, SQLite and
+/// MySQL, can apply it; the drivers of the others decode text themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidUtf8Policy {
+    #[default]
+    Error,
+    Replace,
+    Lossy,
+}
+
+impl InvalidUtf8Policy {
+    /// Decode `bytes` following this policy, borrowing them when they are valid.
+    pub fn decode<'b>(&self, bytes: &'b [u8]) -> Result<Cow<'b, str>, ConnectorXError> {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => Ok(Cow::Borrowed(s)),
+            Err(e) => match self {
+                InvalidUtf8Policy::Error => Err(ConnectorXError::InvalidUtf8(e)),
+                InvalidUtf8Policy::Replace => Ok(Cow::Borrowed("\u{FFFD}")),
+                InvalidUtf8Policy::Lossy => Ok(String::from_utf8_lossy(bytes)),
+            },
+        }
+    }
+}
+
+/// What the `fetch_metadata` of a source resolved, which another source of the same type
+/// reading the same queries can take instead of describing them again.
+pub type SourceMetadata = Arc<dyn Any + Send + Sync>;
 
 pub trait Source {
     /// Supported data orders, ordering by preference.
@@ -45,6 +80,10 @@ pub trait Source {
         unimplemented!("pre_execution_queries is not implemented in this source type");
     }
 
+    /// How to decode text holding invalid UTF-8. Ignored by sources whose driver decodes text
+    /// itself.
+    fn set_invalid_utf8(&mut self, _policy: InvalidUtf8Policy) {}
+
     fn fetch_metadata(&mut self) -> Result<(), Self::Error>;
     /// Get total number of rows if available
     fn result_rows(&mut self) -> Result<Option<usize>, Self::Error>;
@@ -53,6 +92,36 @@ pub trait Source {
 
     fn schema(&self) -> Vec<Self::TypeSystem>;
 
+    /// Declared precision and scale of each decimal column, `None` where the column has
+    /// none. Empty if the source does not report them.
+    fn decimal_types(&self) -> Vec<Option<(u8, i8)>> {
+        vec![]
+    }
+
+    /// Labels of each enum column in their declared order, `None` for other columns. Empty
+    /// if the source has no enum types.
+    fn enum_labels(&self) -> Vec<Option<Vec<String>>> {
+        vec![]
+    }
+
+    /// Comment of each column, `None` where the column has none. Only called when asked for,
+    /// since it costs a catalog query. Empty if the source does not report them.
+    fn column_comments(&mut self) -> Result<Vec<Option<String>>, Self::Error> {
+        Ok(vec![])
+    }
+
+    /// A snapshot of what `fetch_metadata` resolved, `None` if the source cannot take one
+    /// back through `set_metadata`.
+    fn metadata(&self) -> Option<SourceMetadata> {
+        None
+    }
+
+    /// Take the `metadata` of a source that read the same queries, in place of
+    /// `fetch_metadata`. Returns false, leaving the source as it was, if it does not fit.
+    fn set_metadata(&mut self, _metadata: &SourceMetadata) -> bool {
+        false
+    }
+
     fn partition(self) -> Result<Vec<Self::Partition>, Self::Error>;
 }
 
@@ -76,6 +145,16 @@ pub trait SourcePartition {
 
     /// Number of cols this `DataSource` got.
     fn ncols(&self) -> usize;
+
+    /// Time spent checking the partition's connection out of the pool.
+    fn checkout_time(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// Called when reading the partition failed, before the partition is dropped. Sources
+    /// whose connection a failure can leave unusable, such as a Postgres one stuck in an
+    /// aborted transaction, close it here so the pool discards it rather than handing it out.
+    fn release_after_error(&mut self) {}
 }
 
 pub trait PartitionParser<'a>: Send {
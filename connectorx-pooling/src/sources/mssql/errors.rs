@@ -18,6 +18,9 @@ pub enum MsSQLSourceError {
     #[error(transparent)]
     MsSQLPoolError(#[from] bb8_tiberius::Error),
 
+    #[error("Fetch did not complete within the batch timeout")]
+    FetchTimeout(#[from] tokio::time::error::Elapsed),
+
     #[error(transparent)]
     MsSQLUrlError(#[from] url::ParseError),
 
@@ -9,6 +9,7 @@ use crate::constants::DB_BUFFER_SIZE;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
+    runtime::block_on_timeout,
     sources::{PartitionParser, Produce, Source, SourcePartition},
     sql::{count_query, CXQuery},
     utils::DummyBox,
@@ -26,6 +27,7 @@ use rust_decimal::Decimal;
 use sqlparser::dialect::MsSqlDialect;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tiberius::{AuthMethod, Config, EncryptionLevel, QueryItem, QueryStream, Row};
 use tokio::runtime::{Handle, Runtime};
 use url::Url;
@@ -40,6 +42,29 @@ pub struct MsSQLSource {
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<MsSQLTypeSystem>,
+    result_set: Option<MsSQLResultSet>,
+    batch_timeout: Option<Duration>,
+}
+
+/// One result set of a query, read in full by [`MsSQLSource::fetch_result_sets`].
+pub struct MsSQLResultSet {
+    names: Vec<String>,
+    schema: Vec<MsSQLTypeSystem>,
+    rows: Vec<Row>,
+}
+
+impl MsSQLResultSet {
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
 }
 
 #[throws(MsSQLSourceError)]
@@ -123,6 +148,61 @@ impl MsSQLSource {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            result_set: None,
+            batch_timeout: None,
+        }
+    }
+
+    /// Abandons a partition's query once a single fetch of it has waited longer than
+    /// `timeout`, failing its read instead of leaving the worker and the connection to a
+    /// query that may never return. `None` lets fetches wait indefinitely.
+    pub fn set_batch_timeout(&mut self, timeout: Option<Duration>) {
+        self.batch_timeout = timeout;
+    }
+
+    /// Runs `query` once and reads every result set it returns, e.g. the several of a stored
+    /// procedure, in order.
+    #[throws(MsSQLSourceError)]
+    pub fn fetch_result_sets(&self, query: &str) -> Vec<MsSQLResultSet> {
+        let mut conn = self.rt.block_on(self.pool.get())?;
+        let mut stream = self.rt.block_on(conn.query(query, &[]))?;
+        let mut result_sets: Vec<MsSQLResultSet> = vec![];
+        while let Some(item) = self.rt.block_on(stream.next()) {
+            match item? {
+                QueryItem::Metadata(meta) => result_sets.push(MsSQLResultSet {
+                    names: meta
+                        .columns()
+                        .iter()
+                        .map(|c| c.name().to_string())
+                        .collect(),
+                    schema: meta
+                        .columns()
+                        .iter()
+                        .map(|c| MsSQLTypeSystem::from(&c.column_type()))
+                        .collect(),
+                    rows: vec![],
+                }),
+                QueryItem::Row(row) => match result_sets.last_mut() {
+                    Some(result_set) => result_set.rows.push(row),
+                    None => throw!(anyhow!("MsSQL returned a row before its columns")),
+                },
+            }
+        }
+        result_sets
+    }
+
+    /// A source on the same connections that reads `result_set` instead of running its
+    /// queries. It still takes one query, which is only used to name the partition.
+    pub fn with_result_set(&self, result_set: MsSQLResultSet) -> Self {
+        Self {
+            rt: self.rt.clone(),
+            pool: self.pool.clone(),
+            origin_query: None,
+            queries: vec![],
+            names: result_set.names.clone(),
+            schema: result_set.schema.clone(),
+            result_set: Some(result_set),
+            batch_timeout: self.batch_timeout,
         }
     }
 }
@@ -154,6 +234,10 @@ where
     #[throws(MsSQLSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
+        if self.result_set.is_some() {
+            // already known from the result set
+            return;
+        }
 
         let mut conn = self.rt.block_on(self.pool.get())?;
         let first_query = &self.queries[0];
@@ -222,14 +306,19 @@ where
 
     #[throws(MsSQLSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
+        if let Some(result_set) = self.result_set {
+            let mut partition =
+                MsSQLSourcePartition::new(self.pool, self.rt, &self.queries[0], &self.schema);
+            partition.nrows = result_set.rows.len();
+            partition.rows = Some(result_set.rows);
+            return vec![partition];
+        }
         let mut ret = vec![];
         for query in self.queries {
-            ret.push(MsSQLSourcePartition::new(
-                self.pool.clone(),
-                self.rt.clone(),
-                &query,
-                &self.schema,
-            ));
+            let mut partition =
+                MsSQLSourcePartition::new(self.pool.clone(), self.rt.clone(), &query, &self.schema);
+            partition.batch_timeout = self.batch_timeout;
+            ret.push(partition);
         }
         ret
     }
@@ -242,6 +331,9 @@ pub struct MsSQLSourcePartition {
     schema: Vec<MsSQLTypeSystem>,
     nrows: usize,
     ncols: usize,
+    /// Rows read ahead of time by [`MsSQLSource::fetch_result_sets`], replacing the query.
+    rows: Option<Vec<Row>>,
+    batch_timeout: Option<Duration>,
 }
 
 impl MsSQLSourcePartition {
@@ -258,6 +350,8 @@ impl MsSQLSourcePartition {
             schema: schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            rows: None,
+            batch_timeout: None,
         }
     }
 }
@@ -269,6 +363,9 @@ impl SourcePartition for MsSQLSourcePartition {
 
     #[throws(MsSQLSourceError)]
     fn result_rows(&mut self) {
+        if self.rows.is_some() {
+            return;
+        }
         let cquery = count_query(&self.query, &MsSqlDialect {})?;
         let mut conn = self.rt.block_on(self.pool.get())?;
 
@@ -284,19 +381,29 @@ impl SourcePartition for MsSQLSourcePartition {
 
     #[throws(MsSQLSourceError)]
     fn parser<'a>(&'a mut self) -> Self::Parser<'a> {
+        if let Some(rows) = self.rows.take() {
+            return MsSQLSourceParser::new(
+                self.rt.handle(),
+                ParserRows::Buffered(rows.into_iter()),
+                &self.schema,
+                None,
+            );
+        }
         let conn = self.rt.block_on(self.pool.get())?;
         let rows: OwningHandle<Box<Conn<'a>>, DummyBox<QueryStream<'a>>> =
-            OwningHandle::new_with_fn(Box::new(conn), |conn: *const Conn<'a>| unsafe {
+            OwningHandle::try_new(Box::new(conn), |conn: *const Conn<'a>| unsafe {
                 let conn = &mut *(conn as *mut Conn<'a>);
-
-                DummyBox(
-                    self.rt
-                        .block_on(conn.query(self.query.as_str(), &[]))
-                        .unwrap(),
-                )
-            });
-
-        MsSQLSourceParser::new(self.rt.handle(), rows, &self.schema)
+                let query = conn.query(self.query.as_str(), &[]);
+                let stream = block_on_timeout(self.rt.handle(), self.batch_timeout, query)??;
+                Ok::<_, MsSQLSourceError>(DummyBox(stream))
+            })?;
+
+        MsSQLSourceParser::new(
+            self.rt.handle(),
+            ParserRows::Stream(rows),
+            &self.schema,
+            self.batch_timeout,
+        )
     }
 
     fn nrows(&self) -> usize {
@@ -308,25 +415,35 @@ impl SourcePartition for MsSQLSourcePartition {
     }
 }
 
+/// Where a parser takes its rows from: the query's stream, or rows read ahead of time.
+enum ParserRows<'a> {
+    Stream(OwningHandle<Box<Conn<'a>>, DummyBox<QueryStream<'a>>>),
+    Buffered(std::vec::IntoIter<Row>),
+}
+
 pub struct MsSQLSourceParser<'a> {
     rt: &'a Handle,
-    iter: OwningHandle<Box<Conn<'a>>, DummyBox<QueryStream<'a>>>,
+    iter: ParserRows<'a>,
     rowbuf: Vec<Row>,
     ncols: usize,
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    /// Bounds each fetch from the stream, see [`MsSQLSource::set_batch_timeout`].
+    fetch_timeout: Option<Duration>,
 }
 
 impl<'a> MsSQLSourceParser<'a> {
     fn new(
         rt: &'a Handle,
-        iter: OwningHandle<Box<Conn<'a>>, DummyBox<QueryStream<'a>>>,
+        iter: ParserRows<'a>,
         schema: &[MsSQLTypeSystem],
+        fetch_timeout: Option<Duration>,
     ) -> Self {
         Self {
             rt,
             iter,
+            fetch_timeout,
             rowbuf: Vec::with_capacity(DB_BUFFER_SIZE),
             ncols: schema.len(),
             current_row: 0,
@@ -363,14 +480,21 @@ impl<'a> PartitionParser<'a> for MsSQLSourceParser<'a> {
         }
 
         for _ in 0..DB_BUFFER_SIZE {
-            if let Some(item) = self.rt.block_on(self.iter.next()) {
-                match item.map_err(MsSQLSourceError::MsSQLError)? {
-                    QueryItem::Row(row) => self.rowbuf.push(row),
-                    _ => continue,
+            let item = match &mut self.iter {
+                ParserRows::Stream(iter) => {
+                    block_on_timeout(self.rt, self.fetch_timeout, iter.next())?
+                        .transpose()
+                        .map_err(MsSQLSourceError::MsSQLError)?
+                }
+                ParserRows::Buffered(rows) => rows.next().map(QueryItem::Row),
+            };
+            match item {
+                Some(QueryItem::Row(row)) => self.rowbuf.push(row),
+                Some(_) => continue,
+                None => {
+                    self.is_finished = true;
+                    break;
                 }
-            } else {
-                self.is_finished = true;
-                break;
             }
         }
         self.current_row = 0;
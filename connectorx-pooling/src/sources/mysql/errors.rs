@@ -14,6 +14,21 @@ pub enum MySQLSourceError {
     #[error(transparent)]
     MySQLPoolError(#[from] r2d2::Error),
 
+    #[error(transparent)]
+    MySQLCheckoutError(#[from] crate::pool::PoolError),
+
+    #[error("Invalid ssl-mode {0}, expected DISABLED, REQUIRED, VERIFY_CA or VERIFY_IDENTITY")]
+    InvalidSslMode(String),
+
+    #[error("ssl-cert and ssl-key must be given together")]
+    IncompleteClientCert,
+
+    #[error("Invalid charset or collation {0}")]
+    InvalidCharset(String),
+
+    #[error("The MySQL server does not support TLS, which the ssl-mode parameter requires")]
+    TlsNotSupported,
+
     /// Any other errors that are too trivial to be put here explicitly.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
@@ -8,78 +8,370 @@ use crate::constants::DB_BUFFER_SIZE;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
+    params::{mysql_params, ScalarValue},
+    pool::{checkout, LeasedConnection, ManagedPool, PinnedConnection},
+    sources::{
+        InvalidUtf8Policy, PartitionParser, Produce, Source, SourceMetadata, SourcePartition,
+    },
     sql::{count_query, limit1_query, CXQuery},
 };
 use anyhow::anyhow;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use fehler::{throw, throws};
 use log::{debug, warn};
-use r2d2::{Pool, PooledConnection};
 use r2d2_mysql::{
     mysql::{
         consts::{
             ColumnFlags as MySQLColumnFlags, ColumnType as MySQLColumnType, UTF8MB4_GENERAL_CI,
             UTF8_GENERAL_CI,
         },
+        from_value_opt,
         prelude::Queryable,
-        Binary, Opts, OptsBuilder, QueryResult, Row, Text,
+        Binary, ClientIdentity, Column, Opts, OptsBuilder, QueryResult, Row, SslOpts, Text,
+        Value as MySQLValue,
     },
     MySqlConnectionManager,
 };
 use rust_decimal::Decimal;
 use serde_json::Value;
 use sqlparser::dialect::MySqlDialect;
+use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
 pub use typesystem::MySQLTypeSystem;
 
-type MysqlConn = PooledConnection<MySqlConnectionManager>;
+type MysqlConn = LeasedConnection<MySqlConnectionManager>;
+
+/// Collation id of the `binary` character set.
+const BINARY_CHARSET: u16 = 63;
 
 pub enum BinaryProtocol {}
 pub enum TextProtocol {}
 
+/// The driver options parsed from a MySQL connection url by [`mysql_opts`], derefing to them.
+pub struct MySqlOpts {
+    pub opts: Opts,
+    /// The private temp directory holding the `ssl-cert` client certificate the options refer
+    /// to, removed along with its last clone. The driver reads the certificate on every
+    /// connect, so it is kept as long as connections are opened with the options.
+    pub identity: Option<Arc<TempDir>>,
+}
+
+impl std::ops::Deref for MySqlOpts {
+    type Target = Opts;
+
+    fn deref(&self) -> &Opts {
+        &self.opts
+    }
+}
+
+/// Parses a MySQL connection url. The url crate keeps the brackets of an IPv6 literal host
+/// (`[2001:db8::1]`), which the mysql driver would then try to resolve as a hostname, so they
+/// are stripped before building the options. TLS is configured from the `ssl-*` parameters,
+/// see [`mysql_ssl_opts`], and the connection character set from `charset` and `collation`,
+/// see [`mysql_charset_init`].
+#[throws(MySQLSourceError)]
+pub fn mysql_opts(conn: &str) -> MySqlOpts {
+    let (conn, cx_params) = split_params(conn);
+    let opts = Opts::from_url(&conn)?;
+    let mut init = vec![mysql_charset_init(&cx_params)?];
+    init.extend(opts.get_init());
+    let (ssl_opts, identity) = match mysql_ssl_opts(&cx_params)? {
+        Some((ssl_opts, identity)) => (Some(ssl_opts), identity),
+        None => (None, None),
+    };
+    let mut builder = OptsBuilder::from_opts(opts.clone())
+        .ssl_opts(ssl_opts)
+        .init(init);
+    let host = opts.get_ip_or_hostname().to_string();
+    if let Some(ipv6) = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+        builder = builder.ip_or_hostname(Some(ipv6));
+    }
+    MySqlOpts {
+        opts: builder.into(),
+        identity,
+    }
+}
+
+/// Splits the url parameters connectorx reads itself off a MySQL connection url, returning
+/// the url the driver is given and those parameters.
+fn split_params(conn: &str) -> (String, Vec<(String, String)>) {
+    match url::Url::parse(conn) {
+        Ok(mut url) => {
+            let (cx_params, params): (Vec<_>, Vec<_>) =
+                url.query_pairs().into_owned().partition(|(k, _)| {
+                    k.starts_with("ssl-")
+                        || k == "charset"
+                        || k == "collation"
+                        || k == "set_as_list"
+                });
+            url.set_query(None);
+            if !params.is_empty() {
+                url.query_pairs_mut().extend_pairs(params);
+            }
+            (url.to_string(), cx_params)
+        }
+        Err(_) => (conn.to_string(), vec![]),
+    }
+}
+
+/// Whether connections to the MySQL connection url are encrypted, as its `ssl-mode` and
+/// `ssl-ca` parameters ask, see [`mysql_ssl_opts`].
 #[throws(MySQLSourceError)]
-fn get_total_rows(conn: &mut MysqlConn, query: &CXQuery<String>) -> usize {
-    conn.query_first(&count_query(query, &MySqlDialect {})?)?
-        .ok_or_else(|| anyhow!("mysql failed to get the count of query: {}", query))?
+pub fn mysql_tls(conn: &str) -> bool {
+    ssl_verification(&split_params(conn).1)?.is_some()
+}
+
+/// Whether the `set_as_list` url parameter asks for `SET` columns as lists of their members.
+/// By default they are read as the comma-joined string MySQL sends.
+pub fn mysql_set_as_list(conn: &str) -> bool {
+    url::Url::parse(conn).is_ok_and(|url| {
+        url.query_pairs()
+            .any(|(k, v)| k == "set_as_list" && (v == "true" || v == "1"))
+    })
+}
+
+/// The `SET NAMES` statement run on every new connection, from the `charset` and `collation`
+/// url parameters. The charset defaults to `utf8mb4` rather than the server default, which
+/// may be `latin1` or the 3-byte `utf8` and silently mangle 4-byte characters such as emoji.
+#[throws(MySQLSourceError)]
+pub fn mysql_charset_init(params: &[(String, String)]) -> String {
+    let param = |name: &str| {
+        params
+            .iter()
+            .rev()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    };
+    // the names are spliced into the statement, so only plain identifiers are accepted
+    let check = |name: &str| match !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        true => Ok(name.to_string()),
+        false => Err(MySQLSourceError::InvalidCharset(name.to_string())),
+    };
+    let charset = check(param("charset").unwrap_or("utf8mb4"))?;
+    match param("collation") {
+        Some(collation) => format!("SET NAMES {} COLLATE {}", charset, check(collation)?),
+        None => format!("SET NAMES {}", charset),
+    }
+}
+
+/// TLS options from the `ssl-mode`, `ssl-ca`, `ssl-cert` and `ssl-key` url parameters, which
+/// follow the mysql client options of the same name:
+/// - `DISABLED`, the default without `ssl-ca`, connects unencrypted.
+/// - `REQUIRED` encrypts but trusts any server certificate, unless `ssl-ca` is given.
+/// - `VERIFY_CA`, the default with `ssl-ca`, checks the certificate against `ssl-ca`, or the
+///   system roots without it.
+/// - `VERIFY_IDENTITY` also checks that the certificate matches the host name.
+///
+/// `PREFERRED` is rejected: the driver cannot fall back to an unencrypted connection. The
+/// PEM `ssl-cert` and `ssl-key` are the client certificate, which the driver only reads as a
+/// PKCS#12 archive from a file, so they are converted into one in a private temp directory,
+/// returned along with the options.
+#[throws(MySQLSourceError)]
+pub fn mysql_ssl_opts(params: &[(String, String)]) -> Option<(SslOpts, Option<Arc<TempDir>>)> {
+    let (verify_ca, verify_identity) = match ssl_verification(params)? {
+        Some(verification) => verification,
+        None => return None,
+    };
+    let param = |name: &str| {
+        params
+            .iter()
+            .rev()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    };
+    let (identity, dir) = match (param("ssl-cert"), param("ssl-key")) {
+        (Some(cert), Some(key)) => {
+            let (identity, dir) = client_identity(cert, key)?;
+            (Some(identity), Some(Arc::new(dir)))
+        }
+        (None, None) => (None, None),
+        _ => throw!(MySQLSourceError::IncompleteClientCert),
+    };
+    let ssl_opts = SslOpts::default()
+        .with_root_cert_path(param("ssl-ca").map(PathBuf::from))
+        .with_client_identity(identity)
+        .with_danger_accept_invalid_certs(!verify_ca)
+        .with_danger_skip_domain_validation(!verify_identity);
+    Some((ssl_opts, dir))
+}
+
+/// Whether the server certificate and its host name are verified, from the `ssl-mode` and
+/// `ssl-ca` url parameters; `None` if TLS is off.
+#[throws(MySQLSourceError)]
+fn ssl_verification(params: &[(String, String)]) -> Option<(bool, bool)> {
+    let param = |name: &str| {
+        params
+            .iter()
+            .rev()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    };
+    let ca = param("ssl-ca");
+    let mode = match param("ssl-mode") {
+        Some(mode) => mode.to_ascii_uppercase(),
+        None if ca.is_some() => "VERIFY_CA".to_string(),
+        None => "DISABLED".to_string(),
+    };
+    match mode.as_str() {
+        "DISABLED" => None,
+        "REQUIRED" => Some((ca.is_some(), false)),
+        "VERIFY_CA" => Some((true, false)),
+        "VERIFY_IDENTITY" => Some((true, true)),
+        _ => throw!(MySQLSourceError::InvalidSslMode(mode)),
+    }
+}
+
+/// Bundles the PEM certificate and key into a PKCS#12 archive, encrypted with a random
+/// password kept in the returned identity only, and writes it into a new temp directory that
+/// only the current user can open.
+#[throws(MySQLSourceError)]
+fn client_identity(cert: &str, key: &str) -> (ClientIdentity, TempDir) {
+    use openssl::{pkcs12::Pkcs12, pkey::PKey, rand::rand_bytes, x509::X509};
+    use std::io::Write;
+
+    let read = |path: &str| std::fs::read(path).map_err(|e| anyhow!("cannot read {}: {}", path, e));
+    let cert = X509::from_pem(&read(cert)?).map_err(anyhow::Error::from)?;
+    let key = PKey::private_key_from_pem(&read(key)?).map_err(anyhow::Error::from)?;
+    let mut secret = [0u8; 16];
+    rand_bytes(&mut secret).map_err(anyhow::Error::from)?;
+    let password: String = secret.iter().map(|b| format!("{:02x}", b)).collect();
+    let der = Pkcs12::builder()
+        .name("connectorx")
+        .pkey(&key)
+        .cert(&cert)
+        .build2(&password)
+        .and_then(|p12| p12.to_der())
+        .map_err(anyhow::Error::from)?;
+
+    let dir = tempfile::Builder::new()
+        .prefix("cx_mysql_identity")
+        .tempdir()
+        .map_err(anyhow::Error::from)?;
+    let path = dir.path().join("identity.p12");
+    let mut options = std::fs::OpenOptions::new();
+    // never opens a file planted at the path, nor follows a link there
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(&path)
+        .and_then(|mut f| f.write_all(&der))
+        .map_err(anyhow::Error::from)?;
+    (ClientIdentity::new(path).with_password(password), dir)
+}
+
+/// Database, table and name of the table column `col` is read from.
+fn column_origin(col: &Column) -> (String, String, String) {
+    (
+        col.schema_str().into_owned(),
+        col.org_table_str().into_owned(),
+        col.org_name_str().into_owned(),
+    )
+}
+
+#[throws(MySQLSourceError)]
+fn get_total_rows(conn: &mut MysqlConn, query: &CXQuery<String>, params: &[ScalarValue]) -> usize {
+    let count = count_query(query, &MySqlDialect {})?;
+    let nrows = if params.is_empty() {
+        conn.query_first(&count)?
+    } else {
+        conn.exec_first(&count, mysql_params(params))?
+    };
+    nrows.ok_or_else(|| anyhow!("mysql failed to get the count of query: {}", query))?
+}
+
+/// What `fetch_metadata` resolved, handed between sources by [`Source::metadata`].
+#[derive(Clone)]
+struct MySQLMetadata {
+    names: Vec<String>,
+    schema: Vec<MySQLTypeSystem>,
+    column_origins: Vec<(String, String, String)>,
 }
 
 pub struct MySQLSource<P> {
-    pool: Arc<Pool<MySqlConnectionManager>>,
+    pool: Arc<ManagedPool<MySqlConnectionManager>>,
+    pinned: Option<PinnedConnection<MySqlConnectionManager>>,
     origin_query: Option<String>,
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<MySQLTypeSystem>,
+    /// Database, table and name of the table column each column is read from, empty for
+    /// computed columns.
+    column_origins: Vec<(String, String, String)>,
     pre_execution_queries: Option<Vec<String>>,
+    params: Vec<ScalarValue>,
+    /// Read `SET` columns as lists of their members rather than comma-joined strings.
+    set_as_list: bool,
+    invalid_utf8: InvalidUtf8Policy,
     _protocol: PhantomData<P>,
 }
 
 impl<P> MySQLSource<P> {
     #[throws(MySQLSourceError)]
-    pub fn new(conn: &str, nconn: usize, pool: Option<Arc<Pool<MySqlConnectionManager>>>) -> Self {
+    pub fn new(
+        conn: &str,
+        nconn: usize,
+        pool: Option<Arc<ManagedPool<MySqlConnectionManager>>>,
+    ) -> Self {
         let pool = match pool {
             Some(p) => p,
             None => {
-                let manager =
-                    MySqlConnectionManager::new(OptsBuilder::from_opts(Opts::from_url(conn)?));
-                Arc::new(
-                    r2d2::Pool::builder()
-                        .max_size(nconn as u32)
-                        .build(manager)?,
-                )
+                let opts = mysql_opts(conn)?;
+                let manager = MySqlConnectionManager::new(OptsBuilder::from_opts(opts.opts));
+                let pool = ManagedPool::new(manager, nconn as u32)?;
+                if let Some(identity) = opts.identity {
+                    pool.hold(identity);
+                }
+                pool
             }
         };
         Self {
             pool,
+            pinned: None,
             origin_query: None,
             queries: vec![],
             names: vec![],
             schema: vec![],
+            column_origins: vec![],
             pre_execution_queries: None,
+            params: vec![],
+            set_as_list: mysql_set_as_list(conn),
+            invalid_utf8: InvalidUtf8Policy::default(),
             _protocol: PhantomData,
         }
     }
+
+    /// Values bound to the `?` placeholders of every query. Only the binary protocol sends
+    /// them; the text protocol has no server-side binding.
+    pub fn set_params(&mut self, params: &[ScalarValue]) {
+        self.params = params.to_vec();
+    }
+
+    /// Run every query on `conn` instead of connections checked out of the pool.
+    pub fn set_pinned(&mut self, conn: PinnedConnection<MySqlConnectionManager>) {
+        self.pinned = Some(conn);
+    }
+
+    fn column_type(&self, col: &Column) -> MySQLTypeSystem {
+        match MySQLTypeSystem::from(col) {
+            MySQLTypeSystem::Set(null_ok) if self.set_as_list => MySQLTypeSystem::SetList(null_ok),
+            ty => ty,
+        }
+    }
+
+    #[throws(MySQLSourceError)]
+    fn conn(&self) -> MysqlConn {
+        match &self.pinned {
+            Some(pinned) => pinned.lease()?,
+            None => checkout(&self.pool)?.into(),
+        }
+    }
 }
 
 impl<P> Source for MySQLSource<P>
@@ -112,11 +404,15 @@ where
         self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
     }
 
+    fn set_invalid_utf8(&mut self, policy: InvalidUtf8Policy) {
+        self.invalid_utf8 = policy;
+    }
+
     #[throws(MySQLSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
 
-        let mut conn = self.pool.get()?;
+        let mut conn = self.conn()?;
         let server_version_post_5_5_3 = conn.server_version() >= (5, 5, 3);
 
         let first_query = &self.queries[0];
@@ -134,6 +430,19 @@ where
                         let charset_is_utf8 = (server_version_post_5_5_3
                             && charset == UTF8MB4_GENERAL_CI)
                             || (!server_version_post_5_5_3 && charset == UTF8_GENERAL_CI);
+                        // BINARY and VARBINARY are strings in the binary character set
+                        if charset == BINARY_CHARSET
+                            && (col_type == MySQLColumnType::MYSQL_TYPE_STRING
+                                || col_type == MySQLColumnType::MYSQL_TYPE_VAR_STRING
+                                || col_type == MySQLColumnType::MYSQL_TYPE_VARCHAR)
+                        {
+                            return (
+                                col_name,
+                                MySQLTypeSystem::Blob(
+                                    !col_flags.contains(MySQLColumnFlags::NOT_NULL_FLAG),
+                                ),
+                            );
+                        }
                         if charset_is_utf8
                             && (col_type == MySQLColumnType::MYSQL_TYPE_LONG_BLOB
                                 || col_type == MySQLColumnType::MYSQL_TYPE_BLOB
@@ -147,12 +456,12 @@ where
                                 ),
                             );
                         }
-                        let d = MySQLTypeSystem::from((&col_type, &col_flags));
-                        (col_name, d)
+                        (col_name, self.column_type(col))
                     })
                     .unzip();
                 self.names = names;
                 self.schema = types;
+                self.column_origins = stmt.columns().iter().map(column_origin).collect();
             }
             Err(e) => {
                 warn!(
@@ -168,15 +477,12 @@ where
                             let (names, types) = row
                                 .columns_ref()
                                 .iter()
-                                .map(|col| {
-                                    (
-                                        col.name_str().to_string(),
-                                        MySQLTypeSystem::from((&col.column_type(), &col.flags())),
-                                    )
-                                })
+                                .map(|col| (col.name_str().to_string(), self.column_type(col)))
                                 .unzip();
                             self.names = names;
                             self.schema = types;
+                            self.column_origins =
+                                row.columns_ref().iter().map(column_origin).collect();
                             return;
                         }
                         Ok(None) => {}
@@ -213,8 +519,8 @@ where
         match &self.origin_query {
             Some(q) => {
                 let cxq = CXQuery::Naked(q.clone());
-                let mut conn = self.pool.get()?;
-                let nrows = get_total_rows(&mut conn, &cxq)?;
+                let mut conn = self.conn()?;
+                let nrows = get_total_rows(&mut conn, &cxq, &self.params)?;
                 Some(nrows)
             }
             None => None,
@@ -229,11 +535,65 @@ where
         self.schema.clone()
     }
 
+    #[throws(MySQLSourceError)]
+    fn column_comments(&mut self) -> Vec<Option<String>> {
+        let mut conn = self.conn()?;
+        let mut tables: HashMap<(&str, &str), HashMap<String, String>> = HashMap::new();
+        for (db, table, _) in &self.column_origins {
+            if table.is_empty() || tables.contains_key(&(db.as_str(), table.as_str())) {
+                continue;
+            }
+            let comments = conn.exec(
+                "SELECT COLUMN_NAME, COLUMN_COMMENT FROM INFORMATION_SCHEMA.COLUMNS \
+                 WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?",
+                (db, table),
+            )?;
+            tables.insert(
+                (db.as_str(), table.as_str()),
+                comments.into_iter().collect(),
+            );
+        }
+        self.column_origins
+            .iter()
+            .map(|(db, table, column)| {
+                tables
+                    .get(&(db.as_str(), table.as_str()))
+                    .and_then(|comments| comments.get(column))
+                    // columns without a comment have an empty one
+                    .filter(|comment| !comment.is_empty())
+                    .cloned()
+            })
+            .collect()
+    }
+
+    fn metadata(&self) -> Option<SourceMetadata> {
+        Some(Arc::new(MySQLMetadata {
+            names: self.names.clone(),
+            schema: self.schema.clone(),
+            column_origins: self.column_origins.clone(),
+        }))
+    }
+
+    fn set_metadata(&mut self, metadata: &SourceMetadata) -> bool {
+        match metadata.downcast_ref::<MySQLMetadata>() {
+            Some(metadata) => {
+                let metadata = metadata.clone();
+                self.names = metadata.names;
+                self.schema = metadata.schema;
+                self.column_origins = metadata.column_origins;
+                true
+            }
+            None => false,
+        }
+    }
+
     #[throws(MySQLSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
-        for query in self.queries {
-            let mut conn = self.pool.get()?;
+        for query in &self.queries {
+            let checkout_start = Instant::now();
+            let mut conn = self.conn()?;
+            let checkout_time = checkout_start.elapsed();
 
             if let Some(pre_queries) = &self.pre_execution_queries {
                 for pre_query in pre_queries {
@@ -241,7 +601,11 @@ where
                 }
             }
 
-            ret.push(MySQLSourcePartition::new(conn, &query, &self.schema));
+            let mut partition = MySQLSourcePartition::new(conn, query, &self.schema);
+            partition.params = self.params.clone();
+            partition.invalid_utf8 = self.invalid_utf8;
+            partition.checkout_time = checkout_time;
+            ret.push(partition);
         }
         ret
     }
@@ -251,8 +615,11 @@ pub struct MySQLSourcePartition<P> {
     conn: MysqlConn,
     query: CXQuery<String>,
     schema: Vec<MySQLTypeSystem>,
+    params: Vec<ScalarValue>,
+    invalid_utf8: InvalidUtf8Policy,
     nrows: usize,
     ncols: usize,
+    checkout_time: Duration,
     _protocol: PhantomData<P>,
 }
 
@@ -262,8 +629,11 @@ impl<P> MySQLSourcePartition<P> {
             conn,
             query: query.clone(),
             schema: schema.to_vec(),
+            params: vec![],
+            invalid_utf8: InvalidUtf8Policy::default(),
             nrows: 0,
             ncols: schema.len(),
+            checkout_time: Duration::ZERO,
             _protocol: PhantomData,
         }
     }
@@ -276,14 +646,16 @@ impl SourcePartition for MySQLSourcePartition<BinaryProtocol> {
 
     #[throws(MySQLSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = get_total_rows(&mut self.conn, &self.query)?;
+        self.nrows = get_total_rows(&mut self.conn, &self.query, &self.params)?;
     }
 
     #[throws(MySQLSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
         let stmt = self.conn.prep(self.query.as_str())?;
-        let iter = self.conn.exec_iter(stmt, ())?;
-        MySQLBinarySourceParser::new(iter, &self.schema)
+        let iter = self.conn.exec_iter(stmt, mysql_params(&self.params))?;
+        let mut parser = MySQLBinarySourceParser::new(iter, &self.schema);
+        parser.invalid_utf8 = self.invalid_utf8;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -293,6 +665,10 @@ impl SourcePartition for MySQLSourcePartition<BinaryProtocol> {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
 }
 
 impl SourcePartition for MySQLSourcePartition<TextProtocol> {
@@ -302,14 +678,16 @@ impl SourcePartition for MySQLSourcePartition<TextProtocol> {
 
     #[throws(MySQLSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = get_total_rows(&mut self.conn, &self.query)?;
+        self.nrows = get_total_rows(&mut self.conn, &self.query, &self.params)?;
     }
 
     #[throws(MySQLSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
         let query = self.query.clone();
         let iter = self.conn.query_iter(query)?;
-        MySQLTextSourceParser::new(iter, &self.schema)
+        let mut parser = MySQLTextSourceParser::new(iter, &self.schema);
+        parser.invalid_utf8 = self.invalid_utf8;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -319,6 +697,10 @@ impl SourcePartition for MySQLSourcePartition<TextProtocol> {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
 }
 
 pub struct MySQLBinarySourceParser<'a> {
@@ -328,6 +710,7 @@ pub struct MySQLBinarySourceParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    invalid_utf8: InvalidUtf8Policy,
 }
 
 impl<'a> MySQLBinarySourceParser<'a> {
@@ -339,6 +722,7 @@ impl<'a> MySQLBinarySourceParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            invalid_utf8: InvalidUtf8Policy::default(),
         }
     }
 
@@ -427,7 +811,6 @@ impl_produce_binary!(
     NaiveTime,
     NaiveDateTime,
     Decimal,
-    String,
     Vec<u8>,
     Value,
 );
@@ -439,6 +822,7 @@ pub struct MySQLTextSourceParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    invalid_utf8: InvalidUtf8Policy,
 }
 
 impl<'a> MySQLTextSourceParser<'a> {
@@ -450,6 +834,7 @@ impl<'a> MySQLTextSourceParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            invalid_utf8: InvalidUtf8Policy::default(),
         }
     }
 
@@ -536,7 +921,104 @@ impl_produce_text!(
     NaiveTime,
     NaiveDateTime,
     Decimal,
-    String,
     Vec<u8>,
     Value,
 );
+
+fn set_members(set: String) -> Vec<Option<String>> {
+    if set.is_empty() {
+        return vec![];
+    }
+    set.split(',')
+        .map(|member| Some(member.to_string()))
+        .collect()
+}
+
+// `BIT(1)` arrives as a raw byte, which `mysql` only reads as bytes, and `SET` as its members
+// joined by commas
+macro_rules! impl_produce_bit_set {
+    ($($parser: ident,)+) => {
+        $(
+            impl<'r, 'a> Produce<'r, bool> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> bool {
+                    let bits: Vec<u8> = <Self as Produce<'r, Vec<u8>>>::produce(self)?;
+                    bits.iter().any(|b| *b != 0)
+                }
+            }
+
+            impl<'r, 'a> Produce<'r, Option<bool>> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> Option<bool> {
+                    let bits: Option<Vec<u8>> = <Self as Produce<'r, Option<Vec<u8>>>>::produce(self)?;
+                    bits.map(|bits| bits.iter().any(|b| *b != 0))
+                }
+            }
+
+            impl<'r, 'a> Produce<'r, Vec<Option<String>>> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> Vec<Option<String>> {
+                    set_members(<Self as Produce<'r, String>>::produce(self)?)
+                }
+            }
+
+            impl<'r, 'a> Produce<'r, Option<Vec<Option<String>>>> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> Option<Vec<Option<String>>> {
+                    <Self as Produce<'r, Option<String>>>::produce(self)?.map(set_members)
+                }
+            }
+        )+
+    };
+}
+
+impl_produce_bit_set!(MySQLBinarySourceParser, MySQLTextSourceParser,);
+
+/// Decode a text value, which arrives as raw bytes, following `policy`.
+#[throws(MySQLSourceError)]
+fn decode_text(value: MySQLValue, policy: InvalidUtf8Policy) -> Option<String> {
+    match value {
+        MySQLValue::NULL => None,
+        MySQLValue::Bytes(bytes) => Some(policy.decode(&bytes)?.into_owned()),
+        value => Some(from_value_opt::<String>(value).map_err(|e| anyhow!("{}", e))?),
+    }
+}
+
+// text is decoded here rather than by `mysql`, which panics on invalid UTF-8
+macro_rules! impl_produce_string {
+    ($($parser: ident,)+) => {
+        $(
+            impl<'r, 'a> Produce<'r, String> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> String {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let value = self.rowbuf[ridx].take(cidx).ok_or_else(|| anyhow!("mysql cannot parse at position: ({}, {})", ridx, cidx))?;
+                    decode_text(value, self.invalid_utf8)?.ok_or_else(|| anyhow!("mysql cannot parse NULL at position: ({}, {})", ridx, cidx))?
+                }
+            }
+
+            impl<'r, 'a> Produce<'r, Option<String>> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> Option<String> {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let value = self.rowbuf[ridx].take(cidx).ok_or_else(|| anyhow!("mysql cannot parse at position: ({}, {})", ridx, cidx))?;
+                    decode_text(value, self.invalid_utf8)?
+                }
+            }
+        )+
+    };
+}
+
+impl_produce_string!(MySQLBinarySourceParser, MySQLTextSourceParser,);
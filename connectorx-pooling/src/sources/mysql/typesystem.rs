@@ -1,5 +1,6 @@
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use r2d2_mysql::mysql::consts::{ColumnFlags, ColumnType};
+use r2d2_mysql::mysql::Column;
 use rust_decimal::Decimal;
 use serde_json::Value;
 
@@ -32,6 +33,9 @@ pub enum MySQLTypeSystem {
     LongBlob(bool),
     Json(bool),
     Bit(bool),
+    BitFlag(bool),
+    Set(bool),
+    SetList(bool),
 }
 
 impl_typesystem! {
@@ -51,9 +55,11 @@ impl_typesystem! {
         { Date => NaiveDate }
         { Time => NaiveTime }
         { Decimal => Decimal }
-        { Char | VarChar | Enum => String }
+        { Char | VarChar | Enum | Set => String }
         { TinyBlob | Blob | MediumBlob | LongBlob | Bit => Vec<u8>}
         { Json => Value }
+        { BitFlag => bool }
+        { SetList => Vec<Option<String>> }
     }
 }
 
@@ -118,7 +124,26 @@ impl<'a> From<(&'a ColumnType, &'a ColumnFlags)> for MySQLTypeSystem {
             ColumnType::MYSQL_TYPE_JSON => Json(null_ok),
             ColumnType::MYSQL_TYPE_VARCHAR => VarChar(null_ok),
             ColumnType::MYSQL_TYPE_BIT => Bit(null_ok),
+            ColumnType::MYSQL_TYPE_SET => Set(null_ok),
             _ => unimplemented!("{}", format!("{:?}", ty)),
         }
     }
 }
+
+/// Like the conversion from the column type and flags, but reads `BIT(1)` as a flag and tells
+/// `SET` columns, which the server reports as strings, apart.
+impl<'a> From<&'a Column> for MySQLTypeSystem {
+    fn from(col: &'a Column) -> MySQLTypeSystem {
+        let (ty, flags) = (col.column_type(), col.flags());
+        let null_ok = !flags.contains(ColumnFlags::NOT_NULL_FLAG);
+        match ty {
+            ColumnType::MYSQL_TYPE_BIT if col.column_length() == 1 => {
+                MySQLTypeSystem::BitFlag(null_ok)
+            }
+            ColumnType::MYSQL_TYPE_STRING if flags.contains(ColumnFlags::SET_FLAG) => {
+                MySQLTypeSystem::Set(null_ok)
+            }
+            _ => MySQLTypeSystem::from((&ty, &flags)),
+        }
+    }
+}
@@ -12,6 +12,9 @@ pub enum OracleSourceError {
     #[error(transparent)]
     OraclePoolError(#[from] r2d2::Error),
 
+    #[error(transparent)]
+    OracleCheckoutError(#[from] crate::pool::PoolError),
+
     #[error(transparent)]
     OracleUrlError(#[from] url::ParseError),
 
@@ -21,6 +24,18 @@ pub enum OracleSourceError {
     #[error(transparent)]
     DecimalError(#[from] rust_decimal::Error),
 
+    #[error("Invalid arraysize '{0}': expected a positive integer")]
+    InvalidArraySize(String),
+
+    #[error("Invalid max_lob_size '{0}': expected a positive integer")]
+    InvalidMaxLobSize(String),
+
+    #[error("LOB value in column {col} exceeds max_lob_size of {limit} bytes")]
+    LobTooLarge { col: usize, limit: usize },
+
+    #[error("read_only pools need Oracle 23ai or later, the server runs Oracle {0}")]
+    ReadOnlyUnsupported(r2d2_oracle::oracle::Version),
+
     /// Any other errors that are too trivial to be put here explicitly.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
@@ -4,33 +4,43 @@ mod typesystem;
 use std::collections::HashMap;
 
 pub use self::errors::OracleSourceError;
+use self::typesystem::oracle_decimal_type;
 pub use self::typesystem::OracleTypeSystem;
-use crate::constants::{DB_BUFFER_SIZE, ORACLE_ARRAY_SIZE};
+use crate::constants::{
+    DB_BUFFER_SIZE, ORACLE_ARRAY_SIZE, ORACLE_LOB_CHUNK_SIZE, ORACLE_MAX_ARRAY_SIZE,
+};
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
+    params::{oracle_param, ScalarValue},
+    pool::{checkout, LeasedConnection, ManagedPool, PinnedConnection},
+    sources::{PartitionParser, Produce, Source, SourceMetadata, SourcePartition},
     sql::{count_query, limit1_query_oracle, CXQuery},
     utils::DummyBox,
 };
+use anyhow::anyhow;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use fehler::{throw, throws};
 use log::debug;
 use owning_ref::OwningHandle;
-use r2d2::{Pool, PooledConnection};
 use r2d2_oracle::oracle::ResultSet;
 use r2d2_oracle::{
-    oracle::{Connector, Row, Statement},
+    oracle::{
+        sql_type::{Clob, Lob, Nclob, ToSql},
+        Connector, Row, Statement,
+    },
     OracleConnectionManager,
 };
 use rust_decimal::Decimal;
 use sqlparser::dialect::Dialect;
+use std::io::Read;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use url::Url;
 use urlencoding::decode;
 
 type OracleManager = OracleConnectionManager;
-type OracleConn = PooledConnection<OracleManager>;
+type OracleConn = LeasedConnection<OracleManager>;
 
 #[derive(Debug)]
 pub struct OracleDialect {}
@@ -46,13 +56,54 @@ impl Dialect for OracleDialect {
     }
 }
 
+/// What `fetch_metadata` resolved, handed between sources by [`Source::metadata`].
+#[derive(Clone)]
+struct OracleMetadata {
+    names: Vec<String>,
+    schema: Vec<OracleTypeSystem>,
+    decimal_types: Vec<Option<(u8, i8)>>,
+}
+
 pub struct OracleSource {
-    pool: Arc<Pool<OracleManager>>,
+    pool: Arc<ManagedPool<OracleManager>>,
+    pinned: Option<PinnedConnection<OracleManager>>,
     origin_query: Option<String>,
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<OracleTypeSystem>,
+    decimal_types: Vec<Option<(u8, i8)>>,
     current_schema: Option<String>,
+    array_size: u32,
+    max_lob_size: Option<usize>,
+    params: Vec<ScalarValue>,
+}
+
+/// Reads the `arraysize` query parameter, which sets the OCI prefetch rows and fetch array
+/// size used for every statement. Defaults to `ORACLE_ARRAY_SIZE` and is capped at
+/// `ORACLE_MAX_ARRAY_SIZE`.
+#[throws(OracleSourceError)]
+pub fn oracle_array_size(conn: &Url) -> u32 {
+    match conn.query_pairs().find(|(k, _)| k == "arraysize") {
+        Some((_, v)) => match v.parse::<u32>() {
+            Ok(n) if n > 0 => n.min(ORACLE_MAX_ARRAY_SIZE),
+            _ => throw!(OracleSourceError::InvalidArraySize(v.into_owned())),
+        },
+        None => ORACLE_ARRAY_SIZE,
+    }
+}
+
+/// Reads the `max_lob_size` query parameter, the most bytes a single CLOB or NCLOB value
+/// may hold. A longer value fails the read instead of being loaded into memory. No limit
+/// if unset.
+#[throws(OracleSourceError)]
+pub fn oracle_max_lob_size(conn: &Url) -> Option<usize> {
+    match conn.query_pairs().find(|(k, _)| k == "max_lob_size") {
+        Some((_, v)) => match v.parse::<usize>() {
+            Ok(n) if n > 0 => Some(n),
+            _ => throw!(OracleSourceError::InvalidMaxLobSize(v.into_owned())),
+        },
+        None => None,
+    }
 }
 
 #[throws(OracleSourceError)]
@@ -81,33 +132,58 @@ pub fn connect_oracle(conn: &Url) -> Connector {
 
 impl OracleSource {
     #[throws(OracleSourceError)]
-    pub fn new(conn: &str, nconn: usize, pool: Option<Arc<Pool<OracleManager>>>) -> Self {
+    pub fn new(conn: &str, nconn: usize, pool: Option<Arc<ManagedPool<OracleManager>>>) -> Self {
         let conn_url = Url::parse(conn)?;
         let params: HashMap<String, String> = conn_url.query_pairs().into_owned().collect();
         let current_schema = params.get("schema").cloned();
+        let array_size = oracle_array_size(&conn_url)?;
+        let max_lob_size = oracle_max_lob_size(&conn_url)?;
         let pool = match pool {
             Some(p) => p,
             None => {
                 let connector = connect_oracle(&conn_url)?;
                 let manager = OracleConnectionManager::from_connector(connector);
-                Arc::new(
-                    r2d2::Pool::builder()
-                        .max_size(nconn as u32)
-                        .build(manager)?,
-                )
+                ManagedPool::new(manager, nconn as u32)?
             }
         };
         Self {
             pool,
+            pinned: None,
             origin_query: None,
             queries: vec![],
             names: vec![],
             schema: vec![],
+            decimal_types: vec![],
             current_schema,
+            array_size,
+            max_lob_size,
+            params: vec![],
         }
     }
+
+    /// Values bound to the `:1`, `:2`, ... placeholders of every query.
+    pub fn set_params(&mut self, params: &[ScalarValue]) {
+        self.params = params.to_vec();
+    }
+
+    /// Run every query on `conn` instead of connections checked out of the pool.
+    pub fn set_pinned(&mut self, conn: PinnedConnection<OracleManager>) {
+        self.pinned = Some(conn);
+    }
+
+    pub fn array_size(&self) -> u32 {
+        self.array_size
+    }
+
+    pub fn max_lob_size(&self) -> Option<usize> {
+        self.max_lob_size
+    }
+
     pub fn get_conn(&self) -> Result<OracleConn, OracleSourceError> {
-        let conn = self.pool.get()?;
+        let conn = match &self.pinned {
+            Some(pinned) => pinned.lease()?,
+            None => checkout(&self.pool)?.into(),
+        };
         if let Some(schema) = &self.current_schema {
             conn.set_current_schema(schema)?;
         }
@@ -145,12 +221,14 @@ where
         assert!(!self.queries.is_empty());
 
         let conn = self.get_conn()?;
+        let binds: Vec<Box<dyn ToSql>> = self.params.iter().map(oracle_param).collect();
+        let binds: Vec<&dyn ToSql> = binds.iter().map(|b| b.as_ref()).collect();
         for (i, query) in self.queries.iter().enumerate() {
             // assuming all the partition queries yield same schema
             // without rownum = 1, derived type might be wrong
             // example: select avg(test_int), test_char from test_table group by test_char
             // -> (NumInt, Char) instead of (NumtFloat, Char)
-            match conn.query(limit1_query_oracle(query)?.as_str(), &[]) {
+            match conn.query(limit1_query_oracle(query)?.as_str(), &binds) {
                 Ok(rows) => {
                     let (names, types) = rows
                         .column_info()
@@ -164,6 +242,11 @@ where
                         .unzip();
                     self.names = names;
                     self.schema = types;
+                    self.decimal_types = rows
+                        .column_info()
+                        .iter()
+                        .map(|col| oracle_decimal_type(col.oracle_type()))
+                        .collect();
                     return;
                 }
                 Err(e) if i == self.queries.len() - 1 => {
@@ -175,7 +258,7 @@ where
             }
         }
         // tried all queries but all get empty result set
-        let iter = conn.query(self.queries[0].as_str(), &[])?;
+        let iter = conn.query(self.queries[0].as_str(), &binds)?;
         let (names, types) = iter
             .column_info()
             .iter()
@@ -208,12 +291,44 @@ where
         self.schema.clone()
     }
 
+    fn decimal_types(&self) -> Vec<Option<(u8, i8)>> {
+        self.decimal_types.clone()
+    }
+
+    fn metadata(&self) -> Option<SourceMetadata> {
+        Some(Arc::new(OracleMetadata {
+            names: self.names.clone(),
+            schema: self.schema.clone(),
+            decimal_types: self.decimal_types.clone(),
+        }))
+    }
+
+    fn set_metadata(&mut self, metadata: &SourceMetadata) -> bool {
+        match metadata.downcast_ref::<OracleMetadata>() {
+            Some(metadata) => {
+                let metadata = metadata.clone();
+                self.names = metadata.names;
+                self.schema = metadata.schema;
+                self.decimal_types = metadata.decimal_types;
+                true
+            }
+            None => false,
+        }
+    }
+
     #[throws(OracleSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
         for query in &self.queries {
+            let checkout_start = Instant::now();
             let conn = self.get_conn()?;
-            ret.push(OracleSourcePartition::new(conn, &query, &self.schema));
+            let checkout_time = checkout_start.elapsed();
+            let mut partition =
+                OracleSourcePartition::new(conn, query, &self.schema, self.array_size);
+            partition.params = self.params.clone();
+            partition.max_lob_size = self.max_lob_size;
+            partition.checkout_time = checkout_time;
+            ret.push(partition);
         }
         ret
     }
@@ -223,18 +338,31 @@ pub struct OracleSourcePartition {
     conn: OracleConn,
     query: CXQuery<String>,
     schema: Vec<OracleTypeSystem>,
+    params: Vec<ScalarValue>,
     nrows: usize,
     ncols: usize,
+    checkout_time: Duration,
+    array_size: u32,
+    max_lob_size: Option<usize>,
 }
 
 impl OracleSourcePartition {
-    pub fn new(conn: OracleConn, query: &CXQuery<String>, schema: &[OracleTypeSystem]) -> Self {
+    pub fn new(
+        conn: OracleConn,
+        query: &CXQuery<String>,
+        schema: &[OracleTypeSystem],
+        array_size: u32,
+    ) -> Self {
         Self {
             conn,
             query: query.clone(),
             schema: schema.to_vec(),
+            params: vec![],
             nrows: 0,
             ncols: schema.len(),
+            checkout_time: Duration::ZERO,
+            array_size,
+            max_lob_size: None,
         }
     }
 }
@@ -246,9 +374,12 @@ impl SourcePartition for OracleSourcePartition {
 
     #[throws(OracleSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = self
-            .conn
-            .query_row_as::<usize>(count_query(&self.query, &OracleDialect {})?.as_str(), &[])?;
+        let binds: Vec<Box<dyn ToSql>> = self.params.iter().map(oracle_param).collect();
+        let binds: Vec<&dyn ToSql> = binds.iter().map(|b| b.as_ref()).collect();
+        self.nrows = self.conn.query_row_as::<usize>(
+            count_query(&self.query, &OracleDialect {})?.as_str(),
+            &binds,
+        )?;
     }
 
     #[throws(OracleSourceError)]
@@ -256,7 +387,14 @@ impl SourcePartition for OracleSourcePartition {
         let query = self.query.clone();
 
         // let iter = self.conn.query(query.as_str(), &[])?;
-        OracleTextSourceParser::new(&self.conn, query.as_str(), &self.schema)?
+        OracleTextSourceParser::new(
+            &self.conn,
+            query.as_str(),
+            &self.schema,
+            self.array_size,
+            self.max_lob_size,
+            &self.params,
+        )?
     }
 
     fn nrows(&self) -> usize {
@@ -266,6 +404,10 @@ impl SourcePartition for OracleSourcePartition {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
 }
 
 unsafe impl<'a> Send for OracleTextSourceParser<'a> {}
@@ -273,6 +415,9 @@ unsafe impl<'a> Send for OracleTextSourceParser<'a> {}
 pub struct OracleTextSourceParser<'a> {
     rows: OwningHandle<Box<Statement>, DummyBox<ResultSet<'a, Row>>>,
     rowbuf: Vec<Row>,
+    schema: Vec<OracleTypeSystem>,
+    max_lob_size: Option<usize>,
+    lob_buf: Vec<u8>,
     ncols: usize,
     current_col: usize,
     current_row: usize,
@@ -281,20 +426,41 @@ pub struct OracleTextSourceParser<'a> {
 
 impl<'a> OracleTextSourceParser<'a> {
     #[throws(OracleSourceError)]
-    pub fn new(conn: &'a OracleConn, query: &str, schema: &[OracleTypeSystem]) -> Self {
-        let stmt = conn
-            .statement(query)
-            .prefetch_rows(ORACLE_ARRAY_SIZE)
-            .fetch_array_size(ORACLE_ARRAY_SIZE)
-            .build()?;
+    pub fn new(
+        conn: &'a OracleConn,
+        query: &str,
+        schema: &[OracleTypeSystem],
+        array_size: u32,
+        max_lob_size: Option<usize>,
+        params: &[ScalarValue],
+    ) -> Self {
+        let binds: Vec<Box<dyn ToSql>> = params.iter().map(oracle_param).collect();
+        let binds: Vec<&dyn ToSql> = binds.iter().map(|b| b.as_ref()).collect();
+        let has_lobs = schema.iter().any(is_text_lob);
+        let mut builder = conn.statement(query);
+        builder
+            .prefetch_rows(array_size)
+            .fetch_array_size(array_size);
+        if has_lobs {
+            // fetch locators so CLOB/NCLOB values are streamed rather than loaded whole
+            builder.lob_locator();
+        }
+        let stmt = builder.build()?;
         let rows: OwningHandle<Box<Statement>, DummyBox<ResultSet<'a, Row>>> =
             OwningHandle::new_with_fn(Box::new(stmt), |stmt: *const Statement| unsafe {
-                DummyBox((*(stmt as *mut Statement)).query(&[]).unwrap())
+                DummyBox((*(stmt as *mut Statement)).query(&binds).unwrap())
             });
 
         Self {
             rows,
             rowbuf: Vec::with_capacity(DB_BUFFER_SIZE),
+            schema: schema.to_vec(),
+            max_lob_size,
+            lob_buf: if has_lobs {
+                vec![0; ORACLE_LOB_CHUNK_SIZE]
+            } else {
+                vec![]
+            },
             ncols: schema.len(),
             current_row: 0,
             current_col: 0,
@@ -309,6 +475,65 @@ impl<'a> OracleTextSourceParser<'a> {
         self.current_col = (self.current_col + 1) % self.ncols;
         ret
     }
+
+    /// Reads the CLOB or NCLOB at `(ridx, cidx)` through its locator, a chunk at a time.
+    #[throws(OracleSourceError)]
+    fn read_lob(&mut self, ridx: usize, cidx: usize) -> Option<String> {
+        let row = &self.rowbuf[ridx];
+        match self.schema[cidx] {
+            OracleTypeSystem::NClob(_) => match row.get::<_, Option<Nclob>>(cidx)? {
+                Some(lob) => Some(read_lob_chunks(
+                    lob,
+                    cidx,
+                    &mut self.lob_buf,
+                    self.max_lob_size,
+                )?),
+                None => None,
+            },
+            _ => match row.get::<_, Option<Clob>>(cidx)? {
+                Some(lob) => Some(read_lob_chunks(
+                    lob,
+                    cidx,
+                    &mut self.lob_buf,
+                    self.max_lob_size,
+                )?),
+                None => None,
+            },
+        }
+    }
+}
+
+fn is_text_lob(ty: &OracleTypeSystem) -> bool {
+    matches!(ty, OracleTypeSystem::Clob(_) | OracleTypeSystem::NClob(_))
+}
+
+#[throws(OracleSourceError)]
+fn read_lob_chunks<L: Lob + Read>(
+    mut lob: L,
+    col: usize,
+    buf: &mut [u8],
+    max_size: Option<usize>,
+) -> String {
+    // `size` counts characters, never more than the bytes they encode to
+    if let Some(limit) = max_size {
+        if lob.size()? > limit as u64 {
+            throw!(OracleSourceError::LobTooLarge { col, limit });
+        }
+    }
+    let mut value = Vec::new();
+    loop {
+        let n = lob.read(buf).map_err(anyhow::Error::from)?;
+        if n == 0 {
+            break;
+        }
+        value.extend_from_slice(&buf[..n]);
+        if let Some(limit) = max_size {
+            if value.len() > limit {
+                throw!(OracleSourceError::LobTooLarge { col, limit });
+            }
+        }
+    }
+    String::from_utf8(value)?
 }
 
 impl<'a> PartitionParser<'a> for OracleTextSourceParser<'a> {
@@ -370,15 +595,37 @@ macro_rules! impl_produce_text {
     };
 }
 
-impl_produce_text!(
-    i64,
-    f64,
-    String,
-    NaiveDate,
-    NaiveDateTime,
-    DateTime<Utc>,
-    Vec<u8>,
-);
+impl_produce_text!(i64, f64, NaiveDate, NaiveDateTime, DateTime<Utc>, Vec<u8>,);
+
+// CLOB and NCLOB columns are read through their locators, other text columns directly
+impl<'r, 'a> Produce<'r, String> for OracleTextSourceParser<'a> {
+    type Error = OracleSourceError;
+
+    #[throws(OracleSourceError)]
+    fn produce(&'r mut self) -> String {
+        let (ridx, cidx) = self.next_loc()?;
+        if is_text_lob(&self.schema[cidx]) {
+            self.read_lob(ridx, cidx)?
+                .ok_or_else(|| anyhow!("unexpected NULL in LOB column {}", cidx))?
+        } else {
+            self.rowbuf[ridx].get(cidx)?
+        }
+    }
+}
+
+impl<'r, 'a> Produce<'r, Option<String>> for OracleTextSourceParser<'a> {
+    type Error = OracleSourceError;
+
+    #[throws(OracleSourceError)]
+    fn produce(&'r mut self) -> Option<String> {
+        let (ridx, cidx) = self.next_loc()?;
+        if is_text_lob(&self.schema[cidx]) {
+            self.read_lob(ridx, cidx)?
+        } else {
+            self.rowbuf[ridx].get(cidx)?
+        }
+    }
+}
 
 // Manual implementation for Decimal since Oracle doesn't support it directly via FromSql
 impl<'r, 'a> Produce<'r, Decimal> for OracleTextSourceParser<'a> {
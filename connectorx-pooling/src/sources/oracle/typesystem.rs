@@ -5,13 +5,16 @@ use rust_decimal::Decimal;
 #[derive(Copy, Clone, Debug)]
 pub enum OracleTypeSystem {
     NumInt(bool),
+    NumBigInt(bool),
     NumDecimal(bool),
     Float(bool),
     NumFloat(bool),
     BinaryFloat(bool),
     BinaryDouble(bool),
     Blob(bool),
+    Raw(bool),
     Clob(bool),
+    NClob(bool),
     VarChar(bool),
     Char(bool),
     NVarChar(bool),
@@ -27,27 +30,44 @@ impl_typesystem! {
     system = OracleTypeSystem,
     mappings = {
         { NumInt => i64 }
-        { NumDecimal => Decimal }
+        { NumBigInt | NumDecimal => Decimal }
         { Float | NumFloat | BinaryFloat | BinaryDouble => f64 }
-        { Blob => Vec<u8>}
-        { Clob | VarChar | Char | NVarChar | NChar => String }
+        { Blob | Raw => Vec<u8>}
+        { Clob | NClob | VarChar | Char | NVarChar | NChar => String }
         { Date | Timestamp | TimestampNano => NaiveDateTime }
         { TimestampTz | TimestampTzNano => DateTime<Utc> }
     }
 }
 
+/// Widest `NUMBER(p)` whose values all fit in an `i64`.
+const MAX_INT64_PRECISION: u8 = 18;
+
+/// Declared precision and scale of a `NUMBER` column that is read as a decimal. `None` if
+/// the column is not such a decimal or was declared without precision (plain `NUMBER`, the
+/// result of most expressions), in which case the destination's fallback applies.
+pub(crate) fn oracle_decimal_type(ty: &OracleType) -> Option<(u8, i8)> {
+    match ty {
+        OracleType::Number(p, 0) if *p <= MAX_INT64_PRECISION => None,
+        OracleType::Number(p, s) if *s >= 0 && *s as u8 <= *p => Some((*p, *s)),
+        _ => None,
+    }
+}
+
 impl<'a> From<&'a OracleType> for OracleTypeSystem {
     fn from(ty: &'a OracleType) -> OracleTypeSystem {
         use OracleTypeSystem::*;
         match ty {
             OracleType::Number(0, 0) => NumFloat(true),
-            OracleType::Number(_, 0) => NumInt(true),
+            OracleType::Number(p, 0) if *p <= MAX_INT64_PRECISION => NumInt(true),
+            OracleType::Number(_, 0) => NumBigInt(true),
             OracleType::Number(_, _) => NumDecimal(true),
             OracleType::Float(_) => Float(true),
             OracleType::BinaryFloat => BinaryFloat(true),
             OracleType::BinaryDouble => BinaryDouble(true),
             OracleType::BLOB => Blob(true),
+            OracleType::Raw(_) | OracleType::LongRaw => Raw(true),
             OracleType::CLOB => Clob(true),
+            OracleType::NCLOB => NClob(true),
             OracleType::Char(_) | OracleType::Long => Char(true),
             OracleType::NChar(_) => NChar(true),
             OracleType::Varchar2(_) => VarChar(true),
@@ -1,12 +1,59 @@
 use crate::sources::postgres::errors::PostgresSourceError;
-use openssl::ssl::{SslConnector, SslFiletype, SslMethod, SslVerifyMode};
+use anyhow::anyhow;
+use openssl::ssl::{SslConnector, SslConnectorBuilder, SslFiletype, SslMethod, SslVerifyMode};
 use postgres::{config::SslMode, Config};
 use postgres_openssl::MakeTlsConnector;
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::path::PathBuf;
+use std::env;
+use std::path::{Path, PathBuf};
 use url::Url;
 
+/// How much of the server certificate is checked, derived from `sslmode` as in libpq.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsVerification {
+    /// **The connection is encrypted but the server is not authenticated.** Any certificate
+    /// is accepted, so a man-in-the-middle can impersonate the server. This is what
+    /// `sslmode=prefer` and `sslmode=require` give when no `sslrootcert` is supplied, and is
+    /// only meant for trusted networks or servers with self-signed certificates.
+    None,
+    /// The certificate must chain to a trusted root, but its host name is not checked
+    /// (`sslmode=verify-ca`, or `sslmode=require` together with `sslrootcert`).
+    Ca,
+    /// The certificate must chain to a trusted root and match the host connected to
+    /// (`sslmode=verify-full`).
+    Full,
+}
+
+impl TlsVerification {
+    fn from_params(params: &HashMap<String, String>) -> Self {
+        match params.get("sslmode").map(String::as_str) {
+            Some("verify-full") => TlsVerification::Full,
+            Some("verify-ca") => TlsVerification::Ca,
+            // If a root CA file exists, the behavior of sslmode=require will be the same as
+            // that of verify-ca, meaning the server certificate is validated against the CA.
+            //
+            // For more details, check out the note about backwards compatibility in
+            // https://postgresql.org/docs/current/libpq-ssl.html#LIBQ-SSL-CERTIFICATES.
+            Some("require") if params.contains_key("sslrootcert") => TlsVerification::Ca,
+            _ => TlsVerification::None,
+        }
+    }
+
+    /// The OpenSSL verify mode set on the connector.
+    pub fn verify_mode(&self) -> SslVerifyMode {
+        match self {
+            TlsVerification::None => SslVerifyMode::NONE,
+            TlsVerification::Ca | TlsVerification::Full => SslVerifyMode::PEER,
+        }
+    }
+
+    /// Whether the certificate has to match the host name.
+    pub fn verify_hostname(&self) -> bool {
+        *self == TlsVerification::Full
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TlsConfig {
     /// Postgres config, pg_config.sslmode (`sslmode`).
@@ -15,51 +62,68 @@ pub struct TlsConfig {
     pub client_cert: Option<(PathBuf, PathBuf)>,
     /// Location of the root certificate (`sslrootcert`).
     pub root_cert: Option<PathBuf>,
+    /// Server certificate checks requested through `sslmode`.
+    pub verification: TlsVerification,
 }
 
-impl TryFrom<TlsConfig> for MakeTlsConnector {
-    type Error = PostgresSourceError;
-    // The logic of this function adapted primarily from:
-    // https://github.com/sfackler/rust-postgres/pull/774
-    // We only support server side authentication (`sslrootcert`) for now
-    fn try_from(tls_config: TlsConfig) -> Result<Self, Self::Error> {
+impl TlsConfig {
+    /// The OpenSSL connector builder presenting the client certificate and trusting the root
+    /// certificate, if given. Fails when a file cannot be read or the client key does not
+    /// belong to the client certificate.
+    pub fn ssl_connector_builder(&self) -> Result<SslConnectorBuilder, PostgresSourceError> {
         let mut builder = SslConnector::builder(SslMethod::tls_client())?;
-        let ssl_mode = tls_config.pg_config.get_ssl_mode();
-        let (verify_ca, verify_hostname) = match ssl_mode {
-            SslMode::Disable | SslMode::Prefer => (false, false),
-            SslMode::Require => match tls_config.root_cert {
-                // If a root CA file exists, the behavior of sslmode=require will be the same as
-                // that of verify-ca, meaning the server certificate is validated against the CA.
-                //
-                // For more details, check out the note about backwards compatibility in
-                // https://postgresql.org/docs/current/libpq-ssl.html#LIBQ-SSL-CERTIFICATES.
-                Some(_) => (true, false),
-                None => (false, false),
-            },
-            // These two modes will not work until upstream rust-postgres supports parsing
-            // them as part of the TLS config.
-            //
-            // SslMode::VerifyCa => (true, false),
-            // SslMode::VerifyFull => (true, true),
-            _ => panic!("unexpected sslmode {:?}", ssl_mode),
-        };
-
-        if let Some((cert, key)) = tls_config.client_cert {
-            builder.set_certificate_file(cert, SslFiletype::PEM)?;
-            builder.set_private_key_file(key, SslFiletype::PEM)?;
-        }
 
-        if let Some(root_cert) = tls_config.root_cert {
-            builder.set_ca_file(root_cert)?;
+        if let Some((cert, key)) = &self.client_cert {
+            // the key goes first, OpenSSL then drops it when the certificate does not match
+            load_tls_file("client key", key, |path| {
+                builder.set_private_key_file(path, SslFiletype::PEM)
+            })?;
+            load_tls_file("client certificate", cert, |path| {
+                builder.set_certificate_file(path, SslFiletype::PEM)
+            })?;
+            builder
+                .check_private_key()
+                .map_err(|_| PostgresSourceError::TlsKeyMismatch(cert.clone(), key.clone()))?;
         }
 
-        if !verify_ca {
-            builder.set_verify(SslVerifyMode::NONE); // do not verify CA
+        if let Some(root_cert) = &self.root_cert {
+            load_tls_file("root certificate", root_cert, |path| {
+                builder.set_ca_file(path)
+            })?;
         }
 
+        builder.set_verify(self.verification.verify_mode());
+        Ok(builder)
+    }
+}
+
+/// Run `load` on `path`, naming the file in the error if it is missing or unreadable.
+fn load_tls_file(
+    kind: &'static str,
+    path: &Path,
+    load: impl FnOnce(&Path) -> Result<(), openssl::error::ErrorStack>,
+) -> Result<(), PostgresSourceError> {
+    if !path.is_file() {
+        return Err(PostgresSourceError::TlsFile(
+            kind,
+            path.to_path_buf(),
+            "no such file".to_string(),
+        ));
+    }
+    load(path).map_err(|e| PostgresSourceError::TlsFile(kind, path.to_path_buf(), e.to_string()))
+}
+
+impl TryFrom<TlsConfig> for MakeTlsConnector {
+    type Error = PostgresSourceError;
+    // The logic of this function adapted primarily from:
+    // https://github.com/sfackler/rust-postgres/pull/774
+    fn try_from(tls_config: TlsConfig) -> Result<Self, Self::Error> {
+        let builder = tls_config.ssl_connector_builder()?;
+        let verification = tls_config.verification;
+
         let mut tls_connector = MakeTlsConnector::new(builder.build());
 
-        if !verify_hostname {
+        if !verification.verify_hostname() {
             tls_connector.set_callback(|connect, _| {
                 connect.set_verify_hostname(false);
                 Ok(())
@@ -70,11 +134,21 @@ impl TryFrom<TlsConfig> for MakeTlsConnector {
     }
 }
 
-// Strip URL params not accepted by upstream rust-postgres
+// Strip URL params not accepted by upstream rust-postgres. It does not know the verify-*
+// sslmodes either, so those are passed on as `require` and enforced by our TLS connector.
 fn strip_bad_opts(url: &Url) -> Url {
     let stripped_query: Vec<(_, _)> = url
         .query_pairs()
-        .filter(|p| !matches!(&*p.0, "sslkey" | "sslcert" | "sslrootcert"))
+        .filter(|p| {
+            !matches!(
+                &*p.0,
+                "sslkey" | "sslcert" | "sslrootcert" | "cursor_fetch_size" | "client_encoding"
+            )
+        })
+        .map(|(k, v)| match (&*k, &*v) {
+            ("sslmode", "verify-ca" | "verify-full") => (k, "require".into()),
+            _ => (k, v),
+        })
         .collect();
 
     let mut url2 = url.clone();
@@ -85,12 +159,17 @@ fn strip_bad_opts(url: &Url) -> Url {
             .append_pair(&pair.0.to_string()[..], &pair.1.to_string()[..]);
     }
 
+    // rust-postgres only decodes percent escapes, not the `+` the form encoding writes for
+    // a space, e.g. in `options=-c statement_timeout=5000`
+    if let Some(query) = url2.query().map(|q| q.replace('+', "%20")) {
+        url2.set_query(Some(&query));
+    }
+
     url2
 }
 
-pub fn rewrite_tls_args(
-    conn: &Url,
-) -> Result<(Config, Option<MakeTlsConnector>), PostgresSourceError> {
+/// Parse the connection string into the rust-postgres config and the TLS settings.
+pub fn parse_tls_config(conn: &Url) -> TlsConfig {
     // We parse the config, then strip unsupported SSL opts and rewrite the URI
     // before calling conn.parse().
     //
@@ -110,11 +189,155 @@ pub fn rewrite_tls_args(
     let stripped_url = strip_bad_opts(conn);
     let pg_config: Config = stripped_url.as_str().parse().unwrap();
 
-    let tls_config = TlsConfig {
-        pg_config: pg_config.clone(),
+    TlsConfig {
+        pg_config,
         client_cert,
         root_cert,
+        verification: TlsVerification::from_params(&params),
+    }
+}
+
+/// Rows the cursor protocol fetches per round trip, from the `cursor_fetch_size` url
+/// parameter. `None` without it, in which case the whole result is requested at once.
+pub fn cursor_fetch_size(conn: &Url) -> Result<Option<usize>, PostgresSourceError> {
+    conn.query_pairs()
+        .find(|(k, _)| k == "cursor_fetch_size")
+        .map(|(_, v)| match v.parse::<usize>() {
+            Ok(n) if n > 0 && n <= i32::MAX as usize => Ok(n),
+            _ => Err(anyhow!("cursor_fetch_size must be a positive integer, got '{}'", v).into()),
+        })
+        .transpose()
+}
+
+/// The `client_encoding` url parameter, which pins the session's client encoding to UTF-8 on
+/// every pooled connection, after the pool's init queries and its `on_return_query`, so the
+/// server transcodes text from the database's encoding, e.g. LATIN1, whatever they set the
+/// session's to. Only names of UTF-8 are accepted, as text is always decoded as UTF-8.
+pub fn client_encoding(conn: &Url) -> Result<Option<&'static str>, PostgresSourceError> {
+    conn.query_pairs()
+        .find(|(k, _)| k == "client_encoding")
+        .map(|(_, v)| match v.to_ascii_uppercase().as_str() {
+            "UTF8" | "UTF-8" | "UNICODE" => Ok("UTF8"),
+            _ => Err(anyhow!("client_encoding must be UTF8, got '{}'", v).into()),
+        })
+        .transpose()
+}
+
+/// The service files searched for the `service` url parameter, in the order libpq reads
+/// them: `PGSERVICEFILE`, or `~/.pg_service.conf` without it, then `pg_service.conf` in
+/// `PGSYSCONFDIR`.
+fn pg_service_files() -> Vec<PathBuf> {
+    let user_file = match env::var_os("PGSERVICEFILE") {
+        Some(file) => Some(PathBuf::from(file)),
+        None => env::var_os("HOME").map(|home| PathBuf::from(home).join(".pg_service.conf")),
     };
+    let system_file =
+        env::var_os("PGSYSCONFDIR").map(|dir| PathBuf::from(dir).join("pg_service.conf"));
+    user_file.into_iter().chain(system_file).collect()
+}
+
+/// The `key=value` lines of the `[service]` section of the service file `content`, or
+/// `None` if it has no such section.
+fn read_pg_service(
+    content: &str,
+    service: &str,
+    file: &Path,
+) -> Result<Option<Vec<(String, String)>>, PostgresSourceError> {
+    let mut entry: Option<Vec<(String, String)>> = None;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if entry.is_some() {
+                break;
+            }
+            if name == service {
+                entry = Some(vec![]);
+            }
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| {
+            anyhow!(
+                "Syntax error in service file {}, line {}",
+                file.display(),
+                i + 1
+            )
+        })?;
+        if let Some(entry) = entry.as_mut() {
+            entry.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    Ok(entry)
+}
+
+/// Fill in the connection parameters named by a `service` url parameter, such as
+/// `postgres:///?service=prod`, from the entry of that name in the first service file
+/// holding it (see [`pg_service_files`]). Parameters the url sets itself take precedence
+/// over the entry. Urls without `service` are returned unchanged.
+pub fn resolve_pg_service(conn: &Url) -> Result<Url, PostgresSourceError> {
+    let (service, params): (Vec<_>, Vec<_>) = conn
+        .query_pairs()
+        .into_owned()
+        .partition(|(k, _)| k == "service");
+    let service = match service.into_iter().last() {
+        Some((_, service)) => service,
+        None => return Ok(conn.clone()),
+    };
+
+    let file_list = |files: &[PathBuf]| {
+        files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (existing, missing): (Vec<_>, Vec<_>) =
+        pg_service_files().into_iter().partition(|file| file.is_file());
+    if existing.is_empty() {
+        return Err(PostgresSourceError::ServiceFileNotFound(
+            service,
+            file_list(&missing),
+        ));
+    }
+    let mut entry = None;
+    for file in &existing {
+        entry = read_pg_service(&std::fs::read_to_string(file)?, &service, file)?;
+        if entry.is_some() {
+            break;
+        }
+    }
+    let entry = entry
+        .ok_or_else(|| PostgresSourceError::ServiceNotFound(service, file_list(&existing)))?;
+
+    let in_url = |key: &str| match key {
+        "host" | "hostaddr" => conn.host_str().is_some_and(|host| !host.is_empty()),
+        "port" => conn.port().is_some(),
+        "user" => !conn.username().is_empty(),
+        "password" => conn.password().is_some(),
+        "dbname" => !conn.path().trim_start_matches('/').is_empty(),
+        _ => false,
+    };
+    let from_service: Vec<_> = entry
+        .into_iter()
+        .filter(|(k, _)| !in_url(k) && !params.iter().any(|(p, _)| p == k))
+        .collect();
+    let mut url = conn.clone();
+    url.set_query(None);
+    if !params.is_empty() || !from_service.is_empty() {
+        url.query_pairs_mut()
+            .extend_pairs(params)
+            .extend_pairs(from_service);
+    }
+    Ok(url)
+}
+
+pub fn rewrite_tls_args(
+    conn: &Url,
+) -> Result<(Config, Option<MakeTlsConnector>), PostgresSourceError> {
+    let tls_config = parse_tls_config(&resolve_pg_service(conn)?);
+    let pg_config = tls_config.pg_config.clone();
 
     let tls_connector = match pg_config.get_ssl_mode() {
         SslMode::Disable => None,
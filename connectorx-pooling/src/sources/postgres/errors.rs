@@ -1,13 +1,22 @@
+use postgres::error::{DbError, SqlState};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum PostgresSourceError {
+    #[error(
+        "Column {0} holds a multi-dimensional array, only one-dimensional arrays are supported"
+    )]
+    MultiDimensionalArray(usize),
+
     #[error(transparent)]
     ConnectorXError(#[from] crate::errors::ConnectorXError),
 
     #[error(transparent)]
     PostgresPoolError(#[from] r2d2::Error),
 
+    #[error(transparent)]
+    PostgresCheckoutError(#[from] crate::pool::PoolError),
+
     #[error(transparent)]
     PostgresError(#[from] postgres::Error),
 
@@ -23,7 +32,34 @@ pub enum PostgresSourceError {
     #[error(transparent)]
     TlsError(#[from] openssl::error::ErrorStack),
 
+    #[error("Cannot load the TLS {0} {}: {2}", .1.display())]
+    TlsFile(&'static str, std::path::PathBuf, String),
+
+    #[error("The TLS client key {} does not match the certificate {}", .1.display(), .0.display())]
+    TlsKeyMismatch(std::path::PathBuf, std::path::PathBuf),
+
+    #[error("Cannot look up service {0}, none of the service files {1} exists")]
+    ServiceFileNotFound(String, String),
+
+    #[error("Service {0} is not defined in the service files {1}")]
+    ServiceNotFound(String, String),
+
     /// Any other errors that are too trivial to be put here explicitly.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+/// Whether `err` was caused by a serialization failure (`40001`) or a deadlock (`40P01`),
+/// which Postgres resolves by aborting one transaction that is safe to run again.
+pub fn is_serialization_failure(err: &(dyn std::error::Error + 'static)) -> bool {
+    std::iter::successors(Some(err), |e| e.source()).any(|e| {
+        let code = match e.downcast_ref::<postgres::Error>() {
+            Some(e) => e.code(),
+            None => e.downcast_ref::<DbError>().map(DbError::code),
+        };
+        matches!(
+            code,
+            Some(&SqlState::T_R_SERIALIZATION_FAILURE) | Some(&SqlState::T_R_DEADLOCK_DETECTED)
+        )
+    })
+}
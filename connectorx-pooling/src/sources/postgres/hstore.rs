@@ -0,0 +1,95 @@
+use anyhow::{anyhow, ensure};
+use postgres::types::{FromSql, Type};
+use std::collections::HashMap;
+use std::error::Error;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+/// A Postgres `hstore`, its keys mapped to their value, or to `None` where the value is NULL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Hstore(HashMap<String, Option<String>>);
+
+impl Hstore {
+    pub fn into_inner(self) -> HashMap<String, Option<String>> {
+        self.0
+    }
+}
+
+impl<'a> FromSql<'a> for Hstore {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Hstore(HashMap::from_sql(ty, raw)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "hstore"
+    }
+}
+
+/// Parses the text of an hstore, e.g. `"a"=>"1", "b"=>NULL`. Keys and values may also be
+/// left unquoted, as Postgres accepts on input; a key given twice keeps its first value.
+impl FromStr for Hstore {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars().peekable();
+        let mut map = HashMap::new();
+        loop {
+            skip_whitespace(&mut chars);
+            if chars.peek().is_none() {
+                break;
+            }
+            let (key, _) = token(&mut chars)?;
+            skip_whitespace(&mut chars);
+            ensure!(
+                chars.next() == Some('=') && chars.next() == Some('>'),
+                "expected `=>` after the key {:?} of the hstore {:?}",
+                key,
+                s
+            );
+            skip_whitespace(&mut chars);
+            let value = match token(&mut chars)? {
+                (value, false) if value.eq_ignore_ascii_case("NULL") => None,
+                (value, _) => Some(value),
+            };
+            map.entry(key).or_insert(value);
+            skip_whitespace(&mut chars);
+            match chars.next() {
+                None => break,
+                Some(',') => {}
+                Some(c) => return Err(anyhow!("unexpected {:?} in the hstore {:?}", c, s)),
+            }
+        }
+        Ok(Hstore(map))
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// A key or value, unescaped, and whether it was quoted.
+fn token(chars: &mut Peekable<Chars<'_>>) -> anyhow::Result<(String, bool)> {
+    let mut token = String::new();
+    if chars.next_if_eq(&'"').is_some() {
+        loop {
+            match chars.next() {
+                Some('"') => return Ok((token, true)),
+                Some('\\') => token.push(chars.next().ok_or_else(trailing_backslash)?),
+                Some(c) => token.push(c),
+                None => return Err(anyhow!("unterminated quoted string in the hstore")),
+            }
+        }
+    }
+    while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != '=' && c != ',') {
+        match c {
+            '\\' => token.push(chars.next().ok_or_else(trailing_backslash)?),
+            c => token.push(c),
+        }
+    }
+    ensure!(!token.is_empty(), "expected a key or value in the hstore");
+    Ok((token, false))
+}
+
+fn trailing_backslash() -> anyhow::Error {
+    anyhow!("the hstore ends in a backslash")
+}
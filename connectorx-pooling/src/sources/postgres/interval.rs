@@ -0,0 +1,137 @@
+use anyhow::anyhow;
+use postgres::types::{FromSql, Type};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+
+/// A Postgres `interval`. Months, days and the time of day are kept apart as Postgres does,
+/// since how long a month or a day lasts depends on the timestamp the interval is added to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PgInterval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl<'a> FromSql<'a> for PgInterval {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err(format!("invalid interval of {} bytes", raw.len()).into());
+        }
+        Ok(PgInterval {
+            microseconds: i64::from_be_bytes(raw[0..8].try_into()?),
+            days: i32::from_be_bytes(raw[8..12].try_into()?),
+            months: i32::from_be_bytes(raw[12..16].try_into()?),
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+}
+
+/// Parses the text Postgres sends with the default `IntervalStyle` of `postgres`, e.g.
+/// `1 year 2 mons -3 days +04:05:06.5`.
+impl FromStr for PgInterval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || anyhow!("invalid interval {:?}", s);
+        let mut interval = PgInterval::default();
+        let mut tokens = s.split_whitespace();
+        while let Some(token) = tokens.next() {
+            if token.contains(':') {
+                let (sign, time) = match token.strip_prefix('-') {
+                    Some(time) => (-1, time),
+                    None => (1, token.trim_start_matches('+')),
+                };
+                let mut parts = time.splitn(3, ':');
+                let hours: i64 = parts.next().ok_or_else(invalid)?.parse()?;
+                let minutes: i64 = parts.next().ok_or_else(invalid)?.parse()?;
+                let seconds = parts.next().unwrap_or("0");
+                let (whole, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+                if fraction.len() > 6 {
+                    return Err(invalid());
+                }
+                let fraction = match fraction {
+                    "" => 0,
+                    f => f.parse::<i64>()? * 10i64.pow(6 - f.len() as u32),
+                };
+                interval.microseconds += sign
+                    * (hours * MICROS_PER_HOUR
+                        + minutes * MICROS_PER_MINUTE
+                        + whole.parse::<i64>()? * MICROS_PER_SECOND
+                        + fraction);
+            } else {
+                let value: i32 = token.parse()?;
+                match tokens.next().ok_or_else(invalid)? {
+                    "year" | "years" => interval.months += value * 12,
+                    "mon" | "mons" => interval.months += value,
+                    "day" | "days" => interval.days += value,
+                    _ => return Err(invalid()),
+                }
+            }
+        }
+        Ok(interval)
+    }
+}
+
+/// Formats the interval as Postgres does with the default `IntervalStyle` of `postgres`.
+impl fmt::Display for PgInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut is_zero = true;
+        // a positive field following a negative one is written with a `+`
+        let mut is_before = false;
+        let parts = [
+            (self.months / 12, "year"),
+            (self.months % 12, "mon"),
+            (self.days, "day"),
+        ];
+        for (value, unit) in parts {
+            if value == 0 {
+                continue;
+            }
+            write!(
+                f,
+                "{}{}{} {}{}",
+                if is_zero { "" } else { " " },
+                if is_before && value > 0 { "+" } else { "" },
+                value,
+                unit,
+                if value != 1 { "s" } else { "" }
+            )?;
+            is_before = value < 0;
+            is_zero = false;
+        }
+
+        if is_zero || self.microseconds != 0 {
+            let sign = if self.microseconds < 0 {
+                "-"
+            } else if is_before {
+                "+"
+            } else {
+                ""
+            };
+            let micros = self.microseconds.unsigned_abs();
+            write!(
+                f,
+                "{}{}{:02}:{:02}:{:02}",
+                if is_zero { "" } else { " " },
+                sign,
+                micros / MICROS_PER_HOUR as u64,
+                micros % MICROS_PER_HOUR as u64 / MICROS_PER_MINUTE as u64,
+                micros % MICROS_PER_MINUTE as u64 / MICROS_PER_SECOND as u64,
+            )?;
+            let fraction = micros % MICROS_PER_SECOND as u64;
+            if fraction != 0 {
+                write!(f, ".{}", format!("{:06}", fraction).trim_end_matches('0'))?;
+            }
+        }
+        Ok(())
+    }
+}
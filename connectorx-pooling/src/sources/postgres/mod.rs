@@ -2,19 +2,32 @@
 
 mod connection;
 mod errors;
+mod hstore;
+mod interval;
 mod typesystem;
+mod watchdog;
+mod wkb;
 
-pub use self::errors::PostgresSourceError;
+pub use self::errors::{is_serialization_failure, PostgresSourceError};
 pub use cidr_02::IpInet;
-pub use connection::rewrite_tls_args;
+pub use connection::{
+    client_encoding, cursor_fetch_size, parse_tls_config, resolve_pg_service, rewrite_tls_args,
+    TlsConfig, TlsVerification,
+};
+pub use hstore::Hstore;
+pub use interval::PgInterval;
 pub use pgvector::{Bit, HalfVector, SparseVector, Vector};
 pub use typesystem::{PostgresTypePairs, PostgresTypeSystem};
+pub use watchdog::{FetchGuard, FetchWatchdog};
+pub use wkb::Wkb;
 
 use crate::constants::DB_BUFFER_SIZE;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
+    params::ScalarValue,
+    pool::{checkout, LeasedConnection, ManagedPool, PinnedConnection},
+    sources::{PartitionParser, Produce, Source, SourceMetadata, SourcePartition},
     sql::{count_query, CXQuery},
 };
 use anyhow::anyhow;
@@ -22,13 +35,13 @@ use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use csv::{ReaderBuilder, StringRecord, StringRecordsIntoIter};
 use fehler::{throw, throws};
 use hex::decode;
+use log::debug;
 use postgres::{
     binary_copy::{BinaryCopyOutIter, BinaryCopyOutRow},
     fallible_iterator::FallibleIterator,
     tls::{MakeTlsConnect, TlsConnect},
-    Config, CopyOutReader, Row, RowIter, SimpleQueryMessage, Socket,
+    Config, CopyOutReader, Portal, Row, RowIter, SimpleQueryMessage, Socket, Transaction,
 };
-use r2d2::{Pool, PooledConnection};
 use r2d2_postgres::PostgresConnectionManager;
 use rust_decimal::Decimal;
 use serde_json::{from_str, Value};
@@ -37,6 +50,7 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 /// Protocol - Binary based bulk load
@@ -52,7 +66,7 @@ pub enum CursorProtocol {}
 pub enum SimpleProtocol {}
 
 type PgManager<C> = PostgresConnectionManager<C>;
-type PgConn<C> = PooledConnection<PgManager<C>>;
+type PgConn<C> = LeasedConnection<PgManager<C>>;
 
 macro_rules! impl_produce_unimplemented {
     ($(($protocol: ty, $t: ty, $msg: expr),)+) => {
@@ -79,14 +93,12 @@ macro_rules! impl_produce_unimplemented {
 }
 
 impl_produce_unimplemented!(
-    (PostgresCSVSourceParser<'_>, HashMap<String, Option<String>>, "Please use `cursor` protocol for hstore type"),
     (PostgresCSVSourceParser<'_>, Vector, "Please use `binary` protocol for vector type"),
     (PostgresCSVSourceParser<'_>, HalfVector, "Please use `binary` protocol for halfvector type"),
     (PostgresCSVSourceParser<'_>, Bit, "Please use `binary` protocol for bit type"),
     (PostgresCSVSourceParser<'_>, SparseVector, "Please use `binary` protocol for sparsevector type"),
 
 
-    (PostgresSimpleSourceParser,HashMap<String, Option<String>>, "unimplemented"),
     (PostgresSimpleSourceParser,Value, "unimplemented"),
     (PostgresSimpleSourceParser, Vector, "Please use `binary` protocol for vector type"),
     (PostgresSimpleSourceParser, HalfVector, "Please use `binary` protocol for halfvector type"),
@@ -102,7 +114,12 @@ fn convert_row<'b, R: TryFrom<usize> + postgres::types::FromSql<'b> + Clone>(row
 }
 
 #[throws(PostgresSourceError)]
-fn get_total_rows<C>(conn: &mut PgConn<C>, query: &CXQuery<String>) -> usize
+fn get_total_rows<C>(
+    conn: &mut PgConn<C>,
+    query: &CXQuery<String>,
+    params: &[ScalarValue],
+    reuse_prepared: bool,
+) -> usize
 where
     C: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
     C::TlsConnect: Send,
@@ -111,7 +128,15 @@ where
 {
     let dialect = PostgreSqlDialect {};
 
-    let row = conn.query_one(count_query(query, &dialect)?.as_str(), &[])?;
+    let params: Vec<&(dyn postgres::types::ToSql + Sync)> = params.iter().map(|p| p as _).collect();
+    let count_query = count_query(query, &dialect)?;
+    let row = match reuse_prepared {
+        true => {
+            let stmt = prepare_cached(conn, count_query.as_str())?;
+            conn.query_one(&stmt, &params)?
+        }
+        false => conn.query_one(count_query.as_str(), &params)?,
+    };
     let col_type = PostgresTypeSystem::from(row.columns()[0].type_());
     match col_type {
         PostgresTypeSystem::Int2(_) => convert_row::<i16>(&row) as usize,
@@ -123,6 +148,72 @@ where
     }
 }
 
+/// Statements prepared on a pooled connection, by query text.
+struct PreparedStatements(HashMap<String, postgres::Statement>);
+
+/// Prepare `query` on `conn` once, keeping the statement with the connection in the pool so
+/// later checkouts of the same connection execute it without preparing it again.
+#[throws(PostgresSourceError)]
+fn prepare_cached<C>(conn: &mut PgConn<C>, query: &str) -> postgres::Statement
+where
+    C: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
+    C::TlsConnect: Send,
+    C::Stream: Send,
+    <C::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    let cached = conn
+        .extensions_mut()
+        .get::<PreparedStatements>()
+        .and_then(|prepared| prepared.0.get(query).cloned());
+    match cached {
+        Some(stmt) => stmt,
+        None => {
+            let stmt = conn.prepare(query)?;
+            let extensions = conn.extensions_mut();
+            if extensions.get::<PreparedStatements>().is_none() {
+                extensions.insert(PreparedStatements(HashMap::new()));
+            }
+            if let Some(prepared) = extensions.get_mut::<PreparedStatements>() {
+                prepared.0.insert(query.to_string(), stmt.clone());
+            }
+            stmt
+        }
+    }
+}
+
+/// `ident` as a quoted Postgres identifier, so it is taken as is, case included.
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Precision and scale declared by the type modifier of a `NUMERIC(p, s)` column. `None`
+/// for an unconstrained `NUMERIC`, or one Arrow's `Decimal128` cannot hold exactly: wider
+/// than 38 digits, or with a negative scale or one above the precision (Postgres 15+).
+pub fn numeric_precision(type_modifier: i32) -> Option<(u8, i8)> {
+    // ((precision << 16) | scale) + VARHDRSZ, -1 without a modifier
+    let modifier = type_modifier.checked_sub(4).filter(|m| *m >= 0)?;
+    let precision = (modifier >> 16) & 0xffff;
+    // the scale is an 11 bit two's complement number
+    let scale = ((modifier & 0x7ff) ^ 0x400) - 0x400;
+    match (1..=38).contains(&precision) && (0..=precision).contains(&scale) {
+        true => Some((precision as u8, scale as i8)),
+        false => None,
+    }
+}
+
+/// What `fetch_metadata` resolved, handed between sources by [`Source::metadata`].
+#[derive(Clone)]
+struct PostgresMetadata {
+    names: Vec<String>,
+    schema: Vec<PostgresTypeSystem>,
+    pg_schema: Vec<postgres::types::Type>,
+    decimal_types: Vec<Option<(u8, i8)>>,
+    enum_labels: Vec<Option<Vec<String>>>,
+    column_origins: Vec<(u32, i32)>,
+    /// The schema reads geometries as WKT rather than WKB.
+    geometry_as_wkt: bool,
+}
+
 pub struct PostgresSource<P, C>
 where
     C: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
@@ -130,13 +221,26 @@ where
     C::Stream: Send,
     <C::TlsConnect as TlsConnect<Socket>>::Future: Send,
 {
-    pool: Arc<Pool<PgManager<C>>>,
+    pool: Arc<ManagedPool<PgManager<C>>>,
+    tls: C,
+    pinned: Option<PinnedConnection<PgManager<C>>>,
     origin_query: Option<String>,
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<PostgresTypeSystem>,
     pg_schema: Vec<postgres::types::Type>,
+    decimal_types: Vec<Option<(u8, i8)>>,
+    enum_labels: Vec<Option<Vec<String>>>,
+    /// Table oid and attribute number each column is read from, zeros for computed columns.
+    column_origins: Vec<(u32, i32)>,
     pre_execution_queries: Option<Vec<String>>,
+    role: Option<String>,
+    geometry_as_wkt: bool,
+    params: Vec<ScalarValue>,
+    partition_params: Vec<Vec<ScalarValue>>,
+    reuse_prepared: bool,
+    cursor_fetch_size: Option<usize>,
+    batch_timeout: Option<Duration>,
     _protocol: PhantomData<P>,
 }
 
@@ -152,26 +256,105 @@ where
         config: Config,
         tls: C,
         nconn: usize,
-        pool: Option<Arc<Pool<PgManager<C>>>>,
+        pool: Option<Arc<ManagedPool<PgManager<C>>>>,
     ) -> Self {
         let pool = match pool {
             Some(p) => p,
             None => {
-                let manager = PostgresConnectionManager::new(config, tls);
-                Arc::new(Pool::builder().max_size(nconn as u32).build(manager)?)
+                let manager = PostgresConnectionManager::new(config, tls.clone());
+                ManagedPool::new(manager, nconn as u32)?
             }
         };
         Self {
             pool,
+            tls,
+            pinned: None,
             origin_query: None,
             queries: vec![],
             names: vec![],
             schema: vec![],
             pg_schema: vec![],
+            decimal_types: vec![],
+            enum_labels: vec![],
+            column_origins: vec![],
             pre_execution_queries: None,
+            role: None,
+            geometry_as_wkt: false,
+            params: vec![],
+            partition_params: vec![],
+            reuse_prepared: false,
+            cursor_fetch_size: None,
+            batch_timeout: None,
             _protocol: PhantomData,
         }
     }
+
+    /// Rows the cursor protocol fetches per round trip through a portal, trading memory for
+    /// round trips. `None` requests the whole result at once. Ignored on a pinned connection,
+    /// which may already be inside a transaction the portal's own would end.
+    pub fn set_cursor_fetch_size(&mut self, fetch_size: Option<usize>) {
+        self.cursor_fetch_size = fetch_size;
+    }
+
+    /// Cancels a partition's query on the server once a single fetch of it has waited
+    /// longer than `timeout`, failing its read instead of leaving the worker and the
+    /// connection to a query that may never return. `None` lets fetches wait indefinitely.
+    pub fn set_batch_timeout(&mut self, timeout: Option<Duration>) {
+        self.batch_timeout = timeout;
+    }
+
+    /// Role every partition reads under, set with `SET ROLE` on its connection before the
+    /// pre-execution queries and reset with `RESET ROLE` once the partition is dropped,
+    /// whether the read succeeded or not, e.g. for row-level security. Only the partitions'
+    /// connections take the role; the metadata is read under the connection's own.
+    pub fn set_role(&mut self, role: Option<&str>) {
+        self.role = role.map(str::to_string);
+    }
+
+    /// Read PostGIS `geometry` and `geography` columns as WKT text rather than WKB.
+    pub fn set_geometry_as_wkt(&mut self, enabled: bool) {
+        self.geometry_as_wkt = enabled;
+    }
+
+    /// Values bound to the `$n` placeholders of every query. Only the cursor protocol sends
+    /// them; the COPY based protocols cannot carry parameters.
+    pub fn set_params(&mut self, params: &[ScalarValue]) {
+        self.params = params.to_vec();
+    }
+
+    /// Values bound to the placeholders of each query after the shared [`set_params`] ones,
+    /// one list per query, e.g. the bounds of a range partition template.
+    ///
+    /// [`set_params`]: PostgresSource::set_params
+    pub fn set_partition_params(&mut self, params: Vec<Vec<ScalarValue>>) {
+        self.partition_params = params;
+    }
+
+    /// Keep the statements the cursor protocol prepares with the pooled connection they run
+    /// on, so partitions sharing a query text prepare it once per connection, also across
+    /// reads from the same pool.
+    pub fn set_reuse_prepared(&mut self, reuse_prepared: bool) {
+        self.reuse_prepared = reuse_prepared;
+    }
+
+    fn query_params(&self, i: usize) -> Vec<ScalarValue> {
+        let mut params = self.params.clone();
+        params.extend(self.partition_params.get(i).into_iter().flatten().cloned());
+        params
+    }
+
+    /// Run every query on `conn` instead of connections checked out of the pool.
+    pub fn set_pinned(&mut self, conn: PinnedConnection<PgManager<C>>) {
+        self.pinned = Some(conn);
+    }
+
+    #[throws(PostgresSourceError)]
+    fn conn(&self) -> PgConn<C> {
+        match &self.pinned {
+            Some(pinned) => pinned.lease()?,
+            None => checkout(&self.pool)?.into(),
+        }
+    }
 }
 
 impl<P, C> Source for PostgresSource<P, C>
@@ -212,7 +395,7 @@ where
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
 
-        let mut conn = self.pool.get()?;
+        let mut conn = self.conn()?;
         let first_query = &self.queries[0];
 
         let stmt = conn.prepare(first_query.as_str())?;
@@ -222,9 +405,43 @@ where
             .iter()
             .map(|col| (col.name().to_string(), col.type_().clone()))
             .unzip();
+        self.decimal_types = stmt
+            .columns()
+            .iter()
+            .map(|col| match col.type_() {
+                &postgres::types::Type::NUMERIC => numeric_precision(col.type_modifier()),
+                _ => None,
+            })
+            .collect();
+        // the driver reads the labels from pg_enum when it first resolves the type
+        self.enum_labels = pg_types
+            .iter()
+            .map(|ty| match ty.kind() {
+                postgres::types::Kind::Enum(labels) => Some(labels.clone()),
+                _ => None,
+            })
+            .collect();
+        self.column_origins = stmt
+            .columns()
+            .iter()
+            .map(|col| {
+                (
+                    col.table_oid().unwrap_or(0),
+                    col.column_id().unwrap_or(0) as i32,
+                )
+            })
+            .collect();
 
         self.names = names;
-        self.schema = pg_types.iter().map(PostgresTypeSystem::from).collect();
+        self.schema = pg_types
+            .iter()
+            .map(|ty| match PostgresTypeSystem::from(ty) {
+                PostgresTypeSystem::Geometry(nullable) if self.geometry_as_wkt => {
+                    PostgresTypeSystem::GeometryWkt(nullable)
+                }
+                ty => ty,
+            })
+            .collect();
         self.pg_schema = self
             .schema
             .iter()
@@ -238,8 +455,8 @@ where
         match &self.origin_query {
             Some(q) => {
                 let cxq = CXQuery::Naked(q.clone());
-                let mut conn = self.pool.get()?;
-                let nrows = get_total_rows(&mut conn, &cxq)?;
+                let mut conn = self.conn()?;
+                let nrows = get_total_rows(&mut conn, &cxq, &self.params, false)?;
                 Some(nrows)
             }
             None => None,
@@ -254,24 +471,93 @@ where
         self.schema.clone()
     }
 
+    fn decimal_types(&self) -> Vec<Option<(u8, i8)>> {
+        self.decimal_types.clone()
+    }
+
+    fn enum_labels(&self) -> Vec<Option<Vec<String>>> {
+        self.enum_labels.clone()
+    }
+
+    #[throws(PostgresSourceError)]
+    fn column_comments(&mut self) -> Vec<Option<String>> {
+        let (tables, attnums): (Vec<u32>, Vec<i32>) = self.column_origins.iter().copied().unzip();
+        let mut conn = self.conn()?;
+        conn.query(
+            "SELECT col_description(t.reloid, t.attnum) \
+             FROM unnest($1::oid[], $2::int4[]) WITH ORDINALITY AS t(reloid, attnum, i) \
+             ORDER BY t.i",
+            &[&tables, &attnums],
+        )?
+        .iter()
+        .map(|row| row.get(0))
+        .collect()
+    }
+
+    fn metadata(&self) -> Option<SourceMetadata> {
+        Some(Arc::new(PostgresMetadata {
+            names: self.names.clone(),
+            schema: self.schema.clone(),
+            pg_schema: self.pg_schema.clone(),
+            decimal_types: self.decimal_types.clone(),
+            enum_labels: self.enum_labels.clone(),
+            column_origins: self.column_origins.clone(),
+            geometry_as_wkt: self.geometry_as_wkt,
+        }))
+    }
+
+    fn set_metadata(&mut self, metadata: &SourceMetadata) -> bool {
+        match metadata.downcast_ref::<PostgresMetadata>() {
+            Some(metadata) if metadata.geometry_as_wkt == self.geometry_as_wkt => {
+                let metadata = metadata.clone();
+                self.names = metadata.names;
+                self.schema = metadata.schema;
+                self.pg_schema = metadata.pg_schema;
+                self.decimal_types = metadata.decimal_types;
+                self.enum_labels = metadata.enum_labels;
+                self.column_origins = metadata.column_origins;
+                true
+            }
+            _ => false,
+        }
+    }
+
     #[throws(PostgresSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
-        for query in self.queries {
-            let mut conn = self.pool.get()?;
+        for (i, query) in self.queries.iter().enumerate() {
+            let checkout_start = Instant::now();
+            let mut conn = self.conn()?;
+            let checkout_time = checkout_start.elapsed();
+
+            if let Some(role) = &self.role {
+                conn.batch_execute(&format!("SET ROLE {}", quote_ident(role)))?;
+            }
+            // from here on, dropping the partition resets the role
+            let mut partition =
+                PostgresSourcePartition::<P, C>::new(conn, query, &self.schema, &self.pg_schema);
+            partition.reset_role = self.role.is_some();
 
             if let Some(pre_queries) = &self.pre_execution_queries {
                 for pre_query in pre_queries {
-                    conn.query(pre_query, &[])?;
+                    partition.conn.query(pre_query, &[])?;
                 }
             }
 
-            ret.push(PostgresSourcePartition::<P, C>::new(
-                conn,
-                &query,
-                &self.schema,
-                &self.pg_schema,
-            ));
+            partition.params = self.query_params(i);
+            partition.reuse_prepared = self.reuse_prepared;
+            partition.cursor_fetch_size = self.cursor_fetch_size.filter(|_| self.pinned.is_none());
+            partition.checkout_time = checkout_time;
+            partition.watchdog = self.batch_timeout.map(|timeout| {
+                let token = partition.conn.cancel_token();
+                let tls = self.tls.clone();
+                FetchWatchdog::new(timeout, move || {
+                    if let Err(e) = token.cancel_query(tls.clone()) {
+                        debug!("Failed to cancel a stalled query: {}", e);
+                    }
+                })
+            });
+            ret.push(partition);
         }
         ret
     }
@@ -288,8 +574,16 @@ where
     query: CXQuery<String>,
     schema: Vec<PostgresTypeSystem>,
     pg_schema: Vec<postgres::types::Type>,
+    params: Vec<ScalarValue>,
+    reuse_prepared: bool,
+    cursor_fetch_size: Option<usize>,
+    /// Cancels the query once a fetch of it stalls, set by a batch timeout.
+    watchdog: Option<FetchWatchdog>,
+    /// Whether the connection was given a role by `SET ROLE` that must not outlive the read.
+    reset_role: bool,
     nrows: usize,
     ncols: usize,
+    checkout_time: Duration,
     _protocol: PhantomData<P>,
 }
 
@@ -311,11 +605,61 @@ where
             query: query.clone(),
             schema: schema.to_vec(),
             pg_schema: pg_schema.to_vec(),
+            params: vec![],
+            reuse_prepared: false,
+            cursor_fetch_size: None,
+            watchdog: None,
+            reset_role: false,
             nrows: 0,
             ncols: schema.len(),
+            checkout_time: Duration::ZERO,
             _protocol: PhantomData,
         }
     }
+
+    /// Closes the connection if the failed read left it unusable, e.g. in a transaction the
+    /// failure aborted (`25P02`), which still passes r2d2's validation. The closed connection
+    /// fails `has_broken` on check-in, so r2d2 opens a fresh one instead of recycling it. A
+    /// pinned connection is left for its transaction to roll back.
+    fn close_if_poisoned(&mut self) {
+        if self.conn.is_pinned() || self.conn.is_closed() {
+            return;
+        }
+        if let Err(e) = self.conn.batch_execute("SELECT 1") {
+            debug!("Closing a connection left unusable by a failed read: {}", e);
+            let _ = self.conn.batch_execute("ROLLBACK");
+            let _ = self
+                .conn
+                .batch_execute("SELECT pg_terminate_backend(pg_backend_pid())");
+            let _ = self.conn.batch_execute("");
+        }
+    }
+}
+
+impl<P, C> Drop for PostgresSourcePartition<P, C>
+where
+    C: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
+    C::TlsConnect: Send,
+    C::Stream: Send,
+    <C::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    fn drop(&mut self) {
+        if !self.reset_role || self.conn.is_closed() {
+            return;
+        }
+        if let Err(e) = self.conn.batch_execute("RESET ROLE") {
+            // a connection still under the role must not reach the next borrower, so it is
+            // closed; a pinned one is left for its transaction to roll back
+            debug!("Closing a connection whose role could not be reset: {}", e);
+            if !self.conn.is_pinned() {
+                let _ = self.conn.batch_execute("ROLLBACK");
+                let _ = self
+                    .conn
+                    .batch_execute("SELECT pg_terminate_backend(pg_backend_pid())");
+                let _ = self.conn.batch_execute("");
+            }
+        }
+    }
 }
 
 impl<C> SourcePartition for PostgresSourcePartition<BinaryProtocol, C>
@@ -331,16 +675,23 @@ where
 
     #[throws(PostgresSourceError)]
     fn result_rows(&mut self) -> () {
-        self.nrows = get_total_rows(&mut self.conn, &self.query)?;
+        self.nrows = get_total_rows(
+            &mut self.conn,
+            &self.query,
+            &self.params,
+            self.reuse_prepared,
+        )?;
     }
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
         let query = format!("COPY ({}) TO STDOUT WITH BINARY", self.query);
+        let _fetch = self.watchdog.as_ref().map(FetchWatchdog::arm);
         let reader = self.conn.copy_out(&*query)?; // unless reading the data, it seems like issue the query is fast
         let iter = BinaryCopyOutIter::new(reader, &self.pg_schema);
 
         PostgresBinarySourcePartitionParser::new(iter, &self.schema)
+            .with_watchdog(self.watchdog.as_ref())
     }
 
     fn nrows(&self) -> usize {
@@ -350,6 +701,14 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
+
+    fn release_after_error(&mut self) {
+        self.close_if_poisoned();
+    }
 }
 
 impl<C> SourcePartition for PostgresSourcePartition<CSVProtocol, C>
@@ -365,19 +724,25 @@ where
 
     #[throws(PostgresSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = get_total_rows(&mut self.conn, &self.query)?;
+        self.nrows = get_total_rows(
+            &mut self.conn,
+            &self.query,
+            &self.params,
+            self.reuse_prepared,
+        )?;
     }
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
         let query = format!("COPY ({}) TO STDOUT WITH CSV", self.query);
+        let _fetch = self.watchdog.as_ref().map(FetchWatchdog::arm);
         let reader = self.conn.copy_out(&*query)?; // unless reading the data, it seems like issue the query is fast
         let iter = ReaderBuilder::new()
             .has_headers(false)
             .from_reader(reader)
             .into_records();
 
-        PostgresCSVSourceParser::new(iter, &self.schema)
+        PostgresCSVSourceParser::new(iter, &self.schema).with_watchdog(self.watchdog.as_ref())
     }
 
     fn nrows(&self) -> usize {
@@ -387,6 +752,14 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
+
+    fn release_after_error(&mut self) {
+        self.close_if_poisoned();
+    }
 }
 
 impl<C> SourcePartition for PostgresSourcePartition<CursorProtocol, C>
@@ -402,15 +775,36 @@ where
 
     #[throws(PostgresSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = get_total_rows(&mut self.conn, &self.query)?;
+        self.nrows = get_total_rows(
+            &mut self.conn,
+            &self.query,
+            &self.params,
+            self.reuse_prepared,
+        )?;
     }
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        let iter = self
-            .conn
-            .query_raw::<_, bool, _>(self.query.as_str(), vec![])?; // unless reading the data, it seems like issue the query is fast
-        PostgresRawSourceParser::new(iter, &self.schema)
+        let _fetch = self.watchdog.as_ref().map(FetchWatchdog::arm);
+        let stmt = match self.reuse_prepared {
+            true => prepare_cached(&mut self.conn, self.query.as_str())?,
+            false => self.conn.prepare(self.query.as_str())?,
+        };
+        if let Some(fetch_size) = self.cursor_fetch_size {
+            // a portal only lives as long as the transaction it is bound in
+            let mut txn = self.conn.transaction()?;
+            let params: Vec<&(dyn postgres::types::ToSql + Sync)> =
+                self.params.iter().map(|p| p as _).collect();
+            let portal = txn.bind(&stmt, &params)?;
+            return PostgresRawSourceParser::with_portal(txn, portal, fetch_size, &self.schema)
+                .with_watchdog(self.watchdog.as_ref());
+        }
+        let params = self
+            .params
+            .iter()
+            .map(|p| p as &(dyn postgres::types::ToSql + Sync));
+        let iter = self.conn.query_raw(&stmt, params)?; // unless reading the data, it seems like issue the query is fast
+        PostgresRawSourceParser::new(iter, &self.schema).with_watchdog(self.watchdog.as_ref())
     }
 
     fn nrows(&self) -> usize {
@@ -420,6 +814,14 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
+
+    fn release_after_error(&mut self) {
+        self.close_if_poisoned();
+    }
 }
 pub struct PostgresBinarySourcePartitionParser<'a> {
     iter: BinaryCopyOutIter<'a>,
@@ -428,6 +830,7 @@ pub struct PostgresBinarySourcePartitionParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    watchdog: Option<&'a FetchWatchdog>,
 }
 
 impl<'a> PostgresBinarySourcePartitionParser<'a> {
@@ -439,9 +842,16 @@ impl<'a> PostgresBinarySourcePartitionParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            watchdog: None,
         }
     }
 
+    /// Times every fetch with `watchdog`, which cancels the query once one stalls.
+    pub fn with_watchdog(mut self, watchdog: Option<&'a FetchWatchdog>) -> Self {
+        self.watchdog = watchdog;
+        self
+    }
+
     #[throws(PostgresSourceError)]
     fn next_loc(&mut self) -> (usize, usize) {
         let ret = (self.current_row, self.current_col);
@@ -469,6 +879,7 @@ impl<'a> PartitionParser<'a> for PostgresBinarySourcePartitionParser<'a> {
         if !self.rowbuf.is_empty() {
             self.rowbuf.drain(..);
         }
+        let _fetch = self.watchdog.map(FetchWatchdog::arm);
         for _ in 0..DB_BUFFER_SIZE {
             match self.iter.next()? {
                 Some(row) => {
@@ -535,18 +946,63 @@ impl_produce!(
     Uuid,
     Value,
     IpInet,
+    PgInterval,
     Vector,
     HalfVector,
     Bit,
     SparseVector,
-    Vec<Option<bool>>,
-    Vec<Option<i16>>,
-    Vec<Option<i32>>,
-    Vec<Option<i64>>,
-    Vec<Option<Decimal>>,
-    Vec<Option<f32>>,
-    Vec<Option<f64>>,
-    Vec<Option<String>>,
+    Wkb,
+    Hstore,
+);
+
+/// `postgres` rejects arrays of more than one dimension with an opaque deserialization error.
+fn array_error(e: postgres::Error, cidx: usize) -> PostgresSourceError {
+    match std::error::Error::source(&e) {
+        Some(source) if source.to_string() == "array contains too many dimensions" => {
+            PostgresSourceError::MultiDimensionalArray(cidx)
+        }
+        _ => e.into(),
+    }
+}
+
+macro_rules! impl_array_produce {
+    ($parser: ident, $($t: ty,)+) => {
+        $(
+            impl<'r, 'a> Produce<'r, Vec<Option<$t>>> for $parser<'a> {
+                type Error = PostgresSourceError;
+
+                #[throws(PostgresSourceError)]
+                fn produce(&'r mut self) -> Vec<Option<$t>> {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let row = &self.rowbuf[ridx];
+                    row.try_get(cidx).map_err(|e| array_error(e, cidx))?
+                }
+            }
+
+            impl<'r, 'a> Produce<'r, Option<Vec<Option<$t>>>> for $parser<'a> {
+                type Error = PostgresSourceError;
+
+                #[throws(PostgresSourceError)]
+                fn produce(&'r mut self) -> Option<Vec<Option<$t>>> {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let row = &self.rowbuf[ridx];
+                    row.try_get(cidx).map_err(|e| array_error(e, cidx))?
+                }
+            }
+        )+
+    };
+}
+
+impl_array_produce!(
+    PostgresBinarySourcePartitionParser,
+    bool,
+    i16,
+    i32,
+    i64,
+    Decimal,
+    f32,
+    f64,
+    String,
 );
 
 impl<'r> Produce<'r, NaiveDateTime> for PostgresBinarySourcePartitionParser<'_> {
@@ -648,24 +1104,6 @@ impl<'r> Produce<'r, Option<NaiveDate>> for PostgresBinarySourcePartitionParser<
     }
 }
 
-impl Produce<'_, HashMap<String, Option<String>>> for PostgresBinarySourcePartitionParser<'_> {
-    type Error = PostgresSourceError;
-    #[throws(PostgresSourceError)]
-    fn produce(&mut self) -> HashMap<String, Option<String>> {
-        unimplemented!("Please use `cursor` protocol for hstore type");
-    }
-}
-
-impl Produce<'_, Option<HashMap<String, Option<String>>>>
-    for PostgresBinarySourcePartitionParser<'_>
-{
-    type Error = PostgresSourceError;
-    #[throws(PostgresSourceError)]
-    fn produce(&mut self) -> Option<HashMap<String, Option<String>>> {
-        unimplemented!("Please use `cursor` protocol for hstore type");
-    }
-}
-
 pub struct PostgresCSVSourceParser<'a> {
     iter: StringRecordsIntoIter<CopyOutReader<'a>>,
     rowbuf: Vec<StringRecord>,
@@ -673,6 +1111,7 @@ pub struct PostgresCSVSourceParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    watchdog: Option<&'a FetchWatchdog>,
 }
 
 impl<'a> PostgresCSVSourceParser<'a> {
@@ -687,9 +1126,16 @@ impl<'a> PostgresCSVSourceParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            watchdog: None,
         }
     }
 
+    /// Times every fetch with `watchdog`, which cancels the query once one stalls.
+    pub fn with_watchdog(mut self, watchdog: Option<&'a FetchWatchdog>) -> Self {
+        self.watchdog = watchdog;
+        self
+    }
+
     #[throws(PostgresSourceError)]
     fn next_loc(&mut self) -> (usize, usize) {
         let ret = (self.current_row, self.current_col);
@@ -716,6 +1162,7 @@ impl<'a> PartitionParser<'a> for PostgresCSVSourceParser<'a> {
         if !self.rowbuf.is_empty() {
             self.rowbuf.drain(..);
         }
+        let _fetch = self.watchdog.map(FetchWatchdog::arm);
         for _ in 0..DB_BUFFER_SIZE {
             if let Some(row) = self.iter.next() {
                 self.rowbuf.push(row?);
@@ -763,7 +1210,83 @@ macro_rules! impl_csv_produce {
     };
 }
 
-impl_csv_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet,);
+impl_csv_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet, PgInterval, Wkb, Hstore,);
+
+/// Parses the text form of a one-dimensional array, e.g. `{1,NULL,"a,\"b\""}`, converting
+/// each element with `parse`. Quoted elements are unescaped and an unquoted `NULL` is a NULL
+/// element. A nested array is rejected as multi-dimensional.
+#[throws(PostgresSourceError)]
+fn parse_text_array<T>(s: &str, cidx: usize, parse: impl Fn(&str) -> Option<T>) -> Vec<Option<T>> {
+    let malformed = || ConnectorXError::cannot_produce::<Vec<T>>(Some(s.into()));
+    // arrays with non-default bounds are prefixed by them, e.g. `[0:1]={1,2}`
+    let array = match s.starts_with('[') {
+        true => s.split_once('=').map_or(s, |(_, a)| a),
+        false => s,
+    };
+    let inner = array
+        .strip_prefix('{')
+        .and_then(|a| a.strip_suffix('}'))
+        .ok_or_else(malformed)?;
+
+    let mut values = vec![];
+    if inner.is_empty() {
+        return values;
+    }
+    let bytes = inner.as_bytes();
+    let mut i = 0;
+    loop {
+        let value = match bytes.get(i) {
+            Some(b'{') => throw!(PostgresSourceError::MultiDimensionalArray(cidx)),
+            Some(b'"') => {
+                let mut quoted = vec![];
+                i += 1;
+                loop {
+                    match bytes.get(i) {
+                        Some(b'\\') => {
+                            quoted.extend(bytes.get(i + 1));
+                            i += 2;
+                        }
+                        Some(b'"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(&b) => {
+                            quoted.push(b);
+                            i += 1;
+                        }
+                        None => throw!(malformed()),
+                    }
+                }
+                let quoted = String::from_utf8(quoted).map_err(|_| malformed())?;
+                Some(parse(&quoted).ok_or_else(malformed)?)
+            }
+            _ => {
+                let end = inner[i..].find(',').map_or(inner.len(), |n| i + n);
+                let token = &inner[i..end];
+                i = end;
+                match token {
+                    "NULL" => None,
+                    token => Some(parse(token).ok_or_else(malformed)?),
+                }
+            }
+        };
+        values.push(value);
+        match bytes.get(i) {
+            Some(b',') => i += 1,
+            None => break,
+            Some(_) => throw!(malformed()),
+        }
+    }
+    values
+}
+
+fn parse_text_bool(token: &str) -> Option<bool> {
+    match token {
+        "t" => Some(true),
+        "f" => Some(false),
+        _ => None,
+    }
+}
 
 macro_rules! impl_csv_vec_produce {
     ($($t: ty,)+) => {
@@ -774,24 +1297,7 @@ macro_rules! impl_csv_vec_produce {
                 #[throws(PostgresSourceError)]
                 fn produce(&mut self) -> Vec<Option<$t>> {
                     let (ridx, cidx) = self.next_loc()?;
-                    let s = &self.rowbuf[ridx][cidx][..];
-                    match s {
-                        "{}" => vec![],
-                        _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<$t>(Some(s.into()))),
-                        s => s[1..s.len() - 1]
-                            .split(",")
-                            .map(|v| {
-                                if v == "NULL" {
-                                    Ok(None)
-                                } else {
-                                    match v.parse() {
-                                        Ok(v) => Ok(Some(v)),
-                                        Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<$t>(Some(s.into())))
-                                    }
-                                }
-                            })
-                            .collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?,
-                    }
+                    parse_text_array(&self.rowbuf[ridx][cidx], cidx, |v| v.parse().ok())?
                 }
             }
 
@@ -801,26 +1307,9 @@ macro_rules! impl_csv_vec_produce {
                 #[throws(PostgresSourceError)]
                 fn produce(&mut self) -> Option<Vec<Option<$t>>> {
                     let (ridx, cidx) = self.next_loc()?;
-                    let s = &self.rowbuf[ridx][cidx][..];
-                    match s {
+                    match &self.rowbuf[ridx][cidx][..] {
                         "" => None,
-                        "{}" => Some(vec![]),
-                        _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<$t>(Some(s.into()))),
-                        s => Some(
-                            s[1..s.len() - 1]
-                                .split(",")
-                                .map(|v| {
-                                    if v == "NULL" {
-                                        Ok(None)
-                                    } else {
-                                        match v.parse() {
-                                            Ok(v) => Ok(Some(v)),
-                                            Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<$t>(Some(s.into())))
-                                        }
-                                    }
-                                })
-                                .collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?,
-                        ),
+                        s => Some(parse_text_array(s, cidx, |v| v.parse().ok())?),
                     }
                 }
             }
@@ -871,20 +1360,7 @@ impl Produce<'_, Vec<Option<bool>>> for PostgresCSVSourceParser<'_> {
     #[throws(PostgresSourceError)]
     fn produce(&mut self) -> Vec<Option<bool>> {
         let (ridx, cidx) = self.next_loc()?;
-        let s = &self.rowbuf[ridx][cidx][..];
-        match s {
-            "{}" => vec![],
-            _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-            s => s[1..s.len() - 1]
-                .split(',')
-                .map(|v| match v {
-                    "NULL" => Ok(None),
-                    "t" => Ok(Some(true)),
-                    "f" => Ok(Some(false)),
-                    _ => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-                })
-                .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-        }
+        parse_text_array(&self.rowbuf[ridx][cidx], cidx, parse_text_bool)?
     }
 }
 
@@ -894,22 +1370,9 @@ impl Produce<'_, Option<Vec<Option<bool>>>> for PostgresCSVSourceParser<'_> {
     #[throws(PostgresSourceError)]
     fn produce(&mut self) -> Option<Vec<Option<bool>>> {
         let (ridx, cidx) = self.next_loc()?;
-        let s = &self.rowbuf[ridx][cidx][..];
-        match s {
+        match &self.rowbuf[ridx][cidx][..] {
             "" => None,
-            "{}" => Some(vec![]),
-            _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-            s => Some(
-                s[1..s.len() - 1]
-                    .split(',')
-                    .map(|v| match v {
-                        "NULL" => Ok(None),
-                        "t" => Ok(Some(true)),
-                        "f" => Ok(Some(false)),
-                        _ => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-                    })
-                    .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-            ),
+            s => Some(parse_text_array(s, cidx, parse_text_bool)?),
         }
     }
 }
@@ -1154,27 +1617,66 @@ impl<'r> Produce<'r, Option<Value>> for PostgresCSVSourceParser<'_> {
     }
 }
 
+/// Where the cursor protocol reads its rows from.
+enum RawRows<'a> {
+    /// The whole result, requested at once.
+    Stream(RowIter<'a>),
+    /// A portal fetched `fetch_size` rows at a time.
+    Portal {
+        txn: Transaction<'a>,
+        portal: Portal,
+        fetch_size: usize,
+    },
+}
+
 pub struct PostgresRawSourceParser<'a> {
-    iter: RowIter<'a>,
+    rows: RawRows<'a>,
     rowbuf: Vec<Row>,
     ncols: usize,
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    watchdog: Option<&'a FetchWatchdog>,
 }
 
 impl<'a> PostgresRawSourceParser<'a> {
     pub fn new(iter: RowIter<'a>, schema: &[PostgresTypeSystem]) -> Self {
+        Self::with_rows(RawRows::Stream(iter), schema)
+    }
+
+    /// Reads `portal` in `fetch_size` rows per round trip.
+    pub fn with_portal(
+        txn: Transaction<'a>,
+        portal: Portal,
+        fetch_size: usize,
+        schema: &[PostgresTypeSystem],
+    ) -> Self {
+        let rows = RawRows::Portal {
+            txn,
+            portal,
+            fetch_size,
+        };
+        Self::with_rows(rows, schema)
+    }
+
+    fn with_rows(rows: RawRows<'a>, schema: &[PostgresTypeSystem]) -> Self {
         Self {
-            iter,
+            rows,
             rowbuf: Vec::with_capacity(DB_BUFFER_SIZE),
             ncols: schema.len(),
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            watchdog: None,
         }
     }
 
+    /// Times every fetch with `watchdog`, which cancels the query once one stalls.
+    pub fn with_watchdog(mut self, watchdog: Option<&'a FetchWatchdog>) -> Self {
+        self.watchdog = watchdog;
+        self
+    }
+
     #[throws(PostgresSourceError)]
     fn next_loc(&mut self) -> (usize, usize) {
         let ret = (self.current_row, self.current_col);
@@ -1201,12 +1703,28 @@ impl<'a> PartitionParser<'a> for PostgresRawSourceParser<'a> {
         if !self.rowbuf.is_empty() {
             self.rowbuf.drain(..);
         }
-        for _ in 0..DB_BUFFER_SIZE {
-            if let Some(row) = self.iter.next()? {
-                self.rowbuf.push(row);
-            } else {
-                self.is_finished = true;
-                break;
+        let _fetch = self.watchdog.map(FetchWatchdog::arm);
+        match &mut self.rows {
+            RawRows::Stream(iter) => {
+                for _ in 0..DB_BUFFER_SIZE {
+                    if let Some(row) = iter.next()? {
+                        self.rowbuf.push(row);
+                    } else {
+                        self.is_finished = true;
+                        break;
+                    }
+                }
+            }
+            RawRows::Portal {
+                txn,
+                portal,
+                fetch_size,
+            } => {
+                let mut iter = txn.query_portal_raw(portal, *fetch_size as i32)?;
+                while let Some(row) = iter.next()? {
+                    self.rowbuf.push(row);
+                }
+                self.is_finished = self.rowbuf.len() < *fetch_size;
             }
         }
         self.current_row = 0;
@@ -1261,19 +1779,25 @@ impl_produce!(
     Uuid,
     Value,
     IpInet,
+    PgInterval,
     Vector,
     HalfVector,
     Bit,
     SparseVector,
-    HashMap<String, Option<String>>,
-    Vec<Option<bool>>,
-    Vec<Option<String>>,
-    Vec<Option<i16>>,
-    Vec<Option<i32>>,
-    Vec<Option<i64>>,
-    Vec<Option<f32>>,
-    Vec<Option<f64>>,
-    Vec<Option<Decimal>>,
+    Wkb,
+    Hstore,
+);
+
+impl_array_produce!(
+    PostgresRawSourceParser,
+    bool,
+    String,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    Decimal,
 );
 
 impl<'r> Produce<'r, DateTime<Utc>> for PostgresRawSourceParser<'_> {
@@ -1388,11 +1912,18 @@ where
 
     #[throws(PostgresSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = get_total_rows(&mut self.conn, &self.query)?;
+        self.nrows = get_total_rows(
+            &mut self.conn,
+            &self.query,
+            &self.params,
+            self.reuse_prepared,
+        )?;
     }
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
+        // the simple protocol reads the whole result here, so this is its only fetch
+        let _fetch = self.watchdog.as_ref().map(FetchWatchdog::arm);
         let rows = self.conn.simple_query(self.query.as_str())?; // unless reading the data, it seems like issue the query is fast
         PostgresSimpleSourceParser::new(rows, &self.schema)
     }
@@ -1404,6 +1935,14 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
+
+    fn release_after_error(&mut self) {
+        self.close_if_poisoned();
+    }
 }
 
 pub struct PostgresSimpleSourceParser {
@@ -1506,7 +2045,7 @@ macro_rules! impl_simple_produce {
     };
 }
 
-impl_simple_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet,);
+impl_simple_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet, PgInterval, Wkb, Hstore,);
 
 impl<'r> Produce<'r, bool> for PostgresSimpleSourceParser {
     type Error = PostgresSourceError;
@@ -1720,15 +2259,8 @@ impl<'r> Produce<'r, Option<Vec<u8>>> for PostgresSimpleSourceParser {
     }
 }
 
-fn rem_first_and_last(value: &str) -> &str {
-    let mut chars = value.chars();
-    chars.next();
-    chars.next_back();
-    chars.as_str()
-}
-
 macro_rules! impl_simple_vec_produce {
-    ($($t: ty,)+) => {
+    ($($t: ty => $parse: expr,)+) => {
         $(
             impl<'r> Produce<'r, Vec<Option<$t>>> for PostgresSimpleSourceParser {
                 type Error = PostgresSourceError;
@@ -1738,21 +2270,8 @@ macro_rules! impl_simple_vec_produce {
                     let (ridx, cidx) = self.next_loc()?;
                     let val = match &self.rows[ridx] {
                         SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                            Some(s) => match s{
-                                "" => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
-                                "{}" => vec![],
-                                _ => rem_first_and_last(s).split(",").map(|v| {
-                                    if v == "NULL" {
-                                        Ok(None)
-                                    } else {
-                                        match v.parse() {
-                                            Ok(v) => Ok(Some(v)),
-                                            Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<Vec<$t>>(Some(s.into())))
-                                        }
-                                    }
-                                }).collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?
-                            },
-                            None => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
+                            Some("") | None => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
+                            Some(s) => parse_text_array(s, cidx, $parse)?,
                         },
                         SimpleQueryMessage::CommandComplete(c) => {
                             panic!("get command: {}", c);
@@ -1772,25 +2291,10 @@ macro_rules! impl_simple_vec_produce {
                 fn produce(&'r mut self) -> Option<Vec<Option<$t>>> {
                     let (ridx, cidx) = self.next_loc()?;
                     let val = match &self.rows[ridx] {
-
                         SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                            Some(s) => match s{
-                                "" => None,
-                                "{}" => Some(vec![]),
-                                _ => Some(rem_first_and_last(s).split(",").map(|v| {
-                                    if v == "NULL" {
-                                        Ok(None)
-                                    } else {
-                                        match v.parse() {
-                                            Ok(v) => Ok(Some(v)),
-                                            Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<Vec<$t>>(Some(s.into())))
-                                        }
-                                    }
-                                }).collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?)
-                            },
-                            None => None,
+                            Some("") | None => None,
+                            Some(s) => Some(parse_text_array(s, cidx, $parse)?),
                         },
-
                         SimpleQueryMessage::CommandComplete(c) => {
                             panic!("get command: {}", c);
                         }
@@ -1804,83 +2308,17 @@ macro_rules! impl_simple_vec_produce {
         )+
     };
 }
-impl_simple_vec_produce!(i16, i32, i64, f32, f64, Decimal, String,);
-
-impl<'r> Produce<'r, Vec<Option<bool>>> for PostgresSimpleSourceParser {
-    type Error = PostgresSourceError;
-
-    #[throws(PostgresSourceError)]
-    fn produce(&'r mut self) -> Vec<Option<bool>> {
-        let (ridx, cidx) = self.next_loc()?;
-        let val = match &self.rows[ridx] {
-            SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                Some(s) => match s {
-                    "" => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
-                    "{}" => vec![],
-                    _ => rem_first_and_last(s)
-                        .split(',')
-                        .map(|token| match token {
-                            "NULL" => Ok(None),
-                            "t" => Ok(Some(true)),
-                            "f" => Ok(Some(false)),
-                            _ => {
-                                throw!(ConnectorXError::cannot_produce::<Vec<bool>>(Some(s.into())))
-                            }
-                        })
-                        .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-                },
-                None => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
-            },
-            SimpleQueryMessage::CommandComplete(c) => {
-                panic!("get command: {}", c);
-            }
-            _ => {
-                panic!("what?");
-            }
-        };
-        val
-    }
-}
-
-impl<'r> Produce<'r, Option<Vec<Option<bool>>>> for PostgresSimpleSourceParser {
-    type Error = PostgresSourceError;
 
-    #[throws(PostgresSourceError)]
-    fn produce(&'r mut self) -> Option<Vec<Option<bool>>> {
-        let (ridx, cidx) = self.next_loc()?;
-        let val = match &self.rows[ridx] {
-            SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                Some(s) => match s {
-                    "" => None,
-                    "{}" => Some(vec![]),
-                    _ => Some(
-                        rem_first_and_last(s)
-                            .split(',')
-                            .map(|token| match token {
-                                "NULL" => Ok(None),
-                                "t" => Ok(Some(true)),
-                                "f" => Ok(Some(false)),
-                                _ => {
-                                    throw!(ConnectorXError::cannot_produce::<Vec<bool>>(Some(
-                                        s.into()
-                                    )))
-                                }
-                            })
-                            .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-                    ),
-                },
-                None => None,
-            },
-            SimpleQueryMessage::CommandComplete(c) => {
-                panic!("get command: {}", c);
-            }
-            _ => {
-                panic!("what?");
-            }
-        };
-        val
-    }
-}
+impl_simple_vec_produce!(
+    i16 => |v: &str| v.parse().ok(),
+    i32 => |v: &str| v.parse().ok(),
+    i64 => |v: &str| v.parse().ok(),
+    f32 => |v: &str| v.parse().ok(),
+    f64 => |v: &str| v.parse().ok(),
+    Decimal => |v: &str| v.parse().ok(),
+    String => |v: &str| Some(v.to_string()),
+    bool => parse_text_bool,
+);
 
 impl<'r> Produce<'r, NaiveDate> for PostgresSimpleSourceParser {
     type Error = PostgresSourceError;
@@ -1,9 +1,8 @@
-use crate::sources::postgres::IpInet;
+use crate::sources::postgres::{Hstore, IpInet, PgInterval, Wkb};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use postgres::types::Type;
 use rust_decimal::Decimal;
 use serde_json::Value;
-use std::collections::HashMap;
 use uuid::Uuid;
 
 use pgvector::{Bit, HalfVector, SparseVector, Vector};
@@ -34,6 +33,7 @@ pub enum PostgresTypeSystem {
     Text(bool),
     ByteA(bool),
     Time(bool),
+    Interval(bool),
     Timestamp(bool),
     TimestampTz(bool),
     UUID(bool),
@@ -47,6 +47,10 @@ pub enum PostgresTypeSystem {
     HalfVec(bool),
     Bit(bool),
     SparseVec(bool),
+    /// A PostGIS `geometry` or `geography`, read as WKB.
+    Geometry(bool),
+    /// A PostGIS `geometry` or `geography`, read as WKT.
+    GeometryWkt(bool),
 }
 
 impl_typesystem! {
@@ -72,17 +76,19 @@ impl_typesystem! {
         { Text | BpChar | VarChar | Enum | Name => &'r str }
         { ByteA => Vec<u8> }
         { Time => NaiveTime }
+        { Interval => PgInterval }
         { Timestamp => NaiveDateTime }
         { TimestampTz => DateTime<Utc> }
         { Date => NaiveDate }
         { UUID => Uuid }
         { JSON | JSONB => Value }
-        { HSTORE => HashMap<String, Option<String>> }
+        { HSTORE => Hstore }
         { Inet => IpInet }
         { Vector => Vector }
         { HalfVec => HalfVector }
         { Bit => Bit }
         { SparseVec => SparseVector }
+        { Geometry | GeometryWkt => Wkb }
     }
 }
 
@@ -113,6 +119,7 @@ impl<'a> From<&'a Type> for PostgresTypeSystem {
             "varchar" => VarChar(true),
             "bytea" => ByteA(true),
             "time" => Time(true),
+            "interval" => Interval(true),
             "timestamp" => Timestamp(true),
             "timestamptz" => TimestampTz(true),
             "date" => Date(true),
@@ -125,6 +132,7 @@ impl<'a> From<&'a Type> for PostgresTypeSystem {
             "halfvec" => HalfVec(true),
             "bit" => Bit(true),
             "sparsevec" => SparseVec(true),
+            "geometry" | "geography" => Geometry(true),
             _ => match ty.kind() {
                 postgres::types::Kind::Enum(_) => Enum(true),
                 _ => unimplemented!("{}", ty.name()),
@@ -141,7 +149,6 @@ impl From<PostgresTypePairs<'_>> for Type {
         use PostgresTypeSystem::*;
         match ty.1 {
             Enum(_) => Type::TEXT,
-            HSTORE(_) => Type::TEXT, // hstore is not supported in binary protocol (since no corresponding inner TYPE)
             _ => ty.0.clone(),
         }
     }
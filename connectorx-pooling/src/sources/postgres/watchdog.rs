@@ -0,0 +1,95 @@
+//! Server-side cancellation of partition queries whose fetches stall.
+
+use log::debug;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct State {
+    /// When the fetch in progress runs out of time, `None` between fetches.
+    deadline: Option<Instant>,
+    /// Set while a cancel request is on its way to the server.
+    cancelling: bool,
+    closed: bool,
+}
+
+/// Times the fetches of one partition on a thread of its own and cancels the partition's
+/// query on the server once a fetch has not returned within `timeout`. The stalled read then
+/// fails with the server's cancellation error, so the worker gives the connection back
+/// rather than holding it until the query ends, if it ever does.
+///
+/// Arming, disarming and dropping the watchdog wait for a cancel request in flight, so one
+/// sent as the fetch returns cannot reach a query the connection runs afterwards.
+pub struct FetchWatchdog {
+    timeout: Duration,
+    state: Arc<(Mutex<State>, Condvar)>,
+}
+
+impl FetchWatchdog {
+    pub fn new(timeout: Duration, cancel: impl Fn() + Send + 'static) -> Self {
+        let state = Arc::new((Mutex::new(State::default()), Condvar::new()));
+        let watched = Arc::clone(&state);
+        thread::spawn(move || {
+            let (lock, cvar) = &*watched;
+            let mut state = lock.lock().unwrap();
+            while !state.closed {
+                match state.deadline {
+                    Some(deadline) if Instant::now() >= deadline => {
+                        state.deadline = None;
+                        state.cancelling = true;
+                        // the cancel request is a round trip of its own, which must not keep
+                        // the reader from returning from the stalled fetch
+                        drop(state);
+                        debug!("Cancelling a query whose fetch took over {:?}", timeout);
+                        cancel();
+                        state = lock.lock().unwrap();
+                        state.cancelling = false;
+                        cvar.notify_all();
+                    }
+                    Some(deadline) => {
+                        let wait = deadline.saturating_duration_since(Instant::now());
+                        state = cvar.wait_timeout(state, wait).unwrap().0;
+                    }
+                    None => state = cvar.wait(state).unwrap(),
+                }
+            }
+        });
+        Self { timeout, state }
+    }
+
+    /// Starts timing a fetch, which lasts until the returned guard is dropped.
+    pub fn arm(&self) -> FetchGuard<'_> {
+        self.set_deadline(Some(Instant::now() + self.timeout));
+        FetchGuard(self)
+    }
+
+    fn set_deadline(&self, deadline: Option<Instant>) {
+        let mut state = self.settled();
+        state.deadline = deadline;
+        self.state.1.notify_all();
+    }
+
+    /// The state, once no cancel request is in flight.
+    fn settled(&self) -> MutexGuard<'_, State> {
+        let (lock, cvar) = &*self.state;
+        cvar.wait_while(lock.lock().unwrap(), |state| state.cancelling)
+            .unwrap()
+    }
+}
+
+impl Drop for FetchWatchdog {
+    fn drop(&mut self) {
+        self.settled().closed = true;
+        self.state.1.notify_all();
+    }
+}
+
+/// A fetch being timed by a [`FetchWatchdog`].
+pub struct FetchGuard<'a>(&'a FetchWatchdog);
+
+impl Drop for FetchGuard<'_> {
+    fn drop(&mut self) {
+        self.0.set_deadline(None);
+    }
+}
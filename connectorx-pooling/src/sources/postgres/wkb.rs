@@ -0,0 +1,315 @@
+use anyhow::{anyhow, ensure};
+use postgres::types::{FromSql, Type};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Flags PostGIS sets on the geometry type of its extended WKB (EWKB), where ISO WKB adds
+/// 1000 (Z), 2000 (M) or 3000 (ZM) to the type instead and has no SRID.
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// A PostGIS `geometry` or `geography` value as ISO WKB (well-known binary), little-endian
+/// and without the SRID PostGIS embeds in the EWKB it sends. Formats as WKT, e.g.
+/// `POLYGON((0 0,1 0,1 1,0 0))`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wkb(Vec<u8>);
+
+impl Wkb {
+    /// Reads a geometry from WKB or from the EWKB PostGIS sends, in either byte order.
+    pub fn from_ewkb(ewkb: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = Reader { buf: ewkb, pos: 0 };
+        let geometry = reader.geometry()?;
+        ensure!(
+            reader.pos == ewkb.len(),
+            "{} trailing bytes after the geometry",
+            ewkb.len() - reader.pos
+        );
+        let mut wkb = Vec::with_capacity(ewkb.len());
+        geometry.write_wkb(&mut wkb);
+        Ok(Wkb(wkb))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl<'a> FromSql<'a> for Wkb {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Wkb::from_ewkb(raw)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.name(), "geometry" | "geography")
+    }
+}
+
+/// Parses the hex-encoded EWKB Postgres sends as the text of a geometry.
+impl FromStr for Wkb {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Wkb::from_ewkb(&hex::decode(s)?)
+    }
+}
+
+impl fmt::Display for Wkb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let geometry = Reader {
+            buf: &self.0,
+            pos: 0,
+        }
+        .geometry()
+        .map_err(|_| fmt::Error)?;
+        geometry.write_wkt(f, true)
+    }
+}
+
+/// A geometry read from (E)WKB, its coordinates kept flat with `dims` values per point.
+struct Geometry {
+    /// The base type, 1 (Point) to 17 (Triangle).
+    kind: u32,
+    has_z: bool,
+    has_m: bool,
+    body: Body,
+}
+
+enum Body {
+    /// A point, all NaN if empty, or the points of a linestring.
+    Points(Vec<f64>),
+    /// The rings of a polygon.
+    Rings(Vec<Vec<f64>>),
+    /// The members of a multi-geometry or collection.
+    Parts(Vec<Geometry>),
+}
+
+impl Geometry {
+    fn dims(&self) -> usize {
+        2 + self.has_z as usize + self.has_m as usize
+    }
+
+    fn write_wkb(&self, out: &mut Vec<u8>) {
+        let offset = match (self.has_z, self.has_m) {
+            (false, false) => 0,
+            (true, false) => 1000,
+            (false, true) => 2000,
+            (true, true) => 3000,
+        };
+        out.push(1);
+        out.extend_from_slice(&(self.kind + offset).to_le_bytes());
+        let write_points = |out: &mut Vec<u8>, coords: &[f64], counted: bool| {
+            if counted {
+                out.extend_from_slice(&((coords.len() / self.dims()) as u32).to_le_bytes());
+            }
+            for c in coords {
+                out.extend_from_slice(&c.to_le_bytes());
+            }
+        };
+        match &self.body {
+            Body::Points(coords) => write_points(out, coords, self.kind != 1),
+            Body::Rings(rings) => {
+                out.extend_from_slice(&(rings.len() as u32).to_le_bytes());
+                for ring in rings {
+                    write_points(out, ring, true);
+                }
+            }
+            Body::Parts(parts) => {
+                out.extend_from_slice(&(parts.len() as u32).to_le_bytes());
+                for part in parts {
+                    part.write_wkb(out);
+                }
+            }
+        }
+    }
+
+    /// Writes the geometry as WKT, with its type name unless it is a member of a multi-point,
+    /// multi-linestring or multi-polygon, whose members are written as bare coordinates.
+    fn write_wkt(&self, f: &mut fmt::Formatter<'_>, named: bool) -> fmt::Result {
+        if named {
+            f.write_str(wkt_name(self.kind))?;
+            match (self.has_z, self.has_m) {
+                (true, true) => f.write_str(" ZM ")?,
+                (true, false) => f.write_str(" Z ")?,
+                (false, true) => f.write_str(" M ")?,
+                (false, false) => {}
+            }
+        }
+        let empty = match &self.body {
+            Body::Points(coords) => coords.is_empty() || coords.iter().all(|c| c.is_nan()),
+            Body::Rings(rings) => rings.is_empty(),
+            Body::Parts(parts) => parts.is_empty(),
+        };
+        if empty {
+            let separator = if named && !(self.has_z || self.has_m) {
+                " "
+            } else {
+                ""
+            };
+            return write!(f, "{}EMPTY", separator);
+        }
+        let dims = self.dims();
+        let write_points = |f: &mut fmt::Formatter<'_>, coords: &[f64]| {
+            f.write_str("(")?;
+            for (i, point) in coords.chunks(dims).enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                for (j, c) in point.iter().enumerate() {
+                    write!(f, "{}{}", if j > 0 { " " } else { "" }, c)?;
+                }
+            }
+            f.write_str(")")
+        };
+        match &self.body {
+            Body::Points(coords) => write_points(f, coords),
+            Body::Rings(rings) => {
+                f.write_str("(")?;
+                for (i, ring) in rings.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_points(f, ring)?;
+                }
+                f.write_str(")")
+            }
+            Body::Parts(parts) => {
+                // the members of a multi-point, -linestring or -polygon are of a known type
+                let named = !matches!(self.kind, 4..=6);
+                f.write_str("(")?;
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    part.write_wkt(f, named)?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+fn wkt_name(kind: u32) -> &'static str {
+    match kind {
+        1 => "POINT",
+        2 => "LINESTRING",
+        3 => "POLYGON",
+        4 => "MULTIPOINT",
+        5 => "MULTILINESTRING",
+        6 => "MULTIPOLYGON",
+        7 => "GEOMETRYCOLLECTION",
+        8 => "CIRCULARSTRING",
+        9 => "COMPOUNDCURVE",
+        10 => "CURVEPOLYGON",
+        11 => "MULTICURVE",
+        12 => "MULTISURFACE",
+        15 => "POLYHEDRALSURFACE",
+        16 => "TIN",
+        _ => "TRIANGLE",
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> anyhow::Result<&[u8]> {
+        let bytes = self
+            .buf
+            .get(self.pos..self.pos + n)
+            .ok_or_else(|| anyhow!("the geometry ends after {} bytes", self.buf.len()))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self, little_endian: bool) -> anyhow::Result<u32> {
+        let bytes = self.take(4)?.try_into()?;
+        Ok(match little_endian {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    /// A count of items of at least `item_len` bytes each, checked against the bytes left
+    /// so a corrupt count cannot ask for a huge allocation.
+    fn count(&mut self, little_endian: bool, item_len: usize) -> anyhow::Result<usize> {
+        let n = self.u32(little_endian)? as usize;
+        ensure!(
+            n.saturating_mul(item_len) <= self.buf.len() - self.pos,
+            "the geometry claims {} items but holds {} more bytes",
+            n,
+            self.buf.len() - self.pos
+        );
+        Ok(n)
+    }
+
+    fn points(&mut self, little_endian: bool, n: usize, dims: usize) -> anyhow::Result<Vec<f64>> {
+        (0..n * dims)
+            .map(|_| {
+                let bytes = self.take(8)?.try_into()?;
+                Ok(match little_endian {
+                    true => f64::from_le_bytes(bytes),
+                    false => f64::from_be_bytes(bytes),
+                })
+            })
+            .collect()
+    }
+
+    fn geometry(&mut self) -> anyhow::Result<Geometry> {
+        let little_endian = match self.take(1)?[0] {
+            0 => false,
+            1 => true,
+            b => return Err(anyhow!("invalid WKB byte order {}", b)),
+        };
+        let ty = self.u32(little_endian)?;
+        if ty & EWKB_SRID != 0 {
+            self.u32(little_endian)?;
+        }
+        let iso = ty & 0x0fff_ffff;
+        let kind = iso % 1000;
+        let has_z = ty & EWKB_Z != 0 || matches!(iso / 1000, 1 | 3);
+        let has_m = ty & EWKB_M != 0 || matches!(iso / 1000, 2 | 3);
+        let dims = 2 + has_z as usize + has_m as usize;
+        let body = match kind {
+            1 => Body::Points(self.points(little_endian, 1, dims)?),
+            2 | 8 => {
+                let n = self.count(little_endian, dims * 8)?;
+                Body::Points(self.points(little_endian, n, dims)?)
+            }
+            3 | 17 => {
+                let rings = self.count(little_endian, 4)?;
+                Body::Rings(
+                    (0..rings)
+                        .map(|_| {
+                            let n = self.count(little_endian, dims * 8)?;
+                            self.points(little_endian, n, dims)
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                )
+            }
+            4..=7 | 9..=12 | 15 | 16 => {
+                let parts = self.count(little_endian, 5)?;
+                Body::Parts(
+                    (0..parts)
+                        .map(|_| self.geometry())
+                        .collect::<anyhow::Result<_>>()?,
+                )
+            }
+            _ => return Err(anyhow!("unsupported WKB geometry type {}", ty)),
+        };
+        Ok(Geometry {
+            kind,
+            has_z,
+            has_m,
+            body,
+        })
+    }
+}
@@ -6,6 +6,9 @@ pub enum SQLiteSourceError {
     #[error("Cannot infer type from null for SQLite")]
     InferTypeFromNull,
 
+    #[error("in-memory SQLite database {0} must use cache=shared to be read through a pool")]
+    PrivateMemoryDatabase(String),
+
     #[error(transparent)]
     ConnectorXError(#[from] crate::errors::ConnectorXError),
 
@@ -15,6 +18,9 @@ pub enum SQLiteSourceError {
     #[error(transparent)]
     SQLitePoolError(#[from] r2d2::Error),
 
+    #[error(transparent)]
+    SQLiteCheckoutError(#[from] crate::pool::PoolError),
+
     #[error(transparent)]
     SQLiteUrlDecodeError(#[from] FromUtf8Error),
 
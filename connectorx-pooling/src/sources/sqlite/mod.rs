@@ -7,7 +7,11 @@ pub use self::errors::SQLiteSourceError;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
+    params::ScalarValue,
+    pool::{checkout, LeasedConnection, ManagedPool, PinnedConnection},
+    sources::{
+        InvalidUtf8Policy, PartitionParser, Produce, Source, SourceMetadata, SourcePartition,
+    },
     sql::{count_query, limit1_query, CXQuery},
     utils::DummyBox,
 };
@@ -17,45 +21,189 @@ use fallible_streaming_iterator::FallibleStreamingIterator;
 use fehler::{throw, throws};
 use log::debug;
 use owning_ref::OwningHandle;
-use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::{Row, Rows, Statement};
+use rusqlite::{
+    params_from_iter, types::ValueRef, CachedStatement, Connection, OpenFlags, Row, Rows, Statement,
+};
 use sqlparser::dialect::SQLiteDialect;
 use std::convert::TryFrom;
-use std::sync::Arc;
+use std::ops::{Deref, DerefMut};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::time::{Duration, Instant};
 pub use typesystem::SQLiteTypeSystem;
 use urlencoding::decode;
 
+type SQLiteConn = LeasedConnection<SqliteConnectionManager>;
+
+static MEMORY_DB_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The database a `sqlite://` connection string points to.
+#[derive(Debug, Clone)]
+pub struct SQLiteTarget {
+    /// Path or `file:` URI handed to SQLite.
+    pub path: String,
+    pub flags: OpenFlags,
+    /// Whether the database only lives in memory, shared between the connections of a pool.
+    pub in_memory: bool,
+}
+
+impl SQLiteTarget {
+    /// Opens the database read-only, so that writes fail with `SQLITE_READONLY`.
+    pub fn set_read_only(&mut self) {
+        self.flags
+            .remove(OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE);
+        self.flags.insert(OpenFlags::SQLITE_OPEN_READ_ONLY);
+    }
+}
+
+/// Resolves the part of a connection string after `sqlite://`. Besides plain (percent-encoded)
+/// paths this accepts `:memory:` and `file:` URIs. Every pooled connection would open a
+/// separate, empty database for a private in-memory one, so `:memory:` is turned into a
+/// uniquely named shared-cache database and `file:` URIs with `mode=memory` must ask for
+/// `cache=shared` themselves.
+#[throws(SQLiteSourceError)]
+pub fn sqlite_target(conn: &str) -> SQLiteTarget {
+    let decoded = decode(conn)?.into_owned();
+    let uri_flags = OpenFlags::default() | OpenFlags::SQLITE_OPEN_URI;
+    if decoded == ":memory:" {
+        let id = MEMORY_DB_ID.fetch_add(1, Ordering::Relaxed);
+        SQLiteTarget {
+            path: format!(
+                "file:connectorx_memdb_{}_{}?mode=memory&cache=shared",
+                std::process::id(),
+                id
+            ),
+            flags: uri_flags,
+            in_memory: true,
+        }
+    } else if decoded.starts_with("file:") {
+        let params: Vec<&str> = decoded
+            .split_once('?')
+            .map_or(vec![], |(_, q)| q.split('&').collect());
+        let in_memory = params.contains(&"mode=memory");
+        if in_memory && !params.contains(&"cache=shared") {
+            throw!(SQLiteSourceError::PrivateMemoryDatabase(decoded));
+        }
+        SQLiteTarget {
+            path: decoded,
+            flags: uri_flags,
+            in_memory,
+        }
+    } else {
+        SQLiteTarget {
+            path: decoded,
+            flags: OpenFlags::default(),
+            in_memory: false,
+        }
+    }
+}
+
+/// Connection manager for `target`. A shared-cache in-memory database is dropped as soon as
+/// its last connection closes, so the manager keeps one extra connection open for as long
+/// as it lives, and idle connections can be reaped without losing the data.
+#[throws(SQLiteSourceError)]
+pub fn sqlite_manager(target: &SQLiteTarget) -> SqliteConnectionManager {
+    let manager = SqliteConnectionManager::file(&target.path).with_flags(target.flags);
+    if target.in_memory {
+        let keeper = Mutex::new(Connection::open_with_flags(&target.path, target.flags)?);
+        manager.with_init(move |_| {
+            let _keeper = &keeper;
+            Ok(())
+        })
+    } else {
+        manager
+    }
+}
+
+/// What `fetch_metadata` resolved, handed between sources by [`Source::metadata`].
+#[derive(Clone)]
+struct SQLiteMetadata {
+    names: Vec<String>,
+    schema: Vec<SQLiteTypeSystem>,
+}
+
 pub struct SQLiteSource {
-    pool: Arc<Pool<SqliteConnectionManager>>,
+    pool: Arc<ManagedPool<SqliteConnectionManager>>,
+    pinned: Option<PinnedConnection<SqliteConnectionManager>>,
     origin_query: Option<String>,
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<SQLiteTypeSystem>,
+    params: Vec<ScalarValue>,
+    partition_params: Vec<Vec<ScalarValue>>,
+    reuse_prepared: bool,
+    invalid_utf8: InvalidUtf8Policy,
 }
 
 impl SQLiteSource {
     #[throws(SQLiteSourceError)]
-    pub fn new(conn: &str, nconn: usize, pool: Option<Arc<Pool<SqliteConnectionManager>>>) -> Self {
+    pub fn new(
+        conn: &str,
+        nconn: usize,
+        pool: Option<Arc<ManagedPool<SqliteConnectionManager>>>,
+    ) -> Self {
         let pool = match pool {
             Some(p) => p,
             None => {
-                let decoded_conn = decode(conn)?.into_owned();
-                debug!("decoded conn: {}", decoded_conn);
-                let manager = SqliteConnectionManager::file(decoded_conn);
-                Arc::new(
-                    r2d2::Pool::builder()
-                        .max_size(nconn as u32)
-                        .build(manager)?,
-                )
+                let target = sqlite_target(conn)?;
+                debug!("decoded conn: {}", target.path);
+                let manager = sqlite_manager(&target)?;
+                ManagedPool::new(manager, nconn as u32)?
             }
         };
         Self {
             pool,
+            pinned: None,
             origin_query: None,
             queries: vec![],
             names: vec![],
             schema: vec![],
+            params: vec![],
+            partition_params: vec![],
+            reuse_prepared: false,
+            invalid_utf8: InvalidUtf8Policy::default(),
+        }
+    }
+
+    /// Values bound to the `?` placeholders of every query.
+    pub fn set_params(&mut self, params: &[ScalarValue]) {
+        self.params = params.to_vec();
+    }
+
+    /// Values bound to the placeholders of each query after the shared [`set_params`] ones,
+    /// one list per query, e.g. the bounds of a range partition template.
+    ///
+    /// [`set_params`]: SQLiteSource::set_params
+    pub fn set_partition_params(&mut self, params: Vec<Vec<ScalarValue>>) {
+        self.partition_params = params;
+    }
+
+    /// Prepare the partition queries through the statement cache of the connection they run
+    /// on, so partitions sharing a query text compile it once per connection, also across
+    /// reads from the same pool.
+    pub fn set_reuse_prepared(&mut self, reuse_prepared: bool) {
+        self.reuse_prepared = reuse_prepared;
+    }
+
+    fn query_params(&self, i: usize) -> Vec<ScalarValue> {
+        let mut params = self.params.clone();
+        params.extend(self.partition_params.get(i).into_iter().flatten().cloned());
+        params
+    }
+
+    /// Run every query on `conn` instead of connections checked out of the pool.
+    pub fn set_pinned(&mut self, conn: PinnedConnection<SqliteConnectionManager>) {
+        self.pinned = Some(conn);
+    }
+
+    #[throws(SQLiteSourceError)]
+    fn conn(&self) -> SQLiteConn {
+        match &self.pinned {
+            Some(pinned) => pinned.lease()?,
+            None => checkout(&self.pool)?.into(),
         }
     }
 }
@@ -84,10 +232,14 @@ where
         self.origin_query = query;
     }
 
+    fn set_invalid_utf8(&mut self, policy: InvalidUtf8Policy) {
+        self.invalid_utf8 = policy;
+    }
+
     #[throws(SQLiteSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
-        let conn = self.pool.get()?;
+        let conn = self.conn()?;
         let mut names = vec![];
         let mut types = vec![];
         let mut num_empty = 0;
@@ -96,7 +248,8 @@ where
         for (i, query) in self.queries.iter().enumerate() {
             let l1query = limit1_query(query, &SQLiteDialect {})?;
 
-            let is_sucess = conn.query_row(l1query.as_str(), [], |row| {
+            let params = params_from_iter(self.query_params(i));
+            let is_sucess = conn.query_row(l1query.as_str(), params, |row| {
                 for (j, col) in row.as_ref().columns().iter().enumerate() {
                     if j >= names.len() {
                         names.push(col.name().to_string());
@@ -148,16 +301,18 @@ where
             }
         }
 
-        // tried all queries but all get empty result set
+        // tried all queries but all get empty result set, so the types can only come from
+        // the declared column types; other columns are strings (align with pandas)
         let stmt = conn.prepare(self.queries[0].as_str())?;
-
-        self.names = stmt
-            .column_names()
-            .into_iter()
-            .map(|s| s.to_string())
+        let columns = stmt.columns();
+        self.names = columns.iter().map(|col| col.name().to_string()).collect();
+        self.schema = columns
+            .iter()
+            .map(|col| {
+                SQLiteTypeSystem::try_from((col.decl_type(), rusqlite::types::Type::Null))
+                    .unwrap_or(SQLiteTypeSystem::Text(false))
+            })
             .collect();
-        // set all columns as string (align with pandas)
-        self.schema = vec![SQLiteTypeSystem::Text(false); self.names.len()];
     }
 
     #[throws(SQLiteSourceError)]
@@ -165,7 +320,7 @@ where
         match &self.origin_query {
             Some(q) => {
                 let cxq = CXQuery::Naked(q.clone());
-                let conn = self.pool.get()?;
+                let conn = self.conn()?;
                 let nrows =
                     conn.query_row(count_query(&cxq, &SQLiteDialect {})?.as_str(), [], |row| {
                         Ok(row.get::<_, i64>(0)? as usize)
@@ -184,38 +339,67 @@ where
         self.schema.clone()
     }
 
+    fn metadata(&self) -> Option<SourceMetadata> {
+        Some(Arc::new(SQLiteMetadata {
+            names: self.names.clone(),
+            schema: self.schema.clone(),
+        }))
+    }
+
+    fn set_metadata(&mut self, metadata: &SourceMetadata) -> bool {
+        match metadata.downcast_ref::<SQLiteMetadata>() {
+            Some(metadata) => {
+                self.names = metadata.names.clone();
+                self.schema = metadata.schema.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
     #[throws(SQLiteSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
-        for query in self.queries {
-            let conn = self.pool.get()?;
-
-            ret.push(SQLiteSourcePartition::new(conn, &query, &self.schema));
+        for (i, query) in self.queries.iter().enumerate() {
+            let checkout_start = Instant::now();
+            let conn = self.conn()?;
+            let checkout_time = checkout_start.elapsed();
+
+            let mut partition = SQLiteSourcePartition::new(conn, query, &self.schema);
+            partition.params = self.query_params(i);
+            partition.reuse_prepared = self.reuse_prepared;
+            partition.invalid_utf8 = self.invalid_utf8;
+            partition.checkout_time = checkout_time;
+            ret.push(partition);
         }
         ret
     }
 }
 
 pub struct SQLiteSourcePartition {
-    conn: PooledConnection<SqliteConnectionManager>,
+    conn: SQLiteConn,
     query: CXQuery<String>,
     schema: Vec<SQLiteTypeSystem>,
+    params: Vec<ScalarValue>,
+    reuse_prepared: bool,
+    invalid_utf8: InvalidUtf8Policy,
     nrows: usize,
     ncols: usize,
+    checkout_time: Duration,
 }
 
 impl SQLiteSourcePartition {
-    pub fn new(
-        conn: PooledConnection<SqliteConnectionManager>,
-        query: &CXQuery<String>,
-        schema: &[SQLiteTypeSystem],
-    ) -> Self {
+    pub fn new(conn: SQLiteConn, query: &CXQuery<String>, schema: &[SQLiteTypeSystem]) -> Self {
         Self {
             conn,
             query: query.clone(),
             schema: schema.to_vec(),
+            params: vec![],
+            reuse_prepared: false,
+            invalid_utf8: InvalidUtf8Policy::default(),
             nrows: 0,
             ncols: schema.len(),
+            checkout_time: Duration::ZERO,
         }
     }
 }
@@ -227,16 +411,27 @@ impl SourcePartition for SQLiteSourcePartition {
 
     #[throws(SQLiteSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = self.conn.query_row(
-            count_query(&self.query, &SQLiteDialect {})?.as_str(),
-            [],
-            |row| Ok(row.get::<_, i64>(0)? as usize),
-        )?;
+        let count_query = count_query(&self.query, &SQLiteDialect {})?;
+        let mut stmt = match self.reuse_prepared {
+            true => PreparedStatement::Cached(self.conn.prepare_cached(count_query.as_str())?),
+            false => PreparedStatement::Owned(self.conn.prepare(count_query.as_str())?),
+        };
+        self.nrows = stmt.query_row(params_from_iter(&self.params), |row| {
+            Ok(row.get::<_, i64>(0)? as usize)
+        })?;
     }
 
     #[throws(SQLiteSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        SQLiteSourcePartitionParser::new(&self.conn, self.query.as_str(), &self.schema)?
+        let mut parser = SQLiteSourcePartitionParser::new(
+            &self.conn,
+            self.query.as_str(),
+            &self.schema,
+            &self.params,
+            self.reuse_prepared,
+        )?;
+        parser.invalid_utf8 = self.invalid_utf8;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -246,40 +441,85 @@ impl SourcePartition for SQLiteSourcePartition {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    fn checkout_time(&self) -> Duration {
+        self.checkout_time
+    }
+}
+
+/// A statement prepared for one read, or borrowed from the connection's statement cache and
+/// returned to it on drop.
+pub enum PreparedStatement<'a> {
+    Owned(Statement<'a>),
+    Cached(CachedStatement<'a>),
+}
+
+impl<'a> Deref for PreparedStatement<'a> {
+    type Target = Statement<'a>;
+
+    fn deref(&self) -> &Statement<'a> {
+        match self {
+            PreparedStatement::Owned(stmt) => stmt,
+            PreparedStatement::Cached(stmt) => stmt,
+        }
+    }
+}
+
+impl<'a> DerefMut for PreparedStatement<'a> {
+    fn deref_mut(&mut self) -> &mut Statement<'a> {
+        match self {
+            PreparedStatement::Owned(stmt) => stmt,
+            PreparedStatement::Cached(stmt) => stmt,
+        }
+    }
 }
 
 unsafe impl<'a> Send for SQLiteSourcePartitionParser<'a> {}
 
 pub struct SQLiteSourcePartitionParser<'a> {
-    rows: OwningHandle<Box<Statement<'a>>, DummyBox<Rows<'a>>>,
+    rows: OwningHandle<Box<PreparedStatement<'a>>, DummyBox<Rows<'a>>>,
     ncols: usize,
     current_col: usize,
     current_consumed: bool,
     is_finished: bool,
+    invalid_utf8: InvalidUtf8Policy,
 }
 
 impl<'a> SQLiteSourcePartitionParser<'a> {
     #[throws(SQLiteSourceError)]
     pub fn new(
-        conn: &'a PooledConnection<SqliteConnectionManager>,
+        conn: &'a SQLiteConn,
         query: &str,
         schema: &[SQLiteTypeSystem],
+        params: &[ScalarValue],
+        reuse_prepared: bool,
     ) -> Self {
-        let stmt: Statement<'a> = conn.prepare(query)?;
+        let stmt = match reuse_prepared {
+            true => PreparedStatement::Cached(conn.prepare_cached(query)?),
+            false => PreparedStatement::Owned(conn.prepare(query)?),
+        };
 
         // Safety: DummyBox borrows the on-heap stmt, which is owned by the OwningHandle.
         // No matter how we move the owning handle (thus the Box<Statment>), the Statement
         // keeps its address static on the heap, thus the borrow of MyRows keeps valid.
-        let rows: OwningHandle<Box<Statement<'a>>, DummyBox<Rows<'a>>> =
-            OwningHandle::new_with_fn(Box::new(stmt), |stmt: *const Statement<'a>| unsafe {
-                DummyBox((*(stmt as *mut Statement<'_>)).query([]).unwrap())
-            });
+        let rows: OwningHandle<Box<PreparedStatement<'a>>, DummyBox<Rows<'a>>> =
+            OwningHandle::new_with_fn(
+                Box::new(stmt),
+                |stmt: *const PreparedStatement<'a>| unsafe {
+                    DummyBox(
+                        (*(stmt as *mut PreparedStatement<'_>))
+                            .query(params_from_iter(params))
+                            .unwrap(),
+                    )
+                },
+            );
         Self {
             rows,
             ncols: schema.len(),
             current_col: 0,
             current_consumed: true,
             is_finished: false,
+            invalid_utf8: InvalidUtf8Policy::default(),
         }
     }
 
@@ -356,9 +596,36 @@ impl_produce!(
     i32,
     i16,
     f64,
-    Box<str>,
     NaiveDate,
     NaiveTime,
     NaiveDateTime,
     Vec<u8>,
 );
+
+impl<'r, 'a> Produce<'r, Box<str>> for SQLiteSourcePartitionParser<'a> {
+    type Error = SQLiteSourceError;
+
+    #[throws(SQLiteSourceError)]
+    fn produce(&'r mut self) -> Box<str> {
+        let policy = self.invalid_utf8;
+        let (row, col) = self.next_loc()?;
+        match row.get_ref(col)? {
+            ValueRef::Text(bytes) => policy.decode(bytes)?.into(),
+            _ => row.get(col)?,
+        }
+    }
+}
+
+impl<'r, 'a> Produce<'r, Option<Box<str>>> for SQLiteSourcePartitionParser<'a> {
+    type Error = SQLiteSourceError;
+
+    #[throws(SQLiteSourceError)]
+    fn produce(&'r mut self) -> Option<Box<str>> {
+        let policy = self.invalid_utf8;
+        let (row, col) = self.next_loc()?;
+        match row.get_ref(col)? {
+            ValueRef::Text(bytes) => Some(policy.decode(bytes)?.into()),
+            _ => row.get(col)?,
+        }
+    }
+}
@@ -81,7 +81,12 @@ impl TryFrom<(Option<&str>, Type)> for SQLiteTypeSystem {
                     {
                         Real(true)
                     }
-                    _ if decl_type.contains("blob") => Blob(true),
+                    _ if decl_type.contains("blob")
+                        || decl_type.contains("binary")
+                        || decl_type == "bytea" =>
+                    {
+                        Blob(true)
+                    }
                     _ => SQLiteTypeSystem::try_from(ty)?,
                 }
             }
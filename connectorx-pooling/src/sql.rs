@@ -97,6 +97,56 @@ impl<Q, E> CXQuery<Result<Q, E>> {
     }
 }
 
+/// Splits a script into its `;`-separated statements, trimmed. Semicolons inside quotes and
+/// comments do not end a statement, and pieces holding nothing but whitespace and comments
+/// are dropped.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let mut statements = vec![];
+    let mut start = 0;
+    let mut has_code = false;
+    let mut chars = sql.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                has_code = true;
+                // a doubled quote is an escaped one and keeps the literal open
+                while let Some((_, d)) = chars.next() {
+                    if d == c && chars.next_if(|&(_, e)| e == c).is_none() {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.next_if(|&(_, d)| d == '-').is_some() => {
+                for (_, d) in chars.by_ref() {
+                    if d == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.next_if(|&(_, d)| d == '*').is_some() => {
+                while let Some((_, d)) = chars.next() {
+                    if d == '*' && chars.next_if(|&(_, e)| e == '/').is_some() {
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                if has_code {
+                    statements.push(sql[start..i].trim().to_string());
+                }
+                start = i + 1;
+                has_code = false;
+            }
+            c if !c.is_whitespace() => has_code = true,
+            _ => {}
+        }
+    }
+    if has_code {
+        statements.push(sql[start..].trim().to_string());
+    }
+    statements
+}
+
 // wrap a query into a derived table
 fn wrap_query(
     query: &mut Query,
@@ -327,13 +377,89 @@ pub fn single_col_partition_query<T: Dialect>(
     upper: i64,
     dialect: &T,
 ) -> String {
-    trace!("Incoming query: {}", sql);
+    range_partition_query(sql, col, lower, upper, false, dialect)?
+}
+
+/// Wraps `sql` so it only returns rows with `lower <= col < upper`. With `include_nulls`
+/// the rows whose `col` is NULL are kept as well, which a range predicate alone would drop.
+#[throws(ConnectorXError)]
+pub fn range_partition_query<T: Dialect>(
+    sql: &str,
+    col: &str,
+    lower: i64,
+    upper: i64,
+    include_nulls: bool,
+    dialect: &T,
+) -> String {
+    let nulls = match include_nulls {
+        true => NullRows::Keep,
+        false => NullRows::Drop,
+    };
+    bounded_partition_query(
+        sql,
+        col,
+        Value::Number(lower.to_string(), false),
+        Value::Number(upper.to_string(), false),
+        nulls,
+        dialect,
+    )?
+}
+
+/// Like [`range_partition_query`], but the bounds are left to the `lower` and `upper`
+/// placeholders, and the rows whose `col` is NULL are kept when the `keep_nulls` one is true.
+/// Every range partition of `sql` then shares this one statement text, so it can be prepared
+/// once per connection and executed with each partition's bounds bound.
+#[throws(ConnectorXError)]
+pub fn range_partition_template<T: Dialect>(
+    sql: &str,
+    col: &str,
+    lower: &str,
+    upper: &str,
+    keep_nulls: &str,
+    dialect: &T,
+) -> String {
+    bounded_partition_query(
+        sql,
+        col,
+        Value::Placeholder(lower.to_string()),
+        Value::Placeholder(upper.to_string()),
+        NullRows::KeepIf(keep_nulls.to_string()),
+        dialect,
+    )?
+}
+
+/// What a partition query does with the rows whose partition column is NULL.
+enum NullRows {
+    Drop,
+    Keep,
+    /// Keep them when the placeholder holds true.
+    KeepIf(String),
+}
+
+#[throws(ConnectorXError)]
+fn bounded_partition_query<T: Dialect>(
+    sql: &str,
+    col: &str,
+    lower: Value,
+    upper: Value,
+    nulls: NullRows,
+    dialect: &T,
+) -> String {
     const PART_TMP_TAB_NAME: &str = "CXTMPTAB_PART";
+    let range = format!(
+        "CXTMPTAB_PART.{} >= {} AND CXTMPTAB_PART.{} < {}",
+        col, lower, col, upper
+    );
+    let predicate = match &nulls {
+        NullRows::Drop => range,
+        NullRows::Keep => format!("({}) OR CXTMPTAB_PART.{} IS NULL", range, col),
+        NullRows::KeepIf(keep) => format!(
+            "({}) OR (CXTMPTAB_PART.{} IS NULL AND {})",
+            range, col, keep
+        ),
+    };
 
-    #[allow(unused_mut)]
-    let mut table_alias = PART_TMP_TAB_NAME;
-    #[allow(unused_mut)]
-    let mut cid = Box::new(Expr::CompoundIdentifier(vec![
+    let cid = Box::new(Expr::CompoundIdentifier(vec![
         Ident {
             value: PART_TMP_TAB_NAME.to_string(),
             quote_style: None,
@@ -344,19 +470,80 @@ pub fn single_col_partition_query<T: Dialect>(
         },
     ]));
 
+    let lb = Expr::BinaryOp {
+        left: Box::new(Expr::Value(lower)),
+        op: BinaryOperator::LtEq,
+        right: cid.clone(),
+    };
+
+    let ub = Expr::BinaryOp {
+        left: cid.clone(),
+        op: BinaryOperator::Lt,
+        right: Box::new(Expr::Value(upper)),
+    };
+
+    let mut selection = Expr::BinaryOp {
+        left: Box::new(lb),
+        op: BinaryOperator::And,
+        right: Box::new(ub),
+    };
+    let null_rows = match nulls {
+        NullRows::Drop => None,
+        NullRows::Keep => Some(Expr::IsNull(cid)),
+        NullRows::KeepIf(keep) => Some(Expr::Nested(Box::new(Expr::BinaryOp {
+            left: Box::new(Expr::IsNull(cid)),
+            op: BinaryOperator::And,
+            right: Box::new(Expr::Value(Value::Placeholder(keep))),
+        }))),
+    };
+    if let Some(null_rows) = null_rows {
+        selection = Expr::BinaryOp {
+            left: Box::new(Expr::Nested(Box::new(selection))),
+            op: BinaryOperator::Or,
+            right: Box::new(null_rows),
+        };
+    }
+
+    wrap_partition_query(sql, &predicate, Some(selection), dialect)?
+}
+
+/// Wraps `sql` so it only returns the rows matching `predicate`, an SQL expression over the
+/// columns of `sql` qualified as `CXTMPTAB_PART.<col>`, e.g.
+/// `CXTMPTAB_PART.name >= 'a' AND CXTMPTAB_PART.name < 'm'`.
+#[throws(ConnectorXError)]
+pub fn predicate_partition_query<T: Dialect>(sql: &str, predicate: &str, dialect: &T) -> String {
+    // a predicate the parser does not understand is composed into the query as a string
+    let selection = Parser::new(dialect)
+        .try_with_sql(predicate)
+        .and_then(|mut parser| parser.parse_expr())
+        .ok();
+    wrap_partition_query(sql, predicate, selection, dialect)?
+}
+
+/// Wraps `sql` into `SELECT * FROM (sql) AS CXTMPTAB_PART WHERE <selection>`, through the
+/// AST when both parse, or else composing the query from `predicate`.
+#[throws(ConnectorXError)]
+fn wrap_partition_query<T: Dialect>(
+    sql: &str,
+    predicate: &str,
+    selection: Option<Expr>,
+    dialect: &T,
+) -> String {
+    trace!("Incoming query: {}", sql);
+    const PART_TMP_TAB_NAME: &str = "CXTMPTAB_PART";
+
+    #[allow(unused_mut)]
+    let mut table_alias = PART_TMP_TAB_NAME;
+
     // HACK: Some dialect (e.g. Oracle) does not support "AS" for alias
     #[cfg(feature = "src_oracle")]
     if dialect.type_id() == (OracleDialect {}.type_id()) {
-        return format!("SELECT * FROM ({}) CXTMPTAB_PART WHERE CXTMPTAB_PART.{} >= {} AND CXTMPTAB_PART.{} < {}", sql, col, lower, col, upper);
+        return format!("SELECT * FROM ({}) CXTMPTAB_PART WHERE {}", sql, predicate);
         // table_alias = "";
-        // cid = Box::new(Expr::Identifier(Ident {
-        //     value: col.to_string(),
-        //     quote_style: None,
-        // }));
     }
 
-    let tsql = match Parser::parse_sql(dialect, sql) {
-        Ok(ast) => {
+    let tsql = match (Parser::parse_sql(dialect, sql), selection) {
+        (Ok(ast), Some(selection)) => {
             if ast.len() != 1 {
                 throw!(ConnectorXError::SqlQueryNotSupported(sql.to_string()));
             }
@@ -371,26 +558,6 @@ pub fn single_col_partition_query<T: Dialect>(
                 .ok_or_else(|| ConnectorXError::SqlQueryNotSupported(sql.to_string()))?
                 .clone();
 
-            let ast_part: Statement;
-
-            let lb = Expr::BinaryOp {
-                left: Box::new(Expr::Value(Value::Number(lower.to_string(), false))),
-                op: BinaryOperator::LtEq,
-                right: cid.clone(),
-            };
-
-            let ub = Expr::BinaryOp {
-                left: cid,
-                op: BinaryOperator::Lt,
-                right: Box::new(Expr::Value(Value::Number(upper.to_string(), false))),
-            };
-
-            let selection = Expr::BinaryOp {
-                left: Box::new(lb),
-                op: BinaryOperator::And,
-                right: Box::new(ub),
-            };
-
             if query.limit.is_none() && select.top.is_none() && !query.order_by.is_empty() {
                 // order by in a partition query does not make sense because partition is unordered.
                 // clear the order by beceause mssql does not support order by in a derived table.
@@ -398,7 +565,7 @@ pub fn single_col_partition_query<T: Dialect>(
                 query.order_by.clear();
             }
 
-            ast_part = wrap_query(
+            let ast_part = wrap_query(
                 &mut query,
                 vec![SelectItem::Wildcard(WildcardAdditionalOptions::default())],
                 Some(selection),
@@ -406,9 +573,14 @@ pub fn single_col_partition_query<T: Dialect>(
             );
             format!("{}", ast_part)
         }
-        Err(e) => {
-            warn!("parser error: {:?}, manually compose query string", e);
-            format!("SELECT * FROM ({}) AS CXTMPTAB_PART WHERE CXTMPTAB_PART.{} >= {} AND CXTMPTAB_PART.{} < {}", sql, col, lower, col, upper)
+        (parsed, _) => {
+            if let Err(e) = parsed {
+                warn!("parser error: {:?}, manually compose query string", e);
+            }
+            format!(
+                "SELECT * FROM ({}) AS CXTMPTAB_PART WHERE {}",
+                sql, predicate
+            )
         }
     };
 
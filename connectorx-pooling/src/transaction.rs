@@ -0,0 +1,103 @@
+//! Multi-statement transactions on a single pooled connection.
+//!
+//! A [`Transaction`] pins one connection of a [`PoolVariant`] for its whole lifetime, so every
+//! statement and read issued through it sees the same session and the writes made so far.
+//! The connection goes back to the pool once the transaction is committed, rolled back or
+//! dropped; dropping an unfinished transaction rolls it back.
+
+#[cfg(feature = "dst_arrow")]
+use crate::{
+    destinations::arrow::ArrowDestination, errors::ConnectorXOutError, get_arrow::get_arrow_single,
+};
+use crate::{
+    pool::{PinnedVariant, PoolVariant},
+    source_router::SourceConn,
+};
+use anyhow::Result;
+
+pub struct Transaction {
+    source_conn: SourceConn,
+    pool: PoolVariant,
+    conn: PinnedVariant,
+    finished: bool,
+}
+
+impl Transaction {
+    /// Checks a connection out of `pool` and starts a transaction on it. Oracle has no
+    /// explicit `BEGIN`: its sessions are always inside a transaction.
+    pub fn begin(source_conn: &SourceConn, pool: &PoolVariant) -> Result<Self> {
+        let conn = pool.pin()?;
+        let begin = match &conn {
+            #[cfg(feature = "src_mysql")]
+            PinnedVariant::MySQL(_) => Some("START TRANSACTION"),
+            #[cfg(feature = "src_oracle")]
+            PinnedVariant::Oracle(_) => None,
+            #[allow(unreachable_patterns)]
+            _ => Some("BEGIN"),
+        };
+        let txn = Self {
+            source_conn: source_conn.clone(),
+            pool: pool.clone(),
+            conn,
+            finished: false,
+        };
+        if let Some(begin) = begin {
+            txn.execute(begin)?;
+        }
+        Ok(txn)
+    }
+
+    /// Runs `sql` on the transaction's connection, discarding any result set.
+    pub fn execute(&self, sql: &str) -> Result<()> {
+        self.conn.execute(sql)
+    }
+
+    /// Reads `query` into Arrow on the transaction's connection, so it sees the
+    /// transaction's uncommitted writes. The query runs as a single partition.
+    #[cfg(feature = "dst_arrow")]
+    pub fn read_arrow(&self, query: &str) -> Result<ArrowDestination, ConnectorXOutError> {
+        get_arrow_single(
+            &self.source_conn,
+            query,
+            &[],
+            Some(&self.pool),
+            Some(&self.conn),
+        )
+    }
+
+    pub fn commit(mut self) -> Result<()> {
+        self.finished = true;
+        self.finish(true)
+    }
+
+    pub fn rollback(mut self) -> Result<()> {
+        self.finished = true;
+        self.finish(false)
+    }
+
+    fn finish(&self, commit: bool) -> Result<()> {
+        match &self.conn {
+            #[cfg(feature = "src_oracle")]
+            PinnedVariant::Oracle(c) => {
+                let conn = c.lease()?;
+                if commit {
+                    conn.commit()?
+                } else {
+                    conn.rollback()?
+                }
+            }
+            #[allow(unreachable_patterns)]
+            _ => self.execute(if commit { "COMMIT" } else { "ROLLBACK" })?,
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if !self.finished {
+            // best effort: the connection is returned to the pool either way
+            let _ = self.finish(false);
+        }
+    }
+}
@@ -2,7 +2,9 @@
 
 use crate::{
     destinations::arrow::{
-        typesystem::{ArrowTypeSystem, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro},
+        typesystem::{
+            ArrowTypeSystem, JsonString, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro,
+        },
         ArrowDestination, ArrowDestinationError,
     },
     impl_transport,
@@ -46,11 +48,11 @@ impl_transport!(
         { Int24[i32]                 => Int64[i64]              | conversion none }
         { Long[i32]                  => Int64[i64]              | conversion auto }
         { LongLong[i64]              => Int64[i64]              | conversion auto }
-        { UTiny[u8]                  => Int64[i64]              | conversion auto }
-        { UShort[u16]                => Int64[i64]              | conversion auto }
-        { ULong[u32]                 => Int64[i64]              | conversion auto }
-        { UInt24[u32]                => Int64[i64]              | conversion none }
-        { ULongLong[u64]             => Float64[f64]            | conversion auto }
+        { UTiny[u8]                  => UInt8[u8]               | conversion auto }
+        { UShort[u16]                => UInt16[u16]             | conversion auto }
+        { ULong[u32]                 => UInt32[u32]             | conversion auto }
+        { UInt24[u32]                => UInt32[u32]             | conversion none }
+        { ULongLong[u64]             => UInt64[u64]             | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
         { Time[NaiveTime]            => Time64Micro[NaiveTimeWrapperMicro]       | conversion option }
         { Datetime[NaiveDateTime]    => Date64Micro[NaiveDateTimeWrapperMicro]   | conversion option }
@@ -64,8 +66,11 @@ impl_transport!(
         { Blob[Vec<u8>]              => LargeBinary[Vec<u8>]    | conversion none }
         { MediumBlob[Vec<u8>]        => LargeBinary[Vec<u8>]    | conversion none }
         { LongBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion none }
-        { Json[Value]                => LargeUtf8[String]       | conversion option }
+        { Json[Value]                => Json[JsonString]        | conversion option }
         { Bit[Vec<u8>]               => LargeBinary[Vec<u8>]    | conversion none }
+        { BitFlag[bool]              => Boolean[bool]           | conversion auto }
+        { Set[String]                => LargeUtf8[String]       | conversion none }
+        { SetList[Vec<Option<String>>] => Utf8Array[Vec<Option<String>>] | conversion auto }
     }
 );
 
@@ -82,11 +87,11 @@ impl_transport!(
         { Int24[i32]                 => Int64[i64]              | conversion none }
         { Long[i32]                  => Int64[i64]              | conversion auto }
         { LongLong[i64]              => Int64[i64]              | conversion auto }
-        { UTiny[u8]                  => Int64[i64]              | conversion auto }
-        { UShort[u16]                => Int64[i64]              | conversion auto }
-        { ULong[u32]                 => Int64[i64]              | conversion auto }
-        { UInt24[u32]                => Int64[i64]              | conversion none }
-        { ULongLong[u64]             => Float64[f64]            | conversion auto }
+        { UTiny[u8]                  => UInt8[u8]               | conversion auto }
+        { UShort[u16]                => UInt16[u16]             | conversion auto }
+        { ULong[u32]                 => UInt32[u32]             | conversion auto }
+        { UInt24[u32]                => UInt32[u32]             | conversion none }
+        { ULongLong[u64]             => UInt64[u64]             | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
         { Time[NaiveTime]            => Time64Micro[NaiveTimeWrapperMicro]       | conversion option }
         { Datetime[NaiveDateTime]    => Date64Micro[NaiveDateTimeWrapperMicro]   | conversion option }
@@ -100,8 +105,11 @@ impl_transport!(
         { Blob[Vec<u8>]              => LargeBinary[Vec<u8>]    | conversion none }
         { MediumBlob[Vec<u8>]        => LargeBinary[Vec<u8>]    | conversion none }
         { LongBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion none }
-        { Json[Value]                => LargeUtf8[String]       | conversion option }
+        { Json[Value]                => Json[JsonString]        | conversion option }
         { Bit[Vec<u8>]               => LargeBinary[Vec<u8>]    | conversion none }
+        { BitFlag[bool]              => Boolean[bool]           | conversion auto }
+        { Set[String]                => LargeUtf8[String]       | conversion none }
+        { SetList[Vec<Option<String>>] => Utf8Array[Vec<Option<String>>] | conversion auto }
     }
 );
 
@@ -124,9 +132,9 @@ impl<P> TypeConversion<Decimal, f64> for MySQLArrowTransport<P> {
     }
 }
 
-impl<P> TypeConversion<Value, String> for MySQLArrowTransport<P> {
-    fn convert(val: Value) -> String {
-        to_string(&val).unwrap()
+impl<P> TypeConversion<Value, JsonString> for MySQLArrowTransport<P> {
+    fn convert(val: Value) -> JsonString {
+        JsonString(to_string(&val).unwrap())
     }
 }
 
@@ -2,7 +2,8 @@
 
 use crate::{
     destinations::arrowstream::{
-        typesystem::ArrowTypeSystem, ArrowDestination, ArrowDestinationError,
+        typesystem::{ArrowTypeSystem, JsonString},
+        ArrowDestination, ArrowDestinationError,
     },
     impl_transport,
     sources::mysql::{
@@ -45,11 +46,11 @@ impl_transport!(
         { Int24[i32]                 => Int64[i64]              | conversion none }
         { Long[i32]                  => Int64[i64]              | conversion auto }
         { LongLong[i64]              => Int64[i64]              | conversion auto }
-        { UTiny[u8]                  => Int64[i64]              | conversion auto }
-        { UShort[u16]                => Int64[i64]              | conversion auto }
-        { ULong[u32]                 => Int64[i64]              | conversion auto }
-        { UInt24[u32]                => Int64[i64]              | conversion none }
-        { ULongLong[u64]             => Float64[f64]            | conversion auto }
+        { UTiny[u8]                  => UInt8[u8]               | conversion auto }
+        { UShort[u16]                => UInt16[u16]             | conversion auto }
+        { ULong[u32]                 => UInt32[u32]             | conversion auto }
+        { UInt24[u32]                => UInt32[u32]             | conversion none }
+        { ULongLong[u64]             => UInt64[u64]             | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
         { Time[NaiveTime]            => Time64[NaiveTime]       | conversion auto }
         { Datetime[NaiveDateTime]    => Date64[NaiveDateTime]   | conversion auto }
@@ -63,8 +64,11 @@ impl_transport!(
         { Blob[Vec<u8>]              => LargeBinary[Vec<u8>]    | conversion none }
         { MediumBlob[Vec<u8>]        => LargeBinary[Vec<u8>]    | conversion none }
         { LongBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion none }
-        { Json[Value]                => LargeUtf8[String]       | conversion option }
+        { Json[Value]                => Json[JsonString]        | conversion option }
         { Bit[Vec<u8>]               => LargeBinary[Vec<u8>]    | conversion none }
+        { BitFlag[bool]              => Boolean[bool]           | conversion auto }
+        { Set[String]                => LargeUtf8[String]       | conversion none }
+        { SetList[Vec<Option<String>>] => Utf8Array[Vec<Option<String>>] | conversion auto }
     }
 );
 
@@ -81,11 +85,11 @@ impl_transport!(
         { Int24[i32]                 => Int64[i64]              | conversion none }
         { Long[i32]                  => Int64[i64]              | conversion auto }
         { LongLong[i64]              => Int64[i64]              | conversion auto }
-        { UTiny[u8]                  => Int64[i64]              | conversion auto }
-        { UShort[u16]                => Int64[i64]              | conversion auto }
-        { ULong[u32]                 => Int64[i64]              | conversion auto }
-        { UInt24[u32]                => Int64[i64]              | conversion none }
-        { ULongLong[u64]             => Float64[f64]            | conversion auto }
+        { UTiny[u8]                  => UInt8[u8]               | conversion auto }
+        { UShort[u16]                => UInt16[u16]             | conversion auto }
+        { ULong[u32]                 => UInt32[u32]             | conversion auto }
+        { UInt24[u32]                => UInt32[u32]             | conversion none }
+        { ULongLong[u64]             => UInt64[u64]             | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
         { Time[NaiveTime]            => Time64[NaiveTime]       | conversion auto }
         { Datetime[NaiveDateTime]    => Date64[NaiveDateTime]   | conversion auto }
@@ -99,8 +103,11 @@ impl_transport!(
         { Blob[Vec<u8>]              => LargeBinary[Vec<u8>]    | conversion none }
         { MediumBlob[Vec<u8>]        => LargeBinary[Vec<u8>]    | conversion none }
         { LongBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion none }
-        { Json[Value]                => LargeUtf8[String]       | conversion option }
+        { Json[Value]                => Json[JsonString]        | conversion option }
         { Bit[Vec<u8>]               => LargeBinary[Vec<u8>]    | conversion none }
+        { BitFlag[bool]              => Boolean[bool]           | conversion auto }
+        { Set[String]                => LargeUtf8[String]       | conversion none }
+        { SetList[Vec<Option<String>>] => Utf8Array[Vec<Option<String>>] | conversion auto }
     }
 );
 
@@ -111,9 +118,9 @@ impl<P> TypeConversion<Decimal, f64> for MySQLArrowTransport<P> {
     }
 }
 
-impl<P> TypeConversion<Value, String> for MySQLArrowTransport<P> {
-    fn convert(val: Value) -> String {
-        to_string(&val).unwrap()
+impl<P> TypeConversion<Value, JsonString> for MySQLArrowTransport<P> {
+    fn convert(val: Value) -> JsonString {
+        JsonString(to_string(&val).unwrap())
     }
 }
 
@@ -33,12 +33,15 @@ impl_transport!(
     mappings = {
         { NumFloat[f64]                     => Float64[f64]                             | conversion auto }
         { NumDecimal[Decimal]               => Decimal[Decimal]                         | conversion auto }
+        { NumBigInt[Decimal]                => Decimal[Decimal]                         | conversion none }
         { Float[f64]                        => Float64[f64]                             | conversion none }
         { BinaryFloat[f64]                  => Float64[f64]                             | conversion none }
         { BinaryDouble[f64]                 => Float64[f64]                             | conversion none }
         { NumInt[i64]                       => Int64[i64]                               | conversion auto }
         { Blob[Vec<u8>]                     => LargeBinary[Vec<u8>]                     | conversion auto }
+        { Raw[Vec<u8>]                      => LargeBinary[Vec<u8>]                     | conversion none }
         { Clob[String]                      => LargeUtf8[String]                        | conversion none }
+        { NClob[String]                     => LargeUtf8[String]                        | conversion none }
         { VarChar[String]                   => LargeUtf8[String]                        | conversion auto }
         { Char[String]                      => LargeUtf8[String]                        | conversion none }
         { NVarChar[String]                  => LargeUtf8[String]                        | conversion none }
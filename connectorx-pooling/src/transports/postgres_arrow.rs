@@ -2,15 +2,17 @@
 
 use crate::destinations::arrow::{
     typesystem::{
-        ArrowTypeSystem, DateTimeWrapperMicro, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro,
+        ArrowTypeSystem, DateTimeWrapperMicro, JsonString, NaiveDateTimeWrapperMicro,
+        NaiveTimeWrapperMicro, WkbBinary,
     },
     ArrowDestination, ArrowDestinationError,
 };
 use crate::sources::postgres::{
-    BinaryProtocol, CSVProtocol, CursorProtocol, PostgresSource, PostgresSourceError,
-    PostgresTypeSystem, SimpleProtocol,
+    BinaryProtocol, CSVProtocol, CursorProtocol, Hstore, PgInterval, PostgresSource,
+    PostgresSourceError, PostgresTypeSystem, SimpleProtocol, Wkb,
 };
 use crate::typesystem::TypeConversion;
+use arrow::datatypes::IntervalMonthDayNano;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use cidr_02::IpInet;
 use num_traits::ToPrimitive;
@@ -19,6 +21,7 @@ use postgres::NoTls;
 use postgres_openssl::MakeTlsConnector;
 use rust_decimal::Decimal;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use thiserror::Error;
 use uuid::Uuid;
@@ -62,12 +65,13 @@ macro_rules! impl_postgres_transport {
                 { Timestamp[NaiveDateTime]           => Date64Micro[NaiveDateTimeWrapperMicro] | conversion option }
                 { Date[NaiveDate]                    => Date32[NaiveDate]                      | conversion auto   }
                 { Time[NaiveTime]                    => Time64Micro[NaiveTimeWrapperMicro]     | conversion option }
+                { Interval[PgInterval]               => Interval[IntervalMonthDayNano]         | conversion option }
                 { TimestampTz[DateTime<Utc>]         => DateTimeTzMicro[DateTimeWrapperMicro]  | conversion option }
                 { UUID[Uuid]                         => LargeUtf8[String]                      | conversion option }
                 { Char[&'r str]                      => LargeUtf8[String]                      | conversion none   }
                 { ByteA[Vec<u8>]                     => LargeBinary[Vec<u8>]                   | conversion auto   }
-                { JSON[Value]                        => LargeUtf8[String]                      | conversion option }
-                { JSONB[Value]                       => LargeUtf8[String]                      | conversion none   }
+                { JSON[Value]                        => Json[JsonString]                       | conversion option }
+                { JSONB[Value]                       => Json[JsonString]                       | conversion none   }
                 { Inet[IpInet]                       => LargeUtf8[String]                      | conversion none   }
                 { BoolArray[Vec<Option<bool>>]       => BoolArray[Vec<Option<bool>>]           | conversion auto   }
                 { VarcharArray[Vec<Option<String>>]  => Utf8Array[Vec<Option<String>>]         | conversion auto   }
@@ -82,6 +86,9 @@ macro_rules! impl_postgres_transport {
                 { HalfVec[HalfVector]                => Float32Array[Vec<Option<f32>>]         | conversion option }
                 { Bit[Bit]                           => LargeBinary[Vec<u8>]                   | conversion option }
                 { SparseVec[SparseVector]            => Float32Array[Vec<Option<f32>>]         | conversion option }
+                { Geometry[Wkb]                      => Wkb[WkbBinary]                         | conversion option }
+                { GeometryWkt[Wkb]                   => LargeUtf8[String]                      | conversion option }
+                { HSTORE[Hstore]                     => Map[HashMap<String, Option<String>>]   | conversion option }
             }
         );
     }
@@ -96,6 +103,12 @@ impl_postgres_transport!(CursorProtocol, MakeTlsConnector);
 impl_postgres_transport!(SimpleProtocol, NoTls);
 impl_postgres_transport!(SimpleProtocol, MakeTlsConnector);
 
+impl<P, C> TypeConversion<PgInterval, IntervalMonthDayNano> for PostgresArrowTransport<P, C> {
+    fn convert(val: PgInterval) -> IntervalMonthDayNano {
+        IntervalMonthDayNano::new(val.months, val.days, val.microseconds * 1000)
+    }
+}
+
 impl<P, C> TypeConversion<IpInet, String> for PostgresArrowTransport<P, C> {
     fn convert(val: IpInet) -> String {
         val.to_string()
@@ -141,9 +154,9 @@ impl<P, C> TypeConversion<Decimal, f64> for PostgresArrowTransport<P, C> {
     }
 }
 
-impl<P, C> TypeConversion<Value, String> for PostgresArrowTransport<P, C> {
-    fn convert(val: Value) -> String {
-        val.to_string()
+impl<P, C> TypeConversion<Value, JsonString> for PostgresArrowTransport<P, C> {
+    fn convert(val: Value) -> JsonString {
+        JsonString(val.to_string())
     }
 }
 
@@ -170,3 +183,23 @@ impl<P, C> TypeConversion<SparseVector, Vec<Option<f32>>> for PostgresArrowTrans
         val.to_vec().into_iter().map(Some).collect()
     }
 }
+
+impl<P, C> TypeConversion<Wkb, WkbBinary> for PostgresArrowTransport<P, C> {
+    fn convert(val: Wkb) -> WkbBinary {
+        WkbBinary(val.into_bytes())
+    }
+}
+
+impl<P, C> TypeConversion<Wkb, String> for PostgresArrowTransport<P, C> {
+    fn convert(val: Wkb) -> String {
+        val.to_string()
+    }
+}
+
+impl<P, C> TypeConversion<Hstore, HashMap<String, Option<String>>>
+    for PostgresArrowTransport<P, C>
+{
+    fn convert(val: Hstore) -> HashMap<String, Option<String>> {
+        val.into_inner()
+    }
+}
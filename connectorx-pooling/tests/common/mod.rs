@@ -0,0 +1,64 @@
+//! Fixtures shared by the integration tests. Each test file only uses some of them.
+#![allow(dead_code)]
+
+use connectorx::{
+    pool::{PoolConfig, PoolVariant},
+    prelude::*,
+};
+use std::convert::TryFrom;
+use std::path::Path;
+use tempfile::TempPath;
+
+/// A path in the temp dir ending in `suffix`, removed when dropped.
+pub fn temp_path(name: &str, suffix: &str) -> TempPath {
+    tempfile::Builder::new()
+        .prefix(&format!("cx_{}_", name))
+        .suffix(suffix)
+        .tempfile()
+        .unwrap()
+        .into_temp_path()
+}
+
+/// A SQLite database set up by `sql`, removed when dropped.
+pub fn sqlite_db(name: &str, sql: &str) -> TempPath {
+    let path = temp_path(name, ".db");
+    rusqlite::Connection::open(&path)
+        .unwrap()
+        .execute_batch(sql)
+        .unwrap();
+    path
+}
+
+/// A database holding `test_table(test_int, test_str)` with `rows`, given as SQL tuples.
+pub fn create_sqlite_db(name: &str, rows: &str) -> TempPath {
+    sqlite_db(
+        name,
+        &format!(
+            "CREATE TABLE test_table(test_int INTEGER NOT NULL, test_str TEXT);
+             INSERT INTO test_table VALUES {};",
+            rows
+        ),
+    )
+}
+
+/// A `test_table` whose `test_str` repeats four country names.
+pub fn create_countries_db(name: &str, nrows: usize) -> TempPath {
+    let countries = ["United States of America", "Germany", "France", "Japan"];
+    let rows: Vec<String> = (0..nrows)
+        .map(|i| format!("({}, '{}')", i, countries[i % countries.len()]))
+        .collect();
+    create_sqlite_db(name, &rows.join(", "))
+}
+
+pub fn sqlite_conn(path: &Path) -> SourceConn {
+    SourceConn::try_from(format!("sqlite://{}", path.display()).as_str()).unwrap()
+}
+
+/// A pool over a two-row `test_table`, which keeps the database for as long as it lives.
+pub fn sqlite_pool(name: &str, config: &PoolConfig) -> (PoolVariant, TempPath) {
+    let db = create_sqlite_db(name, "(1, 'a'), (2, 'b')");
+    let pool = PoolVariant::from_source_conn(&sqlite_conn(&db), config)
+        .unwrap()
+        .unwrap();
+    (pool, db)
+}
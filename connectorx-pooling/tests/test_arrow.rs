@@ -187,6 +187,43 @@ fn test_arrow_large() {
     );
 }
 
+#[test]
+fn test_arrow_preserve_order() {
+    let schema = [DummyTypeSystem::I64(false)];
+    let nrows = [7, 2, 5];
+    let queries: Vec<CXQuery> = nrows
+        .iter()
+        .map(|v| CXQuery::naked(format!("{},1", v)))
+        .collect();
+    let mut destination = ArrowDestination::new_with_batch_size(3);
+    destination.set_preserve_order(true);
+
+    let dispatcher = Dispatcher::<_, _, DummyArrowTransport>::new(
+        DummySource::new(&["a"], &schema),
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.run().expect("run dispatcher");
+
+    let records: Vec<RecordBatch> = destination.arrow().unwrap();
+    let rsizes: Vec<usize> = records.iter().map(|r| r.num_rows()).collect();
+    assert_eq!(vec![3, 3, 1, 2, 3, 2], rsizes);
+    let values: Vec<i64> = records
+        .iter()
+        .flat_map(|r| {
+            r.column(0)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .values()
+                .to_vec()
+        })
+        .collect();
+    let expected: Vec<i64> = nrows.iter().flat_map(|&n| 0..n as i64).collect();
+    assert_eq!(expected, values);
+}
+
 #[test]
 fn test_postgres_arrow() {
     let _ = env_logger::builder().is_test(true).try_init();
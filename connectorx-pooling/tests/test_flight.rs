@@ -0,0 +1,216 @@
+#![cfg(feature = "flight")]
+
+use arrow::record_batch::RecordBatch;
+use arrow_flight::{
+    decode::FlightRecordBatchStream, error::FlightError, flight_service_server::FlightService,
+    flight_service_server::FlightServiceServer, Action, ActionType, Criteria, Empty, FlightClient,
+    FlightData, FlightDescriptor, FlightInfo, HandshakeRequest, HandshakeResponse, PollInfo,
+    PutResult, SchemaResult, Ticket,
+};
+use connectorx::{
+    destinations::arrow::ArrowDestination, flight::get_arrow_to_flight, get_arrow::get_arrow,
+    sql::CXQuery,
+};
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use std::sync::{Arc, Mutex};
+use tempfile::TempPath;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{transport::Server, Request, Response, Status, Streaming};
+
+mod common;
+
+type Uploads = Arc<Mutex<Vec<(FlightDescriptor, Vec<RecordBatch>)>>>;
+
+/// A Flight server that only accepts `DoPut`, recording every upload it receives.
+#[derive(Clone, Default)]
+struct CollectingFlightService {
+    uploads: Uploads,
+}
+
+#[tonic::async_trait]
+impl FlightService for CollectingFlightService {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("handshake"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("list_flights"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Err(Status::unimplemented("get_flight_info"))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("poll_flight_info"))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        Err(Status::unimplemented("get_schema"))
+    }
+
+    async fn do_get(
+        &self,
+        _request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        Err(Status::unimplemented("do_get"))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let messages: Vec<FlightData> = request.into_inner().try_collect().await?;
+        let descriptor = messages
+            .first()
+            .and_then(|m| m.flight_descriptor.clone())
+            .unwrap_or_default();
+        let batches = FlightRecordBatchStream::new_from_flight_data(futures::stream::iter(
+            messages.into_iter().map(Ok::<_, FlightError>),
+        ))
+        .try_collect()
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?;
+        self.uploads.lock().unwrap().push((descriptor, batches));
+        let ack = PutResult::default();
+        Ok(Response::new(futures::stream::iter([Ok(ack)]).boxed()))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("do_action"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Err(Status::unimplemented("list_actions"))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("do_exchange"))
+    }
+}
+
+/// Starts the collecting server on a free local port and returns a client connected to it.
+async fn start_flight_server(service: CollectingFlightService) -> FlightClient {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(FlightServiceServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let channel = tonic::transport::Channel::from_shared(format!("http://{}", addr))
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+    FlightClient::new(channel)
+}
+
+fn create_sqlite_db(name: &str, nrows: usize) -> TempPath {
+    common::sqlite_db(
+        name,
+        &format!(
+            "CREATE TABLE test_table(test_int INTEGER NOT NULL, test_str TEXT);
+             WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < {})
+             INSERT INTO test_table SELECT n, CASE WHEN n % 3 = 0 THEN NULL ELSE 'row' || n END FROM seq;",
+            nrows
+        ),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sqlite_to_flight() {
+    // More rows than fit in one record batch, so the upload spans several messages.
+    let db = create_sqlite_db("rows", 150_000);
+    let source_conn = common::sqlite_conn(&db);
+    let queries = [CXQuery::naked("SELECT * FROM test_table ORDER BY test_int")];
+
+    let service = CollectingFlightService::default();
+    let uploads = service.uploads.clone();
+    let mut client = start_flight_server(service).await;
+    let descriptor = FlightDescriptor::new_path(vec!["test_table".to_string()]);
+
+    let nrows = get_arrow_to_flight(
+        &source_conn,
+        &queries,
+        &mut client,
+        descriptor.clone(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(150_000, nrows);
+
+    let expected = tokio::task::spawn_blocking(move || {
+        let destination: ArrowDestination =
+            get_arrow(&source_conn, None, &queries, None, None, None).unwrap();
+        destination.arrow().unwrap()
+    })
+    .await
+    .unwrap();
+
+    let uploads = uploads.lock().unwrap();
+    assert_eq!(1, uploads.len());
+    let (received_descriptor, received) = &uploads[0];
+    assert_eq!(&descriptor, received_descriptor);
+    assert!(received.len() > 1);
+    let received = arrow::compute::concat_batches(&received[0].schema(), received).unwrap();
+    let expected = arrow::compute::concat_batches(&expected[0].schema(), &expected).unwrap();
+    assert_eq!(expected, received);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sqlite_to_flight_empty() {
+    let db = create_sqlite_db("empty", 3);
+    let source_conn = common::sqlite_conn(&db);
+    let queries = [CXQuery::naked(
+        "SELECT * FROM test_table WHERE test_int > 100",
+    )];
+
+    let service = CollectingFlightService::default();
+    let uploads = service.uploads.clone();
+    let mut client = start_flight_server(service).await;
+    let descriptor = FlightDescriptor::new_path(vec!["empty".to_string()]);
+
+    let nrows = get_arrow_to_flight(&source_conn, &queries, &mut client, descriptor, None)
+        .await
+        .unwrap();
+    assert_eq!(0, nrows);
+
+    let uploads = uploads.lock().unwrap();
+    assert_eq!(1, uploads.len());
+    assert!(uploads[0].1.is_empty());
+}
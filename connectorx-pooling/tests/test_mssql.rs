@@ -178,3 +178,53 @@ pub fn verify_arrow_results(result: Vec<RecordBatch>) {
         }
     }
 }
+
+#[test]
+#[ignore]
+fn test_mssql_multi_result() {
+    use std::convert::TryFrom;
+
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("MSSQL_URL").unwrap();
+    let rt = Arc::new(Runtime::new().unwrap());
+    let source = MsSQLSource::new(rt, &dburl, 1).unwrap();
+    let created = source
+        .fetch_result_sets(
+            "CREATE OR ALTER PROCEDURE test_multi_result AS \
+             BEGIN SELECT 1 AS a; SELECT 'x' AS b, 2 AS c UNION ALL SELECT 'y', 3 END",
+        )
+        .unwrap();
+    assert!(created.is_empty());
+
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let destinations = get_arrow_multi_result(&source_conn, "EXEC test_multi_result").unwrap();
+    assert_eq!(destinations.len(), 2);
+    let mut destinations = destinations.into_iter();
+
+    let first = destinations.next().unwrap();
+    assert_eq!(first.names(), ["a"]);
+    let rb = first.arrow().unwrap().pop().unwrap();
+    assert!(rb
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap()
+        .eq(&Int64Array::from(vec![1])));
+
+    let second = destinations.next().unwrap();
+    assert_eq!(second.names(), ["b", "c"]);
+    let rb = second.arrow().unwrap().pop().unwrap();
+    assert!(rb
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap()
+        .eq(&StringArray::from(vec!["x", "y"])));
+    assert!(rb
+        .column(1)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap()
+        .eq(&Int64Array::from(vec![2, 3])));
+}
@@ -1,14 +1,16 @@
 use arrow::{
-    array::{Float64Array, Int64Array, StringArray},
+    array::{Float64Array, Int64Array, StringArray, UInt64Array},
     record_batch::RecordBatch,
 };
 use connectorx::{
     destinations::arrow::ArrowDestination,
+    pool::{PoolConfig, PoolVariant},
     prelude::*,
     sources::mysql::{BinaryProtocol, MySQLSource, TextProtocol},
     sql::CXQuery,
     transports::MySQLArrowTransport,
 };
+use std::convert::TryFrom;
 use std::env;
 
 #[test]
@@ -94,16 +96,16 @@ fn test_mysql_pre_execution_queries() {
     assert!(result[0]
         .column(0)
         .as_any()
-        .downcast_ref::<Float64Array>()
+        .downcast_ref::<UInt64Array>()
         .unwrap()
-        .eq(&Float64Array::from(vec![2151.0])));
+        .eq(&UInt64Array::from(vec![2151])));
 
     assert!(result[0]
         .column(1)
         .as_any()
-        .downcast_ref::<Float64Array>()
+        .downcast_ref::<UInt64Array>()
         .unwrap()
-        .eq(&Float64Array::from(vec![2252.0])));
+        .eq(&UInt64Array::from(vec![2252])));
 }
 
 #[test]
@@ -151,14 +153,14 @@ fn test_mysql_partitioned_pre_execution_queries() {
         let setting = record_batch
             .column(1)
             .as_any()
-            .downcast_ref::<Float64Array>()
+            .downcast_ref::<UInt64Array>()
             .unwrap()
             .value(0);
         result_map.insert(name, setting);
     }
 
-    assert_eq!(result_map.get("max_execution_time"), Some(&2151.0));
-    assert_eq!(result_map.get("wait_timeout"), Some(&2252.0));
+    assert_eq!(result_map.get("max_execution_time"), Some(&2151));
+    assert_eq!(result_map.get("wait_timeout"), Some(&2252));
 }
 
 pub fn verify_arrow_results(result: Vec<RecordBatch>) {
@@ -225,3 +227,403 @@ pub fn verify_arrow_results(result: Vec<RecordBatch>) {
         }
     }
 }
+
+#[test]
+fn test_mysql_server_version() {
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let version = pool.server_version().unwrap();
+    assert!(
+        version.starts_with(|c: char| c.is_ascii_digit()),
+        "{}",
+        version
+    );
+    assert_eq!(version, pool.server_version().unwrap());
+}
+
+#[test]
+fn test_mysql_pool_read_only() {
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let config = PoolConfig {
+        read_only: true,
+        ..PoolConfig::default()
+    };
+    let pool = PoolVariant::from_source_conn(&source_conn, &config)
+        .unwrap()
+        .unwrap();
+    pool.execute_unchecked("SELECT 1").unwrap();
+    let err = pool
+        .execute_unchecked("DELETE FROM test_table WHERE 1 = 0")
+        .unwrap_err();
+    assert!(err.to_string().contains("READ ONLY"), "{}", err);
+}
+
+#[test]
+fn test_mysql_list_tables() {
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let schemas = pool.list_schemas().unwrap();
+    assert!(!schemas.contains(&"mysql".to_string()), "{:?}", schemas);
+    let schema = source_conn.conn.path().trim_start_matches('/').to_string();
+    assert!(schemas.contains(&schema), "{:?}", schemas);
+
+    let tables = pool.list_tables(Some(&schema)).unwrap();
+    assert!(
+        tables.contains(&format!("{}.test_table", schema)),
+        "{:?}",
+        tables
+    );
+    assert!(pool.list_tables(None).unwrap().len() >= tables.len());
+}
+
+#[test]
+fn test_mysql_binary_payloads() {
+    use arrow::array::{Array, LargeBinaryArray};
+
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_binary_payloads")
+        .unwrap();
+    pool.execute_unchecked(
+        "CREATE TABLE test_binary_payloads(id INT NOT NULL, payload BLOB, raw VARBINARY(16))",
+    )
+    .unwrap();
+    pool.execute_unchecked(
+        "INSERT INTO test_binary_payloads VALUES
+             (1, x'00ff10', x'80'), (2, x'', x''), (3, NULL, NULL)",
+    )
+    .unwrap();
+
+    let expected: [Vec<Option<&[u8]>>; 2] = [
+        vec![Some(&[0x00, 0xff, 0x10]), Some(&[]), None],
+        vec![Some(&[0x80]), Some(&[]), None],
+    ];
+    for protocol in ["binary", "text"] {
+        let source_conn = parse_source(&dburl, Some(protocol)).unwrap();
+        let rbs = get_arrow(
+            &source_conn,
+            None,
+            &[CXQuery::naked(
+                "SELECT payload, raw FROM test_binary_payloads ORDER BY id",
+            )],
+            None,
+            Some(&pool),
+            None,
+        )
+        .unwrap()
+        .arrow()
+        .unwrap();
+        for (col, expected) in expected.iter().enumerate() {
+            let array = rbs[0]
+                .column(col)
+                .as_any()
+                .downcast_ref::<LargeBinaryArray>()
+                .unwrap();
+            let values: Vec<Option<&[u8]>> = (0..array.len())
+                .map(|i| (!array.is_null(i)).then(|| array.value(i)))
+                .collect();
+            assert_eq!(expected, &values, "{} column {}", protocol, col);
+        }
+    }
+}
+
+#[test]
+fn test_mysql_utf8mb4_round_trip() {
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_utf8mb4")
+        .unwrap();
+    pool.execute_unchecked(
+        "CREATE TABLE test_utf8mb4(id INT NOT NULL, txt TEXT) CHARACTER SET utf8mb4",
+    )
+    .unwrap();
+    // inserted through the pool, so the literal travels in the connection charset too
+    pool.execute_unchecked("INSERT INTO test_utf8mb4 VALUES (1, '🦀 crab'), (2, 'naïve 😀')")
+        .unwrap();
+
+    for protocol in ["binary", "text"] {
+        let source_conn = parse_source(&dburl, Some(protocol)).unwrap();
+        let rbs = get_arrow(
+            &source_conn,
+            None,
+            &[CXQuery::naked("SELECT txt FROM test_utf8mb4 ORDER BY id")],
+            None,
+            Some(&pool),
+            None,
+        )
+        .unwrap()
+        .arrow()
+        .unwrap();
+        assert!(
+            rbs[0]
+                .column(0)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .eq(&StringArray::from(vec!["🦀 crab", "naïve 😀"])),
+            "{}",
+            protocol
+        );
+    }
+}
+
+#[test]
+fn test_mysql_column_comments() {
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_column_comments")
+        .unwrap();
+    pool.execute_unchecked(
+        "CREATE TABLE test_column_comments(id INT NOT NULL COMMENT 'primary key', name TEXT)",
+    )
+    .unwrap();
+    let queries = [CXQuery::naked(
+        "SELECT id, name, id + 1 AS next_id FROM test_column_comments",
+    )];
+
+    for protocol in ["binary", "text"] {
+        let source_conn = parse_source(&dburl, Some(protocol)).unwrap();
+        let destination = ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .pool(&pool)
+            .include_comments(true)
+            .run()
+            .unwrap();
+        let comments: Vec<_> = destination
+            .arrow_schema()
+            .fields()
+            .iter()
+            .map(|field| field.metadata().get("comment").cloned())
+            .collect();
+        assert_eq!(
+            vec![Some("primary key".to_string()), None, None],
+            comments,
+            "{}",
+            protocol
+        );
+    }
+}
+
+#[test]
+fn test_mysql_bit_and_set() {
+    use arrow::array::{Array, BooleanArray, LargeBinaryArray, LargeListArray, LargeStringArray};
+    use arrow::datatypes::DataType;
+
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_bit_and_set")
+        .unwrap();
+    pool.execute_unchecked(
+        "CREATE TABLE test_bit_and_set(
+             id INT NOT NULL,
+             flag BIT(1),
+             bits BIT(4),
+             tags SET('a', 'b', 'c')
+         )",
+    )
+    .unwrap();
+    pool.execute_unchecked(
+        "INSERT INTO test_bit_and_set VALUES
+             (1, b'1', b'0101', 'a,c'),
+             (2, b'0', b'0000', ''),
+             (3, NULL, NULL, NULL)",
+    )
+    .unwrap();
+    let queries = [CXQuery::naked(
+        "SELECT flag, bits, tags FROM test_bit_and_set ORDER BY id",
+    )];
+
+    for protocol in ["binary", "text"] {
+        let source_conn = parse_source(&dburl, Some(protocol)).unwrap();
+        let rb = &ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .pool(&pool)
+            .run()
+            .unwrap()
+            .arrow()
+            .unwrap()[0];
+        let types: Vec<DataType> = rb
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+        assert_eq!(
+            vec![
+                DataType::Boolean,
+                DataType::LargeBinary,
+                DataType::LargeUtf8
+            ],
+            types,
+            "{}",
+            protocol
+        );
+
+        let flag = rb
+            .column(0)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        let flag: Vec<_> = (0..flag.len())
+            .map(|i| (!flag.is_null(i)).then(|| flag.value(i)))
+            .collect();
+        assert_eq!(vec![Some(true), Some(false), None], flag, "{}", protocol);
+
+        let bits = rb
+            .column(1)
+            .as_any()
+            .downcast_ref::<LargeBinaryArray>()
+            .unwrap();
+        assert_eq!(&[0x05], bits.value(0), "{}", protocol);
+        assert!(bits.is_null(2), "{}", protocol);
+
+        let tags = rb
+            .column(2)
+            .as_any()
+            .downcast_ref::<LargeStringArray>()
+            .unwrap();
+        let tags: Vec<_> = (0..tags.len())
+            .map(|i| (!tags.is_null(i)).then(|| tags.value(i)))
+            .collect();
+        assert_eq!(vec![Some("a,c"), Some(""), None], tags, "{}", protocol);
+    }
+
+    // with set_as_list the members come as a list
+    let sep = if dburl.contains('?') { '&' } else { '?' };
+    let source_conn =
+        SourceConn::try_from(format!("{}{}set_as_list=true", dburl, sep).as_str()).unwrap();
+    let rb = &ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .run()
+        .unwrap()
+        .arrow()
+        .unwrap()[0];
+    let tags = rb
+        .column(2)
+        .as_any()
+        .downcast_ref::<LargeListArray>()
+        .unwrap();
+    let tags: Vec<Option<Vec<String>>> = (0..tags.len())
+        .map(|i| {
+            (!tags.is_null(i)).then(|| {
+                let members = tags.value(i);
+                let members = members.as_any().downcast_ref::<StringArray>().unwrap();
+                (0..members.len())
+                    .map(|j| members.value(j).to_string())
+                    .collect()
+            })
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            Some(vec!["a".to_string(), "c".to_string()]),
+            Some(vec![]),
+            None
+        ],
+        tags
+    );
+}
+
+#[test]
+fn test_mysql_unsigned() {
+    use arrow::array::{Array, UInt16Array, UInt32Array, UInt8Array};
+    use arrow::datatypes::DataType;
+
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    pool.execute_unchecked("DROP TABLE IF EXISTS test_unsigned")
+        .unwrap();
+    pool.execute_unchecked(
+        "CREATE TABLE test_unsigned(
+             id INT NOT NULL,
+             tiny TINYINT UNSIGNED,
+             short SMALLINT UNSIGNED,
+             medium MEDIUMINT UNSIGNED,
+             long_ INT UNSIGNED,
+             longlong BIGINT UNSIGNED
+         )",
+    )
+    .unwrap();
+    pool.execute_unchecked(
+        "INSERT INTO test_unsigned VALUES
+             (1, 255, 65535, 16777215, 4294967295, 18446744073709551615),
+             (2, 0, 0, 0, 0, 18446744073709551614),
+             (3, NULL, NULL, NULL, NULL, NULL)",
+    )
+    .unwrap();
+    let queries = [CXQuery::naked(
+        "SELECT tiny, short, medium, long_, longlong FROM test_unsigned ORDER BY id",
+    )];
+
+    for protocol in ["binary", "text"] {
+        let source_conn = parse_source(&dburl, Some(protocol)).unwrap();
+        let rb = &ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .pool(&pool)
+            .run()
+            .unwrap()
+            .arrow()
+            .unwrap()[0];
+        let types: Vec<DataType> = rb
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+        assert_eq!(
+            vec![
+                DataType::UInt8,
+                DataType::UInt16,
+                DataType::UInt32,
+                DataType::UInt32,
+                DataType::UInt64
+            ],
+            types,
+            "{}",
+            protocol
+        );
+
+        let tiny = rb.column(0).as_any().downcast_ref::<UInt8Array>().unwrap();
+        assert_eq!(&[255, 0], &tiny.values()[..2], "{}", protocol);
+        let short = rb.column(1).as_any().downcast_ref::<UInt16Array>().unwrap();
+        assert_eq!(&[65535, 0], &short.values()[..2], "{}", protocol);
+        let long = rb.column(3).as_any().downcast_ref::<UInt32Array>().unwrap();
+        assert_eq!(&[u32::MAX, 0], &long.values()[..2], "{}", protocol);
+
+        let longlong = rb.column(4).as_any().downcast_ref::<UInt64Array>().unwrap();
+        let longlong: Vec<_> = (0..longlong.len())
+            .map(|i| (!longlong.is_null(i)).then(|| longlong.value(i)))
+            .collect();
+        assert_eq!(
+            vec![Some(u64::MAX), Some(u64::MAX - 1), None],
+            longlong,
+            "{}",
+            protocol
+        );
+        for column in rb.columns() {
+            assert!(column.is_null(2), "{}", protocol);
+        }
+    }
+}
@@ -1,14 +1,19 @@
+use connectorx::pool::{PoolConfig, PoolVariant};
 use connectorx::prelude::*;
-use connectorx::sources::oracle::OracleSource;
+use connectorx::sources::oracle::{
+    connect_oracle, oracle_array_size, oracle_max_lob_size, OracleSource,
+};
 use connectorx::sql::CXQuery;
+use std::convert::TryFrom;
 use std::env;
+use url::Url;
 
 #[test]
 #[ignore]
 fn test_types() {
     let _ = env_logger::builder().is_test(true).try_init();
     let dburl = env::var("ORACLE_URL").unwrap();
-    let mut source = OracleSource::new(&dburl, 1).unwrap();
+    let mut source = OracleSource::new(&dburl, 1, None).unwrap();
     #[derive(Debug, PartialEq)]
     struct Row(i64, i64, f64, f64, String, String, String, String);
 
@@ -79,3 +84,319 @@ fn test_types() {
         rows
     );
 }
+
+#[test]
+fn test_arraysize_param() {
+    let parse = |conn: &str| oracle_array_size(&Url::parse(conn).unwrap());
+    assert_eq!(1024, parse("oracle://user:pw@localhost:1521/db").unwrap());
+    assert_eq!(
+        500,
+        parse("oracle://user:pw@localhost:1521/db?arraysize=500").unwrap()
+    );
+    assert_eq!(
+        100 * 1024,
+        parse("oracle://user:pw@localhost:1521/db?arraysize=99999999").unwrap()
+    );
+    assert!(parse("oracle://user:pw@localhost:1521/db?arraysize=0").is_err());
+    assert!(parse("oracle://user:pw@localhost:1521/db?arraysize=-5").is_err());
+    assert!(parse("oracle://user:pw@localhost:1521/db?arraysize=abc").is_err());
+}
+
+#[test]
+fn test_partitioned_reuse_prepared_unsupported() {
+    let source_conn = SourceConn::try_from("oracle://user:pw@localhost:1521/db").unwrap();
+    let strategy = PartitionStrategy::NumericRange {
+        col: "test_int".into(),
+        n: 2,
+    };
+    // rejected up front rather than read as separate queries
+    let res = get_arrow_partitioned(
+        &source_conn,
+        "SELECT * FROM test_table",
+        &strategy,
+        None,
+        true,
+    );
+    assert!(matches!(res, Err(ConnectorXOutError::SourceNotSupport(_))));
+}
+
+#[test]
+fn test_max_lob_size_param() {
+    let parse = |conn: &str| oracle_max_lob_size(&Url::parse(conn).unwrap());
+    assert_eq!(None, parse("oracle://user:pw@localhost:1521/db").unwrap());
+    assert_eq!(
+        Some(1 << 20),
+        parse("oracle://user:pw@localhost:1521/db?max_lob_size=1048576").unwrap()
+    );
+    assert!(parse("oracle://user:pw@localhost:1521/db?max_lob_size=0").is_err());
+    assert!(parse("oracle://user:pw@localhost:1521/db?max_lob_size=1mb").is_err());
+}
+
+#[test]
+#[ignore]
+fn test_arraysize_applied() {
+    let dburl = env::var("ORACLE_URL").unwrap();
+    let sep = if dburl.contains('?') { '&' } else { '?' };
+    let source = OracleSource::new(&format!("{}{}arraysize=250", dburl, sep), 1, None).unwrap();
+    assert_eq!(250, source.array_size());
+}
+
+#[test]
+#[ignore]
+fn test_oracle_server_version() {
+    let dburl = env::var("ORACLE_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let version = pool.server_version().unwrap();
+    assert!(version.contains("Oracle"), "{}", version);
+    assert_eq!(version, pool.server_version().unwrap());
+}
+
+#[test]
+#[ignore]
+fn test_oracle_pool_read_only() {
+    let dburl = env::var("ORACLE_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let config = PoolConfig {
+        read_only: true,
+        ..PoolConfig::default()
+    };
+    let (version, _) = connect_oracle(&source_conn.conn)
+        .unwrap()
+        .connect()
+        .unwrap()
+        .server_version()
+        .unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &config);
+    if version.major() < 23 {
+        // no read-only sessions before 23ai
+        let err = pool.err().unwrap().to_string();
+        assert!(err.contains("Oracle 23ai or later"), "{}", err);
+        return;
+    }
+    let pool = pool.unwrap().unwrap();
+    pool.execute_unchecked("SELECT 1 FROM dual").unwrap();
+    assert!(pool
+        .execute_unchecked("DELETE FROM test_table WHERE 1 = 0")
+        .is_err());
+}
+
+#[test]
+#[ignore]
+fn test_oracle_list_tables() {
+    let dburl = env::var("ORACLE_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let schemas = pool.list_schemas().unwrap();
+    assert!(!schemas.contains(&"SYS".to_string()), "{:?}", schemas);
+    assert!(schemas.contains(&"ADMIN".to_string()), "{:?}", schemas);
+
+    let tables = pool.list_tables(Some("ADMIN")).unwrap();
+    assert!(
+        tables.contains(&"ADMIN.TEST_TABLE".to_string()),
+        "{:?}",
+        tables
+    );
+    assert!(
+        tables.iter().all(|t| t.starts_with("ADMIN.")),
+        "{:?}",
+        tables
+    );
+}
+
+#[test]
+#[ignore]
+fn test_oracle_binary_payloads() {
+    use arrow::array::{Array, LargeBinaryArray};
+
+    let dburl = env::var("ORACLE_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let _ = pool.execute_unchecked("DROP TABLE test_binary_payloads");
+    pool.execute_unchecked(
+        "CREATE TABLE test_binary_payloads(id NUMBER(9) NOT NULL, payload BLOB, raw RAW(16))",
+    )
+    .unwrap();
+    // Oracle stores an empty RAW or BLOB as NULL
+    pool.execute_unchecked(
+        "INSERT ALL
+             INTO test_binary_payloads VALUES (1, HEXTORAW('00FF10'), HEXTORAW('80'))
+             INTO test_binary_payloads VALUES (2, NULL, NULL)
+         SELECT 1 FROM dual",
+    )
+    .unwrap();
+
+    let rbs = get_arrow(
+        &source_conn,
+        None,
+        &[CXQuery::naked(
+            "SELECT payload, raw FROM test_binary_payloads ORDER BY id",
+        )],
+        None,
+        Some(&pool),
+        None,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
+    let expected: [Vec<Option<&[u8]>>; 2] = [
+        vec![Some(&[0x00, 0xff, 0x10]), None],
+        vec![Some(&[0x80]), None],
+    ];
+    for (col, expected) in expected.iter().enumerate() {
+        let array = rbs[0]
+            .column(col)
+            .as_any()
+            .downcast_ref::<LargeBinaryArray>()
+            .unwrap();
+        let values: Vec<Option<&[u8]>> = (0..array.len())
+            .map(|i| (!array.is_null(i)).then(|| array.value(i)))
+            .collect();
+        assert_eq!(expected, &values, "column {}", col);
+    }
+}
+
+#[test]
+#[ignore]
+fn test_oracle_number_types() {
+    use arrow::array::{Array, Decimal128Array, Int64Array};
+    use arrow::datatypes::DataType;
+
+    let dburl = env::var("ORACLE_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let _ = pool.execute_unchecked("DROP TABLE test_number_types");
+    pool.execute_unchecked(
+        "CREATE TABLE test_number_types(
+             id NUMBER(10) PRIMARY KEY,
+             big NUMBER(30),
+             amount NUMBER(10,2),
+             total INTEGER,
+             plain NUMBER
+         )",
+    )
+    .unwrap();
+    pool.execute_unchecked(
+        "INSERT ALL
+             INTO test_number_types VALUES (1, 123456789012345678901234, 12.34, 7, 1.5)
+             INTO test_number_types VALUES (2, NULL, NULL, NULL, NULL)
+         SELECT 1 FROM dual",
+    )
+    .unwrap();
+
+    let rbs = get_arrow(
+        &source_conn,
+        None,
+        &[CXQuery::naked(
+            "SELECT id, big, amount, total, plain FROM test_number_types ORDER BY id",
+        )],
+        None,
+        Some(&pool),
+        None,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
+    let types: Vec<DataType> = rbs[0]
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.data_type().clone())
+        .collect();
+    assert_eq!(
+        vec![
+            DataType::Int64,
+            DataType::Decimal128(30, 0),
+            DataType::Decimal128(10, 2),
+            DataType::Decimal128(38, 0),
+            // unconstrained NUMBER falls back to the destination's default
+            DataType::Decimal128(38, 10),
+        ],
+        types
+    );
+
+    let id = rbs[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(vec![1, 2], id.values().to_vec());
+    let amount = rbs[0]
+        .column(2)
+        .as_any()
+        .downcast_ref::<Decimal128Array>()
+        .unwrap();
+    assert_eq!("12.34", amount.value_as_string(0));
+    assert!(amount.is_null(1));
+}
+
+#[test]
+#[ignore]
+fn test_oracle_clob_columns() {
+    use arrow::array::{Array, LargeStringArray};
+    use arrow::datatypes::DataType;
+
+    let dburl = env::var("ORACLE_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let _ = pool.execute_unchecked("DROP TABLE test_clob_columns");
+    pool.execute_unchecked(
+        "CREATE TABLE test_clob_columns(id NUMBER(9) NOT NULL, body CLOB, nbody NCLOB)",
+    )
+    .unwrap();
+    // 12000 characters, more than a VARCHAR2 literal can hold
+    pool.execute_unchecked(
+        "INSERT ALL
+             INTO test_clob_columns VALUES (
+                 1,
+                 TO_CLOB(RPAD('a', 4000, 'a')) || RPAD('b', 4000, 'b') || RPAD('c', 4000, 'c'),
+                 TO_NCLOB(RPAD(UNISTR('\00e9'), 3000, UNISTR('\00e9'))))
+             INTO test_clob_columns VALUES (2, NULL, NULL)
+         SELECT 1 FROM dual",
+    )
+    .unwrap();
+    let query = [CXQuery::naked(
+        "SELECT body, nbody FROM test_clob_columns ORDER BY id",
+    )];
+
+    let rbs = get_arrow(&source_conn, None, &query, None, Some(&pool), None)
+        .unwrap()
+        .arrow()
+        .unwrap();
+    let expected = [
+        format!(
+            "{}{}{}",
+            "a".repeat(4000),
+            "b".repeat(4000),
+            "c".repeat(4000)
+        ),
+        "\u{e9}".repeat(3000),
+    ];
+    for (col, expected) in expected.iter().enumerate() {
+        assert_eq!(&DataType::LargeUtf8, rbs[0].schema().field(col).data_type());
+        let array = rbs[0]
+            .column(col)
+            .as_any()
+            .downcast_ref::<LargeStringArray>()
+            .unwrap();
+        assert_eq!(2, array.len());
+        assert_eq!(expected, array.value(0), "column {}", col);
+        assert!(array.is_null(1), "column {}", col);
+    }
+
+    // a value over max_lob_size fails the read instead of being loaded
+    let sep = if dburl.contains('?') { '&' } else { '?' };
+    let capped =
+        SourceConn::try_from(format!("{}{}max_lob_size=4096", dburl, sep).as_str()).unwrap();
+    assert!(get_arrow(&capped, None, &query, None, None, None).is_err());
+}
@@ -0,0 +1,76 @@
+use connectorx::{get_arrow::get_arrow_to_parquet, sql::CXQuery};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use std::fs::File;
+use tempfile::TempPath;
+
+mod common;
+
+fn create_sqlite_db(name: &str) -> TempPath {
+    common::sqlite_db(
+        name,
+        "CREATE TABLE test_table(test_int INTEGER NOT NULL, test_str TEXT, test_float REAL);
+         INSERT INTO test_table VALUES (1, 'a', 0.5), (2, 'b', NULL), (3, NULL, 1.5), (4, 'd', 2.5);",
+    )
+}
+
+#[test]
+fn test_sqlite_to_parquet() {
+    let db = create_sqlite_db("rows");
+    let out = common::temp_path("parquet", ".parquet");
+    let source_conn = common::sqlite_conn(&db);
+    let queries = [CXQuery::naked("SELECT * FROM test_table")];
+
+    let nrows = get_arrow_to_parquet(&source_conn, &queries, &out, 2, None).unwrap();
+    assert_eq!(4, nrows);
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&out).unwrap()).unwrap();
+    let names: Vec<_> = reader
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+    assert_eq!(vec!["test_int", "test_str", "test_float"], names);
+    let total: usize = reader
+        .build()
+        .unwrap()
+        .map(|rb| rb.unwrap().num_rows())
+        .sum();
+    assert_eq!(4, total);
+}
+
+#[test]
+fn test_sqlite_to_parquet_empty() {
+    let db = create_sqlite_db("empty");
+    let out = common::temp_path("parquet", ".parquet");
+    let source_conn = common::sqlite_conn(&db);
+    let queries = [CXQuery::naked(
+        "SELECT * FROM test_table WHERE test_int > 100",
+    )];
+
+    let nrows = get_arrow_to_parquet(&source_conn, &queries, &out, 2, None).unwrap();
+    assert_eq!(0, nrows);
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&out).unwrap()).unwrap();
+    assert_eq!(3, reader.schema().fields().len());
+    let total: usize = reader
+        .build()
+        .unwrap()
+        .map(|rb| rb.unwrap().num_rows())
+        .sum();
+    assert_eq!(0, total);
+}
+
+#[test]
+fn test_sqlite_to_parquet_failed_read() {
+    let db = create_sqlite_db("failed");
+    let out = common::temp_path("parquet", ".parquet");
+    let source_conn = common::sqlite_conn(&db);
+    // typed as an integer by the first row, the text of the last row fails the read
+    let queries = [CXQuery::naked(
+        "SELECT CASE WHEN test_int < 4 THEN test_int ELSE 'x' END AS v FROM test_table",
+    )];
+
+    assert!(get_arrow_to_parquet(&source_conn, &queries, &out, 2, None).is_err());
+    assert!(!out.exists());
+}
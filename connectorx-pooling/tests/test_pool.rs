@@ -0,0 +1,909 @@
+use connectorx::{
+    pool::{
+        build_pool, checkout, checkout_unchecked, mysql_connect_attrs, CircuitBreakerConfig,
+        CircuitState, ConnectionExpiry, PoolConfig, PoolError, PoolGuard, PoolVariant, ReusePolicy,
+        SessionInit,
+    },
+    prelude::*,
+    transaction::Transaction,
+};
+use r2d2_sqlite::SqliteConnectionManager;
+use std::convert::TryFrom;
+use std::env;
+use std::path::PathBuf;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::time::{Duration, Instant};
+
+mod common;
+
+use common::{create_sqlite_db, sqlite_pool};
+
+#[test]
+fn test_sqlite_pool_metrics_text() {
+    let config = PoolConfig {
+        max_size: 3,
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("metrics", &config);
+    let conn = pool.sqlite_pool().get().unwrap();
+
+    let text = pool.metrics_text("connectorx_pool");
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(21, lines.len());
+    for chunk in lines.chunks(3) {
+        let name = chunk[0]
+            .strip_prefix("# HELP ")
+            .and_then(|l| l.split(' ').next())
+            .unwrap();
+        assert!(name.starts_with("connectorx_pool_"));
+        let kind = match name.ends_with("_total") {
+            true => "counter",
+            false => "gauge",
+        };
+        assert_eq!(format!("# TYPE {} {}", name, kind), chunk[1]);
+        let (series, value) = chunk[2].split_once(' ').unwrap();
+        assert_eq!(format!("{}{{backend=\"sqlite\"}}", name), series);
+        value.parse::<f64>().unwrap();
+    }
+    assert!(lines.contains(&"connectorx_pool_max_size{backend=\"sqlite\"} 3"));
+    assert!(lines.contains(&"connectorx_pool_active_connections{backend=\"sqlite\"} 1"));
+    assert!(lines.contains(&"connectorx_pool_checkouts_total{backend=\"sqlite\"} 1"));
+    assert!(lines.contains(&"connectorx_pool_validation_failures_total{backend=\"sqlite\"} 0"));
+
+    // the counters keep running totals over checkouts
+    drop(conn);
+    for _ in 0..3 {
+        checkout(&pool.sqlite_pool()).unwrap();
+    }
+    let text = pool.metrics_text("connectorx_pool");
+    assert!(text.contains("connectorx_pool_checkouts_total{backend=\"sqlite\"} 4\n"));
+    assert!(text.contains("connectorx_pool_active_connections{backend=\"sqlite\"} 0\n"));
+}
+
+#[test]
+fn test_sqlite_pool_metrics_text_label() {
+    let config = PoolConfig {
+        max_size: 3,
+        label: Some("tenant-a".to_string()),
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("metrics_label", &config);
+    assert_eq!(Some("tenant-a".to_string()), pool.label());
+
+    let text = pool.metrics_text("connectorx_pool");
+    let series: Vec<&str> = text.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(7, series.len());
+    for line in series {
+        assert!(line.contains("{backend=\"sqlite\",label=\"tenant-a\"} "));
+    }
+    assert!(text.contains("connectorx_pool_max_size{backend=\"sqlite\",label=\"tenant-a\"} 3"));
+
+    let (unlabeled, _unlabeled_db) = sqlite_pool("metrics_unlabeled", &PoolConfig::default());
+    assert_eq!(None, unlabeled.label());
+    assert!(!unlabeled.metrics_text("connectorx_pool").contains("label="));
+}
+
+#[test]
+#[cfg(any(
+    feature = "src_mssql",
+    feature = "src_bigquery",
+    feature = "src_trino",
+    feature = "fed_exec"
+))]
+fn test_sqlite_pool_creates_no_runtime() {
+    use connectorx::runtime::runtimes_created;
+
+    let before = runtimes_created();
+    let (pool, _db) = sqlite_pool("no_runtime", &PoolConfig::default());
+    let conn = pool.sqlite_pool().get().unwrap();
+    conn.execute_batch("SELECT 1").unwrap();
+    assert!(!pool.is_async());
+    assert_eq!(before, runtimes_created());
+}
+
+/// Runs `n` sequential checkouts, tagging each connection's temp table, then returns how many
+/// times each of the pool's connections was used.
+fn checkout_counts(pool: &PoolVariant, n: usize) -> Vec<u32> {
+    let sqlite_pool = pool.sqlite_pool();
+    for _ in 0..n {
+        let conn = checkout(&sqlite_pool).unwrap();
+        conn.execute_batch(
+            "CREATE TEMP TABLE IF NOT EXISTS uses(n INTEGER); INSERT INTO temp.uses VALUES (1);",
+        )
+        .unwrap();
+    }
+    let conns: Vec<_> = (0..pool.max_size())
+        .map(|_| checkout(&sqlite_pool).unwrap())
+        .collect();
+    conns
+        .iter()
+        .map(|conn| {
+            conn.execute_batch("CREATE TEMP TABLE IF NOT EXISTS uses(n INTEGER);")
+                .unwrap();
+            conn.query_row("SELECT count(*) FROM temp.uses", [], |r| r.get(0))
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_sqlite_pool_fifo_reuse() {
+    let config = PoolConfig {
+        max_size: 4,
+        reuse_policy: ReusePolicy::Fifo,
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("fifo", &config);
+    assert_eq!(
+        ReusePolicy::Fifo,
+        connectorx::pool::reuse_policy(&pool.sqlite_pool())
+    );
+
+    let counts = checkout_counts(&pool, 40);
+    assert_eq!(40, counts.iter().sum::<u32>());
+    assert!(counts.iter().all(|&c| c == 10), "{:?}", counts);
+
+    // returned connections wait in the pool's own queue and still count as idle
+    let state = pool.state();
+    assert_eq!((4, 4), (state.connections, state.idle_connections));
+
+    // a checkout waiting on the full pool is handed the next connection returned
+    let sqlite_pool = pool.sqlite_pool();
+    let held: Vec<_> = (0..4).map(|_| checkout(&sqlite_pool).unwrap()).collect();
+    assert_eq!(0, pool.state().idle_connections);
+    let waiter = std::thread::spawn(move || checkout(&sqlite_pool).map(drop));
+    std::thread::sleep(Duration::from_millis(100));
+    drop(held);
+    waiter.join().unwrap().unwrap();
+    assert_eq!(4, pool.state().idle_connections);
+}
+
+#[test]
+fn test_sqlite_pool_fifo_drop_closes_queued() {
+    let db = create_sqlite_db("fifo_drop", "(1, 'a'), (2, 'b')");
+    // SQLite removes the write-ahead log once the last connection to the database closes
+    rusqlite::Connection::open(&db)
+        .unwrap()
+        .pragma_update(None, "journal_mode", "WAL")
+        .unwrap();
+    let wal = PathBuf::from(format!("{}-wal", db.display()));
+    let source_conn = SourceConn::try_from(format!("sqlite://{}", db.display()).as_str()).unwrap();
+    let config = PoolConfig {
+        max_size: 2,
+        reuse_policy: ReusePolicy::Fifo,
+        ..PoolConfig::default()
+    };
+    let pool = PoolVariant::from_source_conn(&source_conn, &config)
+        .unwrap()
+        .unwrap();
+    let sqlite_pool = pool.sqlite_pool();
+    for _ in 0..4 {
+        let conn = checkout(&sqlite_pool).unwrap();
+        conn.query_row("SELECT count(*) FROM test_table", [], |r| {
+            r.get::<_, i64>(0)
+        })
+        .unwrap();
+    }
+    assert!(wal.exists());
+
+    // the connections queued by the pool close along with it
+    drop((sqlite_pool, pool));
+    let start = Instant::now();
+    while wal.exists() && start.elapsed() < Duration::from_secs(5) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(!wal.exists());
+}
+
+#[test]
+fn test_sqlite_pool_lifo_reuse() {
+    let config = PoolConfig {
+        max_size: 4,
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("lifo", &config);
+    assert_eq!(
+        ReusePolicy::Lifo,
+        connectorx::pool::reuse_policy(&pool.sqlite_pool())
+    );
+
+    let mut counts = checkout_counts(&pool, 40);
+    counts.sort_unstable();
+    assert_eq!(vec![0, 0, 0, 40], counts);
+}
+
+/// Sqlite manager counting the validation queries run on checkout, which fail for a
+/// connection with a `poison` temp table.
+#[derive(Debug)]
+struct SpyManager {
+    inner: SqliteConnectionManager,
+    validations: Arc<AtomicUsize>,
+}
+
+fn is_poisoned(conn: &rusqlite::Connection) -> bool {
+    conn.query_row(
+        "SELECT count(*) FROM temp.sqlite_master WHERE name = 'poison'",
+        [],
+        |r| r.get::<_, i64>(0),
+    )
+    .unwrap()
+        > 0
+}
+
+impl r2d2::ManageConnection for SpyManager {
+    type Connection = rusqlite::Connection;
+    type Error = rusqlite::Error;
+
+    fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        self.inner.connect()
+    }
+
+    fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        self.validations.fetch_add(1, Ordering::SeqCst);
+        self.inner.is_valid(conn)?;
+        match is_poisoned(conn) {
+            true => Err(rusqlite::Error::InvalidQuery),
+            false => Ok(()),
+        }
+    }
+
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        self.inner.has_broken(conn)
+    }
+}
+
+#[test]
+fn test_sqlite_pool_checkout_unchecked() {
+    let db = create_sqlite_db("unchecked", "(1, 'a'), (2, 'b')");
+    let validations = Arc::new(AtomicUsize::new(0));
+    let config = PoolConfig {
+        max_size: 2,
+        connection_timeout: Duration::from_millis(200),
+        ..PoolConfig::default()
+    };
+    let pool = build_pool(
+        SpyManager {
+            inner: SqliteConnectionManager::file(&db),
+            validations: validations.clone(),
+        },
+        &config,
+        SessionInit::default(),
+    )
+    .unwrap();
+
+    for _ in 0..10 {
+        let conn = checkout_unchecked(&pool).unwrap();
+        conn.execute_batch("SELECT 1").unwrap();
+    }
+    assert_eq!(0, validations.load(Ordering::SeqCst));
+
+    for _ in 0..10 {
+        let conn = checkout(&pool).unwrap();
+        conn.execute_batch("SELECT 1").unwrap();
+    }
+    assert_eq!(10, validations.load(Ordering::SeqCst));
+
+    // both kinds of checkout share the pool's connections and its max_size
+    let held = [checkout_unchecked(&pool).unwrap(), checkout(&pool).unwrap()];
+    assert!(checkout_unchecked(&pool).unwrap_err().is_saturated());
+    assert_eq!(2, pool.state().connections);
+    drop(held);
+
+    // a connection failing validation is replaced, which an unchecked checkout skips
+    for conn in [checkout(&pool).unwrap(), checkout(&pool).unwrap()] {
+        conn.execute_batch("CREATE TEMP TABLE poison(x)").unwrap();
+    }
+    let unchecked = checkout_unchecked(&pool).unwrap();
+    assert!(is_poisoned(&unchecked));
+    drop(unchecked);
+    for _ in 0..4 {
+        let conn = checkout(&pool).unwrap();
+        assert!(!is_poisoned(&conn));
+    }
+    // r2d2 closed the invalid connections and reopens the pool's in the background
+    let start = Instant::now();
+    while pool.state().connections < 2 && start.elapsed() < Duration::from_secs(5) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(2, pool.state().connections);
+}
+
+#[test]
+fn test_sqlite_pool_execute_unchecked() {
+    let (pool, _db) = sqlite_pool("execute_unchecked", &PoolConfig::default());
+    pool.execute_unchecked("INSERT INTO test_table VALUES (3, 'c')")
+        .unwrap();
+    let count: i64 = checkout(&pool.sqlite_pool())
+        .unwrap()
+        .query_row("SELECT count(*) FROM test_table", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(3, count);
+}
+
+#[test]
+fn test_sqlite_pool_transaction() {
+    let db = create_sqlite_db("transaction", "(1, 'a'), (2, 'b')");
+    let source_conn = SourceConn::try_from(format!("sqlite://{}", db.display()).as_str()).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let count = |pool: &PoolVariant| -> i64 {
+        checkout(&pool.sqlite_pool())
+            .unwrap()
+            .query_row("SELECT count(*) FROM test_table", [], |r| r.get(0))
+            .unwrap()
+    };
+
+    let txn = Transaction::begin(&source_conn, &pool).unwrap();
+    txn.execute("INSERT INTO test_table VALUES (3, 'c')")
+        .unwrap();
+    // reads inside the transaction see its own uncommitted write
+    let rbs = txn
+        .read_arrow("SELECT * FROM test_table")
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(3, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+    txn.rollback().unwrap();
+    assert_eq!(2, count(&pool));
+
+    let txn = Transaction::begin(&source_conn, &pool).unwrap();
+    txn.execute("INSERT INTO test_table VALUES (3, 'c')")
+        .unwrap();
+    txn.commit().unwrap();
+    assert_eq!(3, count(&pool));
+
+    // dropping an unfinished transaction rolls it back and frees the connection
+    let txn = Transaction::begin(&source_conn, &pool).unwrap();
+    txn.execute("INSERT INTO test_table VALUES (4, 'd')")
+        .unwrap();
+    drop(txn);
+    assert_eq!(3, count(&pool));
+    assert_eq!(pool.state().connections, pool.state().idle_connections);
+}
+
+/// Sqlite manager recording when each connection is opened.
+#[derive(Debug)]
+struct ConnectSpy {
+    inner: SqliteConnectionManager,
+    opened: Arc<Mutex<Vec<Instant>>>,
+}
+
+impl r2d2::ManageConnection for ConnectSpy {
+    type Connection = rusqlite::Connection;
+    type Error = rusqlite::Error;
+
+    fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        self.opened.lock().unwrap().push(Instant::now());
+        self.inner.connect()
+    }
+
+    fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+        self.inner.is_valid(conn)
+    }
+
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        self.inner.has_broken(conn)
+    }
+}
+
+#[test]
+fn test_sqlite_pool_reserve() {
+    let config = PoolConfig {
+        max_size: 4,
+        connection_timeout: Duration::from_millis(200),
+        ..Default::default()
+    };
+    let (pool, _db) = sqlite_pool("reserve", &config);
+
+    assert!(pool.reserve(5).is_err());
+
+    let reserved = pool.reserve(3).unwrap();
+    assert_eq!(3, reserved.len());
+    for conn in &reserved {
+        conn.execute("SELECT 1").unwrap();
+    }
+    // the fourth connection is still free, a fifth is not
+    let fourth = pool.pin().unwrap();
+    let start = Instant::now();
+    assert!(pool.pin().is_err());
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    drop(reserved);
+    drop(fourth);
+    assert_eq!(4, pool.reserve(4).unwrap().len());
+}
+
+#[test]
+fn test_sqlite_pool_max_lifetime_jitter() {
+    let db = create_sqlite_db("lifetime_jitter", "(1, 'a'), (2, 'b')");
+    let opened = Arc::new(Mutex::new(vec![]));
+    let config = PoolConfig {
+        max_size: 8,
+        max_lifetime: Some(Duration::from_millis(400)),
+        max_lifetime_jitter: Some(Duration::from_millis(300)),
+        ..PoolConfig::default()
+    };
+    let pool = build_pool(
+        ConnectSpy {
+            inner: SqliteConnectionManager::file(&db),
+            opened: opened.clone(),
+        },
+        &config,
+        SessionInit::default(),
+    )
+    .unwrap();
+    // r2d2 keeps closing idle connections once their full lifetime is up
+    assert_eq!(Some(Duration::from_millis(400)), pool.max_lifetime());
+
+    // hand out every connection at once so they all start their lifetime together
+    let conns: Vec<_> = (0..8).map(|_| checkout(&pool).unwrap()).collect();
+    let expiries: Vec<_> = conns
+        .iter()
+        .map(|c| *PoolGuard::extensions(c).get::<ConnectionExpiry>().unwrap())
+        .collect();
+    for e in &expiries {
+        let lifetime = e.expires - e.created;
+        assert!(lifetime > Duration::from_millis(100) && lifetime <= Duration::from_millis(400));
+    }
+    drop(conns);
+    let start = Instant::now();
+    let initial = opened.lock().unwrap().len();
+
+    // keep cycling through all connections until each has been replaced once
+    while opened.lock().unwrap().len() < initial + 8 {
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let conns: Vec<_> = (0..8).map(|_| checkout(&pool).unwrap()).collect();
+        drop(conns);
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    let reopened = opened.lock().unwrap()[initial..initial + 8].to_vec();
+    let spread = *reopened.iter().max().unwrap() - *reopened.iter().min().unwrap();
+    assert!(spread > Duration::from_millis(50), "{:?}", spread);
+}
+
+#[test]
+fn test_sqlite_pool_timeouts_off() {
+    let (pool, _db) = sqlite_pool("timeouts_default", &PoolConfig::default());
+    assert_eq!(Some(Duration::from_secs(300)), pool.idle_timeout());
+    assert_eq!(Some(Duration::from_secs(1800)), pool.max_lifetime());
+
+    // `None` turns them off rather than falling back to r2d2's own defaults
+    let config = PoolConfig {
+        idle_timeout: None,
+        max_lifetime: None,
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("timeouts_off", &config);
+    assert_eq!(None, pool.idle_timeout());
+    assert_eq!(None, pool.max_lifetime());
+}
+
+#[test]
+fn test_sqlite_pool_trim_idle() {
+    // the default `min_idle` keeps the pool full, so it cannot be trimmed
+    let (pool, _db) = sqlite_pool("trim_idle_default", &PoolConfig::default());
+    assert!(pool.trim_idle(2).is_err());
+    assert_eq!(0, pool.trim_idle(10).unwrap());
+    assert_eq!(10, pool.state().connections);
+
+    for reuse_policy in [ReusePolicy::Lifo, ReusePolicy::Fifo] {
+        let config = PoolConfig {
+            max_size: 4,
+            min_idle: Some(0),
+            reuse_policy,
+            ..PoolConfig::default()
+        };
+        let (pool, _db) = sqlite_pool("trim_idle", &config);
+        drop(pool.reserve(4).unwrap());
+        assert_eq!(4, pool.state().idle_connections);
+
+        assert_eq!(3, pool.trim_idle(1).unwrap());
+        // nothing reopens the trimmed connections until they are needed
+        std::thread::sleep(Duration::from_millis(100));
+        let state = pool.state();
+        assert_eq!(1, state.idle_connections);
+        assert_eq!(1, state.connections);
+
+        drop(pool.reserve(4).unwrap());
+        assert_eq!(4, pool.state().connections);
+    }
+}
+
+#[test]
+fn test_sqlite_pool_server_version() {
+    let (pool, _db) = sqlite_pool("server_version", &PoolConfig::default());
+    let version = pool.server_version().unwrap();
+    assert_eq!(rusqlite::version(), version);
+
+    // cached: no connection is needed the second time
+    let _held: Vec<_> = (0..10)
+        .map(|_| checkout(&pool.sqlite_pool()).unwrap())
+        .collect();
+    assert_eq!(version, pool.server_version().unwrap());
+}
+
+#[test]
+fn test_sqlite_pool_list_tables() {
+    let other = create_sqlite_db("list_tables_other", "(1, 'a'), (2, 'b')");
+    let config = PoolConfig {
+        max_size: 1,
+        init_queries: vec![format!("ATTACH DATABASE '{}' AS other", other.display())],
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("list_tables", &config);
+    pool.execute_unchecked("CREATE TABLE zeta(id INTEGER); CREATE VIEW test_view AS SELECT 1")
+        .unwrap();
+
+    assert_eq!(vec!["main", "other"], pool.list_schemas().unwrap());
+    assert_eq!(
+        vec!["main.test_table", "main.zeta", "other.test_table"],
+        pool.list_tables(None).unwrap()
+    );
+    assert_eq!(
+        vec!["other.test_table"],
+        pool.list_tables(Some("other")).unwrap()
+    );
+    assert!(pool.list_tables(Some("missing")).unwrap().is_empty());
+}
+
+#[test]
+fn test_sqlite_pool_init_queries() {
+    let config = PoolConfig {
+        max_size: 3,
+        reuse_policy: ReusePolicy::Fifo,
+        on_return_query: Some("SELECT 1".to_string()),
+        init_queries: vec![
+            "CREATE TEMP TABLE IF NOT EXISTS init_runs(n INTEGER)".to_string(),
+            "INSERT INTO temp.init_runs VALUES (1)".to_string(),
+        ],
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("init_queries", &config);
+    let sqlite_pool = pool.sqlite_pool();
+    for _ in 0..12 {
+        let conn = checkout(&sqlite_pool).unwrap();
+        conn.query_row("SELECT count(*) FROM temp.init_runs", [], |r| {
+            r.get::<_, i64>(0)
+        })
+        .unwrap();
+    }
+
+    // every physical connection ran the init queries once, however often it was checked out
+    let conns: Vec<_> = (0..pool.max_size())
+        .map(|_| checkout(&sqlite_pool).unwrap())
+        .collect();
+    for conn in &conns {
+        let runs: i64 = conn
+            .query_row("SELECT count(*) FROM temp.init_runs", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(1, runs);
+    }
+}
+
+#[test]
+fn test_sqlite_pool_load_extensions() {
+    let db = create_sqlite_db("load_extensions", "(1, 'a'), (2, 'b')");
+    let source_conn = SourceConn::try_from(format!("sqlite://{}", db.display()).as_str()).unwrap();
+    let config = PoolConfig {
+        connection_timeout: Duration::from_secs(1),
+        load_extensions: vec![env::temp_dir().join("cx_no_such_extension")],
+        ..PoolConfig::default()
+    };
+    // the pool cannot open a connection without the extension
+    assert!(PoolVariant::from_source_conn(&source_conn, &config).is_err());
+}
+
+#[test]
+fn test_sqlite_pool_circuit_breaker() {
+    let config = PoolConfig {
+        max_size: 1,
+        connection_timeout: Duration::from_millis(100),
+        circuit_breaker: Some(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_millis(500),
+        }),
+        ..PoolConfig::default()
+    };
+    let (pool, _db) = sqlite_pool("circuit_breaker", &config);
+    let inner = pool.sqlite_pool();
+    assert_eq!(Some(CircuitState::Closed), pool.circuit_state());
+
+    // with the only connection held every checkout times out
+    let held = checkout(&inner).unwrap();
+    for _ in 0..2 {
+        assert!(matches!(checkout(&inner), Err(PoolError::Saturated(_))));
+    }
+    assert_eq!(Some(CircuitState::Open), pool.circuit_state());
+    let start = Instant::now();
+    assert!(matches!(
+        checkout(&inner),
+        Err(PoolError::CircuitOpen { failures: 2, .. })
+    ));
+    assert!(pool.execute_unchecked("SELECT 1").is_err());
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    // a failed trial opens the breaker again
+    std::thread::sleep(Duration::from_millis(500));
+    assert_eq!(Some(CircuitState::HalfOpen), pool.circuit_state());
+    assert!(matches!(checkout(&inner), Err(PoolError::Saturated(_))));
+    assert_eq!(Some(CircuitState::Open), pool.circuit_state());
+
+    // a successful one closes it
+    drop(held);
+    std::thread::sleep(Duration::from_millis(500));
+    assert_eq!(Some(CircuitState::HalfOpen), pool.circuit_state());
+    checkout(&inner).unwrap();
+    assert_eq!(Some(CircuitState::Closed), pool.circuit_state());
+
+    assert_eq!(
+        None,
+        sqlite_pool("no_circuit_breaker", &PoolConfig::default())
+            .0
+            .circuit_state()
+    );
+}
+
+#[test]
+fn test_session_init_application_name() {
+    let config = PoolConfig {
+        application_name: Some("nightly's etl".to_string()),
+        ..PoolConfig::default()
+    };
+    assert_eq!(
+        ["SET application_name = 'nightly''s etl'"],
+        SessionInit::new(&SourceType::Postgres, &config).statements()
+    );
+    assert_eq!(
+        [
+            "BEGIN DBMS_APPLICATION_INFO.SET_MODULE('nightly''s etl', NULL); \
+          DBMS_APPLICATION_INFO.SET_CLIENT_INFO('nightly''s etl'); END;"
+        ],
+        SessionInit::new(&SourceType::Oracle, &config).statements()
+    );
+    assert!(SessionInit::new(&SourceType::MySQL, &config)
+        .statements()
+        .is_empty());
+    assert!(SessionInit::new(&SourceType::SQLite, &config)
+        .statements()
+        .is_empty());
+    assert_eq!(
+        Some(&"nightly's etl".to_string()),
+        mysql_connect_attrs(&config).unwrap().get("program_name")
+    );
+
+    let config = PoolConfig::default();
+    assert!(SessionInit::new(&SourceType::Postgres, &config)
+        .statements()
+        .is_empty());
+    assert_eq!(None, mysql_connect_attrs(&config));
+
+    // SQLite has nothing to tag, the pool works as before
+    let (pool, _db) = sqlite_pool(
+        "application_name",
+        &PoolConfig {
+            application_name: Some("etl".to_string()),
+            ..PoolConfig::default()
+        },
+    );
+    pool.execute_unchecked("SELECT 1").unwrap();
+}
+
+#[test]
+fn test_session_init_read_only() {
+    let config = PoolConfig {
+        read_only: true,
+        ..PoolConfig::default()
+    };
+    assert_eq!(
+        ["SET default_transaction_read_only = on"],
+        SessionInit::new(&SourceType::Postgres, &config).statements()
+    );
+    assert_eq!(
+        ["SET SESSION TRANSACTION READ ONLY"],
+        SessionInit::new(&SourceType::MySQL, &config).statements()
+    );
+    assert_eq!(
+        ["ALTER SESSION SET READ_ONLY = TRUE"],
+        SessionInit::new(&SourceType::Oracle, &config).statements()
+    );
+    assert!(SessionInit::new(&SourceType::SQLite, &config)
+        .statements()
+        .is_empty());
+}
+
+#[test]
+fn test_sqlite_pool_read_only() {
+    let (pool, _db) = sqlite_pool(
+        "read_only",
+        &PoolConfig {
+            read_only: true,
+            ..PoolConfig::default()
+        },
+    );
+    let count: i64 = checkout(&pool.sqlite_pool())
+        .unwrap()
+        .query_row("SELECT count(*) FROM test_table", [], |r| r.get(0))
+        .unwrap();
+    assert_eq!(2, count);
+
+    let err = pool
+        .execute_unchecked("INSERT INTO test_table VALUES (3, 'c')")
+        .unwrap_err();
+    assert!(err.to_string().contains("readonly"), "{}", err);
+}
+
+#[test]
+fn test_diagnosis_classify() {
+    use connectorx::diagnosis::Diagnosis;
+
+    let cases: &[(SourceType, &str, &str)] = &[
+        (
+            SourceType::Postgres,
+            "error connecting to server: Connection refused (os error 111)",
+            "HostUnreachable",
+        ),
+        (
+            SourceType::Postgres,
+            "error connecting to server: failed to lookup address information: Name or service not known",
+            "HostUnreachable",
+        ),
+        (
+            SourceType::Postgres,
+            "db error: FATAL: password authentication failed for user \"cx\"",
+            "AuthFailed",
+        ),
+        (
+            SourceType::Postgres,
+            "db error: FATAL: no pg_hba.conf entry for host \"10.0.0.1\", user \"cx\", database \"db\", SSL off",
+            "AuthFailed",
+        ),
+        (
+            SourceType::Postgres,
+            "db error: FATAL: database \"missing\" does not exist",
+            "DatabaseNotFound",
+        ),
+        (
+            SourceType::Postgres,
+            "error performing TLS handshake: error:0A000086:SSL routines:tls_post_process_server_certificate:certificate verify failed",
+            "TlsError",
+        ),
+        (
+            SourceType::Postgres,
+            "error performing TLS handshake: server does not support TLS",
+            "TlsError",
+        ),
+        (
+            SourceType::MySQL,
+            "DriverError { Could not connect to address `db:3306': Connection refused (os error 111) }",
+            "HostUnreachable",
+        ),
+        (
+            SourceType::MySQL,
+            "MySqlError { ERROR 1045 (28000): Access denied for user 'cx'@'10.0.0.1' (using password: YES) }",
+            "AuthFailed",
+        ),
+        (
+            SourceType::MySQL,
+            "MySqlError { ERROR 1049 (42000): Unknown database 'missing' }",
+            "DatabaseNotFound",
+        ),
+        (
+            SourceType::MySQL,
+            "TlsError { The certificate was not trusted. }",
+            "TlsError",
+        ),
+        (
+            SourceType::MySQL,
+            "DriverError { Client requires secure connection but server does not have this capability }",
+            "TlsError",
+        ),
+        (
+            SourceType::Oracle,
+            "ORA-12541: TNS:no listener",
+            "HostUnreachable",
+        ),
+        (
+            SourceType::Oracle,
+            "ORA-01017: invalid username/password; logon denied",
+            "AuthFailed",
+        ),
+        (
+            SourceType::Oracle,
+            "ORA-12514: TNS:listener does not currently know of service requested in connect descriptor",
+            "DatabaseNotFound",
+        ),
+        (
+            SourceType::Oracle,
+            "ORA-29024: Certificate validation failure",
+            "TlsError",
+        ),
+        (
+            SourceType::SQLite,
+            "unable to open database file: /missing/dir/db.sqlite",
+            "DatabaseNotFound",
+        ),
+        (SourceType::SQLite, "disk I/O error", "Other"),
+    ];
+    for (ty, message, kind) in cases {
+        let diagnosis = Diagnosis::classify(ty, message);
+        assert_eq!(*kind, diagnosis.kind(), "{:?}: {}", ty, message);
+        assert_eq!(Some(*message), diagnosis.message());
+    }
+}
+
+#[test]
+fn test_sqlite_pool_diagnose() {
+    use connectorx::diagnosis::Diagnosis;
+
+    let db = create_sqlite_db("diagnose", "(1, 'a'), (2, 'b')");
+    let source_conn = SourceConn::try_from(format!("sqlite://{}", db.display()).as_str()).unwrap();
+    assert_eq!(
+        Diagnosis::Ok,
+        PoolVariant::diagnose(&source_conn, &PoolConfig::default())
+    );
+
+    let missing = env::temp_dir().join("cx_missing_dir").join("db.sqlite");
+    let source_conn =
+        SourceConn::try_from(format!("sqlite://{}?mode=ro", missing.display()).as_str()).unwrap();
+    let diagnosis = PoolVariant::diagnose(&source_conn, &PoolConfig::default());
+    assert_eq!("DatabaseNotFound", diagnosis.kind(), "{}", diagnosis);
+}
+
+#[test]
+fn test_sqlite_pool_acquire_deadline() {
+    let dir = env::temp_dir().join(format!("cx_pool_deadline_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let db = dir.join("deadline.db");
+    let source_conn = SourceConn::try_from(format!("sqlite://{}", db.display()).as_str()).unwrap();
+    let config = PoolConfig {
+        max_size: 2,
+        min_idle: Some(0),
+        connection_timeout: Duration::from_secs(2),
+        acquire_deadline: Some(Duration::from_millis(300)),
+        ..PoolConfig::default()
+    };
+    let pool = PoolVariant::from_source_conn(&source_conn, &config)
+        .unwrap()
+        .unwrap();
+
+    // the database is gone, so r2d2 keeps retrying to connect until the deadline
+    std::fs::remove_dir_all(&dir).unwrap();
+    let inner = pool.sqlite_pool();
+    for _ in 0..3 {
+        let start = Instant::now();
+        match checkout(&inner) {
+            Err(PoolError::DeadlineExceeded { deadline, source }) => {
+                assert_eq!(Duration::from_millis(300), deadline);
+                assert!(source.to_string().contains("unable to open"), "{}", source);
+            }
+            Err(e) => panic!("expected the deadline to be exceeded, got {}", e),
+            Ok(_) => panic!("expected the deadline to be exceeded"),
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(2),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+    let start = Instant::now();
+    assert!(matches!(
+        checkout_unchecked(&inner),
+        Err(PoolError::DeadlineExceeded { .. })
+    ));
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    // once it is back, checkouts go through again. r2d2 waits up to half of
+    // `connection_timeout` between its attempts to connect, which can outlast the deadline
+    // of a single checkout.
+    std::fs::create_dir_all(&dir).unwrap();
+    let start = Instant::now();
+    while let Err(e) = checkout(&inner) {
+        assert!(start.elapsed() < Duration::from_secs(10), "{}", e);
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
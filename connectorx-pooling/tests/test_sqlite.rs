@@ -0,0 +1,1311 @@
+use connectorx::{
+    get_arrow::{
+        get_arrow_multi, get_arrow_page, get_arrow_params, get_arrow_partitioned, get_arrow_sample,
+    },
+    partition::{partition_template, PartitionQuery},
+    pool::{PoolConfig, PoolEvent, PoolVariant},
+    prelude::*,
+    sql::CXQuery,
+};
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Logger keeping every debug line emitted by connectorx so tests can assert on them.
+struct CaptureLogger(Mutex<Vec<String>>);
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target().starts_with("connectorx")
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+mod common;
+
+use common::{create_countries_db, create_sqlite_db, sqlite_conn};
+
+#[test]
+fn test_sqlite_multi_shard() {
+    let shard0 = create_sqlite_db("shard0", "(1, 'a'), (2, 'b'), (3, 'c')");
+    let shard1 = create_sqlite_db("shard1", "(4, 'd'), (5, NULL)");
+    let queries = vec![CXQuery::naked("SELECT * FROM test_table")];
+    let sources = [
+        (sqlite_conn(&shard0), queries.clone(), None),
+        (sqlite_conn(&shard1), queries, None),
+    ];
+
+    let destination = get_arrow_multi(&sources, None).unwrap();
+    assert_eq!(&["test_int", "test_str"], destination.names());
+    let rbs = destination.arrow().unwrap();
+    assert_eq!(5, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+}
+
+#[test]
+fn test_sqlite_multi_shard_failure() {
+    let shard0 = create_sqlite_db("ok", "(1, 'a')");
+    let shard1 = create_sqlite_db("broken", "(2, 'b')");
+    let sources = [
+        (
+            sqlite_conn(&shard0),
+            vec![CXQuery::naked("SELECT * FROM test_table")],
+            None,
+        ),
+        (
+            sqlite_conn(&shard1),
+            vec![CXQuery::naked("SELECT * FROM missing_table")],
+            None,
+        ),
+    ];
+
+    match get_arrow_multi(&sources, None) {
+        Err(ConnectorXOutError::ShardError(shard, _)) => assert_eq!(1, shard),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("expected shard 1 to fail"),
+    }
+}
+
+#[test]
+fn test_sqlite_multi_shard_schema_mismatch() {
+    let shard0 = create_sqlite_db("schema0", "(1, 'a')");
+    let shard1 = create_sqlite_db("schema1", "(2, 'b')");
+    let sources = [
+        (
+            sqlite_conn(&shard0),
+            vec![CXQuery::naked("SELECT test_int, test_str FROM test_table")],
+            None,
+        ),
+        (
+            sqlite_conn(&shard1),
+            vec![CXQuery::naked("SELECT test_str, test_int FROM test_table")],
+            None,
+        ),
+    ];
+
+    match get_arrow_multi(&sources, None) {
+        Err(ConnectorXOutError::ShardSchemaMismatch(shard, _, _)) => assert_eq!(1, shard),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("expected a schema mismatch"),
+    }
+}
+
+#[test]
+fn test_sqlite_logs_selected_branch() {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let db = create_sqlite_db("logging", "(1, 'a'), (2, 'b')");
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let queries = [
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int < 2"),
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int >= 2"),
+    ];
+    get_arrow(&source_conn, None, &queries, None, Some(&pool), None).unwrap();
+
+    let lines = LOGGER.0.lock().unwrap();
+    assert!(
+        lines
+            .iter()
+            .any(|l| l
+                == "selected backend=sqlite protocol=binary tls=false pooled=true partitions=2"),
+        "{:?}",
+        lines
+    );
+}
+
+#[test]
+fn test_sqlite_arrow_schema_matches_fetch() {
+    let db = create_sqlite_db("schema", "(1, 'a'), (2, NULL)");
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let query = CXQuery::naked("SELECT test_int, test_str FROM test_table");
+
+    let schema = get_arrow_schema(&source_conn, &query, Some(&pool)).unwrap();
+    let fetched = get_arrow(&source_conn, None, &[query], None, Some(&pool), None)
+        .unwrap()
+        .arrow_schema();
+    assert_eq!(fetched, schema);
+
+    let missing = CXQuery::naked("SELECT missing_column FROM test_table");
+    assert!(get_arrow_schema(&source_conn, &missing, Some(&pool)).is_err());
+}
+
+#[test]
+fn test_sqlite_null_counts() {
+    let db = create_sqlite_db(
+        "null_counts",
+        "(1, 'a'), (2, NULL), (3, NULL), (4, 'd'), (5, NULL), (6, 'f')",
+    );
+    let source_conn = sqlite_conn(&db);
+    let queries = [
+        CXQuery::naked("SELECT test_int, test_str FROM test_table WHERE test_int <= 3"),
+        CXQuery::naked("SELECT test_int, test_str FROM test_table WHERE test_int > 3"),
+    ];
+
+    let destination = get_arrow(&source_conn, None, &queries, None, None, None).unwrap();
+    let counts = destination.null_counts().unwrap();
+    assert_eq!(2, counts.len());
+    assert_eq!(0, counts["test_int"]);
+    assert_eq!(3, counts["test_str"]);
+}
+
+#[test]
+fn test_sqlite_params() {
+    let db = create_sqlite_db("params", "(1, 'a'), (2, 'b'), (3, 'b'), (4, NULL)");
+    let query = "SELECT test_int FROM test_table WHERE test_int > ? AND test_str = ?";
+    let params = [ScalarValue::Int(1), ScalarValue::Text("b".to_string())];
+
+    let rbs = get_arrow_params(&sqlite_conn(&db), query, &params, None)
+        .unwrap()
+        .arrow()
+        .unwrap();
+    let mut ints: Vec<i64> = rbs
+        .iter()
+        .flat_map(|rb| {
+            rb.column(0)
+                .as_any()
+                .downcast_ref::<arrow::array::Int64Array>()
+                .unwrap()
+                .values()
+                .to_vec()
+        })
+        .collect();
+    ints.sort();
+    assert_eq!(vec![2, 3], ints);
+
+    // the bound text is compared as a value, never spliced into the SQL
+    let params = [
+        ScalarValue::Int(0),
+        ScalarValue::Text("b' OR '1'='1".to_string()),
+    ];
+    let rbs = get_arrow_params(&sqlite_conn(&db), query, &params, None)
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(0, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+}
+
+#[test]
+fn test_sqlite_empty_stream_schema() {
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    let db = create_sqlite_db("empty_stream", "(1, 'a'), (2, 'b')");
+    let queries = [CXQuery::naked(
+        "SELECT test_int, test_str FROM test_table WHERE 1=0",
+    )];
+    let mut batch_iter =
+        new_record_batch_iter(&sqlite_conn(&db), None, &queries, 1024, None, None, None).unwrap();
+    let expected = Schema::new(vec![
+        Field::new("test_int", DataType::Int64, true),
+        Field::new("test_str", DataType::Utf8, true),
+    ]);
+    assert_eq!(&expected, batch_iter.schema().as_ref());
+
+    // the stream itself has no batch to carry the schema
+    batch_iter.prepare();
+    let mut nrows = 0;
+    while let Some(rb) = batch_iter.try_next_batch().unwrap() {
+        assert_eq!(batch_iter.schema(), rb.schema());
+        nrows += rb.num_rows();
+    }
+    assert_eq!(0, nrows);
+}
+
+#[test]
+fn test_sqlite_keyset_pages() {
+    // inserted out of order, so the pages only come back sorted through the keyset order
+    let mut ids: Vec<i64> = (1..=10).collect();
+    ids.reverse();
+    ids.swap(2, 7);
+    let rows: Vec<String> = ids.iter().map(|i| format!("({}, 's{}')", i, i)).collect();
+    let db = create_sqlite_db("keyset_pages", &rows.join(", "));
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let query = "SELECT test_int, test_str FROM test_table";
+
+    let mut read = vec![];
+    let mut page_sizes = vec![];
+    let mut after = None;
+    loop {
+        let (page, next) =
+            get_arrow_page(&source_conn, query, "test_int", after, 4, Some(&pool)).unwrap();
+        let rbs = page.arrow().unwrap();
+        page_sizes.push(rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+        for rb in rbs.iter() {
+            let ints = rb
+                .column(0)
+                .as_any()
+                .downcast_ref::<arrow::array::Int64Array>()
+                .unwrap();
+            read.extend(ints.values().iter().copied());
+        }
+        match next {
+            Some(token) => after = Some(token),
+            None => break,
+        }
+    }
+    assert_eq!((1..=10).collect::<Vec<i64>>(), read);
+    // the last full page is followed by an empty one, which ends the read
+    assert_eq!(vec![4, 4, 2, 0], page_sizes);
+
+    // a read resumes from a saved token, here a text one
+    let (page, next) = get_arrow_page(
+        &source_conn,
+        query,
+        "test_str",
+        Some(ScalarValue::Text("s7".to_string())),
+        10,
+        None,
+    )
+    .unwrap();
+    // "s10" sorts before "s7" as text
+    assert_eq!(2, page.arrow().unwrap()[0].num_rows());
+    assert!(matches!(next, Some(ScalarValue::Text(s)) if s == "s9"));
+
+    assert!(get_arrow_page(&source_conn, query, "test_int", None, 0, None).is_err());
+    assert!(get_arrow_page(&source_conn, query, "missing", None, 2, None).is_err());
+}
+
+#[test]
+fn test_sqlite_partitioned() {
+    let rows: Vec<String> = (1..=10).map(|i| format!("({}, 's{}')", i, i)).collect();
+    let db = create_sqlite_db("partitioned", &rows.join(", "));
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(
+        &source_conn,
+        &PoolConfig {
+            max_size: 4,
+            ..PoolConfig::default()
+        },
+    )
+    .unwrap()
+    .unwrap();
+    let query = "SELECT * FROM test_table";
+
+    let whole: usize = get_arrow(
+        &source_conn,
+        None,
+        &[CXQuery::naked(query)],
+        None,
+        Some(&pool),
+        None,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap()
+    .iter()
+    .map(|rb| rb.num_rows())
+    .sum();
+    let rbs = get_arrow_partitioned(
+        &source_conn,
+        query,
+        &PartitionStrategy::NumericRange {
+            col: "test_int".into(),
+            n: 4,
+        },
+        Some(&pool),
+        false,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
+    assert_eq!(whole, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+    assert_eq!(10, whole);
+}
+
+#[test]
+fn test_sqlite_partitioned_single_value_and_nulls() {
+    let db = create_sqlite_db("partitioned_nulls", "(7, 'a'), (7, 'b')");
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE nullable(id INTEGER);
+         INSERT INTO nullable VALUES (1), (NULL), (2), (3), (NULL);",
+    )
+    .unwrap();
+    let source_conn = sqlite_conn(&db);
+
+    // min == max collapses to a single partition
+    let rbs = get_arrow_partitioned(
+        &source_conn,
+        "SELECT * FROM test_table",
+        &PartitionStrategy::NumericRange {
+            col: "test_int".into(),
+            n: 4,
+        },
+        None,
+        false,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
+    assert_eq!(2, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+
+    // rows with a NULL partition column are not dropped
+    let rbs = get_arrow_partitioned(
+        &source_conn,
+        "SELECT * FROM nullable",
+        &PartitionStrategy::NumericRange {
+            col: "id".into(),
+            n: 3,
+        },
+        None,
+        false,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
+    assert_eq!(5, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+}
+
+#[test]
+fn test_sqlite_partitioned_reuse_prepared() {
+    let rows: Vec<String> = (1..=10).map(|i| format!("({}, 's{}')", i, i)).collect();
+    let db = create_sqlite_db("partitioned_prepared", &rows.join(", "));
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(
+        &source_conn,
+        &PoolConfig {
+            max_size: 2,
+            ..PoolConfig::default()
+        },
+    )
+    .unwrap()
+    .unwrap();
+    let query = "SELECT * FROM test_table";
+
+    let expected = get_arrow_partitioned(
+        &source_conn,
+        query,
+        &PartitionStrategy::NumericRange {
+            col: "test_int".into(),
+            n: 2,
+        },
+        Some(&pool),
+        false,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
+    for _ in 0..3 {
+        let rbs = get_arrow_partitioned(
+            &source_conn,
+            query,
+            &PartitionStrategy::NumericRange {
+                col: "test_int".into(),
+                n: 2,
+            },
+            Some(&pool),
+            true,
+        )
+        .unwrap()
+        .arrow()
+        .unwrap();
+        assert_eq!(expected, rbs);
+    }
+
+    // each connection kept the one template statement it prepared, and ran it for every
+    // partition it read: 3 reads of 2 partitions over 2 connections
+    let part = PartitionQuery::new(query, "test_int", None, None, 2);
+    let (template, params) = partition_template(&part, &source_conn, Some(&pool)).unwrap();
+    assert_eq!(2, params.len());
+    let sqlite_pool = pool.sqlite_pool();
+    let conns = [sqlite_pool.get().unwrap(), sqlite_pool.get().unwrap()];
+    let runs: i32 = conns
+        .iter()
+        .map(|conn| {
+            let stmt = conn.prepare_cached(template.as_str()).unwrap();
+            stmt.get_status(rusqlite::StatementStatus::Run)
+        })
+        .sum();
+    assert_eq!(6, runs);
+}
+
+fn count_rows(pool: &PoolVariant, conn: &str) -> usize {
+    get_arrow(
+        &SourceConn::try_from(conn).unwrap(),
+        None,
+        &[CXQuery::naked("SELECT * FROM test_table")],
+        None,
+        Some(pool),
+        None,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap()
+    .iter()
+    .map(|rb| rb.num_rows())
+    .sum()
+}
+
+#[test]
+fn test_sqlite_memory_pool() {
+    let conn = "sqlite://:memory:";
+    let pool =
+        PoolVariant::from_source_conn(&SourceConn::try_from(conn).unwrap(), &PoolConfig::default())
+            .unwrap()
+            .unwrap();
+    // every pooled connection sees the same database
+    pool.execute_unchecked(
+        "CREATE TABLE test_table(test_int INTEGER NOT NULL, test_str TEXT);
+         INSERT INTO test_table VALUES (1, 'a'), (2, 'b');",
+    )
+    .unwrap();
+    assert_eq!(2, count_rows(&pool, conn));
+
+    // a second pool gets its own database
+    let other =
+        PoolVariant::from_source_conn(&SourceConn::try_from(conn).unwrap(), &PoolConfig::default())
+            .unwrap()
+            .unwrap();
+    assert!(other.execute_unchecked("SELECT * FROM test_table").is_err());
+}
+
+#[test]
+fn test_sqlite_file_uri() {
+    let conn = format!(
+        "sqlite://file:cx_uri_{}?mode=memory&cache=shared",
+        std::process::id()
+    );
+    let pool = PoolVariant::from_source_conn(
+        &SourceConn::try_from(conn.as_str()).unwrap(),
+        &PoolConfig::default(),
+    )
+    .unwrap()
+    .unwrap();
+    pool.execute_unchecked(
+        "CREATE TABLE test_table(test_int INTEGER NOT NULL, test_str TEXT);
+         INSERT INTO test_table VALUES (1, 'a'), (2, 'b'), (3, 'c');",
+    )
+    .unwrap();
+    assert_eq!(3, count_rows(&pool, &conn));
+
+    let db = create_sqlite_db("file_uri", "(1, 'a')");
+    let conn = format!("sqlite://file:{}?mode=ro", db.display());
+    let pool = PoolVariant::from_source_conn(
+        &SourceConn::try_from(conn.as_str()).unwrap(),
+        &PoolConfig::default(),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(1, count_rows(&pool, &conn));
+    assert!(pool
+        .execute_unchecked("INSERT INTO test_table VALUES (2, 'b')")
+        .is_err());
+
+    // a private in-memory database would differ per pooled connection
+    let conn = "sqlite://file:cx_private?mode=memory";
+    assert!(PoolVariant::from_source_conn(
+        &SourceConn::try_from(conn).unwrap(),
+        &PoolConfig::default()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_sqlite_binary() {
+    use arrow::array::{Array, LargeBinaryArray};
+
+    let conn = "sqlite://:memory:";
+    let source_conn = SourceConn::try_from(conn).unwrap();
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    pool.execute_unchecked(
+        "CREATE TABLE test_binary(id INTEGER NOT NULL, payload BLOB, raw VARBINARY(16));
+         INSERT INTO test_binary VALUES (1, x'00ff10', x'80'), (2, x'', x''), (3, NULL, NULL);",
+    )
+    .unwrap();
+
+    let rbs = get_arrow(
+        &source_conn,
+        None,
+        &[CXQuery::naked(
+            "SELECT payload, raw FROM test_binary ORDER BY id",
+        )],
+        None,
+        Some(&pool),
+        None,
+    )
+    .unwrap()
+    .arrow()
+    .unwrap();
+    assert_eq!(1, rbs.len());
+    let expected: [&[Option<&[u8]>]; 2] = [
+        &[Some(&[0x00, 0xff, 0x10]), Some(&[]), None],
+        &[Some(&[0x80]), Some(&[]), None],
+    ];
+    for (col, expected) in expected.iter().enumerate() {
+        let array = rbs[0]
+            .column(col)
+            .as_any()
+            .downcast_ref::<LargeBinaryArray>()
+            .unwrap();
+        let values: Vec<Option<&[u8]>> = (0..array.len())
+            .map(|i| (!array.is_null(i)).then(|| array.value(i)))
+            .collect();
+        assert_eq!(expected.to_vec(), values, "column {}", col);
+    }
+}
+
+#[test]
+fn test_sqlite_max_bytes() {
+    let rows: Vec<String> = (1..=100)
+        .map(|i| format!("({}, 'row number {}')", i, i))
+        .collect();
+    let db = create_sqlite_db("max_bytes", &rows.join(", "));
+    let source_conn = sqlite_conn(&db);
+    let queries = [CXQuery::naked(
+        "SELECT test_int, test_str, test_int * 2.5 AS test_float, test_str || test_str AS test_long FROM test_table",
+    )];
+
+    match get_arrow(&source_conn, None, &queries, None, None, Some(256)) {
+        Err(ConnectorXOutError::MemoryLimitExceeded { limit, used }) => {
+            assert_eq!(256, limit);
+            assert!(used > limit, "{} bytes used", used);
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("the memory limit did not trigger"),
+    }
+
+    let rbs = get_arrow(&source_conn, None, &queries, None, None, Some(1 << 20))
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(100, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+}
+
+#[test]
+fn test_sqlite_max_partitions() {
+    let db = create_sqlite_db("max_partitions", "(1, 'a'), (2, 'b')");
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let checkouts = Arc::new(AtomicUsize::new(0));
+    let counter = checkouts.clone();
+    pool.set_event_handler(Some(Arc::new(move |event| {
+        if let PoolEvent::Acquire { .. } = event {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    })));
+    let queries: Vec<CXQuery<String>> = (0..8)
+        .map(|_| CXQuery::naked("SELECT * FROM test_table"))
+        .collect();
+
+    match ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .pool(&pool)
+        .max_partitions(3)
+        .run()
+    {
+        Err(ConnectorXOutError::TooManyPartitions { count, max }) => {
+            assert_eq!((8, 3), (count, max));
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("the partition limit did not trigger"),
+    }
+    assert_eq!(0, checkouts.load(Ordering::SeqCst));
+
+    let rbs = ArrowQuery::new(&source_conn)
+        .queries(&queries[..3])
+        .pool(&pool)
+        .max_partitions(3)
+        .run()
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(6, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+    assert!(checkouts.load(Ordering::SeqCst) >= 3);
+}
+
+#[test]
+fn test_sqlite_arrow_query() {
+    let rows: Vec<String> = (1..=10).map(|i| format!("({}, 's{}')", i, i)).collect();
+    let db = create_sqlite_db("arrow_query", &rows.join(", "));
+    let source_conn = sqlite_conn(&db);
+    let count = |destination: ArrowDestination| {
+        destination
+            .arrow()
+            .unwrap()
+            .iter()
+            .map(|rb| rb.num_rows())
+            .sum::<usize>()
+    };
+
+    let queries = [CXQuery::naked("SELECT * FROM test_table")];
+    let destination = ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .run()
+        .unwrap();
+    assert_eq!(&["test_int", "test_str"], destination.names());
+    assert_eq!(10, count(destination));
+
+    let partitions = [
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int <= 4"),
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int > 4"),
+    ];
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let destination = ArrowQuery::new(&source_conn)
+        .queries(&partitions)
+        .origin_query("SELECT * FROM test_table")
+        .pool(&pool)
+        .run()
+        .unwrap();
+    assert_eq!(10, count(destination));
+
+    assert!(matches!(
+        ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .max_bytes(8)
+            .run(),
+        Err(ConnectorXOutError::MemoryLimitExceeded { limit: 8, .. })
+    ));
+}
+
+#[test]
+fn test_sqlite_arrow_query_timings() {
+    let rows: Vec<String> = (1..=10).map(|i| format!("({}, 's{}')", i, i)).collect();
+    let db = create_sqlite_db("arrow_query_timings", &rows.join(", "));
+    let source_conn = sqlite_conn(&db);
+    let partitions = [
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int <= 4"),
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int > 4"),
+    ];
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let (_, timings) = ArrowQuery::new(&source_conn)
+        .queries(&partitions)
+        .origin_query("SELECT * FROM test_table")
+        .pool(&pool)
+        .run_with_timings()
+        .unwrap();
+
+    assert_eq!(partitions.len(), timings.len());
+    for (timing, (query, rows)) in timings.iter().zip(partitions.iter().zip([4, 6])) {
+        assert_eq!(query.to_string(), timing.query);
+        assert_eq!(rows, timing.rows);
+        assert!(timing.checkout_ms >= 0.0);
+        assert!(timing.exec_ms >= 0.0);
+    }
+}
+
+#[test]
+fn test_sqlite_pool_execute_many() {
+    let db = create_sqlite_db("execute_many", "(0, 'seed')");
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let rows: Vec<Vec<ScalarValue>> = (1..=1000)
+        .map(|i| vec![ScalarValue::Int(i), ScalarValue::Text(format!("s{}", i))])
+        .collect();
+    assert_eq!(
+        1000,
+        pool.execute_many("INSERT INTO test_table VALUES (?, ?)", &rows)
+            .unwrap()
+    );
+    assert_eq!(
+        1001,
+        count_rows(&pool, &format!("sqlite://{}", db.display()))
+    );
+
+    // a failing row rolls back the rows before it
+    let rows = vec![
+        vec![ScalarValue::Int(2000), ScalarValue::Null],
+        vec![ScalarValue::Null, ScalarValue::Null],
+    ];
+    assert!(pool
+        .execute_many("INSERT INTO test_table VALUES (?, ?)", &rows)
+        .is_err());
+    assert_eq!(
+        1001,
+        count_rows(&pool, &format!("sqlite://{}", db.display()))
+    );
+}
+
+#[test]
+fn test_sqlite_arrow_string_type() {
+    use arrow::array::{Array, LargeStringArray, StringArray};
+    use arrow::datatypes::DataType;
+
+    let long = "x".repeat(8 << 20);
+    let db = create_sqlite_db("string_type", &format!("(1, '{}'), (2, NULL)", long));
+    let source_conn = sqlite_conn(&db);
+    let queries = [CXQuery::naked(
+        "SELECT test_str FROM test_table ORDER BY test_int",
+    )];
+
+    let rbs = ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .run()
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(&DataType::Utf8, rbs[0].schema().field(0).data_type());
+    let column = rbs[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(long, column.value(0));
+    assert!(column.is_null(1));
+
+    let rbs = ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .string_type(ArrowStringType::LargeUtf8)
+        .run()
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(&DataType::LargeUtf8, rbs[0].schema().field(0).data_type());
+    let column = rbs[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<LargeStringArray>()
+        .unwrap();
+    assert_eq!(long, column.value(0));
+    assert!(column.is_null(1));
+}
+
+#[test]
+fn test_sqlite_pool_events() {
+    use connectorx::pool::PoolEvent;
+    use std::sync::Arc;
+
+    let db = create_sqlite_db("pool_events", "(1, 'a'), (2, 'b')");
+    let pool = PoolVariant::from_source_conn(&sqlite_conn(&db), &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    pool.set_event_handler(Some(Arc::new(move |e| sink.lock().unwrap().push(e))));
+
+    assert_eq!(2, count_rows(&pool, &format!("sqlite://{}", db.display())));
+    let seen = events.lock().unwrap().clone();
+    assert!(matches!(seen.first(), Some(PoolEvent::Acquire { .. })));
+    assert!(matches!(seen.last(), Some(PoolEvent::Release { .. })));
+
+    pool.emit_event(PoolEvent::Close { connections: 1 });
+    assert_eq!("close", events.lock().unwrap().last().unwrap().name());
+
+    // removed handlers see nothing further
+    pool.set_event_handler(None);
+    let before = events.lock().unwrap().len();
+    count_rows(&pool, &format!("sqlite://{}", db.display()));
+    assert_eq!(before, events.lock().unwrap().len());
+}
+
+#[test]
+fn test_sqlite_capabilities() {
+    let db = create_sqlite_db("capabilities", "(1, 'a')");
+    let pool = PoolVariant::from_source_conn(&sqlite_conn(&db), &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let capabilities = pool.capabilities();
+    assert!(capabilities.supports_transactions);
+    assert!(capabilities.supports_prepared);
+    assert!(!capabilities.supports_copy);
+    assert!(capabilities.supports_partition);
+    assert!(!capabilities.supports_tls);
+}
+
+#[test]
+fn test_sqlite_preserve_order() {
+    let rows: Vec<String> = (1..=200).map(|i| format!("({}, 's{}')", i, i)).collect();
+    let db = create_sqlite_db("preserve_order", &rows.join(", "));
+    let source_conn = sqlite_conn(&db);
+    // ordered ranges, the first ones largest so they tend to finish last
+    let queries: Vec<CXQuery<String>> = [(1, 120), (121, 170), (171, 195), (196, 200)]
+        .iter()
+        .map(|(lo, hi)| {
+            CXQuery::naked(format!(
+                "SELECT test_int FROM test_table WHERE test_int BETWEEN {} AND {} ORDER BY test_int",
+                lo, hi
+            ))
+        })
+        .collect();
+
+    for _ in 0..5 {
+        let rbs = ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .preserve_order(true)
+            .run()
+            .unwrap()
+            .arrow()
+            .unwrap();
+        let read: Vec<i64> = rbs
+            .iter()
+            .flat_map(|rb| {
+                rb.column(0)
+                    .as_any()
+                    .downcast_ref::<arrow::array::Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        assert_eq!((1..=200).collect::<Vec<i64>>(), read);
+    }
+}
+
+#[test]
+fn test_sqlite_invalid_utf8() {
+    use arrow::array::StringArray;
+
+    let db = create_sqlite_db("invalid_utf8", "(1, 'ok')");
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    // a blob cast to text keeps its bytes, valid UTF-8 or not
+    conn.execute_batch("INSERT INTO test_table VALUES (2, CAST(x'6869ff21' AS TEXT))")
+        .unwrap();
+    let source_conn = sqlite_conn(&db);
+    let queries = [CXQuery::naked("SELECT * FROM test_table ORDER BY test_int")];
+    let read = |policy: InvalidUtf8Policy| {
+        ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .invalid_utf8(policy)
+            .run()
+    };
+
+    let err = read(InvalidUtf8Policy::default()).err().unwrap();
+    assert!(err.to_string().contains("invalid UTF-8"), "{}", err);
+
+    for (policy, expected) in [
+        (InvalidUtf8Policy::Replace, "\u{FFFD}"),
+        (InvalidUtf8Policy::Lossy, "hi\u{FFFD}!"),
+    ] {
+        let rbs = read(policy).unwrap().arrow().unwrap();
+        let texts = rbs[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!("ok", texts.value(0));
+        assert_eq!(expected, texts.value(1));
+    }
+}
+
+#[test]
+fn test_sqlite_bypass_pool() {
+    let db = create_sqlite_db("bypass_pool", "(1, 'a'), (2, 'b'), (3, 'c')");
+    let source_conn = sqlite_conn(&db);
+    let config = PoolConfig {
+        max_size: 1,
+        min_idle: Some(0),
+        connection_timeout: Duration::from_millis(200),
+        ..PoolConfig::default()
+    };
+    let pool = PoolVariant::from_source_conn(&source_conn, &config)
+        .unwrap()
+        .unwrap();
+    let queries = [
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int < 3"),
+        CXQuery::naked("SELECT * FROM test_table WHERE test_int >= 3"),
+    ];
+    let read = |bypass: bool| {
+        ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .pool(&pool)
+            .bypass_pool(bypass)
+            .run()
+    };
+
+    assert_eq!(0, pool.state().connections);
+    let rbs = read(true).unwrap().arrow().unwrap();
+    assert_eq!(3, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+    assert_eq!(0, pool.state().connections);
+
+    // holding the pool's only connection starves a pooled read, but not a bypassing one
+    let held = pool.sqlite_pool().get().unwrap();
+    assert!(read(false).is_err());
+    assert_eq!(1, pool.state().connections);
+    assert!(read(true).is_ok());
+    assert_eq!(1, pool.state().connections);
+    drop(held);
+}
+
+#[test]
+fn test_sqlite_dictionary_encode_size() {
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::DataType;
+
+    let db = create_countries_db("dict_size", 10_000);
+    let source_conn = sqlite_conn(&db);
+    let queries = [CXQuery::naked("SELECT test_str FROM test_table")];
+    let read = |policy: DictPolicy| {
+        let rbs = ArrowQuery::new(&source_conn)
+            .queries(&queries)
+            .dictionary_encode(policy)
+            .run()
+            .unwrap()
+            .arrow()
+            .unwrap();
+        assert_eq!(1, rbs.len());
+        rbs[0].column(0).clone()
+    };
+
+    let plain = read(DictPolicy::Never);
+    let encoded = read(DictPolicy::Always);
+    assert_eq!(&DataType::Utf8, plain.data_type());
+    assert_eq!(
+        &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8),),
+        encoded.data_type()
+    );
+    assert_eq!(4, encoded.as_any_dictionary().values().len());
+    assert!(encoded.get_array_memory_size() * 2 < plain.get_array_memory_size());
+    let decoded = arrow::compute::cast(&encoded, plain.data_type()).unwrap();
+    assert_eq!(&plain, &decoded);
+}
+
+#[test]
+fn test_sqlite_dictionary_encode_shared_across_batches() {
+    use arrow::array::AsArray;
+    use arrow::datatypes::DataType;
+
+    let db = create_countries_db("dict_batches", 1_000);
+    let source_conn = sqlite_conn(&db);
+    let queries = [
+        CXQuery::naked(
+            "SELECT test_str, 'row' || test_int AS unique_str FROM test_table WHERE test_int < 500",
+        ),
+        CXQuery::naked(
+            "SELECT test_str, 'row' || test_int AS unique_str FROM test_table WHERE test_int >= 500",
+        ),
+    ];
+    let destination = ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .dictionary_encode(DictPolicy::Auto)
+        .run()
+        .unwrap();
+
+    // only the low-cardinality column is encoded
+    let schema = destination.arrow_schema();
+    assert!(matches!(
+        schema.field(0).data_type(),
+        DataType::Dictionary(_, _)
+    ));
+    assert_eq!(&DataType::Utf8, schema.field(1).data_type());
+
+    let rbs = destination.arrow().unwrap();
+    assert_eq!(2, rbs.len());
+    assert!(rbs.iter().all(|rb| rb.schema() == schema));
+    let dictionaries: Vec<_> = rbs
+        .iter()
+        .map(|rb| rb.column(0).as_any_dictionary().values().clone())
+        .collect();
+    assert_eq!(4, dictionaries[0].len());
+    assert!(Arc::ptr_eq(&dictionaries[0], &dictionaries[1]));
+    let all = arrow::compute::concat_batches(&schema, &rbs).unwrap();
+    assert_eq!(1_000, all.num_rows());
+}
+
+#[test]
+fn test_sqlite_pool_timeout() {
+    let db = create_sqlite_db("pool_timeout", "(1, 'a')");
+    let source_conn = sqlite_conn(&db);
+    let config = PoolConfig {
+        max_size: 1,
+        connection_timeout: Duration::from_millis(200),
+        ..PoolConfig::default()
+    };
+    let pool = PoolVariant::from_source_conn(&source_conn, &config)
+        .unwrap()
+        .unwrap();
+    let held = pool.reserve(1).unwrap();
+
+    let queries = [CXQuery::naked("SELECT test_int FROM test_table")];
+    match ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .pool(&pool)
+        .run()
+    {
+        Err(ConnectorXOutError::PoolTimeout(_)) => {}
+        Err(e) => panic!("unexpected error: {:?}", e),
+        Ok(_) => panic!("the checkout did not time out"),
+    }
+
+    drop(held);
+    let rbs = ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .pool(&pool)
+        .run()
+        .unwrap();
+    assert_eq!(1, rbs.arrow().unwrap()[0].num_rows());
+}
+
+#[test]
+fn test_sqlite_partitioned_explicit_ranges() {
+    use arrow::array::{Array, StringArray};
+
+    let db = create_sqlite_db(
+        "partitioned_explicit",
+        "(1, 'apple'), (2, 'cherry'), (3, 'kiwi'), (4, 'mango'), (5, 'melon'), (6, NULL), \
+         (7, 'zucchini'), (8, 'm')",
+    );
+    let source_conn = sqlite_conn(&db);
+    let strs = |rbs: Vec<arrow::record_batch::RecordBatch>| {
+        let mut strs: Vec<Option<String>> = rbs
+            .iter()
+            .flat_map(|rb| {
+                let col = rb.column(1).as_any().downcast_ref::<StringArray>().unwrap();
+                (0..col.len())
+                    .map(|i| col.is_valid(i).then(|| col.value(i).to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        strs.sort();
+        strs
+    };
+    let query = "SELECT * FROM test_table";
+    let whole = strs(
+        get_arrow(
+            &source_conn,
+            None,
+            &[CXQuery::naked(query)],
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+        .arrow()
+        .unwrap(),
+    );
+
+    // `m` is the lower bound of the second range, and the NULL row lands in the first
+    let strategy = PartitionStrategy::ExplicitRanges {
+        col: "test_str".into(),
+        bounds: vec![ScalarValue::Text("c".into()), ScalarValue::Text("m".into())],
+    };
+    let rbs = get_arrow_partitioned(&source_conn, query, &strategy, None, false)
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(whole, strs(rbs));
+    assert_eq!(8, whole.len());
+
+    // SQLite has no hash function to partition with
+    let strategy = PartitionStrategy::Hash {
+        col: "test_str".into(),
+        n: 2,
+    };
+    assert!(matches!(
+        get_arrow_partitioned(&source_conn, query, &strategy, None, false),
+        Err(ConnectorXOutError::SourceNotSupport(_))
+    ));
+}
+
+#[test]
+fn test_sqlite_schema_cache() {
+    use arrow::datatypes::DataType;
+    use connectorx::schema_cache::SchemaCache;
+
+    let db = create_sqlite_db("schema_cache", "(1, 'a'), (2, 'b')");
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+    let cache = SchemaCache::new();
+    let read = |query: &str| {
+        ArrowQuery::new(&source_conn)
+            .queries(&[CXQuery::naked(query)])
+            .pool(&pool)
+            .schema_cache(&cache)
+            .run()
+    };
+
+    // the second read, differing only in whitespace, is not described again
+    read("SELECT * FROM test_table").unwrap();
+    let rbs = read("SELECT *\n    FROM  test_table")
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(2, rbs[0].num_rows());
+    assert_eq!((1, 1), (cache.hits(), cache.misses()));
+    assert_eq!(1, cache.len());
+
+    // a read failing on the cached schema drops it, so the next one describes the new schema
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    conn.execute_batch(
+        "DROP TABLE test_table;
+         CREATE TABLE test_table(test_int TEXT, test_str TEXT);
+         INSERT INTO test_table VALUES ('x', 'a');",
+    )
+    .unwrap();
+    assert!(read("SELECT * FROM test_table").is_err());
+    assert!(cache.is_empty());
+    let rbs = read("SELECT * FROM test_table").unwrap().arrow().unwrap();
+    assert_eq!(&DataType::Utf8, rbs[0].schema().field(0).data_type());
+    assert_eq!((2, 2), (cache.hits(), cache.misses()));
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_sqlite_thread_pool() {
+    use rusqlite::functions::FunctionFlags;
+
+    let db = create_sqlite_db("thread_pool", "(1, 'a'), (2, 'b')");
+    let source_conn = sqlite_conn(&db);
+    let config = PoolConfig {
+        max_size: 8,
+        ..PoolConfig::default()
+    };
+    let pool = PoolVariant::from_source_conn(&source_conn, &config)
+        .unwrap()
+        .unwrap();
+    // `busy()` holds its partition for a while, tracking how many partitions are busy at once
+    let busy = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let conns: Vec<_> = (0..8).map(|_| pool.sqlite_pool().get().unwrap()).collect();
+    for conn in &conns {
+        let (busy, peak) = (busy.clone(), peak.clone());
+        conn.create_scalar_function("busy", 0, FunctionFlags::SQLITE_UTF8, move |_| {
+            peak.fetch_max(busy.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(50));
+            busy.fetch_sub(1, Ordering::SeqCst);
+            Ok(1i64)
+        })
+        .unwrap();
+    }
+    drop(conns);
+    let queries: Vec<CXQuery<String>> = (0..8)
+        .map(|i| CXQuery::naked(format!("SELECT busy() + {} AS b", i)))
+        .collect();
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+
+    let rbs = ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .pool(&pool)
+        .thread_pool(&thread_pool)
+        .run()
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(8, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+    let peak = peak.load(Ordering::SeqCst);
+    assert!((1..=2).contains(&peak), "{} partitions at once", peak);
+}
+
+#[test]
+fn test_sqlite_pool_checkout() {
+    let db = create_sqlite_db("checkout", "(1, 'a'), (2, 'b')");
+    let source_conn = sqlite_conn(&db);
+    let pool = PoolVariant::from_source_conn(&source_conn, &PoolConfig::default())
+        .unwrap()
+        .unwrap();
+
+    let mut conn = pool.checkout().unwrap();
+    // a temporary table only exists on the connection that created it
+    conn.execute("CREATE TEMP TABLE scratch (v INTEGER)")
+        .unwrap();
+    assert_eq!(
+        3,
+        conn.execute("INSERT INTO scratch VALUES (1), (2), (3)")
+            .unwrap()
+    );
+    assert_eq!(2, conn.execute("DELETE FROM scratch WHERE v > 1").unwrap());
+
+    let rbs = conn
+        .query_arrow("SELECT v FROM scratch")
+        .unwrap()
+        .arrow()
+        .unwrap();
+    assert_eq!(1, rbs.iter().map(|rb| rb.num_rows()).sum::<usize>());
+    assert_eq!(1, pool.state().connections - pool.state().idle_connections);
+    drop(conn);
+    assert_eq!(0, pool.state().connections - pool.state().idle_connections);
+}
+
+#[test]
+fn test_sqlite_progress() {
+    let db = create_sqlite_db("progress", "(1, 'a'), (2, 'b')");
+    let source_conn = sqlite_conn(&db);
+    let queries: Vec<CXQuery<String>> = (0..4)
+        .map(|_| CXQuery::naked("SELECT * FROM test_table"))
+        .collect();
+
+    let calls = Arc::new(Mutex::new(vec![]));
+    let sink = calls.clone();
+    ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .progress(Arc::new(move |completed, total| {
+            sink.lock().unwrap().push((completed, total));
+            Ok(())
+        }))
+        .run()
+        .unwrap();
+    assert_eq!(
+        vec![(1, Some(4)), (2, Some(4)), (3, Some(4)), (4, Some(4))],
+        *calls.lock().unwrap()
+    );
+
+    let res = ArrowQuery::new(&source_conn)
+        .queries(&queries)
+        .progress(Arc::new(|_, _| Err(anyhow::anyhow!("cancelled"))))
+        .run();
+    match res {
+        Err(e) => assert!(e.to_string().contains("cancelled"), "{}", e),
+        Ok(_) => panic!("progress error should abort the read"),
+    }
+}
+
+#[test]
+fn test_sqlite_sample() {
+    use arrow::array::Int64Array;
+
+    let rows = (0..10000)
+        .map(|i| format!("({}, 'row{}')", i, i))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let db = create_sqlite_db("sample", &rows);
+    let source_conn = sqlite_conn(&db);
+
+    let count = |destination: ArrowDestination| -> (usize, i64) {
+        let ids: Vec<i64> = destination
+            .arrow()
+            .unwrap()
+            .iter()
+            .flat_map(|rb| {
+                let column = rb.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+                column.values().to_vec()
+            })
+            .collect();
+        (ids.len(), ids.iter().sum())
+    };
+
+    let (sampled, sum) = count(get_arrow_sample(&source_conn, "test_table", 0.1, None).unwrap());
+    assert!((900..=1100).contains(&sampled), "sampled {} rows", sampled);
+    // spread over the table rather than taken from its start
+    let mean = sum / sampled as i64;
+    assert!((3000..=7000).contains(&mean), "mean of {}", mean);
+
+    let (all, _) = count(get_arrow_sample(&source_conn, "test_table", 1.0, None).unwrap());
+    assert_eq!(10000, all);
+
+    for fraction in [0.0, -0.5, 1.5, f64::NAN] {
+        assert!(get_arrow_sample(&source_conn, "test_table", fraction, None).is_err());
+    }
+}
@@ -112,3 +112,74 @@ pub fn verify_arrow_results(result: Vec<RecordBatch>) {
         }
     }
 }
+
+#[test]
+fn test_trino_runtime_init_error() {
+    let res =
+        connectorx::runtime::init_runtime(|| Err(std::io::Error::other("thread limit reached")));
+    match res {
+        Err(ConnectorXOutError::RuntimeInit(e)) => {
+            assert_eq!("thread limit reached", e.to_string())
+        }
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("expected runtime creation to fail"),
+    }
+}
+
+#[test]
+fn test_trino_shared_runtime_reused() {
+    let first = connectorx::runtime::shared_runtime().unwrap();
+    for i in 0..100 {
+        let rt = connectorx::runtime::shared_runtime().unwrap();
+        assert!(Arc::ptr_eq(&first, &rt));
+        assert_eq!(i, rt.block_on(async move { i }));
+    }
+    assert_eq!(1, connectorx::runtime::runtimes_created());
+}
+
+#[test]
+fn test_trino_runtime_worker_threads() {
+    use connectorx::runtime::RuntimeConfig;
+
+    let rt = RuntimeConfig {
+        worker_threads: Some(2),
+    }
+    .build()
+    .unwrap();
+    assert_eq!(2, rt.metrics().num_workers());
+    let tasks: Vec<_> = (0..16).map(|i| rt.spawn(async move { i * 2 })).collect();
+    let sum: i32 = rt.block_on(async {
+        let mut sum = 0;
+        for task in tasks {
+            sum += task.await.unwrap();
+        }
+        sum
+    });
+    assert_eq!(240, sum);
+}
+
+#[test]
+#[ignore]
+fn test_trino_two_worker_threads() {
+    use connectorx::runtime::RuntimeConfig;
+
+    let dburl = env::var("TRINO_URL").unwrap();
+    let queries = [
+        CXQuery::naked("select * from test.test_table where test_int <= 2 order by test_int"),
+        CXQuery::naked("select * from test.test_table where test_int > 2 order by test_int"),
+    ];
+
+    let rt = RuntimeConfig {
+        worker_threads: Some(2),
+    }
+    .build()
+    .unwrap();
+    let builder = TrinoSource::new(Arc::new(rt), &dburl).unwrap();
+    let mut destination = ArrowDestination::new();
+    let dispatcher =
+        Dispatcher::<_, _, TrinoArrowTransport>::new(builder, &mut destination, &queries, None);
+    dispatcher.run().unwrap();
+
+    let result = destination.arrow().unwrap();
+    verify_arrow_results(result);
+}
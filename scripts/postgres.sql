@@ -5,6 +5,7 @@ DROP TABLE IF EXISTS test_infinite_values;
 DROP TYPE IF EXISTS happiness;
 DROP EXTENSION IF EXISTS citext;
 DROP EXTENSION IF EXISTS ltree;
+DROP EXTENSION IF EXISTS hstore;
 
 CREATE TABLE IF NOT EXISTS test_table(
     test_int INTEGER NOT NULL,
@@ -53,6 +54,7 @@ INSERT INTO test_str VALUES (8, '', NULL);
 CREATE TYPE happiness AS ENUM ('happy', 'very happy', 'ecstatic');
 CREATE EXTENSION citext;
 CREATE EXTENSION ltree;
+CREATE EXTENSION hstore;
 CREATE TABLE IF NOT EXISTS test_types(
     test_bool BOOLEAN,
     test_date DATE,